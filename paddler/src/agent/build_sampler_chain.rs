@@ -0,0 +1,135 @@
+use anyhow::Result;
+use llama_cpp_bindings::sampling::LlamaSampler;
+use paddler_types::mirostat_parameters::MirostatParameters;
+use paddler_types::mirostat_version::MirostatVersion;
+use paddler_types::sampler_type::SamplerType;
+use paddler_types::sampling_parameters::SamplingParameters;
+
+use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
+
+/// Tokens mirostat 1.0 estimates the Zipf exponent from, the value used in the paper
+const MIROSTAT_V1_ESTIMATED_TOKENS: i32 = 100;
+
+/// Disabled samplers (the ones without parameters) are left out of the chain
+fn ordered_sampler(
+    scheduler_context: &ContinuousBatchSchedulerContext,
+    sampler_type: SamplerType,
+    sampling: &SamplingParameters,
+    seed: u32,
+) -> Result<Option<LlamaSampler>> {
+    let inference_parameters = &scheduler_context.inference_parameters;
+
+    Ok(match sampler_type {
+        SamplerType::Dry => inference_parameters
+            .dry
+            .as_ref()
+            .map(|dry| {
+                LlamaSampler::dry(
+                    &scheduler_context.model,
+                    dry.multiplier,
+                    dry.base,
+                    dry.allowed_length,
+                    dry.penalty_last_n,
+                    &dry.sequence_breakers,
+                )
+            })
+            .transpose()?,
+        SamplerType::MinP => Some(LlamaSampler::min_p(
+            sampling.min_p.unwrap_or(inference_parameters.min_p),
+            0,
+        )),
+        SamplerType::Penalties => Some(LlamaSampler::penalties(
+            sampling
+                .penalty_last_n
+                .unwrap_or(inference_parameters.penalty_last_n),
+            sampling
+                .penalty_repeat
+                .unwrap_or(inference_parameters.penalty_repeat),
+            sampling
+                .penalty_frequency
+                .unwrap_or(inference_parameters.penalty_frequency),
+            sampling
+                .penalty_presence
+                .unwrap_or(inference_parameters.penalty_presence),
+        )),
+        SamplerType::Temperature => Some(LlamaSampler::temp(
+            sampling
+                .temperature
+                .unwrap_or(inference_parameters.temperature),
+        )),
+        SamplerType::TopK => Some(LlamaSampler::top_k(
+            sampling.top_k.unwrap_or(inference_parameters.top_k),
+        )),
+        SamplerType::TopNSigma => inference_parameters
+            .top_n_sigma
+            .map(LlamaSampler::top_n_sigma),
+        SamplerType::TopP => Some(LlamaSampler::top_p(
+            sampling.top_p.unwrap_or(inference_parameters.top_p),
+            0,
+        )),
+        SamplerType::TypicalP => inference_parameters
+            .typical_p
+            .map(|typical_p| LlamaSampler::typical(typical_p, 0)),
+        SamplerType::Xtc => inference_parameters
+            .xtc
+            .as_ref()
+            .map(|xtc| LlamaSampler::xtc(xtc.probability, xtc.threshold, 0, seed)),
+    })
+}
+
+fn mirostat_sampler(
+    scheduler_context: &ContinuousBatchSchedulerContext,
+    &MirostatParameters { eta, tau, version }: &MirostatParameters,
+    seed: u32,
+) -> LlamaSampler {
+    match version {
+        MirostatVersion::V1 => LlamaSampler::mirostat(
+            scheduler_context.model.n_vocab(),
+            seed,
+            tau,
+            eta,
+            MIROSTAT_V1_ESTIMATED_TOKENS,
+        ),
+        MirostatVersion::V2 => LlamaSampler::mirostat_v2(seed, tau, eta),
+    }
+}
+
+/// Logit biases come first. Then either the samplers in the configured order narrow down the
+/// candidates for the seeded distribution sampler, or mirostat picks the token after the
+/// temperature is applied.
+pub fn build_sampler_chain(
+    scheduler_context: &ContinuousBatchSchedulerContext,
+    sampling: &SamplingParameters,
+    seed: u32,
+) -> Result<LlamaSampler> {
+    let inference_parameters = &scheduler_context.inference_parameters;
+    let mut samplers = Vec::new();
+
+    if !scheduler_context.logit_biases.is_empty() {
+        samplers.push(LlamaSampler::logit_bias(
+            scheduler_context.model.n_vocab(),
+            &scheduler_context.logit_biases,
+        )?);
+    }
+
+    if let Some(mirostat) = &inference_parameters.mirostat {
+        samplers.push(LlamaSampler::temp(
+            sampling
+                .temperature
+                .unwrap_or(inference_parameters.temperature),
+        ));
+        samplers.push(mirostat_sampler(scheduler_context, mirostat, seed));
+
+        return Ok(LlamaSampler::chain_simple(samplers));
+    }
+
+    for sampler_type in &inference_parameters.sampler_order {
+        if let Some(sampler) = ordered_sampler(scheduler_context, *sampler_type, sampling, seed)? {
+            samplers.push(sampler);
+        }
+    }
+
+    samplers.push(LlamaSampler::dist(seed));
+
+    Ok(LlamaSampler::chain_simple(samplers))
+}
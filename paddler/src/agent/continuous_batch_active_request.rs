@@ -1,26 +1,42 @@
 use llama_cpp_bindings::sampling::LlamaSampler;
 use llama_cpp_bindings::token::LlamaToken;
 use log::warn;
+use paddler_types::finish_reason::FinishReason;
 use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::token_usage::TokenUsage;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TryRecvError;
 
 use crate::agent::continuous_batch_request_phase::ContinuousBatchRequestPhase;
+use crate::agent::reasoning_detector::ReasoningDetector;
+use crate::agent::stop_sequence_detector::StopSequenceDetector;
+use crate::agent::tool_call_detector::ToolCallDetector;
 
 pub struct ContinuousBatchActiveRequest {
     pub chain: LlamaSampler,
     pub current_token_position: i32,
     pub grammar_sampler: Option<LlamaSampler>,
+    /// Sampled tokens that were already decoded into the KV cache
+    pub generated_tokens: Vec<LlamaToken>,
     pub generated_tokens_count: i32,
     pub generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
     pub generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
     pub i_batch: Option<i32>,
+    /// Alternatives to report along with the logprob of every sampled token, if requested
+    pub logprobs: Option<u32>,
     pub max_tokens: i32,
     pub pending_sampled_token: Option<LlamaToken>,
     pub phase: ContinuousBatchRequestPhase,
     pub prompt_tokens: Vec<LlamaToken>,
+    /// Multimodal prompts leave `prompt_tokens` empty, so the count is kept separately
+    pub prompt_tokens_count: usize,
     pub prompt_tokens_ingested: usize,
+    pub reasoning_detector: ReasoningDetector,
+    /// Echoed back when the request finishes, so that it can be reproduced
+    pub seed: u32,
     pub sequence_id: i32,
+    pub stop_sequence_detector: StopSequenceDetector,
+    pub tool_call_detector: ToolCallDetector,
     pub utf8_decoder: encoding_rs::Decoder,
 }
 
@@ -41,6 +57,63 @@ impl ContinuousBatchActiveRequest {
         self.phase = ContinuousBatchRequestPhase::Completed;
     }
 
+    /// Sends out the text held back while waiting for a possible stop sequence, reasoning marker
+    /// or tool call, the seed and the token usage, then completes the request.
+    pub fn finish(&mut self, agent_name: &Option<String>, finish_reason: FinishReason) {
+        let held_back_text = self.stop_sequence_detector.flush();
+        let mut reasoning_results = self.reasoning_detector.push(&held_back_text);
+
+        reasoning_results.extend(self.reasoning_detector.flush());
+
+        let mut held_back_results = self.detect_tool_calls(reasoning_results);
+
+        held_back_results.extend(self.tool_call_detector.flush());
+
+        for held_back_result in held_back_results {
+            if matches!(
+                &held_back_result,
+                GeneratedTokenResult::ReasoningToken(text) | GeneratedTokenResult::Token(text)
+                    if text.is_empty()
+            ) {
+                continue;
+            }
+
+            if self.generated_tokens_tx.send(held_back_result).is_err() {
+                warn!(
+                    "{agent_name:?}: sequence {} failed to send held back text to client (receiver dropped)",
+                    self.sequence_id
+                );
+            }
+        }
+
+        if self
+            .generated_tokens_tx
+            .send(GeneratedTokenResult::Seed(self.seed))
+            .is_err()
+        {
+            warn!(
+                "{agent_name:?}: sequence {} failed to send seed to client (receiver dropped)",
+                self.sequence_id
+            );
+        }
+
+        if self
+            .generated_tokens_tx
+            .send(GeneratedTokenResult::Usage(TokenUsage {
+                completion_tokens: usize::try_from(self.generated_tokens_count).unwrap_or(0),
+                prompt_tokens: self.prompt_tokens_count,
+            }))
+            .is_err()
+        {
+            warn!(
+                "{agent_name:?}: sequence {} failed to send token usage to client (receiver dropped)",
+                self.sequence_id
+            );
+        }
+
+        self.complete_with_outcome(agent_name, GeneratedTokenResult::Done(finish_reason));
+    }
+
     pub fn is_stop_requested(&mut self) -> bool {
         match self.generate_tokens_stop_rx.try_recv() {
             Ok(()) | Err(TryRecvError::Disconnected) => true,
@@ -48,8 +121,43 @@ impl ContinuousBatchActiveRequest {
         }
     }
 
+    /// Tokens held by the sequence in the KV cache, if they are known. Multimodal prompts are
+    /// ingested as chunks that do not map back to tokens.
+    #[must_use]
+    pub fn into_decoded_tokens(self) -> Option<Vec<LlamaToken>> {
+        let mut decoded_tokens = self.prompt_tokens;
+
+        decoded_tokens.truncate(self.prompt_tokens_ingested);
+        decoded_tokens.extend(self.generated_tokens);
+
+        let decoded_tokens_count = i32::try_from(decoded_tokens.len()).ok()?;
+
+        (decoded_tokens_count == self.current_token_position).then_some(decoded_tokens)
+    }
+
     #[must_use]
     pub fn remaining_prompt_tokens(&self) -> &[LlamaToken] {
         &self.prompt_tokens[self.prompt_tokens_ingested..]
     }
+
+    /// Splits text released by the stop sequence detector into reasoning, answer and tool calls
+    pub fn separate_released_text(&mut self, released_text: &str) -> Vec<GeneratedTokenResult> {
+        let reasoning_results = self.reasoning_detector.push(released_text);
+
+        self.detect_tool_calls(reasoning_results)
+    }
+
+    /// Tool calls are only looked for in the answer, never in the reasoning
+    fn detect_tool_calls(
+        &mut self,
+        reasoning_results: Vec<GeneratedTokenResult>,
+    ) -> Vec<GeneratedTokenResult> {
+        reasoning_results
+            .into_iter()
+            .flat_map(|reasoning_result| match reasoning_result {
+                GeneratedTokenResult::Token(text) => self.tool_call_detector.push(&text),
+                reasoning_result => vec![reasoning_result],
+            })
+            .collect()
+    }
 }
@@ -24,12 +24,15 @@ use paddler_types::agent_issue_params::SlotCannotStartParams;
 use paddler_types::chat_template::ChatTemplate;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::model_metadata::ModelMetadata;
+use paddler_types::reasoning_markers::ReasoningMarkers;
 use tokio::sync::oneshot;
 
 use crate::agent::continuous_batch_arbiter_handle::ContinuousBatchArbiterHandle;
 use crate::agent::continuous_batch_scheduler::ContinuousBatchScheduler;
 use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
 use crate::agent::model_metadata_holder::ModelMetadataHolder;
+use crate::agent::resolve_logit_biases::resolve_logit_biases;
+use crate::agent::tool_call_format::ToolCallFormat;
 use crate::agent_issue_fix::AgentIssueFix;
 use crate::chat_template_renderer::ChatTemplateRenderer;
 use crate::converts_to_llama_kv_cache_dtype::ConvertsToLlamaKvCacheDtype;
@@ -162,6 +165,14 @@ impl ContinuousBatchArbiter {
                 return Err(anyhow!(message));
             }
 
+            let reasoning_markers = inference_parameters
+                .reasoning_markers
+                .clone()
+                .or_else(|| ReasoningMarkers::detect(&llama_chat_template_string));
+            let tool_call_format = ToolCallFormat::detect(&llama_chat_template_string);
+            let logit_biases = resolve_logit_biases(&model, &inference_parameters.logit_bias)
+                .context("Failed to resolve logit biases")?;
+
             let chat_template_renderer = Arc::new(
                 match ChatTemplateRenderer::new(ChatTemplate {
                     content: llama_chat_template_string.clone(),
@@ -252,8 +263,10 @@ impl ContinuousBatchArbiter {
                 chat_template_renderer,
                 desired_slots_total,
                 inference_parameters,
+                logit_biases,
                 model_path: model_path.clone(),
                 multimodal_context,
+                reasoning_markers,
                 token_bos_str: model.token_to_piece(
                     model.token_bos(),
                     &mut special_token_decoder,
@@ -272,6 +285,7 @@ impl ContinuousBatchArbiter {
                     true,
                     None,
                 )?,
+                tool_call_format,
                 model: model.clone(),
             });
 
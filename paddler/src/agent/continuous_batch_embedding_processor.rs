@@ -6,15 +6,20 @@ use anyhow::anyhow;
 use llama_cpp_bindings::context::LlamaContext;
 use llama_cpp_bindings::llama_batch::LlamaBatch;
 use llama_cpp_bindings::model::AddBos;
+use llama_cpp_bindings::token::LlamaToken;
 use paddler_types::embedding::Embedding;
+use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::pooling_type::PoolingType;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
 use tokio::sync::mpsc;
 
 use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
 use crate::agent::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
 use crate::agent::plan_embedding_batches::plan_embedding_batches;
+use crate::agent::prepared_embedding_batch_request::PreparedEmbeddingBatchRequest;
+use crate::agent::render_rerank_prompt::render_rerank_prompt;
 use crate::embedding_input_tokenized::EmbeddingInputTokenized;
 
 pub struct ContinuousBatchEmbeddingProcessor<'context> {
@@ -33,18 +38,21 @@ impl<'context> ContinuousBatchEmbeddingProcessor<'context> {
         }
     }
 
-    pub fn process_embedding_batch(
-        &mut self,
+    pub fn prepare_embedding_batch(
+        &self,
         GenerateEmbeddingBatchRequest {
-            mut generate_embedding_stop_rx,
+            generate_embedding_stop_rx,
             generated_embedding_tx,
             params:
                 GenerateEmbeddingBatchParams {
                     input_batch,
+                    model: _,
                     normalization_method,
+                    priority: _,
+                    rerank_query,
                 },
         }: GenerateEmbeddingBatchRequest,
-    ) -> Result<()> {
+    ) -> Result<PreparedEmbeddingBatchRequest> {
         if !self
             .scheduler_context
             .inference_parameters
@@ -57,88 +65,197 @@ impl<'context> ContinuousBatchEmbeddingProcessor<'context> {
             return Err(anyhow!("Embeddings are not enabled"));
         }
 
+        if rerank_query.is_some()
+            && self.scheduler_context.inference_parameters.pooling_type != PoolingType::Rank
+        {
+            generated_embedding_tx.send(EmbeddingResult::Error(
+                "Reranking requires the rank pooling type".to_owned(),
+            ))?;
+
+            return Err(anyhow!("Reranking requires the rank pooling type"));
+        }
+
         let tokens_lines_list = input_batch
             .into_iter()
-            .map(|input| {
-                match self
-                    .scheduler_context
-                    .model
-                    .str_to_token(&input.content, AddBos::Always)
-                {
-                    Ok(tokens) => Ok(EmbeddingInputTokenized {
-                        id: input.id,
-                        tokens,
-                    }),
-                    Err(err) => Err(anyhow!("Failed to tokenize input: {err:?}")),
-                }
-            })
-            .collect::<Result<Vec<EmbeddingInputTokenized>, _>>()
-            .context("failed to tokenize embedding input batch")?;
+            .map(|input| self.tokenize_input(input, rerank_query.as_deref()))
+            .collect::<Result<Vec<EmbeddingInputTokenized>, _>>();
 
+        let tokens_lines_list = match tokens_lines_list {
+            Ok(tokens_lines_list) => tokens_lines_list,
+            Err(err) => {
+                generated_embedding_tx.send(EmbeddingResult::Error(format!("{err:#}")))?;
+
+                return Err(err.context("failed to tokenize embedding input batch"));
+            }
+        };
+
+        Ok(PreparedEmbeddingBatchRequest {
+            generate_embedding_stop_rx,
+            generated_embedding_tx,
+            normalization_method,
+            pending_inputs: tokens_lines_list.into(),
+        })
+    }
+
+    /// Embeds as many of the pending inputs as fit in a single batch, one input per given
+    /// sequence. Only those sequences are touched, so generation can go on in the others.
+    pub fn process_next_embedding_batch(
+        &mut self,
+        request: &mut PreparedEmbeddingBatchRequest,
+        sequence_ids: &[i32],
+    ) -> Result<()> {
         let batch_n_tokens = self.scheduler_context.inference_parameters.batch_n_tokens;
-        let max_sequences_per_batch = self.scheduler_context.desired_slots_total;
-        let token_counts: Vec<usize> = tokens_lines_list
+        let max_sequences_per_batch = i32::try_from(sequence_ids.len())?;
+        let token_counts: Vec<usize> = request
+            .pending_inputs
             .iter()
             .map(|input| input.tokens.len())
             .collect();
-        let planned_batches =
-            plan_embedding_batches(&token_counts, batch_n_tokens, max_sequences_per_batch);
+        let Some(planned_batch) =
+            plan_embedding_batches(&token_counts, batch_n_tokens, max_sequences_per_batch)
+                .into_iter()
+                .next()
+        else {
+            return Ok(());
+        };
+
+        let batch_inputs: Vec<EmbeddingInputTokenized> =
+            request.pending_inputs.drain(planned_batch).collect();
         let mut batch = LlamaBatch::new(batch_n_tokens, max_sequences_per_batch)?;
 
-        #[expect(
-            clippy::cast_possible_truncation,
-            clippy::cast_possible_wrap,
-            reason = "sequence index within a planned batch is bounded by max_sequences_per_batch which fits in i32"
-        )]
-        for planned_batch in planned_batches {
-            if generate_embedding_stop_rx.try_recv().is_ok() {
-                break;
-            }
+        for (input, sequence_id) in batch_inputs.iter().zip(sequence_ids) {
+            batch.add_sequence(&input.tokens, *sequence_id, true)?;
+        }
 
-            let batch_inputs: Vec<&EmbeddingInputTokenized> =
-                tokens_lines_list[planned_batch].iter().collect();
+        self.embedding_batch_decode(
+            &mut batch,
+            &batch_inputs,
+            sequence_ids,
+            &request.generated_embedding_tx,
+            &request.normalization_method,
+        )
+    }
 
-            for (sequence_index, input) in batch_inputs.iter().enumerate() {
-                batch.add_sequence(&input.tokens, sequence_index as i32, true)?;
-            }
+    fn tokenize_input(
+        &self,
+        input: EmbeddingInputDocument,
+        rerank_query: Option<&str>,
+    ) -> Result<EmbeddingInputTokenized> {
+        if let Some(rerank_query) = rerank_query {
+            return self.tokenize_rerank_pair(rerank_query, input);
+        }
 
-            self.embedding_batch_decode(
-                &mut batch,
-                &batch_inputs,
-                &generated_embedding_tx,
-                &normalization_method,
-            )?;
+        let Some(tokens) = input.tokens else {
+            return match self
+                .scheduler_context
+                .model
+                .str_to_token(&input.content, AddBos::Always)
+            {
+                Ok(tokens) => Ok(EmbeddingInputTokenized {
+                    id: input.id,
+                    tokens,
+                }),
+                Err(err) => Err(anyhow!("Failed to tokenize input: {err:?}")),
+            };
+        };
+
+        let n_vocab = self.scheduler_context.model.n_vocab();
+
+        // llama.cpp aborts the whole process on tokens outside of the vocabulary
+        if let Some(invalid_token) = tokens.iter().find(|token| !(0..n_vocab).contains(*token)) {
+            return Err(anyhow!(
+                "Token {invalid_token} of input {} is outside of the vocabulary of {n_vocab} tokens",
+                input.id
+            ));
         }
 
-        generated_embedding_tx.send(EmbeddingResult::Done)?;
+        Ok(EmbeddingInputTokenized {
+            id: input.id,
+            tokens: tokens.into_iter().map(LlamaToken::new).collect(),
+        })
+    }
 
-        Ok(())
+    /// Follows llama.cpp: models that ship a rerank prompt template get the pair rendered into
+    /// it, others get the query and the document separated by their special tokens.
+    fn tokenize_rerank_pair(
+        &self,
+        rerank_query: &str,
+        input: EmbeddingInputDocument,
+    ) -> Result<EmbeddingInputTokenized> {
+        let model = &self.scheduler_context.model;
+
+        if input.tokens.is_some() {
+            return Err(anyhow!(
+                "Input {} is already tokenized and cannot be paired with the rerank query",
+                input.id
+            ));
+        }
+
+        if let Ok(rerank_template) = model.chat_template(Some("rerank")) {
+            let prompt =
+                render_rerank_prompt(rerank_template.to_str()?, rerank_query, &input.content);
+
+            return match model.str_to_token(&prompt, AddBos::Always) {
+                Ok(tokens) => Ok(EmbeddingInputTokenized {
+                    id: input.id,
+                    tokens,
+                }),
+                Err(err) => Err(anyhow!("Failed to tokenize rerank prompt: {err:?}")),
+            };
+        }
+
+        let tokenize = |content: &str| {
+            model
+                .str_to_token(content, AddBos::Never)
+                .map_err(|err| anyhow!("Failed to tokenize rerank input: {err:?}"))
+        };
+        let n_vocab = model.n_vocab();
+        let tokens = [model.token_bos()]
+            .into_iter()
+            .chain(tokenize(rerank_query)?)
+            .chain([model.token_eos(), model.token_sep()])
+            .chain(tokenize(&input.content)?)
+            .chain([model.token_eos()])
+            // Vocabularies without some of the special tokens report them as out of range
+            .filter(|token| (0..n_vocab).contains(&token.0))
+            .collect();
+
+        Ok(EmbeddingInputTokenized {
+            id: input.id,
+            tokens,
+        })
     }
 
     fn embedding_batch_decode(
         &mut self,
         batch: &mut LlamaBatch,
-        current_batch_embeddings: &[&EmbeddingInputTokenized],
+        current_batch_embeddings: &[EmbeddingInputTokenized],
+        sequence_ids: &[i32],
         generated_embedding_tx: &mpsc::UnboundedSender<EmbeddingResult>,
         normalization_method: &EmbeddingNormalizationMethod,
     ) -> Result<()> {
-        self.llama_context.clear_kv_cache();
+        for sequence_id in sequence_ids {
+            self.llama_context.clear_kv_cache_seq(
+                Some(u32::try_from(*sequence_id)?),
+                None,
+                None,
+            )?;
+        }
+
         self.llama_context.decode(batch)?;
 
-        #[expect(
-            clippy::cast_possible_truncation,
-            clippy::cast_possible_wrap,
-            reason = "embedding sequence index fits in i32 for llama.cpp FFI"
-        )]
-        for (index, embedding_input_tokenized) in current_batch_embeddings.iter().enumerate() {
+        for (embedding_input_tokenized, sequence_id) in
+            current_batch_embeddings.iter().zip(sequence_ids)
+        {
             let embedding = self
                 .llama_context
-                .embeddings_seq_ith(index as i32)
+                .embeddings_seq_ith(*sequence_id)
                 .context("Failed to get embeddings")?;
 
             generated_embedding_tx.send(EmbeddingResult::Embedding(
                 Embedding {
                     embedding: embedding.to_vec(),
+                    input_tokens: embedding_input_tokenized.tokens.len(),
                     normalization_method: EmbeddingNormalizationMethod::None,
                     pooling_type: self
                         .scheduler_context
@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+
+use llama_cpp_bindings::token::LlamaToken;
+
+/// Sharing just a handful of tokens (BOS, chat template preamble) is not worth giving up a
+/// cached sequence for.
+pub const MINIMUM_REUSED_PREFIX_TOKENS: usize = 16;
+
+struct CachedSequence {
+    sequence_id: i32,
+    tokens: Vec<LlamaToken>,
+}
+
+/// Finished sequences whose KV cells are kept around so that requests sharing their prefix
+/// (the same system prompt, earlier turns of a conversation) do not have to decode it again.
+///
+/// Every cached sequence keeps holding its sequence id, so the cache is bounded by the number
+/// of slots that are not processing anything.
+#[derive(Default)]
+pub struct ContinuousBatchPrefixCache {
+    cached_sequences: VecDeque<CachedSequence>,
+}
+
+impl ContinuousBatchPrefixCache {
+    pub fn drain(&mut self) -> Vec<i32> {
+        self.cached_sequences
+            .drain(..)
+            .map(|cached_sequence| cached_sequence.sequence_id)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cached_sequences.is_empty()
+    }
+
+    pub fn retain(&mut self, sequence_id: i32, tokens: Vec<LlamaToken>) {
+        self.cached_sequences.push_back(CachedSequence {
+            sequence_id,
+            tokens,
+        });
+    }
+
+    pub fn take_least_recently_retained(&mut self) -> Option<i32> {
+        self.cached_sequences
+            .pop_front()
+            .map(|cached_sequence| cached_sequence.sequence_id)
+    }
+
+    /// Returns the sequence that shares the longest prefix with the prompt and the number of
+    /// tokens that can be reused from it. At least one prompt token is always left to decode,
+    /// since its logits are needed to sample the first generated token.
+    pub fn take_longest_prefix(&mut self, prompt_tokens: &[LlamaToken]) -> Option<(i32, usize)> {
+        let reusable_limit = prompt_tokens.len().saturating_sub(1);
+
+        let (best_index, reused_tokens) = self
+            .cached_sequences
+            .iter()
+            .enumerate()
+            .map(|(index, cached_sequence)| {
+                let common_prefix = cached_sequence
+                    .tokens
+                    .iter()
+                    .zip(prompt_tokens)
+                    .take_while(|(cached_token, prompt_token)| cached_token == prompt_token)
+                    .count();
+
+                (index, common_prefix.min(reusable_limit))
+            })
+            .max_by_key(|(_, reused_tokens)| *reused_tokens)?;
+
+        if reused_tokens < MINIMUM_REUSED_PREFIX_TOKENS {
+            return None;
+        }
+
+        self.cached_sequences
+            .remove(best_index)
+            .map(|cached_sequence| (cached_sequence.sequence_id, reused_tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(range: std::ops::Range<i32>) -> Vec<LlamaToken> {
+        range.map(LlamaToken).collect()
+    }
+
+    #[test]
+    fn picks_sequence_with_longest_common_prefix() {
+        let mut prefix_cache = ContinuousBatchPrefixCache::default();
+
+        prefix_cache.retain(0, tokens(0..20));
+        prefix_cache.retain(1, tokens(0..40));
+
+        assert_eq!(
+            prefix_cache.take_longest_prefix(&tokens(0..50)),
+            Some((1, 40))
+        );
+        assert_eq!(prefix_cache.take_least_recently_retained(), Some(0));
+        assert!(prefix_cache.is_empty());
+    }
+
+    #[test]
+    fn leaves_last_prompt_token_to_decode() {
+        let mut prefix_cache = ContinuousBatchPrefixCache::default();
+
+        prefix_cache.retain(3, tokens(0..40));
+
+        assert_eq!(
+            prefix_cache.take_longest_prefix(&tokens(0..30)),
+            Some((3, 29))
+        );
+    }
+
+    #[test]
+    fn ignores_prefixes_too_short_to_be_worth_reusing() {
+        let mut prefix_cache = ContinuousBatchPrefixCache::default();
+
+        prefix_cache.retain(0, tokens(0..40));
+
+        let mut prompt = tokens(0..4);
+
+        prompt.extend(tokens(100..140));
+
+        assert_eq!(prefix_cache.take_longest_prefix(&prompt), None);
+        assert_eq!(prefix_cache.drain(), vec![0]);
+    }
+}
@@ -14,32 +14,46 @@ use llama_cpp_bindings::mtmd::MtmdBitmap;
 use llama_cpp_bindings::mtmd::MtmdContext;
 use llama_cpp_bindings::mtmd::MtmdInputText;
 use llama_cpp_bindings::sampling::LlamaSampler;
+use llama_cpp_bindings::token::LlamaToken;
 use log::debug;
 use log::error;
 use log::info;
 use log::warn;
 use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::finish_reason::FinishReason;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::sampling_parameters::SamplingParameters;
 use rand::Rng as _;
 use rand::rngs::ThreadRng;
 use tokio::sync::mpsc;
 
+use crate::agent::build_sampler_chain::build_sampler_chain;
 use crate::agent::continue_from_conversation_history_request::ContinueFromConversationHistoryRequest;
 use crate::agent::continue_from_raw_prompt_request::ContinueFromRawPromptRequest;
 use crate::agent::continuous_batch_active_request::ContinuousBatchActiveRequest;
 use crate::agent::continuous_batch_embedding_processor::ContinuousBatchEmbeddingProcessor;
+use crate::agent::continuous_batch_prefix_cache::ContinuousBatchPrefixCache;
+use crate::agent::continuous_batch_prefix_cache::MINIMUM_REUSED_PREFIX_TOKENS;
 use crate::agent::continuous_batch_request_phase::ContinuousBatchRequestPhase;
 use crate::agent::continuous_batch_scheduler_command::ContinuousBatchSchedulerCommand;
 use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
+use crate::agent::fit_prompt_in_context::fit_raw_prompt_in_context;
 use crate::agent::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
 use crate::agent::grammar_sampler::GrammarSampler;
 use crate::agent::prepare_conversation_history_request::prepare_conversation_history_request;
 use crate::agent::prepared_conversation_history_request::PreparedConversationHistoryRequest;
+use crate::agent::prepared_embedding_batch_request::PreparedEmbeddingBatchRequest;
+use crate::agent::reasoning_detector::ReasoningDetector;
 use crate::agent::resolve_grammar::resolve_grammar;
+use crate::agent::resolve_sampling_parameters::resolve_sampling_parameters;
 use crate::agent::sample_token_at_batch_index::sample_token_at_batch_index;
 use crate::agent::sampling_outcome::SamplingOutcome;
 use crate::agent::sequence_id_pool::SequenceIdPool;
+use crate::agent::stop_sequence_detector::StopSequenceDetector;
+use crate::agent::stop_sequence_outcome::StopSequenceOutcome;
+use crate::agent::token_logprobs_at_batch_index::token_logprobs_at_batch_index;
+use crate::agent::tool_call_detector::ToolCallDetector;
 use crate::decoded_image::DecodedImage;
 use crate::dispenses_slots::DispensesSlots;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
@@ -60,7 +74,8 @@ pub struct ContinuousBatchScheduler {
     active_requests: Vec<ContinuousBatchActiveRequest>,
     command_rx: Receiver<ContinuousBatchSchedulerCommand>,
     llama_context: LlamaContext<'static>,
-    pending_embedding_requests: VecDeque<GenerateEmbeddingBatchRequest>,
+    pending_embedding_requests: VecDeque<PreparedEmbeddingBatchRequest>,
+    prefix_cache: ContinuousBatchPrefixCache,
     rng: ThreadRng,
     running: bool,
     scheduler_context: Arc<ContinuousBatchSchedulerContext>,
@@ -89,6 +104,7 @@ impl ContinuousBatchScheduler {
             command_rx,
             llama_context,
             pending_embedding_requests: VecDeque::new(),
+            prefix_cache: ContinuousBatchPrefixCache::default(),
             rng: rand::rng(),
             running: true,
             scheduler_context,
@@ -105,9 +121,10 @@ impl ContinuousBatchScheduler {
 
         while self.running {
             self.remove_completed_requests();
+            // Goes before new generation requests, so it gets the sequences that just finished
+            self.advance_embedding_requests();
             self.accept_new_commands();
             self.check_stop_signals();
-            self.try_process_embedding_request();
 
             if self.has_active_requests() {
                 if let Err(err) = self.execute_one_iteration() {
@@ -116,7 +133,7 @@ impl ContinuousBatchScheduler {
                         self.scheduler_context.agent_name
                     );
                 }
-            } else {
+            } else if self.pending_embedding_requests.is_empty() {
                 match self.command_rx.recv_timeout(Duration::from_millis(10)) {
                     Ok(command) => self.process_command(command),
                     Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
@@ -132,7 +149,7 @@ impl ContinuousBatchScheduler {
         }
 
         while !self.active_requests.is_empty() {
-            self.cleanup_completed_request(0);
+            self.discard_completed_request(0);
         }
 
         self.llama_context.synchronize();
@@ -167,7 +184,7 @@ impl ContinuousBatchScheduler {
                 self.accept_raw_prompt_request(request);
             }
             ContinuousBatchSchedulerCommand::GenerateEmbeddingBatch(request) => {
-                self.pending_embedding_requests.push_back(request);
+                self.accept_embedding_batch_request(request);
             }
             ContinuousBatchSchedulerCommand::Shutdown => {
                 self.running = false;
@@ -175,6 +192,23 @@ impl ContinuousBatchScheduler {
         }
     }
 
+    fn accept_embedding_batch_request(&mut self, request: GenerateEmbeddingBatchRequest) {
+        let processor = ContinuousBatchEmbeddingProcessor::new(
+            &mut self.llama_context,
+            &self.scheduler_context,
+        );
+
+        match processor.prepare_embedding_batch(request) {
+            Ok(prepared_request) => self.pending_embedding_requests.push_back(prepared_request),
+            Err(err) => {
+                error!(
+                    "{:?}: failed to prepare embedding batch: {err:#}",
+                    self.scheduler_context.agent_name
+                );
+            }
+        }
+    }
+
     fn accept_conversation_history_request(
         &mut self,
         request: ContinueFromConversationHistoryRequest,
@@ -201,12 +235,22 @@ impl ContinuousBatchScheduler {
         match prepared {
             PreparedConversationHistoryRequest::TextPrompt {
                 raw_prompt,
+                logprobs,
                 max_tokens,
+                sampling,
+                stop,
+                reasoning_detector,
+                tool_call_detector,
                 grammar_sampler,
             } => {
                 self.accept_text_prompt(
                     &raw_prompt,
+                    logprobs,
                     max_tokens,
+                    &sampling,
+                    stop,
+                    reasoning_detector,
+                    tool_call_detector,
                     grammar_sampler,
                     generated_tokens_tx,
                     generate_tokens_stop_rx,
@@ -215,7 +259,12 @@ impl ContinuousBatchScheduler {
             PreparedConversationHistoryRequest::MultimodalPrompt {
                 raw_prompt,
                 images,
+                logprobs,
                 max_tokens,
+                sampling,
+                stop,
+                reasoning_detector,
+                tool_call_detector,
                 grammar_sampler,
             } => {
                 let multimodal_context = self.scheduler_context.multimodal_context.clone();
@@ -225,7 +274,12 @@ impl ContinuousBatchScheduler {
                         multimodal_context,
                         raw_prompt,
                         &images,
+                        logprobs,
                         max_tokens,
+                        &sampling,
+                        stop,
+                        reasoning_detector,
+                        tool_call_detector,
                         grammar_sampler,
                         generated_tokens_tx,
                         generate_tokens_stop_rx,
@@ -243,11 +297,29 @@ impl ContinuousBatchScheduler {
             params:
                 ContinueFromRawPromptParams {
                     grammar,
+                    logprobs,
                     max_tokens,
+                    model: _,
+                    priority: _,
                     raw_prompt,
+                    sampling,
+                    stop,
+                    truncation,
                 },
         }: ContinueFromRawPromptRequest,
     ) {
+        let sampling = match resolve_sampling_parameters(sampling, &generated_tokens_tx) {
+            Ok(sampling) => sampling,
+            Err(err) => {
+                error!(
+                    "{:?}: failed to resolve sampling parameters: {err}",
+                    self.scheduler_context.agent_name
+                );
+
+                return;
+            }
+        };
+
         let grammar_sampler = match resolve_grammar(grammar.as_ref(), false, &generated_tokens_tx) {
             Ok(sampler) => sampler,
             Err(err) => {
@@ -260,31 +332,72 @@ impl ContinuousBatchScheduler {
             }
         };
 
+        let raw_prompt = match fit_raw_prompt_in_context(
+            raw_prompt,
+            truncation,
+            max_tokens,
+            &generated_tokens_tx,
+            &self.scheduler_context,
+        ) {
+            Ok(raw_prompt) => raw_prompt,
+            Err(err) => {
+                error!(
+                    "{:?}: failed to fit prompt in context: {err}",
+                    self.scheduler_context.agent_name
+                );
+
+                return;
+            }
+        };
+
         self.accept_text_prompt(
             &raw_prompt,
+            logprobs,
             max_tokens,
+            &sampling,
+            stop,
+            ReasoningDetector::new(None, &raw_prompt),
+            ToolCallDetector::new(None, vec![]),
             grammar_sampler,
             generated_tokens_tx,
             generate_tokens_stop_rx,
         );
     }
 
-    fn create_sampler_chain(&mut self) -> LlamaSampler {
-        LlamaSampler::chain_simple([
-            LlamaSampler::penalties(
-                self.scheduler_context.inference_parameters.penalty_last_n,
-                self.scheduler_context.inference_parameters.penalty_repeat,
-                self.scheduler_context
-                    .inference_parameters
-                    .penalty_frequency,
-                self.scheduler_context.inference_parameters.penalty_presence,
-            ),
-            LlamaSampler::top_k(self.scheduler_context.inference_parameters.top_k),
-            LlamaSampler::top_p(self.scheduler_context.inference_parameters.top_p, 0),
-            LlamaSampler::min_p(self.scheduler_context.inference_parameters.min_p, 0),
-            LlamaSampler::temp(self.scheduler_context.inference_parameters.temperature),
-            LlamaSampler::dist(self.rng.random::<u32>()),
-        ])
+    /// The request's own seed takes precedence over the one the agent was configured with
+    fn resolve_seed(&mut self, sampling: &SamplingParameters) -> u32 {
+        sampling
+            .seed
+            .or(self.scheduler_context.inference_parameters.seed)
+            .unwrap_or_else(|| self.rng.random::<u32>())
+    }
+
+    fn create_sampler_chain(
+        &self,
+        sampling: &SamplingParameters,
+        seed: u32,
+        generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    ) -> Result<LlamaSampler> {
+        build_sampler_chain(&self.scheduler_context, sampling, seed).map_err(|err| {
+            let message = format!(
+                "{:?}: failed to initialize sampler chain: {err}",
+                self.scheduler_context.agent_name
+            );
+
+            error!("{message}");
+
+            if generated_tokens_tx
+                .send(GeneratedTokenResult::SamplerError(message.clone()))
+                .is_err()
+            {
+                warn!(
+                    "{:?}: failed to send result to client (receiver dropped)",
+                    self.scheduler_context.agent_name
+                );
+            }
+
+            anyhow!(message)
+        })
     }
 
     fn create_grammar_llama_sampler(
@@ -324,42 +437,23 @@ impl ContinuousBatchScheduler {
         )
     }
 
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "text prompt handling genuinely requires all these parameters from the caller"
+    )]
     fn accept_text_prompt(
         &mut self,
         prompt: &str,
+        logprobs: Option<u32>,
         max_tokens: i32,
+        sampling: &SamplingParameters,
+        stop: Vec<String>,
+        reasoning_detector: ReasoningDetector,
+        tool_call_detector: ToolCallDetector,
         grammar_sampler: Option<GrammarSampler>,
         generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
     ) {
-        let mut sequence_id_option = self.sequence_id_pool.acquire();
-
-        if sequence_id_option.is_none() {
-            self.remove_completed_requests();
-            sequence_id_option = self.sequence_id_pool.acquire();
-        }
-
-        let Some(sequence_id) = sequence_id_option else {
-            let message = format!(
-                "{:?}: no available sequence slots, all slots are busy",
-                self.scheduler_context.agent_name
-            );
-
-            error!("{message}");
-
-            if generated_tokens_tx
-                .send(GeneratedTokenResult::SamplerError(message))
-                .is_err()
-            {
-                warn!(
-                    "{:?}: failed to send result to client (receiver dropped)",
-                    self.scheduler_context.agent_name
-                );
-            }
-
-            return;
-        };
-
         let prompt_tokens = match self
             .scheduler_context
             .model
@@ -373,7 +467,6 @@ impl ContinuousBatchScheduler {
                 );
 
                 error!("{message}");
-                self.sequence_id_pool.release(sequence_id);
 
                 if generated_tokens_tx
                     .send(GeneratedTokenResult::SamplerError(message))
@@ -389,52 +482,87 @@ impl ContinuousBatchScheduler {
             }
         };
 
+        let Some((sequence_id, reusable_tokens)) = self.acquire_sequence_for_prompt(&prompt_tokens)
+        else {
+            let message = format!(
+                "{:?}: no available sequence slots, all slots are busy",
+                self.scheduler_context.agent_name
+            );
+
+            error!("{message}");
+
+            if generated_tokens_tx
+                .send(GeneratedTokenResult::SamplerError(message))
+                .is_err()
+            {
+                warn!(
+                    "{:?}: failed to send result to client (receiver dropped)",
+                    self.scheduler_context.agent_name
+                );
+            }
+
+            return;
+        };
+
         let Ok(llama_grammar_sampler) =
             self.create_grammar_llama_sampler(grammar_sampler, &generated_tokens_tx)
         else {
-            self.sequence_id_pool.release(sequence_id);
+            self.release_sequence(sequence_id);
 
             return;
         };
 
-        let chain = self.create_sampler_chain();
+        let seed = self.resolve_seed(sampling);
+        let Ok(chain) = self.create_sampler_chain(sampling, seed, &generated_tokens_tx) else {
+            self.release_sequence(sequence_id);
 
-        #[expect(
-            clippy::cast_sign_loss,
-            reason = "sequence IDs are always non-negative"
-        )]
-        if let Err(err) =
-            self.llama_context
-                .clear_kv_cache_seq(Some(sequence_id as u32), None, None)
-        {
-            error!(
-                "{:?}: failed to clear KV cache for sequence {sequence_id}: {err}",
-                self.scheduler_context.agent_name
-            );
+            return;
+        };
+        let reused_tokens = self.trim_kv_cache_seq(sequence_id, reusable_tokens);
+
+        if reused_tokens > 0 {
+            self.slot_aggregated_status.register_prefix_cache_hit();
+        } else {
+            self.slot_aggregated_status.register_prefix_cache_miss();
         }
 
         self.slot_aggregated_status.take_slot();
 
         debug!(
-            "{:?}: accepted text prompt request on sequence {sequence_id} ({} tokens)",
+            "{:?}: accepted text prompt request on sequence {sequence_id} ({} tokens, {reused_tokens} reused from the prefix cache)",
             self.scheduler_context.agent_name,
             prompt_tokens.len()
         );
 
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_possible_wrap,
+            reason = "token positions fit in i32 for llama.cpp FFI"
+        )]
+        let current_token_position = reused_tokens as i32;
+        let prompt_tokens_count = prompt_tokens.len();
+
         self.active_requests.push(ContinuousBatchActiveRequest {
             chain,
-            current_token_position: 0,
+            current_token_position,
             grammar_sampler: llama_grammar_sampler,
+            generated_tokens: Vec::new(),
             generated_tokens_count: 0,
             generated_tokens_tx,
             generate_tokens_stop_rx,
             i_batch: None,
+            logprobs,
             max_tokens,
             pending_sampled_token: None,
             phase: ContinuousBatchRequestPhase::Ingesting,
             prompt_tokens,
-            prompt_tokens_ingested: 0,
+            prompt_tokens_count,
+            prompt_tokens_ingested: reused_tokens,
+            reasoning_detector,
+            seed,
             sequence_id,
+            stop_sequence_detector: StopSequenceDetector::new(stop),
+            tool_call_detector,
             utf8_decoder: encoding_rs::UTF_8.new_decoder(),
         });
     }
@@ -448,12 +576,17 @@ impl ContinuousBatchScheduler {
         multimodal_context: &MtmdContext,
         prompt: String,
         images: &[DecodedImage],
+        logprobs: Option<u32>,
         max_tokens: i32,
+        sampling: &SamplingParameters,
+        stop: Vec<String>,
+        reasoning_detector: ReasoningDetector,
+        tool_call_detector: ToolCallDetector,
         grammar_sampler: Option<GrammarSampler>,
         generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
     ) {
-        let Some(sequence_id) = self.sequence_id_pool.acquire() else {
+        let Some(sequence_id) = self.acquire_sequence_id() else {
             let message = format!(
                 "{:?}: no available sequence slots for multimodal request",
                 self.scheduler_context.agent_name
@@ -610,7 +743,12 @@ impl ContinuousBatchScheduler {
             return;
         };
 
-        let chain = self.create_sampler_chain();
+        let seed = self.resolve_seed(sampling);
+        let Ok(chain) = self.create_sampler_chain(sampling, seed, &generated_tokens_tx) else {
+            self.sequence_id_pool.release(sequence_id);
+
+            return;
+        };
 
         self.slot_aggregated_status.take_slot();
 
@@ -623,16 +761,23 @@ impl ContinuousBatchScheduler {
             chain,
             current_token_position: tokens_ingested,
             grammar_sampler: llama_grammar_sampler,
+            generated_tokens: Vec::new(),
             generated_tokens_count: 0,
             generated_tokens_tx,
             generate_tokens_stop_rx,
             i_batch: Some(-1),
+            logprobs,
             max_tokens,
             pending_sampled_token: None,
             phase: ContinuousBatchRequestPhase::Generating,
             prompt_tokens: Vec::new(),
+            prompt_tokens_count: usize::try_from(tokens_ingested).unwrap_or(0),
             prompt_tokens_ingested: 0,
+            reasoning_detector,
+            seed,
             sequence_id,
+            stop_sequence_detector: StopSequenceDetector::new(stop),
+            tool_call_detector,
             utf8_decoder: encoding_rs::UTF_8.new_decoder(),
         });
     }
@@ -703,50 +848,102 @@ impl ContinuousBatchScheduler {
     fn check_stop_signals(&mut self) {
         for active_request in &mut self.active_requests {
             if active_request.is_stop_requested() {
-                active_request.complete_with_outcome(
-                    &self.scheduler_context.agent_name,
-                    GeneratedTokenResult::Done,
-                );
+                active_request.finish(&self.scheduler_context.agent_name, FinishReason::Cancelled);
             }
         }
     }
 
-    fn try_process_embedding_request(&mut self) {
-        let Some(request) = self.pending_embedding_requests.pop_front() else {
+    /// Embeds one batch of the oldest embedding request in sequences that no generation request
+    /// is using, so embeddings and generation take turns instead of waiting for each other
+    fn advance_embedding_requests(&mut self) {
+        let Some(mut request) = self.pending_embedding_requests.pop_front() else {
             return;
         };
 
-        if self.has_active_requests() {
-            if request
-                .generated_embedding_tx
-                .send(EmbeddingResult::Error(
-                    "Embedding requests cannot be processed while generation requests are active"
-                        .to_owned(),
-                ))
-                .is_err()
-            {
-                warn!(
-                    "{:?}: failed to send result to client (receiver dropped)",
+        if request.generate_embedding_stop_rx.try_recv().is_ok() {
+            request.pending_inputs.clear();
+        }
+
+        if !request.pending_inputs.is_empty() {
+            let sequence_ids = self.acquire_embedding_sequence_ids(request.pending_inputs.len());
+
+            if sequence_ids.is_empty() {
+                // Every sequence is generating, so the request waits for one of them to finish
+                self.pending_embedding_requests.push_front(request);
+
+                return;
+            }
+
+            // The embedding batch decode overwrites the logits the generating sequences sample from
+            self.harvest_pending_samples_before_external_decode();
+
+            let mut processor = ContinuousBatchEmbeddingProcessor::new(
+                &mut self.llama_context,
+                &self.scheduler_context,
+            );
+            let processed = processor.process_next_embedding_batch(&mut request, &sequence_ids);
+
+            for sequence_id in sequence_ids {
+                self.release_sequence(sequence_id);
+            }
+
+            if let Err(err) = processed {
+                let message = format!(
+                    "{:?}: failed to process embedding batch: {err:#}",
                     self.scheduler_context.agent_name
                 );
+
+                error!("{message}");
+
+                if request
+                    .generated_embedding_tx
+                    .send(EmbeddingResult::Error(message))
+                    .is_err()
+                {
+                    warn!(
+                        "{:?}: failed to send result to client (receiver dropped)",
+                        self.scheduler_context.agent_name
+                    );
+                }
+
+                return;
             }
 
-            return;
-        }
+            if !request.pending_inputs.is_empty() {
+                self.pending_embedding_requests.push_front(request);
 
-        let mut processor = ContinuousBatchEmbeddingProcessor::new(
-            &mut self.llama_context,
-            &self.scheduler_context,
-        );
+                return;
+            }
+        }
 
-        if let Err(err) = processor.process_embedding_batch(request) {
-            error!(
-                "{:?}: failed to process embedding batch: {err:#}",
+        if request
+            .generated_embedding_tx
+            .send(EmbeddingResult::Done)
+            .is_err()
+        {
+            warn!(
+                "{:?}: failed to send result to client (receiver dropped)",
                 self.scheduler_context.agent_name
             );
         }
     }
 
+    /// Takes up to `max_sequences` of the free sequences. Cached prefixes are only given up when
+    /// there are no free sequences at all.
+    fn acquire_embedding_sequence_ids(&mut self, max_sequences: usize) -> Vec<i32> {
+        let mut sequence_ids: Vec<i32> = std::iter::from_fn(|| self.sequence_id_pool.acquire())
+            .take(max_sequences)
+            .collect();
+
+        if sequence_ids.is_empty()
+            && let Some(sequence_id) = self.acquire_sequence_id()
+        {
+            sequence_ids.push(sequence_id);
+        }
+
+        sequence_ids
+    }
+
     fn has_active_requests(&self) -> bool {
         self.active_requests
             .iter()
@@ -804,6 +1001,12 @@ impl ContinuousBatchScheduler {
                     return Ok(());
                 }
                 Err(DecodeError::NoKvCacheSlot) => {
+                    if let Some(sequence_id) = self.prefix_cache.take_least_recently_retained() {
+                        self.release_sequence(sequence_id);
+
+                        continue;
+                    }
+
                     self.evict_largest_sequence();
 
                     if self.active_requests.is_empty() {
@@ -884,13 +1087,51 @@ impl ContinuousBatchScheduler {
             };
 
             if self.scheduler_context.model.is_eog_token(sampled_token) {
-                active_request.complete_with_outcome(
-                    &self.scheduler_context.agent_name,
-                    GeneratedTokenResult::Done,
-                );
+                active_request.finish(&self.scheduler_context.agent_name, FinishReason::Eos);
                 continue;
             }
 
+            if let Some(top_logprobs) = active_request.logprobs {
+                let token_logprobs = match token_logprobs_at_batch_index(
+                    &self.llama_context,
+                    &self.scheduler_context.model,
+                    batch_index,
+                    sampled_token,
+                    top_logprobs,
+                ) {
+                    Ok(token_logprobs) => token_logprobs,
+                    Err(err) => {
+                        error!(
+                            "{:?}: sequence {} failed to compute logprobs: {err:#}",
+                            self.scheduler_context.agent_name, active_request.sequence_id
+                        );
+                        active_request.complete_with_outcome(
+                            &self.scheduler_context.agent_name,
+                            GeneratedTokenResult::SamplerError(format!(
+                                "Failed to compute logprobs: {err:#}"
+                            )),
+                        );
+                        continue;
+                    }
+                };
+
+                if active_request
+                    .generated_tokens_tx
+                    .send(GeneratedTokenResult::TokenLogprobs(token_logprobs))
+                    .is_err()
+                {
+                    warn!(
+                        "{:?}: sequence {} client disconnected (receiver dropped)",
+                        self.scheduler_context.agent_name, active_request.sequence_id
+                    );
+
+                    active_request.i_batch = None;
+                    active_request.phase = ContinuousBatchRequestPhase::Completed;
+
+                    continue;
+                }
+            }
+
             let output_string = match self.scheduler_context.model.token_to_piece(
                 sampled_token,
                 &mut active_request.utf8_decoder,
@@ -913,11 +1154,27 @@ impl ContinuousBatchScheduler {
                 }
             };
 
-            if active_request
-                .generated_tokens_tx
-                .send(GeneratedTokenResult::Token(output_string))
-                .is_err()
+            let released_results = match active_request.stop_sequence_detector.push(&output_string)
             {
+                StopSequenceOutcome::Pending => vec![],
+                StopSequenceOutcome::Release(released_text) => {
+                    active_request.separate_released_text(&released_text)
+                }
+                StopSequenceOutcome::Stopped(stop_sequence) => {
+                    active_request.finish(
+                        &self.scheduler_context.agent_name,
+                        FinishReason::StopSequence(stop_sequence),
+                    );
+                    continue;
+                }
+            };
+
+            if released_results.into_iter().any(|released_result| {
+                active_request
+                    .generated_tokens_tx
+                    .send(released_result)
+                    .is_err()
+            }) {
                 warn!(
                     "{:?}: sequence {} client disconnected (receiver dropped)",
                     self.scheduler_context.agent_name, active_request.sequence_id
@@ -932,10 +1189,7 @@ impl ContinuousBatchScheduler {
             active_request.generated_tokens_count += 1;
 
             if active_request.generated_tokens_count >= active_request.max_tokens {
-                active_request.complete_with_outcome(
-                    &self.scheduler_context.agent_name,
-                    GeneratedTokenResult::Done,
-                );
+                active_request.finish(&self.scheduler_context.agent_name, FinishReason::MaxTokens);
                 continue;
             }
 
@@ -1055,7 +1309,10 @@ impl ContinuousBatchScheduler {
         for contribution in generating_contributions {
             let request = &mut self.active_requests[contribution.request_index];
 
-            request.pending_sampled_token = None;
+            if let Some(decoded_token) = request.pending_sampled_token.take() {
+                request.generated_tokens.push(decoded_token);
+            }
+
             request.i_batch = Some(contribution.batch_position);
             request.current_token_position += 1;
         }
@@ -1098,22 +1355,9 @@ impl ContinuousBatchScheduler {
                 evicted_request.current_token_position
             );
 
-            if evicted_request
-                .generated_tokens_tx
-                .send(GeneratedTokenResult::SamplerError(
-                    "Request evicted due to KV cache pressure".to_owned(),
-                ))
-                .is_err()
-            {
-                warn!(
-                    "{:?}: failed to send result to client (receiver dropped)",
-                    self.scheduler_context.agent_name
-                );
-            }
-
-            evicted_request.phase = ContinuousBatchRequestPhase::Completed;
+            evicted_request.finish(&self.scheduler_context.agent_name, FinishReason::Evicted);
 
-            self.cleanup_completed_request(eviction_index);
+            self.discard_completed_request(eviction_index);
         }
     }
 
@@ -1132,25 +1376,103 @@ impl ContinuousBatchScheduler {
         }
     }
 
-    fn cleanup_completed_request(&mut self, index: usize) {
-        let removed_request = self.active_requests.swap_remove(index);
+    fn acquire_sequence_id(&mut self) -> Option<i32> {
+        if let Some(sequence_id) = self.sequence_id_pool.acquire() {
+            return Some(sequence_id);
+        }
+
+        self.remove_completed_requests();
+
+        if let Some(sequence_id) = self.sequence_id_pool.acquire() {
+            return Some(sequence_id);
+        }
+
+        let sequence_id = self.prefix_cache.take_least_recently_retained()?;
+
+        self.release_sequence(sequence_id);
+        self.sequence_id_pool.acquire()
+    }
+
+    /// Picks the cached sequence sharing the longest prefix with the prompt, or a fresh one.
+    /// Returns the sequence id and the number of prompt tokens already in its KV cache.
+    fn acquire_sequence_for_prompt(
+        &mut self,
+        prompt_tokens: &[LlamaToken],
+    ) -> Option<(i32, usize)> {
+        self.remove_completed_requests();
 
+        if let Some(cached_prefix) = self.prefix_cache.take_longest_prefix(prompt_tokens) {
+            return Some(cached_prefix);
+        }
+
+        self.acquire_sequence_id()
+            .map(|sequence_id| (sequence_id, 0))
+    }
+
+    /// Removes everything past the reused prefix from the sequence. Returns how many tokens
+    /// were actually kept, as some models (recurrent ones) can only clear whole sequences.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "sequence IDs are always non-negative and token positions fit in u32"
+    )]
+    fn trim_kv_cache_seq(&mut self, sequence_id: i32, reusable_tokens: usize) -> usize {
+        match self.llama_context.clear_kv_cache_seq(
+            Some(sequence_id as u32),
+            Some(reusable_tokens as u32),
+            None,
+        ) {
+            Ok(true) => reusable_tokens,
+            Ok(false) => {
+                debug!(
+                    "{:?}: sequence {sequence_id} cannot be partially cleared, ingesting the whole prompt",
+                    self.scheduler_context.agent_name
+                );
+
+                if let Err(err) =
+                    self.llama_context
+                        .clear_kv_cache_seq(Some(sequence_id as u32), None, None)
+                {
+                    error!(
+                        "{:?}: failed to clear KV cache for sequence {sequence_id}: {err}",
+                        self.scheduler_context.agent_name
+                    );
+                }
+
+                0
+            }
+            Err(err) => {
+                error!(
+                    "{:?}: failed to clear KV cache for sequence {sequence_id}: {err}",
+                    self.scheduler_context.agent_name
+                );
+
+                0
+            }
+        }
+    }
+
+    fn release_sequence(&mut self, sequence_id: i32) {
         #[expect(
             clippy::cast_sign_loss,
             reason = "sequence IDs are always non-negative"
         )]
-        if let Err(err) = self.llama_context.clear_kv_cache_seq(
-            Some(removed_request.sequence_id as u32),
-            None,
-            None,
-        ) {
+        if let Err(err) =
+            self.llama_context
+                .clear_kv_cache_seq(Some(sequence_id as u32), None, None)
+        {
             error!(
-                "{:?}: failed to clear KV cache for sequence {}: {err}",
-                self.scheduler_context.agent_name, removed_request.sequence_id
+                "{:?}: failed to clear KV cache for sequence {sequence_id}: {err}",
+                self.scheduler_context.agent_name
             );
         }
 
-        self.sequence_id_pool.release(removed_request.sequence_id);
+        self.sequence_id_pool.release(sequence_id);
+    }
+
+    fn remove_active_request(&mut self, index: usize) -> ContinuousBatchActiveRequest {
+        let removed_request = self.active_requests.swap_remove(index);
+
         self.slot_aggregated_status.release_slot();
 
         debug!(
@@ -1159,5 +1481,26 @@ impl ContinuousBatchScheduler {
             removed_request.sequence_id,
             removed_request.generated_tokens_count,
         );
+
+        removed_request
+    }
+
+    /// Keeps the KV cells of the finished sequence around so later requests can reuse its prefix.
+    fn cleanup_completed_request(&mut self, index: usize) {
+        let removed_request = self.remove_active_request(index);
+        let sequence_id = removed_request.sequence_id;
+
+        match removed_request.into_decoded_tokens() {
+            Some(decoded_tokens) if decoded_tokens.len() >= MINIMUM_REUSED_PREFIX_TOKENS => {
+                self.prefix_cache.retain(sequence_id, decoded_tokens);
+            }
+            _ => self.release_sequence(sequence_id),
+        }
+    }
+
+    fn discard_completed_request(&mut self, index: usize) {
+        let removed_request = self.remove_active_request(index);
+
+        self.release_sequence(removed_request.sequence_id);
     }
 }
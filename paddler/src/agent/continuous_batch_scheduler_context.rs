@@ -3,8 +3,11 @@ use std::sync::Arc;
 
 use llama_cpp_bindings::model::LlamaModel;
 use llama_cpp_bindings::mtmd::MtmdContext;
+use llama_cpp_bindings::token::logit_bias::LlamaLogitBias;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::reasoning_markers::ReasoningMarkers;
 
+use crate::agent::tool_call_format::ToolCallFormat;
 use crate::chat_template_renderer::ChatTemplateRenderer;
 
 pub struct ContinuousBatchSchedulerContext {
@@ -12,10 +15,28 @@ pub struct ContinuousBatchSchedulerContext {
     pub chat_template_renderer: Arc<ChatTemplateRenderer>,
     pub desired_slots_total: i32,
     pub inference_parameters: InferenceParameters,
+    /// `InferenceParameters::logit_bias` resolved with the model's vocabulary
+    pub logit_biases: Vec<LlamaLogitBias>,
     pub model: Arc<LlamaModel>,
     pub model_path: PathBuf,
     pub multimodal_context: Option<Arc<MtmdContext>>,
+    pub reasoning_markers: Option<ReasoningMarkers>,
     pub token_bos_str: String,
     pub token_eos_str: String,
     pub token_nl_str: String,
+    pub tool_call_format: Option<ToolCallFormat>,
+}
+
+impl ContinuousBatchSchedulerContext {
+    /// The KV cache is not unified, so every slot gets an equal share of the context
+    #[must_use]
+    pub fn sequence_context_size(&self) -> usize {
+        let context_size =
+            usize::try_from(self.inference_parameters.context_size).unwrap_or(usize::MAX);
+        let slots_total = usize::try_from(self.desired_slots_total)
+            .unwrap_or(1)
+            .max(1);
+
+        context_size / slots_total
+    }
 }
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use llama_cpp_bindings::model::AddBos;
+use log::error;
+use log::warn;
+use paddler_types::context_overflow::ContextOverflow;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::truncation_policy::TruncationPolicy;
+use tokio::sync::mpsc;
+
+use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
+use crate::agent::truncate_prompt_middle_out::truncate_prompt_middle_out;
+
+pub fn count_prompt_tokens(
+    prompt: &str,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<usize> {
+    scheduler_context
+        .model
+        .str_to_token(prompt, AddBos::Always)
+        .map(|tokens| tokens.len())
+        .map_err(|err| {
+            let message = format!(
+                "{:?}: failed to tokenize prompt: {err}",
+                scheduler_context.agent_name
+            );
+
+            error!("{message}");
+
+            if generated_tokens_tx
+                .send(GeneratedTokenResult::SamplerError(message.clone()))
+                .is_err()
+            {
+                warn!(
+                    "{:?}: failed to send result to client (receiver dropped)",
+                    scheduler_context.agent_name
+                );
+            }
+
+            anyhow!(message)
+        })
+}
+
+pub fn report_context_overflow(
+    context_overflow: ContextOverflow,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> anyhow::Error {
+    let message = format!("{:?}: {context_overflow}", scheduler_context.agent_name);
+
+    error!("{message}");
+
+    if generated_tokens_tx
+        .send(GeneratedTokenResult::ContextOverflow(context_overflow))
+        .is_err()
+    {
+        warn!(
+            "{:?}: failed to send result to client (receiver dropped)",
+            scheduler_context.agent_name
+        );
+    }
+
+    anyhow!(message)
+}
+
+/// Cuts tokens out of the middle of a prompt that, along with `max_tokens`, overflows the context
+pub fn truncate_overflowing_prompt_middle_out(
+    raw_prompt: &str,
+    context_overflow: ContextOverflow,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<String> {
+    let max_prompt_tokens = context_overflow
+        .context_size
+        .saturating_sub(context_overflow.max_tokens);
+
+    let truncated_prompt =
+        truncate_prompt_middle_out(&scheduler_context.model, raw_prompt, max_prompt_tokens)
+            .map_err(|err| {
+                let message = format!(
+                    "{:?}: failed to truncate prompt: {err}",
+                    scheduler_context.agent_name
+                );
+
+                error!("{message}");
+
+                if generated_tokens_tx
+                    .send(GeneratedTokenResult::SamplerError(message.clone()))
+                    .is_err()
+                {
+                    warn!(
+                        "{:?}: failed to send result to client (receiver dropped)",
+                        scheduler_context.agent_name
+                    );
+                }
+
+                anyhow!(message)
+            })?;
+
+    truncated_prompt.ok_or_else(|| {
+        report_context_overflow(context_overflow, generated_tokens_tx, scheduler_context)
+    })
+}
+
+/// Applies the truncation policy, if there is one, when a raw prompt leaves no room for
+/// `max_tokens` in the sequence's share of the context
+pub fn fit_raw_prompt_in_context(
+    raw_prompt: String,
+    truncation: Option<TruncationPolicy>,
+    max_tokens: i32,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<String> {
+    let Some(truncation) = truncation else {
+        return Ok(raw_prompt);
+    };
+
+    let context_size = scheduler_context.sequence_context_size();
+    let max_tokens = usize::try_from(max_tokens).unwrap_or(0);
+    let prompt_tokens = count_prompt_tokens(&raw_prompt, generated_tokens_tx, scheduler_context)?;
+
+    if prompt_tokens <= context_size.saturating_sub(max_tokens) {
+        return Ok(raw_prompt);
+    }
+
+    let context_overflow = ContextOverflow {
+        context_size,
+        max_tokens,
+        prompt_tokens,
+    };
+
+    match truncation {
+        TruncationPolicy::MiddleOut => truncate_overflowing_prompt_middle_out(
+            &raw_prompt,
+            context_overflow,
+            generated_tokens_tx,
+            scheduler_context,
+        ),
+        // A raw prompt has no messages to drop
+        TruncationPolicy::DropOldestMessages | TruncationPolicy::Reject => Err(
+            report_context_overflow(context_overflow, generated_tokens_tx, scheduler_context),
+        ),
+    }
+}
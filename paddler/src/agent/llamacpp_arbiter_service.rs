@@ -170,6 +170,9 @@ impl LlamaCppArbiterService {
             info!("Reconciled state change applied successfully");
         }
 
+        self.slot_aggregated_status_manager
+            .slot_aggregated_status
+            .increment_state_application_count();
         self.slot_aggregated_status_manager
             .slot_aggregated_status
             .set_state_application_status(AgentStateApplicationStatus::Applied);
@@ -11,10 +11,12 @@ use log::debug;
 use log::error;
 use log::info;
 use log::warn;
+use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::time::Duration;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
+use tokio::time::sleep;
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::protocol::Message;
 use tokio_util::sync::CancellationToken;
@@ -35,6 +37,7 @@ use crate::agent::jsonrpc::Response as JsonRpcResponse;
 use crate::agent::jsonrpc::notification_params::VersionParams;
 use crate::agent::model_metadata_holder::ModelMetadataHolder;
 use crate::agent::receive_stream_stopper_collection::ReceiveStreamStopperCollection;
+use crate::agent::reconnect_backoff::ReconnectBackoff;
 use crate::agent_applicable_state_holder::AgentApplicableStateHolder;
 use crate::agent_desired_state::AgentDesiredState;
 use crate::balancer::management_service::http_route::api::ws_agent_socket::jsonrpc::Message as ManagementJsonRpcMessage;
@@ -46,17 +49,21 @@ use crate::service::Service;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
 use crate::subscribes_to_updates::SubscribesToUpdates as _;
 
+/// Responses queue up here while the agent reconnects. Once it is full, in-flight requests wait
+/// for the connection to come back instead of piling up more of them.
+const MESSAGE_QUEUE_CAPACITY: usize = 1024;
+
 struct IncomingMessageContext {
     agent_applicable_state_holder: Arc<AgentApplicableStateHolder>,
     agent_desired_state_tx: mpsc::UnboundedSender<AgentDesiredState>,
-    connection_close: CancellationToken,
     continue_from_conversation_history_request_tx:
         mpsc::UnboundedSender<ContinueFromConversationHistoryRequest>,
     continue_from_raw_prompt_request_tx: mpsc::UnboundedSender<ContinueFromRawPromptRequest>,
     generate_embedding_batch_request_tx: mpsc::UnboundedSender<GenerateEmbeddingBatchRequest>,
     model_metadata_holder: Arc<ModelMetadataHolder>,
     receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
-    message_tx: mpsc::UnboundedSender<ManagementJsonRpcMessage>,
+    message_tx: mpsc::Sender<ManagementJsonRpcMessage>,
+    shutdown: CancellationToken,
 }
 
 pub struct ManagementSocketClientService {
@@ -66,18 +73,56 @@ pub struct ManagementSocketClientService {
         mpsc::UnboundedSender<ContinueFromConversationHistoryRequest>,
     pub continue_from_raw_prompt_request_tx: mpsc::UnboundedSender<ContinueFromRawPromptRequest>,
     pub generate_embedding_batch_request_tx: mpsc::UnboundedSender<GenerateEmbeddingBatchRequest>,
+    pub join_secret: Option<String>,
     pub model_metadata_holder: Arc<ModelMetadataHolder>,
+    pub model_pool: Option<String>,
     pub name: Option<String>,
     pub receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
+    /// How long in-flight requests keep going while the agent reconnects; the balancer holds
+    /// them for about as long before it gives up on the agent
+    pub reconnect_grace_period: Duration,
     pub slot_aggregated_status: Arc<SlotAggregatedStatus>,
     pub socket_url: String,
+    pub weight: u32,
 }
 
 impl ManagementSocketClientService {
+    /// Once the grace period runs out the balancer has failed the in-flight requests already, so
+    /// there is no point in generating or queueing responses for them
+    async fn abandon_in_flight_requests_unless_reconnected(
+        message_rx: Arc<Mutex<mpsc::Receiver<ManagementJsonRpcMessage>>>,
+        receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
+        reconnect_grace_period: Duration,
+        reconnected: CancellationToken,
+        unsent_message: Arc<Mutex<Option<Message>>>,
+    ) {
+        tokio::select! {
+            () = reconnected.cancelled() => {}
+            () = sleep(reconnect_grace_period) => {
+                warn!(
+                    "Did not reconnect to the management server within {reconnect_grace_period:?}, stopping in-flight requests"
+                );
+
+                receive_stream_stopper_collection.stop_all();
+
+                // A new connection holds the lock for as long as it forwards the queue
+                tokio::select! {
+                    biased;
+
+                    () = reconnected.cancelled() => {}
+                    mut locked_message_rx = message_rx.lock() => {
+                        while locked_message_rx.try_recv().is_ok() {}
+
+                        unsent_message.lock().await.take();
+                    }
+                }
+            }
+        }
+    }
+
     async fn generate_responses<TRequest: FromRequestParams + 'static>(
-        connection_close: CancellationToken,
         id: String,
-        message_tx: mpsc::UnboundedSender<ManagementJsonRpcMessage>,
+        message_tx: mpsc::Sender<ManagementJsonRpcMessage>,
         request_params: TRequest::RequestParams,
         receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
         request_tx: mpsc::UnboundedSender<TRequest>,
@@ -95,25 +140,14 @@ impl ManagementSocketClientService {
             stop_rx,
         ))?;
 
-        loop {
-            tokio::select! {
-                () = connection_close.cancelled() => break,
-                response = response_rx.recv() => {
-                    match response {
-                        Some(response) => {
-                            message_tx.send(
-                                ManagementJsonRpcMessage::Response(
-                                    ResponseEnvelope {
-                                        request_id: id.clone(),
-                                        response: response.into(),
-                                    }
-                                ),
-                            )?;
-                        }
-                        None => break,
-                    }
-                }
-            }
+        // Keeps going while the agent reconnects, the responses wait in the queue or for room in it
+        while let Some(response) = response_rx.recv().await {
+            message_tx
+                .send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
+                    request_id: id.clone(),
+                    response: response.into(),
+                }))
+                .await?;
         }
 
         Ok(())
@@ -123,13 +157,13 @@ impl ManagementSocketClientService {
         IncomingMessageContext {
             agent_applicable_state_holder,
             agent_desired_state_tx,
-            connection_close,
             continue_from_conversation_history_request_tx,
             continue_from_raw_prompt_request_tx,
             generate_embedding_batch_request_tx,
             message_tx,
             model_metadata_holder,
             receive_stream_stopper_collection,
+            shutdown: _,
         }: IncomingMessageContext,
         deserialized_message: JsonRpcMessage,
     ) -> Result<()> {
@@ -179,7 +213,6 @@ impl ManagementSocketClientService {
                     ),
             }) => {
                 Self::generate_responses(
-                    connection_close,
                     id,
                     message_tx,
                     continue_from_conversation_history_params,
@@ -193,7 +226,6 @@ impl ManagementSocketClientService {
                 request: JsonRpcRequest::ContinueFromRawPrompt(generate_tokens_params),
             }) => {
                 Self::generate_responses(
-                    connection_close,
                     id,
                     message_tx,
                     generate_tokens_params,
@@ -207,7 +239,6 @@ impl ManagementSocketClientService {
                 request: JsonRpcRequest::GenerateEmbeddingBatch(generate_embedding_batch_params),
             }) => {
                 Self::generate_responses(
-                    connection_close,
                     id,
                     message_tx,
                     generate_embedding_batch_params,
@@ -219,8 +250,8 @@ impl ManagementSocketClientService {
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::GetChatTemplateOverride,
-            }) => Ok(
-                message_tx.send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
+            }) => Ok(message_tx
+                .send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
                     request_id: id,
                     response: JsonRpcResponse::ChatTemplateOverride(
                         if let Some(agent_applicable_state) =
@@ -231,19 +262,19 @@ impl ManagementSocketClientService {
                             None
                         },
                     ),
-                }))?,
-            ),
+                }))
+                .await?),
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::GetModelMetadata,
-            }) => Ok(
-                message_tx.send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
+            }) => Ok(message_tx
+                .send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
                     request_id: id,
                     response: JsonRpcResponse::ModelMetadata(
                         model_metadata_holder.get_model_metadata(),
                     ),
-                }))?,
-            ),
+                }))
+                .await?),
         }
     }
 
@@ -254,12 +285,12 @@ impl ManagementSocketClientService {
     ) -> Result<()> {
         match msg {
             Message::Text(text) => {
-                let connection_close = incoming_message_context.connection_close.clone();
+                let shutdown = incoming_message_context.shutdown.clone();
 
                 rt::spawn(async move {
                     tokio::select! {
-                        () = connection_close.cancelled() => {
-                            info!("Connection close signal received, shutting down");
+                        () = shutdown.cancelled() => {
+                            info!("Shutdown signal received, abandoning the message");
                         }
                         result = Self::handle_deserialized_message(
                             incoming_message_context,
@@ -302,7 +333,14 @@ impl ManagementSocketClientService {
         }
     }
 
-    async fn keep_connection_alive(&self, shutdown: CancellationToken) -> Result<()> {
+    async fn keep_connection_alive(
+        &self,
+        message_rx: Arc<Mutex<mpsc::Receiver<ManagementJsonRpcMessage>>>,
+        message_tx: mpsc::Sender<ManagementJsonRpcMessage>,
+        reconnected: CancellationToken,
+        shutdown: CancellationToken,
+        unsent_message: Arc<Mutex<Option<Message>>>,
+    ) -> Result<()> {
         info!("Connecting to management server at {}", self.socket_url);
 
         let (ws_stream, _response) = connect_async(self.socket_url.clone()).await?;
@@ -310,14 +348,49 @@ impl ManagementSocketClientService {
         info!("Connected to management server");
 
         let connection_close = CancellationToken::new();
-        let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (mut write, mut read) = ws_stream.split();
 
+        // Registers before anything queued up while the agent was disconnected goes out
+        write
+            .send(Message::Text(
+                serde_json::to_string(&ManagementJsonRpcMessage::Notification(
+                    ManagementJsonRpcNotification::RegisterAgent(RegisterAgentParams {
+                        join_secret: self.join_secret.clone(),
+                        model_pool: self.model_pool.clone(),
+                        name: self.name.clone(),
+                        slot_aggregated_status_snapshot: self
+                            .slot_aggregated_status
+                            .make_snapshot()
+                            .context("Failed to create slot aggregated status snapshot")?,
+                        weight: self.weight,
+                    }),
+                ))?
+                .into(),
+            ))
+            .await
+            .context("Failed to send register agent notification")?;
+
+        reconnected.cancel();
+
         let forward_connection_close = connection_close.clone();
         let forward_shutdown = shutdown.clone();
 
         let message_forward_handle = rt::spawn(async move {
+            let mut message_rx = message_rx.lock().await;
+            let mut unsent_message = unsent_message.lock().await;
+
+            // The message the previous connection broke on goes out before the rest of the queue
+            if let Some(message) = unsent_message.take()
+                && let Err(err) = write.send(message.clone()).await
+            {
+                error!("Failed to resend message: {err}");
+                unsent_message.replace(message);
+                forward_connection_close.cancel();
+
+                return;
+            }
+
             loop {
                 tokio::select! {
                     () = forward_connection_close.cancelled() => {
@@ -349,8 +422,10 @@ impl ManagementSocketClientService {
                                     Ok(serialized_message) => {
                                         let message = Message::Text(serialized_message.into());
 
-                                        if let Err(err) = write.send(message).await {
+                                        if let Err(err) = write.send(message.clone()).await {
                                             error!("Failed to send message: {err}");
+                                            unsent_message.replace(message);
+                                            forward_connection_close.cancel();
                                             break;
                                         }
                                     },
@@ -376,30 +451,10 @@ impl ManagementSocketClientService {
             }
         });
 
-        match self.slot_aggregated_status.make_snapshot() {
-            Ok(slot_aggregated_status_snapshot) => {
-                message_tx
-                    .send(ManagementJsonRpcMessage::Notification(
-                        ManagementJsonRpcNotification::RegisterAgent(RegisterAgentParams {
-                            name: self.name.clone(),
-                            slot_aggregated_status_snapshot,
-                        }),
-                    ))
-                    .unwrap_or_else(|err| {
-                        error!("Failed to send register agent notification: {err}");
-                    });
-            }
-            Err(err) => {
-                error!("Failed to create slot aggregated status snapshot: {err}");
-
-                return Err(err);
-            }
-        }
-
         let do_send_status_update = || match self.slot_aggregated_status.make_snapshot() {
             Ok(slot_aggregated_status_snapshot) => {
                 message_tx
-                    .send(ManagementJsonRpcMessage::Notification(
+                    .try_send(ManagementJsonRpcMessage::Notification(
                         ManagementJsonRpcNotification::UpdateAgentStatus(UpdateAgentStatusParams {
                             slot_aggregated_status_snapshot,
                         }),
@@ -438,13 +493,13 @@ impl ManagementSocketClientService {
                                     IncomingMessageContext {
                                         agent_applicable_state_holder: self.agent_applicable_state_holder.clone(),
                                         agent_desired_state_tx: self.agent_desired_state_tx.clone(),
-                                        connection_close: connection_close.clone(),
                                         continue_from_conversation_history_request_tx: self.continue_from_conversation_history_request_tx.clone(),
                                         continue_from_raw_prompt_request_tx: self.continue_from_raw_prompt_request_tx.clone(),
                                         generate_embedding_batch_request_tx: self.generate_embedding_batch_request_tx.clone(),
                                         model_metadata_holder: self.model_metadata_holder.clone(),
                                         receive_stream_stopper_collection: self.receive_stream_stopper_collection.clone(),
                                         message_tx: message_tx.clone(),
+                                        shutdown: shutdown.clone(),
                                     },
                                     msg,
                                     &pong_tx,
@@ -488,22 +543,51 @@ impl Service for ManagementSocketClientService {
     }
 
     async fn run(&mut self, shutdown: CancellationToken) -> Result<()> {
-        let mut ticker = interval(Duration::from_secs(1));
-
-        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // Outlives the connections, so that in-flight requests can keep responding while the
+        // agent reconnects
+        let (message_tx, message_rx) =
+            mpsc::channel::<ManagementJsonRpcMessage>(MESSAGE_QUEUE_CAPACITY);
+        let message_rx = Arc::new(Mutex::new(message_rx));
+        let unsent_message = Arc::new(Mutex::new(None));
+        let mut reconnect_backoff = ReconnectBackoff::default();
+        let mut reconnected = CancellationToken::new();
 
         loop {
+            match self
+                .keep_connection_alive(
+                    message_rx.clone(),
+                    message_tx.clone(),
+                    reconnected.clone(),
+                    shutdown.clone(),
+                    unsent_message.clone(),
+                )
+                .await
+            {
+                Err(err) => {
+                    error!("Failed to keep the connection alive: {err:?}");
+                }
+                Ok(()) => {
+                    info!("Gracefully closed connection to management server");
+
+                    reconnect_backoff.reset();
+                    reconnected = CancellationToken::new();
+
+                    rt::spawn(Self::abandon_in_flight_requests_unless_reconnected(
+                        message_rx.clone(),
+                        self.receive_stream_stopper_collection.clone(),
+                        self.reconnect_grace_period,
+                        reconnected.clone(),
+                        unsent_message.clone(),
+                    ));
+                }
+            }
+
+            let reconnect_delay = reconnect_backoff.next_delay();
+
             tokio::select! {
                 () = shutdown.cancelled() => break Ok(()),
-                _ = ticker.tick() => {
-                    match self.keep_connection_alive(shutdown.clone()).await {
-                        Err(err) => {
-                            error!("Failed to keep the connection alive: {err:?}");
-                        }
-                        Ok(()) => {
-                            info!("Gracefully closed connection to management server");
-                        }
-                    }
+                () = sleep(reconnect_delay) => {
+                    info!("Reconnecting to management server after {reconnect_delay:?}");
                 }
             }
         }
@@ -1,14 +1,17 @@
+pub mod build_sampler_chain;
 pub mod continue_from_conversation_history_request;
 pub mod continue_from_raw_prompt_request;
 pub mod continuous_batch_active_request;
 pub mod continuous_batch_arbiter;
 pub mod continuous_batch_arbiter_handle;
 pub mod continuous_batch_embedding_processor;
+pub mod continuous_batch_prefix_cache;
 pub mod continuous_batch_request_phase;
 pub mod continuous_batch_scheduler;
 pub mod continuous_batch_scheduler_command;
 pub mod continuous_batch_scheduler_context;
 pub mod drain_in_flight_requests;
+pub mod fit_prompt_in_context;
 mod from_request_params;
 pub mod generate_embedding_batch_request;
 pub mod grammar_sampler;
@@ -16,15 +19,28 @@ pub mod jsonrpc;
 pub mod llamacpp_arbiter_service;
 pub mod management_socket_client_service;
 pub mod model_metadata_holder;
+pub mod model_prefetch;
 pub mod plan_embedding_batches;
 pub mod prepare_conversation_history_request;
 pub mod prepared_conversation_history_request;
+pub mod prepared_embedding_batch_request;
+pub mod reasoning_detector;
 pub mod receive_stream_stopper_collection;
 mod receive_stream_stopper_drop_guard;
+mod reconnect_backoff;
 pub mod reconciliation_service;
+pub mod render_rerank_prompt;
 pub mod resolve_grammar;
 pub mod resolve_grammar_to_gbnf;
+pub mod resolve_logit_biases;
+pub mod resolve_sampling_parameters;
 pub mod resolved_grammar;
 pub mod sample_token_at_batch_index;
 pub mod sampling_outcome;
 pub mod sequence_id_pool;
+pub mod stop_sequence_detector;
+pub mod stop_sequence_outcome;
+pub mod tool_call_detector;
+pub mod token_logprobs_at_batch_index;
+pub mod tool_call_format;
+pub mod truncate_prompt_middle_out;
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use log::info;
+use log::warn;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::converts_to_applicable_state::ConvertsToApplicableState as _;
+use crate::slot_aggregated_status::SlotAggregatedStatus;
+
+/// Downloads a model in the background while the agent keeps serving the one it has loaded.
+/// Dropping it abandons the download.
+pub struct ModelPrefetch {
+    pub model: AgentDesiredModel,
+    finished: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+impl ModelPrefetch {
+    #[must_use]
+    pub fn spawn(
+        model: AgentDesiredModel,
+        slot_aggregated_status: Arc<SlotAggregatedStatus>,
+    ) -> Self {
+        let finished = CancellationToken::new();
+        let task_finished = finished.clone();
+        let task_model = model.clone();
+
+        let task = tokio::spawn(async move {
+            let _finished_guard = task_finished.drop_guard();
+
+            match task_model.to_applicable_state(slot_aggregated_status).await {
+                Ok(Some(model_path)) => info!("Prefetched model: {}", model_path.display()),
+                Ok(None) => {}
+                Err(err) => warn!("Failed to prefetch model: {err}"),
+            }
+        });
+
+        Self {
+            model,
+            finished,
+            task,
+        }
+    }
+
+    /// Resolves once the download either completed or failed
+    pub async fn finished(&self) {
+        self.finished.cancelled().await;
+    }
+}
+
+impl Drop for ModelPrefetch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
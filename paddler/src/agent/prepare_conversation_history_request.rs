@@ -4,31 +4,216 @@ use llama_cpp_bindings::mtmd::mtmd_default_marker;
 use log::error;
 use log::warn;
 use minijinja::context;
+use paddler_types::context_overflow::ContextOverflow;
+use paddler_types::conversation_history::ConversationHistory;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::media_marker::MediaMarker;
 use paddler_types::request_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::Tool;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::truncation_policy::TruncationPolicy;
 use tokio::sync::mpsc;
 
 use crate::agent::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
+use crate::agent::fit_prompt_in_context::count_prompt_tokens;
+use crate::agent::fit_prompt_in_context::report_context_overflow;
+use crate::agent::fit_prompt_in_context::truncate_overflowing_prompt_middle_out;
 use crate::agent::prepared_conversation_history_request::PreparedConversationHistoryRequest;
+use crate::agent::reasoning_detector::ReasoningDetector;
 use crate::agent::resolve_grammar::resolve_grammar;
+use crate::agent::resolve_sampling_parameters::resolve_sampling_parameters;
+use crate::agent::tool_call_detector::ToolCallDetector;
 use crate::decoded_image::DecodedImage;
 use crate::decoded_image_error::DecodedImageError;
 
+fn render_chat_template(
+    conversation_history: &ConversationHistory,
+    add_generation_prompt: bool,
+    enable_thinking: bool,
+    tools: &[Tool<ValidatedParametersSchema>],
+    media_marker: &MediaMarker,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<String> {
+    let chat_template_messages = conversation_history.replace_images_with_marker(media_marker);
+
+    scheduler_context
+        .chat_template_renderer
+        .render(context! {
+            add_generation_prompt,
+            bos_token => scheduler_context.token_bos_str,
+            enable_thinking,
+            eos_token => scheduler_context.token_eos_str,
+            messages => chat_template_messages.messages,
+            nl_token => scheduler_context.token_nl_str,
+            tools => tools,
+        })
+        .map_err(|err| {
+            let message = format!(
+                "{:?}: failed to render chat template: {err:?}",
+                scheduler_context.agent_name
+            );
+
+            error!("{message}");
+
+            if generated_tokens_tx
+                .send(GeneratedTokenResult::ChatTemplateError(message.clone()))
+                .is_err()
+            {
+                warn!(
+                    "{:?}: failed to send result to client (receiver dropped)",
+                    scheduler_context.agent_name
+                );
+            }
+
+            anyhow!(message)
+        })
+}
+
+/// Applies the truncation policy, if there is one, when the prompt leaves no room for `max_tokens` in the
+/// sequence's share of the context. Image tokens are not counted, since images are only
+/// tokenized during ingestion.
+fn fit_in_context(
+    conversation_history: ConversationHistory,
+    raw_prompt: String,
+    truncation: Option<TruncationPolicy>,
+    max_tokens: i32,
+    render_prompt: &impl Fn(&ConversationHistory) -> Result<String>,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<(ConversationHistory, String)> {
+    let Some(truncation) = truncation else {
+        return Ok((conversation_history, raw_prompt));
+    };
+
+    let context_size = scheduler_context.sequence_context_size();
+    let max_tokens = usize::try_from(max_tokens).unwrap_or(0);
+    let max_prompt_tokens = context_size.saturating_sub(max_tokens);
+    let count_tokens =
+        |prompt: &str| count_prompt_tokens(prompt, generated_tokens_tx, scheduler_context);
+    let overflow = |prompt_tokens: usize| {
+        report_context_overflow(
+            ContextOverflow {
+                context_size,
+                max_tokens,
+                prompt_tokens,
+            },
+            generated_tokens_tx,
+            scheduler_context,
+        )
+    };
+
+    let prompt_tokens = count_tokens(&raw_prompt)?;
+
+    if prompt_tokens <= max_prompt_tokens {
+        return Ok((conversation_history, raw_prompt));
+    }
+
+    match truncation {
+        TruncationPolicy::DropOldestMessages => {
+            let droppable_messages_count = conversation_history.droppable_messages_count();
+            let shortest_conversation_history =
+                conversation_history.without_oldest_messages(droppable_messages_count);
+            let shortest_prompt = render_prompt(&shortest_conversation_history)?;
+            let shortest_prompt_tokens = count_tokens(&shortest_prompt)?;
+
+            if shortest_prompt_tokens > max_prompt_tokens {
+                return Err(overflow(shortest_prompt_tokens));
+            }
+
+            // Looks for the fewest dropped messages that make the prompt fit; dropping
+            // `fewest_fitting` messages always fits, and dropping `most_not_fitting` never does
+            let mut fitting = (shortest_conversation_history, shortest_prompt);
+            let mut most_not_fitting = 0;
+            let mut fewest_fitting = droppable_messages_count;
+
+            while fewest_fitting - most_not_fitting > 1 {
+                let dropped_messages_count =
+                    most_not_fitting + (fewest_fitting - most_not_fitting) / 2;
+                let candidate_conversation_history =
+                    conversation_history.without_oldest_messages(dropped_messages_count);
+                let candidate_prompt = render_prompt(&candidate_conversation_history)?;
+
+                if count_tokens(&candidate_prompt)? <= max_prompt_tokens {
+                    fewest_fitting = dropped_messages_count;
+                    fitting = (candidate_conversation_history, candidate_prompt);
+                } else {
+                    most_not_fitting = dropped_messages_count;
+                }
+            }
+
+            Ok(fitting)
+        }
+        TruncationPolicy::MiddleOut => {
+            // Cutting through the prompt could take the image markers out of it
+            if !conversation_history.extract_image_urls().is_empty() {
+                return Err(overflow(prompt_tokens));
+            }
+
+            let truncated_prompt = truncate_overflowing_prompt_middle_out(
+                &raw_prompt,
+                ContextOverflow {
+                    context_size,
+                    max_tokens,
+                    prompt_tokens,
+                },
+                generated_tokens_tx,
+                scheduler_context,
+            )?;
+
+            Ok((conversation_history, truncated_prompt))
+        }
+        TruncationPolicy::Reject => Err(overflow(prompt_tokens)),
+    }
+}
+
 pub fn prepare_conversation_history_request(
     ContinueFromConversationHistoryParams {
         add_generation_prompt,
         enable_thinking,
         grammar,
         conversation_history,
+        logprobs,
         max_tokens,
+        model: _,
+        priority: _,
+        sampling,
+        session_id: _,
+        stop,
         tools,
+        truncation,
     }: ContinueFromConversationHistoryParams<ValidatedParametersSchema>,
     generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
     scheduler_context: &ContinuousBatchSchedulerContext,
 ) -> Result<PreparedConversationHistoryRequest> {
+    let sampling = resolve_sampling_parameters(sampling, generated_tokens_tx)?;
     let grammar_sampler = resolve_grammar(grammar.as_ref(), enable_thinking, generated_tokens_tx)?;
+    let tool_call_detector =
+        ToolCallDetector::new(scheduler_context.tool_call_format, tools.clone());
+
+    let media_marker = MediaMarker::new(mtmd_default_marker().to_owned());
+    let render_prompt = |conversation_history: &ConversationHistory| {
+        render_chat_template(
+            conversation_history,
+            add_generation_prompt,
+            enable_thinking,
+            &tools,
+            &media_marker,
+            generated_tokens_tx,
+            scheduler_context,
+        )
+    };
+
+    let raw_prompt = render_prompt(&conversation_history)?;
+    let (conversation_history, raw_prompt) = fit_in_context(
+        conversation_history,
+        raw_prompt,
+        truncation,
+        max_tokens,
+        &render_prompt,
+        generated_tokens_tx,
+        scheduler_context,
+    )?;
 
     let image_resize_to_fit = scheduler_context.inference_parameters.image_resize_to_fit;
 
@@ -62,40 +247,8 @@ pub fn prepare_conversation_history_request(
             anyhow!(message)
         })?;
 
-    let media_marker = MediaMarker::new(mtmd_default_marker().to_owned());
-    let chat_template_messages = conversation_history.replace_images_with_marker(&media_marker);
-
-    let raw_prompt = scheduler_context
-        .chat_template_renderer
-        .render(context! {
-            add_generation_prompt,
-            bos_token => scheduler_context.token_bos_str,
-            enable_thinking,
-            eos_token => scheduler_context.token_eos_str,
-            messages => chat_template_messages.messages,
-            nl_token => scheduler_context.token_nl_str,
-            tools => tools,
-        })
-        .map_err(|err| {
-            let message = format!(
-                "{:?}: failed to render chat template: {err:?}",
-                scheduler_context.agent_name
-            );
-
-            error!("{message}");
-
-            if generated_tokens_tx
-                .send(GeneratedTokenResult::ChatTemplateError(message.clone()))
-                .is_err()
-            {
-                warn!(
-                    "{:?}: failed to send result to client (receiver dropped)",
-                    scheduler_context.agent_name
-                );
-            }
-
-            anyhow!(message)
-        })?;
+    let reasoning_detector =
+        ReasoningDetector::new(scheduler_context.reasoning_markers.clone(), &raw_prompt);
 
     let has_images = !images.is_empty();
     let has_multimodal_context = scheduler_context.multimodal_context.is_some();
@@ -127,14 +280,24 @@ pub fn prepare_conversation_history_request(
         return Ok(PreparedConversationHistoryRequest::MultimodalPrompt {
             raw_prompt,
             images,
+            logprobs,
             max_tokens,
+            sampling,
+            stop,
+            reasoning_detector,
+            tool_call_detector,
             grammar_sampler,
         });
     }
 
     Ok(PreparedConversationHistoryRequest::TextPrompt {
         raw_prompt,
+        logprobs,
         max_tokens,
+        sampling,
+        stop,
+        reasoning_detector,
+        tool_call_detector,
         grammar_sampler,
     })
 }
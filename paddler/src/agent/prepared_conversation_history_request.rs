@@ -1,16 +1,30 @@
+use paddler_types::sampling_parameters::SamplingParameters;
+
 use crate::agent::grammar_sampler::GrammarSampler;
+use crate::agent::reasoning_detector::ReasoningDetector;
+use crate::agent::tool_call_detector::ToolCallDetector;
 use crate::decoded_image::DecodedImage;
 
 pub enum PreparedConversationHistoryRequest {
     TextPrompt {
         raw_prompt: String,
+        logprobs: Option<u32>,
         max_tokens: i32,
+        sampling: SamplingParameters,
+        stop: Vec<String>,
+        reasoning_detector: ReasoningDetector,
+        tool_call_detector: ToolCallDetector,
         grammar_sampler: Option<GrammarSampler>,
     },
     MultimodalPrompt {
         raw_prompt: String,
         images: Vec<DecodedImage>,
+        logprobs: Option<u32>,
         max_tokens: i32,
+        sampling: SamplingParameters,
+        stop: Vec<String>,
+        reasoning_detector: ReasoningDetector,
+        tool_call_detector: ToolCallDetector,
         grammar_sampler: Option<GrammarSampler>,
     },
 }
@@ -0,0 +1,15 @@
+use std::collections::VecDeque;
+
+use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_types::embedding_result::EmbeddingResult;
+use tokio::sync::mpsc;
+
+use crate::embedding_input_tokenized::EmbeddingInputTokenized;
+
+pub struct PreparedEmbeddingBatchRequest {
+    pub generate_embedding_stop_rx: mpsc::UnboundedReceiver<()>,
+    pub generated_embedding_tx: mpsc::UnboundedSender<EmbeddingResult>,
+    pub normalization_method: EmbeddingNormalizationMethod,
+    /// Inputs that are still waiting for their turn in one of the embedding batches
+    pub pending_inputs: VecDeque<EmbeddingInputTokenized>,
+}
@@ -0,0 +1,197 @@
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::reasoning_markers::ReasoningMarkers;
+
+/// Separates the reasoning a model writes between its reasoning markers from the answer.
+///
+/// Text that might be the beginning of a marker is held back until it is known whether it is
+/// one. The markers themselves are not released.
+pub struct ReasoningDetector {
+    held_back_text: String,
+    is_reasoning: bool,
+    reasoning_markers: Option<ReasoningMarkers>,
+    /// Models separate the reasoning from the surrounding text with whitespace
+    trims_leading_whitespace: bool,
+}
+
+impl ReasoningDetector {
+    /// Chat templates that open the reasoning block themselves end the prompt with the start
+    /// marker, so the model begins generating inside of it.
+    #[must_use]
+    pub fn new(reasoning_markers: Option<ReasoningMarkers>, raw_prompt: &str) -> Self {
+        let is_reasoning = reasoning_markers.as_ref().is_some_and(|reasoning_markers| {
+            raw_prompt.trim_end().ends_with(&reasoning_markers.start)
+        });
+
+        Self {
+            held_back_text: String::new(),
+            is_reasoning,
+            reasoning_markers,
+            trims_leading_whitespace: is_reasoning,
+        }
+    }
+
+    pub fn flush(&mut self) -> Vec<GeneratedTokenResult> {
+        let held_back_text = std::mem::take(&mut self.held_back_text);
+        let mut results = vec![];
+
+        self.release(&held_back_text, &mut results);
+
+        results
+    }
+
+    pub fn push(&mut self, piece: &str) -> Vec<GeneratedTokenResult> {
+        let Some(reasoning_markers) = self.reasoning_markers.clone() else {
+            return vec![GeneratedTokenResult::Token(piece.to_owned())];
+        };
+
+        let mut results = vec![];
+
+        self.held_back_text.push_str(piece);
+
+        loop {
+            let marker = if self.is_reasoning {
+                &reasoning_markers.end
+            } else {
+                &reasoning_markers.start
+            };
+
+            if let Some(position) = self.held_back_text.find(marker.as_str()) {
+                let remaining_text = self.held_back_text.split_off(position + marker.len());
+
+                self.held_back_text.truncate(position);
+
+                let released_text = std::mem::replace(&mut self.held_back_text, remaining_text);
+
+                self.release(&released_text, &mut results);
+                self.is_reasoning = !self.is_reasoning;
+                self.trims_leading_whitespace = true;
+
+                continue;
+            }
+
+            let still_held_back = self
+                .held_back_text
+                .split_off(self.held_back_text.len() - self.partial_marker_length(marker));
+            let released_text = std::mem::replace(&mut self.held_back_text, still_held_back);
+
+            self.release(&released_text, &mut results);
+
+            break;
+        }
+
+        results
+    }
+
+    /// Length of the longest suffix of the held back text that begins the marker
+    fn partial_marker_length(&self, marker: &str) -> usize {
+        self.held_back_text
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|index| marker.starts_with(&self.held_back_text[*index..]))
+            .map_or(0, |index| self.held_back_text.len() - index)
+    }
+
+    fn release(&mut self, text: &str, results: &mut Vec<GeneratedTokenResult>) {
+        let text = if self.trims_leading_whitespace {
+            text.trim_start()
+        } else {
+            text
+        };
+
+        if text.is_empty() {
+            return;
+        }
+
+        self.trims_leading_whitespace = false;
+
+        results.push(if self.is_reasoning {
+            GeneratedTokenResult::ReasoningToken(text.to_owned())
+        } else {
+            GeneratedTokenResult::Token(text.to_owned())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn think_markers() -> ReasoningMarkers {
+        ReasoningMarkers {
+            end: "</think>".to_owned(),
+            start: "<think>".to_owned(),
+        }
+    }
+
+    fn push_all(detector: &mut ReasoningDetector, pieces: &[&str]) -> (String, String) {
+        let mut results: Vec<GeneratedTokenResult> = pieces
+            .iter()
+            .flat_map(|piece| detector.push(piece))
+            .collect();
+
+        results.extend(detector.flush());
+
+        let mut reasoning = String::new();
+        let mut answer = String::new();
+
+        for result in results {
+            match result {
+                GeneratedTokenResult::ReasoningToken(text) => reasoning.push_str(&text),
+                GeneratedTokenResult::Token(text) => answer.push_str(&text),
+                _ => {}
+            }
+        }
+
+        (reasoning, answer)
+    }
+
+    #[test]
+    fn passes_text_through_without_markers() {
+        let mut detector = ReasoningDetector::new(None, "");
+
+        assert_eq!(
+            push_all(&mut detector, &["<think>a</think>", "b"]),
+            (String::new(), "<think>a</think>b".to_owned())
+        );
+    }
+
+    #[test]
+    fn separates_reasoning_split_across_pieces() {
+        let mut detector = ReasoningDetector::new(Some(think_markers()), "<|im_start|>assistant\n");
+
+        assert_eq!(
+            push_all(
+                &mut detector,
+                &[
+                    "<th",
+                    "ink>\nLet me",
+                    " think.</th",
+                    "ink>\n\nThe answer",
+                    " is 4."
+                ]
+            ),
+            ("Let me think.".to_owned(), "The answer is 4.".to_owned())
+        );
+    }
+
+    #[test]
+    fn starts_reasoning_when_prompt_opens_the_block() {
+        let mut detector =
+            ReasoningDetector::new(Some(think_markers()), "<|im_start|>assistant\n<think>\n");
+
+        assert_eq!(
+            push_all(&mut detector, &["Hmm.", "</think>", "\n\nHello"]),
+            ("Hmm.".to_owned(), "Hello".to_owned())
+        );
+    }
+
+    #[test]
+    fn releases_text_resembling_a_marker() {
+        let mut detector = ReasoningDetector::new(Some(think_markers()), "");
+
+        assert_eq!(
+            push_all(&mut detector, &["a <", "b", " <th"]),
+            (String::new(), "a <b <th".to_owned())
+        );
+    }
+}
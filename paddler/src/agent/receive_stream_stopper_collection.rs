@@ -61,6 +61,13 @@ impl ReceiveStreamStopperCollection {
             Err(anyhow!("No stopper found for request_id {request_id}"))
         }
     }
+
+    pub fn stop_all(&self) {
+        for receive_stopper in &self.receive_stoppers {
+            // The request might have just finished on its own, which is fine
+            let _ = receive_stopper.value().send(());
+        }
+    }
 }
 
 impl Default for ReceiveStreamStopperCollection {
@@ -138,6 +145,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn stop_all_sends_signal_to_every_stopper() -> Result<()> {
+        let collection = ReceiveStreamStopperCollection::default();
+        let (sender_1, mut receiver_1) = mpsc::unbounded_channel();
+        let (sender_2, mut receiver_2) = mpsc::unbounded_channel();
+
+        collection.register_stopper("req_1".to_owned(), sender_1)?;
+        collection.register_stopper("req_2".to_owned(), sender_2)?;
+
+        collection.stop_all();
+
+        assert!(receiver_1.try_recv().is_ok());
+        assert!(receiver_2.try_recv().is_ok());
+
+        Ok(())
+    }
+
     #[test]
     fn stop_missing_stopper_fails() {
         let collection = ReceiveStreamStopperCollection::default();
@@ -3,12 +3,14 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
+use paddler_types::agent_desired_model::AgentDesiredModel;
 use tokio::sync::mpsc;
 use tokio::time::Duration;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 
+use crate::agent::model_prefetch::ModelPrefetch;
 use crate::agent_applicable_state_holder::AgentApplicableStateHolder;
 use crate::agent_desired_state::AgentDesiredState;
 use crate::agent_issue_fix::AgentIssueFix;
@@ -21,6 +23,7 @@ pub struct ReconciliationService {
     pub agent_desired_state: Option<AgentDesiredState>,
     pub agent_desired_state_rx: mpsc::UnboundedReceiver<AgentDesiredState>,
     pub is_converted_to_applicable_state: bool,
+    pub model_prefetch: Option<ModelPrefetch>,
     pub slot_aggregated_status: Arc<SlotAggregatedStatus>,
 }
 
@@ -29,6 +32,13 @@ impl ReconciliationService {
         let applicable_state = match &self.agent_desired_state {
             None => None,
             Some(agent_desired_state) => {
+                // Waits for the prefetch instead of contending with it for the download lock
+                if let Some(model_prefetch) = &self.model_prefetch
+                    && model_prefetch.model == agent_desired_state.model
+                {
+                    model_prefetch.finished().await;
+                }
+
                 agent_desired_state
                     .to_applicable_state(self.slot_aggregated_status.clone())
                     .await?
@@ -36,6 +46,10 @@ impl ReconciliationService {
         };
 
         self.is_converted_to_applicable_state = true;
+        self.slot_aggregated_status
+            .set_accepts_embeddings(applicable_state.as_ref().is_some_and(|applicable_state| {
+                applicable_state.inference_parameters.enable_embeddings
+            }));
         self.slot_aggregated_status.set_uses_chat_template_override(
             applicable_state
                 .as_ref()
@@ -47,11 +61,46 @@ impl ReconciliationService {
             .set_agent_applicable_state(applicable_state)
     }
 
+    pub async fn set_agent_desired_state(&mut self, agent_desired_state: AgentDesiredState) {
+        self.update_model_prefetch(&agent_desired_state);
+
+        let requires_reload = !self.is_converted_to_applicable_state
+            || self
+                .agent_desired_state
+                .as_ref()
+                .is_none_or(|current| current.requires_reload_to_switch_to(&agent_desired_state));
+
+        self.agent_desired_state = Some(agent_desired_state);
+
+        if requires_reload {
+            self.is_converted_to_applicable_state = false;
+            self.try_convert_to_applicable_state().await;
+        }
+    }
+
     pub async fn try_convert_to_applicable_state(&mut self) {
         if let Err(err) = self.convert_to_applicable_state().await {
             error!("Failed to convert to applicable state: {err}");
         }
     }
+
+    fn update_model_prefetch(&mut self, agent_desired_state: &AgentDesiredState) {
+        // A download that is still in progress is kept if the model is switched to it already
+        if self.model_prefetch.as_ref().is_some_and(|model_prefetch| {
+            model_prefetch.model == agent_desired_state.prefetch_model
+                || model_prefetch.model == agent_desired_state.model
+        }) {
+            return;
+        }
+
+        self.model_prefetch = match &agent_desired_state.prefetch_model {
+            AgentDesiredModel::None => None,
+            prefetch_model => Some(ModelPrefetch::spawn(
+                prefetch_model.clone(),
+                self.slot_aggregated_status.clone(),
+            )),
+        };
+    }
 }
 
 #[async_trait]
@@ -74,13 +123,13 @@ impl Service for ReconciliationService {
                     }
                 },
                 next_agent_desired_state = self.agent_desired_state_rx.recv() => {
-                    self.is_converted_to_applicable_state = false;
-                    self.agent_desired_state = if let Some(agent_desired_state) = next_agent_desired_state { Some(agent_desired_state) } else {
+                    let Some(agent_desired_state) = next_agent_desired_state else {
                         error!("Agent desired state channel closed, stopping reconciliation service.");
 
                         break Ok(())
                     };
-                    self.try_convert_to_applicable_state().await;
+
+                    self.set_agent_desired_state(agent_desired_state).await;
                 }
             }
         }
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use rand::Rng as _;
+
+const INITIAL_DELAY: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Exponential backoff with jitter, so that agents which lost the balancer at the same time do
+/// not all reconnect at the same moment
+pub struct ReconnectBackoff {
+    attempt: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+}
+
+impl ReconnectBackoff {
+    #[must_use]
+    pub const fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            attempt: 0,
+            initial_delay,
+            max_delay,
+        }
+    }
+
+    /// The ceiling doubles with every attempt until it reaches the maximum delay; the delay
+    /// itself is picked at random from the upper half of it
+    pub fn next_delay(&mut self) -> Duration {
+        let ceiling = self
+            .initial_delay
+            .saturating_mul(2_u32.saturating_pow(self.attempt))
+            .min(self.max_delay);
+
+        self.attempt = self.attempt.saturating_add(1);
+
+        ceiling.mul_f64(rand::rng().random_range(0.5..=1.0))
+    }
+
+    pub const fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(INITIAL_DELAY, MAX_DELAY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_delay_doubles_up_to_max_delay() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_millis(100), Duration::from_secs(1));
+
+        for ceiling_ms in [100, 200, 400, 800, 1000, 1000] {
+            let delay = backoff.next_delay();
+
+            assert!(delay >= Duration::from_millis(ceiling_ms / 2));
+            assert!(delay <= Duration::from_millis(ceiling_ms));
+        }
+    }
+
+    #[test]
+    fn next_delay_does_not_overflow_after_many_attempts() {
+        let mut backoff = ReconnectBackoff::default();
+
+        for _ in 0..100 {
+            assert!(backoff.next_delay() <= MAX_DELAY);
+        }
+    }
+
+    #[test]
+    fn reset_starts_over_from_initial_delay() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_millis(100), Duration::from_secs(1));
+
+        for _ in 0..5 {
+            backoff.next_delay();
+        }
+
+        backoff.reset();
+
+        assert!(backoff.next_delay() <= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,57 @@
+/// Substitutes `{query}` and `{document}` in the rerank template of a model. The template is read
+/// only once, so placeholders that appear in the query or in the document are kept as they are.
+#[must_use]
+pub fn render_rerank_prompt(rerank_template: &str, query: &str, document: &str) -> String {
+    let mut prompt = String::with_capacity(rerank_template.len() + query.len() + document.len());
+    let mut remaining_template = rerank_template;
+
+    while let Some(brace_index) = remaining_template.find('{') {
+        let (literal, placeholder) = remaining_template.split_at(brace_index);
+
+        prompt.push_str(literal);
+
+        if let Some(rest) = placeholder.strip_prefix("{query}") {
+            prompt.push_str(query);
+            remaining_template = rest;
+        } else if let Some(rest) = placeholder.strip_prefix("{document}") {
+            prompt.push_str(document);
+            remaining_template = rest;
+        } else {
+            prompt.push('{');
+            remaining_template = &placeholder[1..];
+        }
+    }
+
+    prompt.push_str(remaining_template);
+
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_query_and_document() {
+        assert_eq!(
+            render_rerank_prompt("<q>{query}</q><d>{document}</d>", "capital?", "Paris"),
+            "<q>capital?</q><d>Paris</d>"
+        );
+    }
+
+    #[test]
+    fn keeps_placeholders_that_come_from_the_inputs() {
+        assert_eq!(
+            render_rerank_prompt("{query}|{document}", "{document}", "{query}"),
+            "{document}|{query}"
+        );
+    }
+
+    #[test]
+    fn keeps_other_braces() {
+        assert_eq!(
+            render_rerank_prompt("{{query}} {other} {", "q", "d"),
+            "{q} {other} {"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use llama_cpp_bindings::model::AddBos;
+use llama_cpp_bindings::model::LlamaModel;
+use llama_cpp_bindings::token::LlamaToken;
+use llama_cpp_bindings::token::logit_bias::LlamaLogitBias;
+use paddler_types::logit_bias::LogitBias;
+use paddler_types::logit_bias_token::LogitBiasToken;
+
+/// Resolved once per loaded model, since texts are tokenized with the model's vocabulary
+pub fn resolve_logit_biases(
+    model: &LlamaModel,
+    logit_biases: &[LogitBias],
+) -> Result<Vec<LlamaLogitBias>> {
+    let mut resolved_logit_biases = Vec::with_capacity(logit_biases.len());
+
+    for LogitBias { bias, token } in logit_biases {
+        match token {
+            LogitBiasToken::Id(id) => {
+                if *id >= model.n_vocab() {
+                    bail!(
+                        "logit bias token id {id} is out of the vocabulary of {} tokens",
+                        model.n_vocab()
+                    );
+                }
+
+                resolved_logit_biases.push(LlamaLogitBias::new(LlamaToken::new(*id), *bias));
+            }
+            LogitBiasToken::Text(text) => {
+                let tokens = model
+                    .str_to_token(text, AddBos::Never)
+                    .with_context(|| format!("failed to tokenize logit bias text {text:?}"))?;
+
+                resolved_logit_biases.extend(
+                    tokens
+                        .into_iter()
+                        .map(|token| LlamaLogitBias::new(token, *bias)),
+                );
+            }
+        }
+    }
+
+    Ok(resolved_logit_biases)
+}
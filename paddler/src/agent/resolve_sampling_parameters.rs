@@ -0,0 +1,32 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::validates::Validates as _;
+use tokio::sync::mpsc;
+
+pub fn resolve_sampling_parameters(
+    sampling: Option<SamplingParameters>,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+) -> Result<SamplingParameters> {
+    let Some(sampling) = sampling else {
+        return Ok(SamplingParameters::default());
+    };
+
+    match sampling.validate() {
+        Ok(sampling) => Ok(sampling),
+        Err(err) => {
+            let message = format!("Invalid sampling parameters: {err}");
+
+            generated_tokens_tx
+                .send(GeneratedTokenResult::InvalidSamplingParameters(
+                    message.clone(),
+                ))
+                .map_err(|send_err| {
+                    anyhow!("Failed to send invalid sampling parameters error: {send_err}")
+                })?;
+
+            Err(anyhow!(message))
+        }
+    }
+}
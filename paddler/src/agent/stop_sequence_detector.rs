@@ -0,0 +1,138 @@
+use crate::agent::stop_sequence_outcome::StopSequenceOutcome;
+
+/// Holds back generated text for as long as it might be the beginning of a stop sequence, so
+/// that stop sequences split across several tokens never reach the client.
+pub struct StopSequenceDetector {
+    held_back_text: String,
+    stop_sequences: Vec<String>,
+}
+
+impl StopSequenceDetector {
+    #[must_use]
+    pub fn new(stop_sequences: Vec<String>) -> Self {
+        Self {
+            held_back_text: String::new(),
+            stop_sequences: stop_sequences
+                .into_iter()
+                .filter(|stop_sequence| !stop_sequence.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn flush(&mut self) -> String {
+        std::mem::take(&mut self.held_back_text)
+    }
+
+    pub fn push(&mut self, piece: &str) -> StopSequenceOutcome {
+        if self.stop_sequences.is_empty() {
+            return StopSequenceOutcome::Release(piece.to_owned());
+        }
+
+        self.held_back_text.push_str(piece);
+
+        if let Some((position, stop_sequence)) = self
+            .stop_sequences
+            .iter()
+            .filter_map(|stop_sequence| {
+                self.held_back_text
+                    .find(stop_sequence.as_str())
+                    .map(|position| (position, stop_sequence))
+            })
+            .min_by_key(|(position, _)| *position)
+        {
+            let stop_sequence = stop_sequence.clone();
+
+            self.held_back_text.truncate(position);
+
+            return StopSequenceOutcome::Stopped(stop_sequence);
+        }
+
+        let releasable_length = self.held_back_text.len() - self.partial_match_length();
+
+        if releasable_length == 0 {
+            return StopSequenceOutcome::Pending;
+        }
+
+        let still_held_back = self.held_back_text.split_off(releasable_length);
+
+        StopSequenceOutcome::Release(std::mem::replace(&mut self.held_back_text, still_held_back))
+    }
+
+    /// Length of the longest suffix of the held back text that begins some stop sequence
+    fn partial_match_length(&self) -> usize {
+        self.held_back_text
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|index| {
+                let suffix = &self.held_back_text[*index..];
+
+                self.stop_sequences
+                    .iter()
+                    .any(|stop_sequence| stop_sequence.starts_with(suffix))
+            })
+            .map_or(0, |index| self.held_back_text.len() - index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn released(outcome: StopSequenceOutcome) -> Option<String> {
+        match outcome {
+            StopSequenceOutcome::Release(text) => Some(text),
+            StopSequenceOutcome::Pending | StopSequenceOutcome::Stopped(_) => None,
+        }
+    }
+
+    #[test]
+    fn releases_everything_without_stop_sequences() {
+        let mut detector = StopSequenceDetector::new(vec![]);
+
+        assert_eq!(released(detector.push("Hello")), Some("Hello".to_owned()));
+        assert_eq!(released(detector.push("")), Some(String::new()));
+    }
+
+    #[test]
+    fn holds_back_partial_match_until_it_diverges() {
+        let mut detector = StopSequenceDetector::new(vec!["\nUser:".to_owned()]);
+
+        assert_eq!(released(detector.push("Hi\nUs")), Some("Hi".to_owned()));
+        assert!(matches!(detector.push("e"), StopSequenceOutcome::Pending));
+        assert_eq!(
+            released(detector.push("d to")),
+            Some("\nUsed to".to_owned())
+        );
+    }
+
+    #[test]
+    fn stops_on_sequence_split_across_pieces() {
+        let mut detector = StopSequenceDetector::new(vec!["STOP".to_owned()]);
+
+        assert_eq!(released(detector.push("one ST")), Some("one ".to_owned()));
+        assert!(matches!(
+            detector.push("OP two"),
+            StopSequenceOutcome::Stopped(ref stop_sequence) if stop_sequence == "STOP"
+        ));
+        assert_eq!(detector.flush(), "");
+    }
+
+    #[test]
+    fn keeps_text_before_stop_sequence_for_flushing() {
+        let mut detector = StopSequenceDetector::new(vec!["###".to_owned(), "END".to_owned()]);
+
+        assert!(matches!(
+            detector.push("done END ###"),
+            StopSequenceOutcome::Stopped(ref stop_sequence) if stop_sequence == "END"
+        ));
+        assert_eq!(detector.flush(), "done ");
+    }
+
+    #[test]
+    fn does_not_split_multibyte_characters() {
+        let mut detector = StopSequenceDetector::new(vec!["żółw".to_owned()]);
+
+        assert_eq!(released(detector.push("zażó")), Some("za".to_owned()));
+        assert_eq!(detector.flush(), "żó");
+    }
+}
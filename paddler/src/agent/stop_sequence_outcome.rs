@@ -0,0 +1,8 @@
+pub enum StopSequenceOutcome {
+    /// Everything that is held back could still turn out to be the beginning of a stop sequence
+    Pending,
+    /// Text that can no longer become a part of any stop sequence
+    Release(String),
+    /// Text preceding the stop sequence stays held back until it is flushed
+    Stopped(String),
+}
@@ -0,0 +1,131 @@
+use anyhow::Context as _;
+use anyhow::Result;
+use llama_cpp_bindings::TokenToStringError;
+use llama_cpp_bindings::context::LlamaContext;
+use llama_cpp_bindings::model::LlamaModel;
+use llama_cpp_bindings::token::LlamaToken;
+use paddler_types::token_logprob::TokenLogprob;
+use paddler_types::token_logprobs::TokenLogprobs;
+
+/// Most pieces fit, longer ones are retried with the size llama.cpp asks for
+const TOKEN_PIECE_BUFFER_SIZE: usize = 8;
+
+/// Logarithm of the softmax denominator, shifted by the largest logit to avoid overflowing
+fn log_normalizer(logits: &[f32]) -> f32 {
+    let max_logit = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    if !max_logit.is_finite() {
+        return max_logit;
+    }
+
+    max_logit
+        + logits
+            .iter()
+            .map(|logit| (logit - max_logit).exp())
+            .sum::<f32>()
+            .ln()
+}
+
+/// Token ids of the `top_n` largest logits, starting with the largest one
+fn top_token_ids(logits: &[f32], top_n: usize) -> Vec<usize> {
+    let mut token_ids: Vec<usize> = (0..logits.len()).collect();
+    let top_n = top_n.min(token_ids.len());
+
+    if top_n == 0 {
+        return vec![];
+    }
+
+    let descending_logits = |left: &usize, right: &usize| logits[*right].total_cmp(&logits[*left]);
+
+    token_ids.select_nth_unstable_by(top_n - 1, descending_logits);
+    token_ids.truncate(top_n);
+    token_ids.sort_unstable_by(descending_logits);
+
+    token_ids
+}
+
+fn token_piece_bytes(model: &LlamaModel, token: LlamaToken) -> Result<Vec<u8>> {
+    match model.token_to_piece_bytes(token, TOKEN_PIECE_BUFFER_SIZE, true, None) {
+        Err(TokenToStringError::InsufficientBufferSpace(required_size)) => {
+            Ok(model.token_to_piece_bytes(token, usize::try_from(-required_size)?, true, None)?)
+        }
+        other => Ok(other?),
+    }
+}
+
+fn token_logprob(model: &LlamaModel, token_id: usize, logprob: f32) -> Result<TokenLogprob> {
+    let id = i32::try_from(token_id)?;
+    let bytes = token_piece_bytes(model, LlamaToken::new(id))
+        .with_context(|| format!("failed to read the piece of token {id}"))?;
+
+    let token = String::from_utf8_lossy(&bytes).into_owned();
+
+    Ok(TokenLogprob {
+        bytes,
+        id,
+        logprob,
+        token,
+    })
+}
+
+/// Reads the logits the model produced at the batch index, which the samplers only ever
+/// modify copies of, so the logprobs do not depend on the sampling parameters.
+pub fn token_logprobs_at_batch_index(
+    llama_context: &LlamaContext,
+    model: &LlamaModel,
+    batch_index: i32,
+    sampled_token: LlamaToken,
+    top_logprobs: u32,
+) -> Result<TokenLogprobs> {
+    let logits = llama_context
+        .get_logits_ith(batch_index)
+        .context("failed to read logits for logprobs")?;
+    let log_normalizer = log_normalizer(logits);
+    let sampled_token_id = usize::try_from(sampled_token.0)?;
+    let sampled_logit = logits
+        .get(sampled_token_id)
+        .context("sampled token is out of the vocabulary")?;
+
+    Ok(TokenLogprobs {
+        sampled: token_logprob(model, sampled_token_id, sampled_logit - log_normalizer)?,
+        top_logprobs: top_token_ids(logits, usize::try_from(top_logprobs)?)
+            .into_iter()
+            .map(|token_id| token_logprob(model, token_id, logits[token_id] - log_normalizer))
+            .collect::<Result<Vec<_>>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_normalizer_turns_logits_into_log_probabilities() {
+        let logits = [2.0_f32, 1.0, 0.5];
+        let log_normalizer = log_normalizer(&logits);
+        let total_probability: f32 = logits
+            .iter()
+            .map(|logit| (logit - log_normalizer).exp())
+            .sum();
+
+        assert!((total_probability - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn log_normalizer_does_not_overflow_with_large_logits() {
+        let log_normalizer = log_normalizer(&[1000.0, 1000.0]);
+
+        assert!((log_normalizer - (1000.0 + 2.0_f32.ln())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn top_token_ids_are_sorted_by_descending_logit() {
+        assert_eq!(top_token_ids(&[0.1, 3.0, -1.0, 2.0], 3), vec![1, 3, 0]);
+    }
+
+    #[test]
+    fn top_token_ids_are_limited_by_the_vocabulary() {
+        assert_eq!(top_token_ids(&[0.1, 3.0], 5), vec![1, 0]);
+        assert!(top_token_ids(&[0.1, 3.0], 0).is_empty());
+    }
+}
@@ -0,0 +1,340 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use log::warn;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::Tool;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::tool_call::ToolCall;
+
+use crate::agent::tool_call_format::ToolCallFormat;
+
+/// Turns the tool calls a model writes out as text into `ToolCall` results.
+///
+/// Text that might be the beginning of a tool call is held back until it is known whether it is
+/// one, and calls that do not match any of the request's tools are released as plain text.
+pub struct ToolCallDetector {
+    /// Text of the tool call that is being generated, starting with its opening marker
+    captured_call: Option<String>,
+    held_back_text: String,
+    released_any_text: bool,
+    tool_call_format: Option<ToolCallFormat>,
+    tools: Vec<Tool<ValidatedParametersSchema>>,
+}
+
+impl ToolCallDetector {
+    #[must_use]
+    pub const fn new(
+        tool_call_format: Option<ToolCallFormat>,
+        tools: Vec<Tool<ValidatedParametersSchema>>,
+    ) -> Self {
+        Self {
+            captured_call: None,
+            held_back_text: String::new(),
+            released_any_text: false,
+            tool_call_format: if tools.is_empty() {
+                None
+            } else {
+                tool_call_format
+            },
+            tools,
+        }
+    }
+
+    pub fn flush(&mut self) -> Vec<GeneratedTokenResult> {
+        let held_back_text = std::mem::take(&mut self.held_back_text);
+
+        match self.captured_call.take() {
+            Some(mut captured_call) => {
+                captured_call.push_str(&held_back_text);
+
+                self.complete_call(captured_call)
+            }
+            None if held_back_text.is_empty() => vec![],
+            None => vec![GeneratedTokenResult::Token(held_back_text)],
+        }
+    }
+
+    pub fn push(&mut self, piece: &str) -> Vec<GeneratedTokenResult> {
+        let Some(tool_call_format) = self.tool_call_format else {
+            return vec![GeneratedTokenResult::Token(piece.to_owned())];
+        };
+
+        let mut results = vec![];
+
+        self.held_back_text.push_str(piece);
+
+        loop {
+            if let Some(captured_call) = &mut self.captured_call {
+                captured_call.push_str(&std::mem::take(&mut self.held_back_text));
+
+                let Some(closing_marker) = tool_call_format.closing_marker() else {
+                    break;
+                };
+                let Some(position) = captured_call.find(closing_marker) else {
+                    break;
+                };
+
+                self.held_back_text = captured_call.split_off(position + closing_marker.len());
+
+                if let Some(captured_call) = self.captured_call.take() {
+                    results.extend(self.complete_call(captured_call));
+                }
+
+                continue;
+            }
+
+            if tool_call_format == ToolCallFormat::Llama3Json && !self.released_any_text {
+                let trimmed_text = self.held_back_text.trim_start();
+
+                if trimmed_text.is_empty() {
+                    break;
+                }
+
+                if trimmed_text.starts_with('{') {
+                    self.captured_call = Some(std::mem::take(&mut self.held_back_text));
+
+                    continue;
+                }
+            }
+
+            let opening_marker = tool_call_format.opening_marker();
+
+            if let Some(position) = self.held_back_text.find(opening_marker) {
+                let captured_call = self.held_back_text.split_off(position);
+
+                self.release_held_back_text(&mut results);
+                self.captured_call = Some(captured_call);
+
+                continue;
+            }
+
+            let still_held_back = self
+                .held_back_text
+                .split_off(self.held_back_text.len() - self.partial_marker_length(opening_marker));
+
+            self.release_held_back_text(&mut results);
+            self.held_back_text = still_held_back;
+
+            break;
+        }
+
+        results
+    }
+
+    fn complete_call(&self, captured_call: String) -> Vec<GeneratedTokenResult> {
+        match self.parse_and_validate(&captured_call) {
+            Ok(tool_calls) => tool_calls
+                .into_iter()
+                .map(GeneratedTokenResult::ToolCall)
+                .collect(),
+            Err(err) => {
+                warn!("Releasing generated tool call as text: {err:#}");
+
+                vec![GeneratedTokenResult::Token(captured_call)]
+            }
+        }
+    }
+
+    fn parse_and_validate(&self, captured_call: &str) -> Result<Vec<ToolCall>> {
+        let tool_call_format = self
+            .tool_call_format
+            .ok_or_else(|| anyhow!("Tool calls are not detected for this request"))?;
+        let body = captured_call
+            .strip_prefix(tool_call_format.opening_marker())
+            .unwrap_or(captured_call);
+        let body = tool_call_format
+            .closing_marker()
+            .and_then(|closing_marker| body.strip_suffix(closing_marker))
+            .unwrap_or(body);
+        let tool_calls = tool_call_format.parse(body)?;
+
+        for tool_call in &tool_calls {
+            let Some(Tool::Function(function_call)) =
+                self.tools.iter().find(|Tool::Function(function_call)| {
+                    function_call.function.name == tool_call.name
+                })
+            else {
+                return Err(anyhow!("Tool '{}' was not offered", tool_call.name));
+            };
+
+            if let Parameters::Schema(schema) = &function_call.function.parameters {
+                schema
+                    .validate_arguments(&tool_call.arguments)
+                    .map_err(|err| {
+                        anyhow!("Invalid arguments for tool '{}': {err}", tool_call.name)
+                    })?;
+            }
+        }
+
+        Ok(tool_calls)
+    }
+
+    /// Length of the longest suffix of the held back text that begins the opening marker
+    fn partial_marker_length(&self, opening_marker: &str) -> usize {
+        self.held_back_text
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|index| opening_marker.starts_with(&self.held_back_text[*index..]))
+            .map_or(0, |index| self.held_back_text.len() - index)
+    }
+
+    fn release_held_back_text(&mut self, results: &mut Vec<GeneratedTokenResult>) {
+        if !self.held_back_text.is_empty() {
+            self.released_any_text = true;
+
+            results.push(GeneratedTokenResult::Token(std::mem::take(
+                &mut self.held_back_text,
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::FunctionCall;
+    use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+    use serde_json::json;
+
+    use super::*;
+
+    fn weather_tool() -> Result<Tool<ValidatedParametersSchema>> {
+        Ok(Tool::Function(FunctionCall {
+            function: Function {
+                name: "get_weather".to_owned(),
+                description: "Get the weather".to_owned(),
+                parameters: Parameters::Schema(serde_json::from_value(json!({
+                    "type": "object",
+                    "properties": {"city": {"type": "string"}},
+                    "required": ["city"]
+                }))?),
+            },
+        }))
+    }
+
+    fn push_all(detector: &mut ToolCallDetector, pieces: &[&str]) -> Vec<GeneratedTokenResult> {
+        let mut results: Vec<GeneratedTokenResult> = pieces
+            .iter()
+            .flat_map(|piece| detector.push(piece))
+            .collect();
+
+        results.extend(detector.flush());
+        results
+    }
+
+    fn text_of(results: &[GeneratedTokenResult]) -> String {
+        results
+            .iter()
+            .filter_map(|result| match result {
+                GeneratedTokenResult::Token(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn tool_calls_of(results: &[GeneratedTokenResult]) -> Vec<&ToolCall> {
+        results
+            .iter()
+            .filter_map(|result| match result {
+                GeneratedTokenResult::ToolCall(tool_call) => Some(tool_call),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn passes_text_through_without_tools() {
+        let mut detector = ToolCallDetector::new(Some(ToolCallFormat::Hermes), vec![]);
+        let results = push_all(&mut detector, &["<tool_call>", "{}", "</tool_call>"]);
+
+        assert_eq!(text_of(&results), "<tool_call>{}</tool_call>");
+    }
+
+    #[test]
+    fn detects_hermes_call_split_across_pieces() -> Result<()> {
+        let mut detector =
+            ToolCallDetector::new(Some(ToolCallFormat::Hermes), vec![weather_tool()?]);
+        let results = push_all(
+            &mut detector,
+            &[
+                "Let me check.\n<tool",
+                "_call>\n{\"name\": \"get_weather\", ",
+                "\"arguments\": {\"city\": \"Paris\"}}\n</tool_",
+                "call>",
+            ],
+        );
+
+        assert_eq!(text_of(&results), "Let me check.\n");
+
+        let tool_calls = tool_calls_of(&results);
+
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].name, "get_weather");
+        assert_eq!(tool_calls[0].arguments, json!({"city": "Paris"}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn releases_call_with_invalid_arguments_as_text() -> Result<()> {
+        let mut detector =
+            ToolCallDetector::new(Some(ToolCallFormat::Hermes), vec![weather_tool()?]);
+        let generated_text =
+            "<tool_call>{\"name\": \"get_weather\", \"arguments\": {}}</tool_call>";
+        let results = push_all(&mut detector, &[generated_text]);
+
+        assert_eq!(text_of(&results), generated_text);
+        assert!(tool_calls_of(&results).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn releases_call_to_unknown_tool_as_text() -> Result<()> {
+        let mut detector =
+            ToolCallDetector::new(Some(ToolCallFormat::Mistral), vec![weather_tool()?]);
+        let results = push_all(
+            &mut detector,
+            &["[TOOL_CALLS][{\"name\": \"send_email\", \"arguments\": {}}]"],
+        );
+
+        assert!(tool_calls_of(&results).is_empty());
+        assert!(text_of(&results).starts_with("[TOOL_CALLS]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn detects_bare_llama3_json_call() -> Result<()> {
+        let mut detector =
+            ToolCallDetector::new(Some(ToolCallFormat::Llama3Json), vec![weather_tool()?]);
+        let results = push_all(
+            &mut detector,
+            &[
+                "\n",
+                "{\"name\": \"get_weather\", ",
+                "\"parameters\": {\"city\": \"Oslo\"}}",
+            ],
+        );
+
+        assert_eq!(text_of(&results), "");
+        assert_eq!(
+            tool_calls_of(&results)[0].arguments,
+            json!({"city": "Oslo"})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn does_not_capture_json_after_text_in_llama3_format() -> Result<()> {
+        let mut detector =
+            ToolCallDetector::new(Some(ToolCallFormat::Llama3Json), vec![weather_tool()?]);
+        let results = push_all(&mut detector, &["Example: ", "{\"a\": 1}"]);
+
+        assert_eq!(text_of(&results), "Example: {\"a\": 1}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use nanoid::nanoid;
+use paddler_types::tool_call::ToolCall;
+use serde_json::Value;
+
+/// Syntax a model family uses to call tools, recognized from its chat template
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ToolCallFormat {
+    /// `<tool_call>{"name": ..., "arguments": ...}</tool_call>`, used by Hermes and Qwen
+    Hermes,
+    /// `<|python_tag|>{"name": ..., "parameters": ...}` or a bare JSON object, used by Llama 3
+    Llama3Json,
+    /// `[TOOL_CALLS][{"name": ..., "arguments": ...}]`, used by Mistral
+    Mistral,
+}
+
+impl ToolCallFormat {
+    #[must_use]
+    pub fn detect(chat_template: &str) -> Option<Self> {
+        if chat_template.contains("<tool_call>") {
+            Some(Self::Hermes)
+        } else if chat_template.contains("[TOOL_CALLS]") {
+            Some(Self::Mistral)
+        } else if chat_template.contains("<|python_tag|>") || chat_template.contains("ipython") {
+            Some(Self::Llama3Json)
+        } else {
+            None
+        }
+    }
+
+    /// Tool calls without a closing marker last until the end of the generated output
+    #[must_use]
+    pub const fn closing_marker(self) -> Option<&'static str> {
+        match self {
+            Self::Hermes => Some("</tool_call>"),
+            Self::Llama3Json | Self::Mistral => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn opening_marker(self) -> &'static str {
+        match self {
+            Self::Hermes => "<tool_call>",
+            Self::Llama3Json => "<|python_tag|>",
+            Self::Mistral => "[TOOL_CALLS]",
+        }
+    }
+
+    /// Parses the text between the markers, which holds either a single call or a list of them
+    pub fn parse(self, body: &str) -> Result<Vec<ToolCall>> {
+        let body = body.trim();
+
+        match self {
+            Self::Llama3Json => parse_semicolon_separated_tool_calls(body),
+            Self::Hermes | Self::Mistral => parse_tool_calls(&serde_json::from_str(body)?),
+        }
+    }
+}
+
+/// Llama 3 separates parallel calls with semicolons, so each call is read as the next JSON value
+/// in the body; semicolons inside the arguments are left to the JSON parser
+fn parse_semicolon_separated_tool_calls(body: &str) -> Result<Vec<ToolCall>> {
+    let mut tool_calls = Vec::new();
+    let mut remaining_body = body;
+
+    while !remaining_body.is_empty() {
+        let mut values = serde_json::Deserializer::from_str(remaining_body).into_iter::<Value>();
+        let value = values
+            .next()
+            .ok_or_else(|| anyhow!("Tool call is empty"))??;
+
+        tool_calls.extend(parse_tool_calls(&value)?);
+
+        let rest = remaining_body[values.byte_offset()..].trim_start();
+
+        remaining_body = rest.strip_prefix(';').unwrap_or(rest).trim_start();
+    }
+
+    Ok(tool_calls)
+}
+
+fn parse_tool_calls(value: &Value) -> Result<Vec<ToolCall>> {
+    match value {
+        Value::Array(calls) => calls.iter().map(parse_tool_call).collect(),
+        call => Ok(vec![parse_tool_call(call)?]),
+    }
+}
+
+fn parse_tool_call(call: &Value) -> Result<ToolCall> {
+    let name = call
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Tool call does not have a name"))?;
+
+    let arguments = match call.get("arguments").or_else(|| call.get("parameters")) {
+        None => Value::Object(serde_json::Map::new()),
+        // Some models emit the arguments as an already serialized JSON string
+        Some(Value::String(arguments)) => serde_json::from_str(arguments)?,
+        Some(arguments) => arguments.clone(),
+    };
+
+    let id = call
+        .get("id")
+        .and_then(Value::as_str)
+        .map_or_else(|| format!("call_{}", nanoid!()), ToOwned::to_owned);
+
+    Ok(ToolCall {
+        arguments,
+        id,
+        name: name.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn detects_format_from_chat_template() {
+        assert_eq!(
+            ToolCallFormat::detect("{%- for tool in tools %}<tool_call>{%- endfor %}"),
+            Some(ToolCallFormat::Hermes)
+        );
+        assert_eq!(
+            ToolCallFormat::detect("{{- '[TOOL_CALLS]' }}"),
+            Some(ToolCallFormat::Mistral)
+        );
+        assert_eq!(
+            ToolCallFormat::detect("<|start_header_id|>ipython<|end_header_id|>"),
+            Some(ToolCallFormat::Llama3Json)
+        );
+        assert_eq!(ToolCallFormat::detect("{{ message.content }}"), None);
+    }
+
+    #[test]
+    fn parses_hermes_call() -> Result<()> {
+        let tool_calls = ToolCallFormat::Hermes
+            .parse(r#" {"name": "get_weather", "arguments": {"city": "Paris"}} "#)?;
+
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].name, "get_weather");
+        assert_eq!(tool_calls[0].arguments, json!({"city": "Paris"}));
+        assert!(tool_calls[0].id.starts_with("call_"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_llama3_calls_with_parameters() -> Result<()> {
+        let tool_calls = ToolCallFormat::Llama3Json.parse(
+            r#"{"name": "a", "parameters": {"x": 1}}; {"name": "b", "parameters": "{\"y\": 2}"}"#,
+        )?;
+
+        assert_eq!(tool_calls.len(), 2);
+        assert_eq!(tool_calls[0].arguments, json!({"x": 1}));
+        assert_eq!(tool_calls[1].arguments, json!({"y": 2}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_llama3_calls_with_semicolons_in_arguments() -> Result<()> {
+        let tool_calls = ToolCallFormat::Llama3Json.parse(
+            r#"{"name": "a", "parameters": {"sql": "SELECT 1; SELECT 2"}};{"name": "b", "parameters": {}}"#,
+        )?;
+
+        assert_eq!(tool_calls.len(), 2);
+        assert_eq!(
+            tool_calls[0].arguments,
+            json!({"sql": "SELECT 1; SELECT 2"})
+        );
+        assert_eq!(tool_calls[1].name, "b");
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_llama3_calls_with_trailing_garbage() {
+        assert!(
+            ToolCallFormat::Llama3Json
+                .parse(r#"{"name": "a", "parameters": {}} not json"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn keeps_mistral_call_ids() -> Result<()> {
+        let tool_calls = ToolCallFormat::Mistral
+            .parse(r#"[{"name": "a", "arguments": {}, "id": "abc123XYZ"}]"#)?;
+
+        assert_eq!(tool_calls[0].id, "abc123XYZ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_call_without_name() {
+        assert!(
+            ToolCallFormat::Hermes
+                .parse(r#"{"arguments": {}}"#)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use llama_cpp_bindings::model::AddBos;
+use llama_cpp_bindings::model::LlamaModel;
+use llama_cpp_bindings::token::LlamaToken;
+
+fn detokenize(model: &LlamaModel, tokens: &[LlamaToken]) -> Result<String> {
+    let mut decoder = encoding_rs::UTF_8.new_decoder();
+    let mut text = String::new();
+
+    for token in tokens {
+        text.push_str(&model.token_to_piece(*token, &mut decoder, true, None)?);
+    }
+
+    Ok(text)
+}
+
+/// Keeps the beginning and the end of the prompt, which hold the instructions and the latest
+/// messages, and cuts out the tokens in between. Returns `None` if not even the BOS token fits.
+pub fn truncate_prompt_middle_out(
+    model: &LlamaModel,
+    prompt: &str,
+    max_prompt_tokens: usize,
+) -> Result<Option<String>> {
+    let tokens = model.str_to_token(prompt, AddBos::Never)?;
+    let bos_tokens_count = model.str_to_token("", AddBos::Always)?.len();
+    let mut kept_tokens_count = max_prompt_tokens
+        .saturating_sub(bos_tokens_count)
+        .min(tokens.len());
+
+    // Tokens can merge differently where the head and the tail meet, so the result is
+    // tokenized again and shrunk by however many tokens it is still over
+    while kept_tokens_count > 0 {
+        let head_tokens_count = kept_tokens_count / 2;
+        let tail_tokens_count = kept_tokens_count - head_tokens_count;
+        let (head_tokens, _) = tokens.split_at(head_tokens_count);
+        let (_, tail_tokens) = tokens.split_at(tokens.len() - tail_tokens_count);
+        let truncated_prompt = format!(
+            "{}{}",
+            detokenize(model, head_tokens)?,
+            detokenize(model, tail_tokens)?
+        );
+        let truncated_tokens_count = model.str_to_token(&truncated_prompt, AddBos::Always)?.len();
+
+        if truncated_tokens_count <= max_prompt_tokens {
+            return Ok(Some(truncated_prompt));
+        }
+
+        kept_tokens_count =
+            kept_tokens_count.saturating_sub(truncated_tokens_count - max_prompt_tokens);
+    }
+
+    Ok(None)
+}
@@ -52,6 +52,10 @@ impl AtomicValue<AtomicI32> {
         self.value.fetch_sub(1, Ordering::SeqCst);
     }
 
+    pub fn decrement_by(&self, decrement: i32) {
+        self.value.fetch_sub(decrement, Ordering::SeqCst);
+    }
+
     pub fn get(&self) -> i32 {
         self.value.load(Ordering::SeqCst)
     }
@@ -60,6 +64,10 @@ impl AtomicValue<AtomicI32> {
         self.value.fetch_add(1, Ordering::SeqCst);
     }
 
+    pub fn increment_by(&self, increment: i32) {
+        self.value.fetch_add(increment, Ordering::SeqCst);
+    }
+
     pub fn reset(&self) {
         self.value.store(0, Ordering::SeqCst);
     }
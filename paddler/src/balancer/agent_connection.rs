@@ -0,0 +1,16 @@
+use tokio_util::sync::CancellationToken;
+
+/// Websocket connection an agent currently talks to the balancer through. An agent that
+/// reconnects gets a new one, while its controller stays the same.
+#[derive(Clone)]
+pub struct AgentConnection {
+    pub close: CancellationToken,
+    pub id: String,
+}
+
+impl AgentConnection {
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        !self.close.is_cancelled()
+    }
+}
@@ -9,6 +9,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use log::debug;
 use nanoid::nanoid;
+use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
@@ -27,6 +28,7 @@ use crate::agent::jsonrpc::Request as AgentJsonRpcRequest;
 use crate::agent::jsonrpc::notification_params::SetStateParams;
 use crate::agent_desired_state::AgentDesiredState;
 use crate::atomic_value::AtomicValue;
+use crate::balancer::agent_connection::AgentConnection;
 use crate::balancer::agent_controller_update_result::AgentControllerUpdateResult;
 use crate::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use crate::balancer::embedding_sender_collection::EmbeddingSenderCollection;
@@ -40,10 +42,18 @@ use crate::sends_rpc_message::SendsRpcMessage;
 use crate::sets_desired_state::SetsDesiredState;
 
 pub struct AgentController {
+    pub accepts_embeddings: AtomicValue<AtomicBool>,
+    /// Messages wait here while the agent is reconnecting
+    pub agent_message_rx: Mutex<mpsc::UnboundedReceiver<AgentJsonRpcMessage>>,
     pub agent_message_tx: mpsc::UnboundedSender<AgentJsonRpcMessage>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
+    pub connection: RwLock<AgentConnection>,
+    /// Cancelled once the agent is gone for good, either because it deregistered or because it
+    /// did not reconnect within the grace period
     pub connection_close: CancellationToken,
     pub desired_slots_total: AtomicValue<AtomicI32>,
+    /// State the balancer sent to the agent last
+    pub desired_state: RwLock<Option<AgentDesiredState>>,
     pub download_current: AtomicValue<AtomicUsize>,
     pub download_filename: RwLock<Option<String>>,
     pub download_total: AtomicValue<AtomicUsize>,
@@ -53,15 +63,40 @@ pub struct AgentController {
     pub issues: RwLock<BTreeSet<AgentIssue>>,
     pub model_metadata_sender_collection: Arc<ModelMetadataSenderCollection>,
     pub model_path: RwLock<Option<String>>,
+    /// Named model pool the balancer assigned the agent to; `None` is the default pool
+    pub model_pool: RwLock<Option<String>>,
     pub name: Option<String>,
     pub newest_update_version: AtomicValue<AtomicI32>,
+    pub outstanding_tokens: AtomicValue<AtomicI32>,
+    pub prefix_cache_hits: AtomicValue<AtomicUsize>,
+    pub prefix_cache_misses: AtomicValue<AtomicUsize>,
+    /// Model pool the agent asked to join when it registered
+    pub requested_model_pool: Option<String>,
     pub slots_processing: AtomicValue<AtomicI32>,
     pub slots_total: AtomicValue<AtomicI32>,
+    pub state_application_count: AtomicValue<AtomicUsize>,
     pub state_application_status_code: AtomicValue<AtomicI32>,
+    /// Serialized message the previous connection broke on, sent first once the agent reconnects
+    pub unsent_agent_message: Mutex<Option<String>>,
     pub uses_chat_template_override: AtomicValue<AtomicBool>,
+    pub weight: u32,
 }
 
 impl AgentController {
+    /// Makes the controller talk through the connection the agent reconnected with, closing the
+    /// previous one in case the balancer did not notice it went stale yet
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn attach_connection(&self, connection: AgentConnection) {
+        let mut locked_connection = self
+            .connection
+            .write()
+            .expect("Poisoned lock on agent connection");
+
+        locked_connection.close.cancel();
+
+        *locked_connection = connection;
+    }
+
     pub async fn get_chat_template_override(
         &self,
     ) -> Result<ManagesSendersController<ChatTemplateOverrideSenderCollection>> {
@@ -72,6 +107,22 @@ impl AgentController {
         .await
     }
 
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_connection(&self) -> AgentConnection {
+        self.connection
+            .read()
+            .expect("Poisoned lock on agent connection")
+            .clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_desired_state(&self) -> Option<AgentDesiredState> {
+        self.desired_state
+            .read()
+            .expect("Poisoned lock on desired state")
+            .clone()
+    }
+
     #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     pub fn get_download_filename(&self) -> Option<String> {
         self.download_filename
@@ -103,6 +154,19 @@ impl AgentController {
             .clone()
     }
 
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_model_pool(&self) -> Option<String> {
+        self.model_pool
+            .read()
+            .expect("Poisoned lock on model pool")
+            .clone()
+    }
+
+    /// Agents that are reconnecting keep their in-flight requests, but do not get new ones
+    pub fn is_connected(&self) -> bool {
+        self.get_connection().is_open()
+    }
+
     #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     pub fn set_download_filename(&self, filename: Option<String>) {
         let mut locked_filename = self
@@ -130,6 +194,16 @@ impl AgentController {
         *locked_path = model_path;
     }
 
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn set_model_pool(&self, model_pool: Option<String>) {
+        let mut locked_model_pool = self
+            .model_pool
+            .write()
+            .expect("Poisoned lock on model pool");
+
+        *locked_model_pool = model_pool;
+    }
+
     pub async fn stop_responding_to(&self, request_id: String) -> Result<()> {
         self.send_rpc_message(AgentJsonRpcMessage::Notification(
             AgentJsonRpcNotification::StopRespondingTo(request_id),
@@ -142,13 +216,17 @@ impl AgentController {
     pub fn update_from_slot_aggregated_status_snapshot(
         &self,
         SlotAggregatedStatusSnapshot {
+            accepts_embeddings,
             desired_slots_total,
             download_current,
             download_filename,
             download_total,
             issues,
             model_path,
+            prefix_cache_hits,
+            prefix_cache_misses,
             slots_total,
+            state_application_count,
             state_application_status,
             uses_chat_template_override,
             version,
@@ -165,18 +243,22 @@ impl AgentController {
 
         let mut changed = false;
 
-        changed = changed || self.desired_slots_total.set_check(desired_slots_total);
-        changed = changed || self.download_current.set_check(download_current);
-        changed = changed || self.download_total.set_check(download_total);
-        changed = changed || self.slots_total.set_check(slots_total);
-        changed = changed
-            || self
-                .state_application_status_code
-                .set_check(state_application_status as i32);
-        changed = changed
-            || self
-                .uses_chat_template_override
-                .set_check(uses_chat_template_override);
+        changed |= self.accepts_embeddings.set_check(accepts_embeddings);
+        changed |= self.desired_slots_total.set_check(desired_slots_total);
+        changed |= self.download_current.set_check(download_current);
+        changed |= self.download_total.set_check(download_total);
+        changed |= self.prefix_cache_hits.set_check(prefix_cache_hits);
+        changed |= self.prefix_cache_misses.set_check(prefix_cache_misses);
+        changed |= self.slots_total.set_check(slots_total);
+        changed |= self
+            .state_application_count
+            .set_check(state_application_count);
+        changed |= self
+            .state_application_status_code
+            .set_check(state_application_status as i32);
+        changed |= self
+            .uses_chat_template_override
+            .set_check(uses_chat_template_override);
 
         self.newest_update_version
             .compare_and_swap(newest_update_version, version);
@@ -312,6 +394,7 @@ impl ProducesSnapshot for AgentController {
 
     fn make_snapshot(&self) -> Result<Self::Snapshot> {
         Ok(AgentControllerSnapshot {
+            accepts_embeddings: self.accepts_embeddings.get(),
             desired_slots_total: self.desired_slots_total.get(),
             download_current: self.download_current.get(),
             download_filename: self.get_download_filename(),
@@ -319,7 +402,10 @@ impl ProducesSnapshot for AgentController {
             id: self.id.clone(),
             issues: self.get_issues(),
             model_path: self.get_model_path(),
+            model_pool: self.get_model_pool(),
             name: self.name.clone(),
+            prefix_cache_hits: self.prefix_cache_hits.get(),
+            prefix_cache_misses: self.prefix_cache_misses.get(),
             slots_processing: self.slots_processing.get(),
             slots_total: self.slots_total.get(),
             state_application_status: self.state_application_status_code.get().try_into()?,
@@ -341,7 +427,13 @@ impl SendsRpcMessage for AgentController {
 
 #[async_trait]
 impl SetsDesiredState for AgentController {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     async fn set_desired_state(&self, desired_state: AgentDesiredState) -> Result<()> {
+        *self
+            .desired_state
+            .write()
+            .expect("Poisoned lock on desired state") = Some(desired_state.clone());
+
         self.send_rpc_message(AgentJsonRpcMessage::Notification(
             AgentJsonRpcNotification::SetState(Box::new(SetStateParams { desired_state })),
         ))
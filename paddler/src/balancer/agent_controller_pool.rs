@@ -1,49 +1,219 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::sync::Arc;
+use std::sync::RwLock;
 
 use anyhow::Result;
-use async_trait::async_trait;
 use dashmap::DashMap;
 use paddler_types::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
 use paddler_types::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_types::dispatch_strategy::DispatchStrategy;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use tokio::sync::watch;
 
 use super::agent_controller::AgentController;
 use super::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
 use crate::agent_desired_state::AgentDesiredState;
 use crate::balancer::agent_controller_slot_guard::AgentControllerSlotGuard;
+use crate::balancer::dispatch_affinity::DispatchAffinity;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::dispatch_strategy::create_dispatch_strategy;
 use crate::balancer::dispatched_agent::DispatchedAgent;
+use crate::balancer::metrics_registry::MetricsRegistry;
+use crate::balancer::model_not_found::ModelNotFound;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+use crate::balancer_applicable_state::BalancerApplicableState;
 use crate::produces_snapshot::ProducesSnapshot;
-use crate::sets_desired_state::SetsDesiredState;
+use crate::sets_desired_state::SetsDesiredState as _;
 use crate::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct AgentControllerPool {
     pub agents: DashMap<String, Arc<AgentController>>,
+    dispatch_affinity: DispatchAffinity,
+    dispatch_strategy: RwLock<Arc<dyn OrdersDispatchCandidates>>,
+    pub metrics_registry: Arc<MetricsRegistry>,
+    model_pools: RwLock<BTreeSet<String>>,
     update_tx: watch::Sender<()>,
 }
 
 impl AgentControllerPool {
     #[must_use]
-    pub fn take_least_busy_agent_controller(&self) -> Option<DispatchedAgent> {
-        let mut candidates: Vec<Arc<AgentController>> = self
+    pub fn new(dispatch_strategy: DispatchStrategy) -> Self {
+        let (update_tx, _initial_rx) = watch::channel(());
+
+        Self {
+            agents: DashMap::new(),
+            dispatch_affinity: DispatchAffinity::default(),
+            dispatch_strategy: RwLock::new(create_dispatch_strategy(dispatch_strategy)),
+            metrics_registry: Arc::new(MetricsRegistry::default()),
+            model_pools: RwLock::new(BTreeSet::new()),
+            update_tx,
+        }
+    }
+
+    /// Sends every agent the desired state of the model pool it serves, moving agents between
+    /// pools first so that each pool that was added gets at least one of them.
+    ///
+    /// When rolling out agent by agent, agents that would have to reload their model only start
+    /// prefetching the new one; they are returned so that they can be switched one at a time.
+    pub async fn apply_balancer_applicable_state(
+        &self,
+        balancer_applicable_state: &BalancerApplicableState,
+        rollout_strategy: RolloutStrategy,
+    ) -> Result<Vec<(Arc<AgentController>, AgentDesiredState)>> {
+        let model_pools: BTreeSet<String> = balancer_applicable_state
+            .model_pool_agent_desired_states
+            .keys()
+            .cloned()
+            .collect();
+
+        self.rebalance_model_pools(&model_pools);
+        self.set_model_pools(model_pools);
+
+        let agent_controllers: Vec<Arc<AgentController>> = self
             .agents
             .iter()
             .map(|entry| entry.value().clone())
             .collect();
+        let mut pending_agent_states = Vec::new();
+
+        for agent_controller in agent_controllers {
+            let desired_state = balancer_applicable_state
+                .agent_desired_state_for(agent_controller.get_model_pool().as_deref())
+                .clone();
+
+            match agent_controller.get_desired_state() {
+                Some(current_desired_state)
+                    if rollout_strategy == RolloutStrategy::AgentByAgent
+                        && current_desired_state.requires_reload_to_switch_to(&desired_state) =>
+                {
+                    agent_controller
+                        .set_desired_state(AgentDesiredState {
+                            prefetch_model: desired_state.model.clone(),
+                            ..current_desired_state
+                        })
+                        .await?;
+
+                    pending_agent_states.push((agent_controller, desired_state));
+                }
+                _ => agent_controller.set_desired_state(desired_state).await?,
+            }
+        }
 
-        candidates.sort_by_key(|agent| agent.slots_processing.get());
+        self.update_tx.send_replace(());
 
-        for agent_controller in candidates {
-            let limit = agent_controller.slots_total.get();
+        Ok(pending_agent_states)
+    }
+
+    /// Puts a newly registered agent into the pool it asked for, or into the pool with the
+    /// fewest agents when it did not ask for any that exists.
+    pub fn assign_model_pool(&self, agent_controller: &AgentController) {
+        let model_pools = self.get_model_pools();
+
+        let model_pool = match &agent_controller.requested_model_pool {
+            Some(requested_model_pool) if model_pools.contains(requested_model_pool) => {
+                Some(requested_model_pool.clone())
+            }
+            _ => self.least_populated_model_pool(&model_pools),
+        };
+
+        agent_controller.set_model_pool(model_pool);
+    }
+
+    #[must_use]
+    pub fn get_dispatch_strategy(&self) -> DispatchStrategy {
+        self.get_dispatch_candidates_orderer().dispatch_strategy()
+    }
 
-            if agent_controller.slots_processing.try_increment_below(limit) {
-                self.update_tx.send_replace(());
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn set_dispatch_strategy(&self, dispatch_strategy: DispatchStrategy) {
+        let mut locked_dispatch_strategy = self
+            .dispatch_strategy
+            .write()
+            .expect("Poisoned lock on dispatch strategy");
 
-                let slot_guard = AgentControllerSlotGuard::new(
-                    agent_controller.clone(),
-                    self.update_tx.clone(),
-                );
+        // Keep the existing instance so that stateful strategies (round-robin) do not restart
+        if locked_dispatch_strategy.dispatch_strategy() != dispatch_strategy {
+            *locked_dispatch_strategy = create_dispatch_strategy(dispatch_strategy);
+        }
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_model_pools(&self) -> BTreeSet<String> {
+        self.model_pools
+            .read()
+            .expect("Poisoned lock on model pools")
+            .clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn set_model_pools(&self, model_pools: BTreeSet<String>) {
+        *self
+            .model_pools
+            .write()
+            .expect("Poisoned lock on model pools") = model_pools;
+    }
+
+    /// Requests that do not name a model are served by the default model, and so are all of them
+    /// while no model pools are configured
+    pub fn resolve_model_pool(&self, model: Option<&str>) -> Result<Option<String>, ModelNotFound> {
+        let model_pools = self.get_model_pools();
+
+        match model {
+            Some(model) if model_pools.contains(model) => Ok(Some(model.to_owned())),
+            Some(model) if !model_pools.is_empty() => Err(ModelNotFound {
+                model: model.to_owned(),
+            }),
+            Some(_) | None => Ok(None),
+        }
+    }
+
+    #[must_use]
+    pub fn take_agent_controller(
+        &self,
+        dispatch_criteria: &DispatchCriteria,
+    ) -> Option<DispatchedAgent> {
+        // Routes reject such requests upfront, unless the pools changed in the meantime
+        let Ok(model_pool) = self.resolve_model_pool(dispatch_criteria.model.as_deref()) else {
+            return None;
+        };
+
+        if let Some(affinity_key) = dispatch_criteria.affinity_key
+            && let Some(agent_controller) = self
+                .dispatch_affinity
+                .get_agent_id(affinity_key)
+                .and_then(|agent_id| self.get_agent_controller(&agent_id))
+                .filter(|agent_controller| {
+                    Self::can_serve(agent_controller, model_pool.as_ref(), dispatch_criteria)
+                })
+            && let Some(dispatched_agent) =
+                self.try_dispatch_to(agent_controller, dispatch_criteria)
+        {
+            return Some(dispatched_agent);
+        }
 
-                return Some(DispatchedAgent::new(agent_controller, slot_guard));
+        let mut candidates: Vec<Arc<AgentController>> = self
+            .agents
+            .iter()
+            .map(|entry| entry.value().clone())
+            .filter(|agent_controller| {
+                Self::can_serve(agent_controller, model_pool.as_ref(), dispatch_criteria)
+            })
+            .collect();
+
+        self.get_dispatch_candidates_orderer()
+            .order_candidates(&mut candidates, dispatch_criteria);
+
+        for agent_controller in candidates {
+            if let Some(dispatched_agent) =
+                self.try_dispatch_to(agent_controller, dispatch_criteria)
+            {
+                if let Some(affinity_key) = dispatch_criteria.affinity_key {
+                    self.dispatch_affinity
+                        .remember(affinity_key, &dispatched_agent.agent_controller.id);
+                }
+
+                return Some(dispatched_agent);
             }
         }
 
@@ -70,6 +240,8 @@ impl AgentControllerPool {
     }
 
     pub fn remove_agent_controller(&self, agent_id: &str) -> Result<bool> {
+        self.dispatch_affinity.forget_agent(agent_id);
+
         if self.agents.remove(agent_id).is_some() {
             self.update_tx.send_replace(());
 
@@ -79,10 +251,148 @@ impl AgentControllerPool {
         }
     }
 
+    fn can_serve(
+        agent_controller: &AgentController,
+        model_pool: Option<&String>,
+        dispatch_criteria: &DispatchCriteria,
+    ) -> bool {
+        agent_controller.is_connected()
+            && agent_controller.get_model_pool().as_ref() == model_pool
+            && (!dispatch_criteria.requires_embeddings || agent_controller.accepts_embeddings.get())
+    }
+
+    fn is_pinned_to_model_pool(
+        agent_controller: &AgentController,
+        model_pools: &BTreeSet<String>,
+    ) -> bool {
+        agent_controller
+            .requested_model_pool
+            .as_ref()
+            .is_some_and(|requested_model_pool| model_pools.contains(requested_model_pool))
+    }
+
+    fn least_populated_model_pool(&self, model_pools: &BTreeSet<String>) -> Option<String> {
+        self.model_pool_sizes(model_pools)
+            .into_iter()
+            .min_by_key(|(_, size)| *size)
+            .and_then(|(model_pool, _)| model_pool)
+    }
+
+    /// The default pool is keyed by `None`, so it comes first and wins ties
+    fn model_pool_sizes(&self, model_pools: &BTreeSet<String>) -> BTreeMap<Option<String>, usize> {
+        let mut sizes: BTreeMap<Option<String>, usize> = BTreeMap::new();
+
+        sizes.insert(None, 0);
+
+        for model_pool in model_pools {
+            sizes.insert(Some(model_pool.clone()), 0);
+        }
+
+        for entry in &self.agents {
+            if let Some(size) = sizes.get_mut(&entry.value().get_model_pool()) {
+                *size += 1;
+            }
+        }
+
+        sizes
+    }
+
+    fn rebalance_model_pools(&self, model_pools: &BTreeSet<String>) {
+        let agent_controllers: Vec<Arc<AgentController>> = self
+            .agents
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+
+        let mut displaced_agent_controllers: Vec<&Arc<AgentController>> = Vec::new();
+
+        for agent_controller in &agent_controllers {
+            if Self::is_pinned_to_model_pool(agent_controller, model_pools) {
+                agent_controller.set_model_pool(agent_controller.requested_model_pool.clone());
+            } else if let Some(model_pool) = agent_controller.get_model_pool()
+                && !model_pools.contains(&model_pool)
+            {
+                agent_controller.set_model_pool(None);
+                displaced_agent_controllers.push(agent_controller);
+            }
+        }
+
+        // Agents whose pool was removed are spread over the remaining ones
+        for agent_controller in displaced_agent_controllers {
+            agent_controller.set_model_pool(self.least_populated_model_pool(model_pools));
+        }
+
+        // Pools that are left without agents take them from the most populated pools
+        loop {
+            let sizes = self.model_pool_sizes(model_pools);
+
+            let Some(empty_model_pool) = sizes
+                .iter()
+                .find(|(_, size)| **size == 0)
+                .map(|(model_pool, _)| model_pool.clone())
+            else {
+                break;
+            };
+
+            let Some(agent_controller) = agent_controllers
+                .iter()
+                .filter(|agent_controller| {
+                    !Self::is_pinned_to_model_pool(agent_controller, model_pools)
+                })
+                .filter_map(|agent_controller| {
+                    sizes
+                        .get(&agent_controller.get_model_pool())
+                        .filter(|size| **size > 1)
+                        .map(|size| (agent_controller, *size))
+                })
+                .max_by_key(|(_, size)| *size)
+                .map(|(agent_controller, _)| agent_controller)
+            else {
+                break;
+            };
+
+            agent_controller.set_model_pool(empty_model_pool);
+        }
+    }
+
     pub fn signal_update(&self) {
         self.update_tx.send_replace(());
     }
 
+    fn try_dispatch_to(
+        &self,
+        agent_controller: Arc<AgentController>,
+        dispatch_criteria: &DispatchCriteria,
+    ) -> Option<DispatchedAgent> {
+        let limit = agent_controller.slots_total.get();
+
+        if !agent_controller.slots_processing.try_increment_below(limit) {
+            return None;
+        }
+
+        agent_controller
+            .outstanding_tokens
+            .increment_by(dispatch_criteria.estimated_tokens);
+
+        self.update_tx.send_replace(());
+
+        let slot_guard = AgentControllerSlotGuard::new(
+            agent_controller.clone(),
+            dispatch_criteria.estimated_tokens,
+            self.update_tx.clone(),
+        );
+
+        Some(DispatchedAgent::new(agent_controller, slot_guard))
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_dispatch_candidates_orderer(&self) -> Arc<dyn OrdersDispatchCandidates> {
+        self.dispatch_strategy
+            .read()
+            .expect("Poisoned lock on dispatch strategy")
+            .clone()
+    }
+
     #[must_use]
     pub fn total_slots(&self) -> AgentControllerPoolTotalSlots {
         let mut slots_processing = 0;
@@ -104,12 +414,7 @@ impl AgentControllerPool {
 
 impl Default for AgentControllerPool {
     fn default() -> Self {
-        let (update_tx, _initial_rx) = watch::channel(());
-
-        Self {
-            agents: DashMap::new(),
-            update_tx,
-        }
+        Self::new(DispatchStrategy::default())
     }
 }
 
@@ -135,21 +440,6 @@ impl ProducesSnapshot for AgentControllerPool {
     }
 }
 
-#[async_trait]
-impl SetsDesiredState for AgentControllerPool {
-    async fn set_desired_state(&self, desired_state: AgentDesiredState) -> Result<()> {
-        for agent in &self.agents {
-            let agent_controller = agent.value();
-
-            agent_controller
-                .set_desired_state(desired_state.clone())
-                .await?;
-        }
-
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
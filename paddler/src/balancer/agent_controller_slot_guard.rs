@@ -6,16 +6,19 @@ use crate::balancer::agent_controller::AgentController;
 
 pub struct AgentControllerSlotGuard {
     agent_controller: Arc<AgentController>,
+    outstanding_tokens: i32,
     pool_update_tx: watch::Sender<()>,
 }
 
 impl AgentControllerSlotGuard {
     pub const fn new(
         agent_controller: Arc<AgentController>,
+        outstanding_tokens: i32,
         pool_update_tx: watch::Sender<()>,
     ) -> Self {
         Self {
             agent_controller,
+            outstanding_tokens,
             pool_update_tx,
         }
     }
@@ -24,6 +27,9 @@ impl AgentControllerSlotGuard {
 impl Drop for AgentControllerSlotGuard {
     fn drop(&mut self) {
         self.agent_controller.slots_processing.decrement();
+        self.agent_controller
+            .outstanding_tokens
+            .decrement_by(self.outstanding_tokens);
         self.pool_update_tx.send_replace(());
     }
 }
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use actix_web::Error;
+use actix_web::HttpMessage as _;
+use actix_web::ResponseError as _;
+use actix_web::body::EitherBody;
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use paddler_types::api_key_scope::ApiKeyScope;
+
+use crate::balancer::api_key_registry::ApiKeyRegistry;
+use crate::balancer::api_key_rejection::ApiKeyRejection;
+
+/// Anthropic SDKs send the key in this header instead of the `Authorization` one
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Middleware state of a service that accepts only requests bearing an API key with its scope.
+/// Authorized keys are put into the request extensions, so handlers can charge their tokens.
+#[derive(Clone)]
+pub struct ApiKeyGuard {
+    /// Also reads the key from the `x-api-key` header, for services that SDKs of other vendors
+    /// talk to
+    pub accepts_api_key_header: bool,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    /// Routes that stay reachable without a key (health checks, agents that use the join secret)
+    pub public_path_prefixes: &'static [&'static str],
+    pub require_api_key: bool,
+    pub scope: ApiKeyScope,
+}
+
+impl ApiKeyGuard {
+    pub async fn authorize<TBody: MessageBody>(
+        self,
+        service_request: ServiceRequest,
+        next: Next<TBody>,
+    ) -> Result<ServiceResponse<EitherBody<TBody>>, Error> {
+        let is_public = self
+            .public_path_prefixes
+            .iter()
+            .any(|public_path_prefix| service_request.path().starts_with(public_path_prefix));
+
+        if !self.require_api_key || is_public {
+            return Ok(next.call(service_request).await?.map_into_left_body());
+        }
+
+        let authorization = service_request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| {
+                self.accepts_api_key_header
+                    .then(|| service_request.headers().get(API_KEY_HEADER))
+                    .flatten()
+                    .and_then(|value| value.to_str().ok())
+            })
+            .ok_or(ApiKeyRejection::MissingBearerToken)
+            .and_then(|secret| self.api_key_registry.authorize(secret.trim(), self.scope));
+
+        match authorization {
+            Ok(api_key) => {
+                service_request.extensions_mut().insert(api_key);
+
+                Ok(next.call(service_request).await?.map_into_left_body())
+            }
+            Err(rejection) => Ok(service_request
+                .into_response(rejection.error_response())
+                .map_into_right_body()),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::RwLock;
+use std::time::Instant;
+
+use dashmap::DashMap;
+use paddler_types::api_key::ApiKey;
+use paddler_types::api_key_rate_limit::ApiKeyRateLimit;
+use paddler_types::api_key_scope::ApiKeyScope;
+use subtle::ConstantTimeEq as _;
+
+use crate::balancer::api_key_rejection::ApiKeyRejection;
+use crate::balancer::api_key_usage_window::ApiKeyUsageWindow;
+
+const BOOTSTRAP_API_KEY_NAME: &str = "bootstrap";
+
+/// API keys the balancer accepts, indexed by their name, along with their recent usage
+#[derive(Default)]
+pub struct ApiKeyRegistry {
+    api_keys: RwLock<BTreeMap<String, ApiKey>>,
+    /// Management key given on the command line, so that the first keys can be created while
+    /// the management service requires one; it is neither stored nor listed
+    bootstrap_api_key: Option<ApiKey>,
+    usage_windows: DashMap<String, ApiKeyUsageWindow>,
+}
+
+impl ApiKeyRegistry {
+    #[must_use]
+    pub fn new(bootstrap_secret: Option<String>) -> Self {
+        Self {
+            bootstrap_api_key: bootstrap_secret.map(|secret| ApiKey {
+                fair_share_weight: None,
+                name: BOOTSTRAP_API_KEY_NAME.to_owned(),
+                rate_limit: ApiKeyRateLimit::default(),
+                scopes: BTreeSet::from([ApiKeyScope::Management]),
+                secret,
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Counts the request against the rate limit of the key
+    pub fn authorize(&self, secret: &str, scope: ApiKeyScope) -> Result<ApiKey, ApiKeyRejection> {
+        let api_key = self
+            .find_by_secret(secret)
+            .ok_or(ApiKeyRejection::UnknownApiKey)?;
+
+        if !api_key.scopes.contains(&scope) {
+            return Err(ApiKeyRejection::OutOfScope { name: api_key.name });
+        }
+
+        self.charge(&api_key, 1, 0)?;
+
+        Ok(api_key)
+    }
+
+    pub fn charge_tokens(&self, api_key: &ApiKey, tokens: i32) -> Result<(), ApiKeyRejection> {
+        self.charge(api_key, 0, u64::try_from(tokens).unwrap_or(0))
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_api_keys(&self) -> Vec<ApiKey> {
+        self.api_keys
+            .read()
+            .expect("Poisoned lock on API keys")
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn set_api_keys(&self, api_keys: Vec<ApiKey>) {
+        self.usage_windows
+            .retain(|api_key_name, _| api_keys.iter().any(|api_key| &api_key.name == api_key_name));
+
+        *self.api_keys.write().expect("Poisoned lock on API keys") = api_keys
+            .into_iter()
+            .map(|api_key| (api_key.name.clone(), api_key))
+            .collect();
+    }
+
+    /// Compares the secret with every key in constant time, so that the time it takes does not
+    /// tell how much of a secret was guessed
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn find_by_secret(&self, secret: &str) -> Option<ApiKey> {
+        let api_keys = self.api_keys.read().expect("Poisoned lock on API keys");
+        let mut found_api_key = None;
+
+        for api_key in api_keys.values().chain(self.bootstrap_api_key.iter()) {
+            if bool::from(api_key.secret.as_bytes().ct_eq(secret.as_bytes())) {
+                found_api_key = Some(api_key.clone());
+            }
+        }
+
+        found_api_key
+    }
+
+    fn charge(&self, api_key: &ApiKey, requests: u32, tokens: u64) -> Result<(), ApiKeyRejection> {
+        let now = Instant::now();
+
+        self.usage_windows
+            .entry(api_key.name.clone())
+            .or_insert_with(|| ApiKeyUsageWindow::new(now))
+            .charge(&api_key.name, &api_key.rate_limit, now, requests, tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_registry() -> ApiKeyRegistry {
+        let api_key_registry = ApiKeyRegistry::default();
+
+        api_key_registry.set_api_keys(vec![ApiKey {
+            fair_share_weight: None,
+            name: "inference".to_owned(),
+            rate_limit: ApiKeyRateLimit {
+                requests_per_minute: Some(1),
+                tokens_per_minute: None,
+            },
+            scopes: BTreeSet::from([ApiKeyScope::Inference]),
+            secret: "inference-secret".to_owned(),
+        }]);
+
+        api_key_registry
+    }
+
+    #[test]
+    fn bootstrap_key_is_accepted_only_for_management() {
+        let api_key_registry = ApiKeyRegistry::new(Some("bootstrap-secret".to_owned()));
+
+        assert!(
+            api_key_registry
+                .authorize("bootstrap-secret", ApiKeyScope::Management)
+                .is_ok()
+        );
+        assert!(matches!(
+            api_key_registry.authorize("bootstrap-secret", ApiKeyScope::Inference),
+            Err(ApiKeyRejection::OutOfScope { .. })
+        ));
+        assert!(api_key_registry.get_api_keys().is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_secret() {
+        assert!(matches!(
+            make_registry().authorize("other-secret", ApiKeyScope::Inference),
+            Err(ApiKeyRejection::UnknownApiKey)
+        ));
+    }
+
+    #[test]
+    fn rejects_key_outside_of_its_scope() {
+        assert!(matches!(
+            make_registry().authorize("inference-secret", ApiKeyScope::Management),
+            Err(ApiKeyRejection::OutOfScope { .. })
+        ));
+    }
+
+    #[test]
+    fn counts_authorized_requests_against_rate_limit() {
+        let api_key_registry = make_registry();
+
+        assert!(
+            api_key_registry
+                .authorize("inference-secret", ApiKeyScope::Inference)
+                .is_ok()
+        );
+        assert!(matches!(
+            api_key_registry.authorize("inference-secret", ApiKeyScope::Inference),
+            Err(ApiKeyRejection::RequestRateExceeded { .. })
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use actix_web::HttpResponse;
+use actix_web::ResponseError;
+use actix_web::http::StatusCode;
+use serde_json::json;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyRejection {
+    #[error("Missing bearer token in the Authorization header")]
+    MissingBearerToken,
+
+    #[error("API key '{name}' is not allowed to use this service")]
+    OutOfScope { name: String },
+
+    #[error("API key '{name}' exceeded its requests per minute limit")]
+    RequestRateExceeded { name: String },
+
+    #[error("API key '{name}' exceeded its tokens per minute limit")]
+    TokenRateExceeded { name: String },
+
+    #[error("Unknown API key")]
+    UnknownApiKey,
+}
+
+impl ResponseError for ApiKeyRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::MissingBearerToken | Self::UnknownApiKey => StatusCode::UNAUTHORIZED,
+            Self::OutOfScope { .. } => StatusCode::FORBIDDEN,
+            Self::RequestRateExceeded { .. } | Self::TokenRateExceeded { .. } => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(json!({
+            "error": self.to_string(),
+        }))
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use paddler_types::api_key_rate_limit::ApiKeyRateLimit;
+
+use crate::balancer::api_key_rejection::ApiKeyRejection;
+
+const WINDOW_DURATION: Duration = Duration::from_secs(60);
+
+/// Usage of a single API key in the current one-minute window
+pub struct ApiKeyUsageWindow {
+    requests: u32,
+    started_at: Instant,
+    tokens: u64,
+}
+
+impl ApiKeyUsageWindow {
+    #[must_use]
+    pub const fn new(started_at: Instant) -> Self {
+        Self {
+            requests: 0,
+            started_at,
+            tokens: 0,
+        }
+    }
+
+    /// Nothing is charged when either limit would be exceeded
+    pub fn charge(
+        &mut self,
+        api_key_name: &str,
+        rate_limit: &ApiKeyRateLimit,
+        now: Instant,
+        requests: u32,
+        tokens: u64,
+    ) -> Result<(), ApiKeyRejection> {
+        if now.duration_since(self.started_at) >= WINDOW_DURATION {
+            *self = Self::new(now);
+        }
+
+        let charged_requests = self.requests.saturating_add(requests);
+        let charged_tokens = self.tokens.saturating_add(tokens);
+
+        if let Some(requests_per_minute) = rate_limit.requests_per_minute
+            && charged_requests > requests_per_minute
+        {
+            return Err(ApiKeyRejection::RequestRateExceeded {
+                name: api_key_name.to_owned(),
+            });
+        }
+
+        if let Some(tokens_per_minute) = rate_limit.tokens_per_minute
+            && charged_tokens > u64::from(tokens_per_minute)
+        {
+            return Err(ApiKeyRejection::TokenRateExceeded {
+                name: api_key_name.to_owned(),
+            });
+        }
+
+        self.requests = charged_requests;
+        self.tokens = charged_tokens;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(
+        requests_per_minute: Option<u32>,
+        tokens_per_minute: Option<u32>,
+    ) -> ApiKeyRateLimit {
+        ApiKeyRateLimit {
+            requests_per_minute,
+            tokens_per_minute,
+        }
+    }
+
+    #[test]
+    fn rejects_requests_over_the_limit_until_window_ends() {
+        let started_at = Instant::now();
+        let rate_limit = rate_limit(Some(2), None);
+        let mut usage_window = ApiKeyUsageWindow::new(started_at);
+
+        assert!(
+            usage_window
+                .charge("key", &rate_limit, started_at, 1, 0)
+                .is_ok()
+        );
+        assert!(
+            usage_window
+                .charge("key", &rate_limit, started_at, 1, 0)
+                .is_ok()
+        );
+        assert!(matches!(
+            usage_window.charge("key", &rate_limit, started_at, 1, 0),
+            Err(ApiKeyRejection::RequestRateExceeded { .. })
+        ));
+        assert!(
+            usage_window
+                .charge("key", &rate_limit, started_at + WINDOW_DURATION, 1, 0)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejected_charge_does_not_consume_tokens() {
+        let started_at = Instant::now();
+        let rate_limit = rate_limit(None, Some(100));
+        let mut usage_window = ApiKeyUsageWindow::new(started_at);
+
+        assert!(
+            usage_window
+                .charge("key", &rate_limit, started_at, 0, 80)
+                .is_ok()
+        );
+        assert!(matches!(
+            usage_window.charge("key", &rate_limit, started_at, 0, 40),
+            Err(ApiKeyRejection::TokenRateExceeded { .. })
+        ));
+        assert!(
+            usage_window
+                .charge("key", &rate_limit, started_at, 0, 20)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BufferedRequestLimits {
+    /// Requests that stay in the buffer longer than that are rejected with the 504 error
+    pub buffered_request_timeout: Duration,
+    /// Requests over that limit are rejected with the 503 error; 0 disables buffering
+    pub max_buffered_requests: i32,
+}
@@ -1,22 +1,34 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Instant;
 
 use anyhow::Result;
 use paddler_types::buffered_request_manager_snapshot::BufferedRequestManagerSnapshot;
+use paddler_types::request_priority::RequestPriority;
+use tokio::sync::oneshot;
 use tokio::sync::watch;
 use tokio::time::timeout;
 
 use crate::balancer::agent_controller_pool::AgentControllerPool;
 use crate::balancer::buffered_request_agent_wait_result::BufferedRequestAgentWaitResult;
 use crate::balancer::buffered_request_counter::BufferedRequestCounter;
+use crate::balancer::buffered_request_limits::BufferedRequestLimits;
+use crate::balancer::buffered_request_queue::BufferedRequestQueue;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::metrics_registry::MetricsRegistry;
+use crate::balancer::model_not_found::ModelNotFound;
 use crate::produces_snapshot::ProducesSnapshot;
 use crate::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct BufferedRequestManager {
     agent_controller_pool: Arc<AgentControllerPool>,
+    batch_limits: BufferedRequestLimits,
     pub buffered_request_counter: Arc<BufferedRequestCounter>,
-    buffered_request_timeout: Duration,
-    max_buffered_requests: i32,
+    buffered_request_queue: Mutex<BufferedRequestQueue>,
+    interactive_limits: BufferedRequestLimits,
+    pub metrics_registry: Arc<MetricsRegistry>,
     update_tx: watch::Sender<()>,
 }
 
@@ -24,58 +36,118 @@ impl BufferedRequestManager {
     #[must_use]
     pub fn new(
         agent_controller_pool: Arc<AgentControllerPool>,
-        buffered_request_timeout: Duration,
-        max_buffered_requests: i32,
+        batch_limits: BufferedRequestLimits,
+        interactive_limits: BufferedRequestLimits,
     ) -> Self {
         let (update_tx, _initial_rx) = watch::channel(());
+        let metrics_registry = agent_controller_pool.metrics_registry.clone();
 
         Self {
             agent_controller_pool,
+            batch_limits,
             buffered_request_counter: Arc::new(BufferedRequestCounter::new(update_tx.clone())),
-            buffered_request_timeout,
-            max_buffered_requests,
+            buffered_request_queue: Mutex::new(BufferedRequestQueue::default()),
+            interactive_limits,
+            metrics_registry,
             update_tx,
         }
     }
 
-    pub async fn wait_for_available_agent(&self) -> Result<BufferedRequestAgentWaitResult> {
-        // Quick path: a slot is available right now, no buffering needed.
-        if let Some(dispatched_agent) = self
-            .agent_controller_pool
-            .take_least_busy_agent_controller()
+    /// Requests for a model no pool serves would only wait in the buffer until they time out
+    pub fn ensure_model_is_served(&self, model: Option<&str>) -> Result<(), ModelNotFound> {
+        self.agent_controller_pool
+            .resolve_model_pool(model)
+            .map(drop)
+    }
+
+    pub async fn wait_for_available_agent(
+        &self,
+        dispatch_criteria: &DispatchCriteria,
+        fair_share_tenant: Option<FairShareTenant>,
+    ) -> Result<BufferedRequestAgentWaitResult> {
+        let limits = match dispatch_criteria.priority {
+            RequestPriority::Batch => self.batch_limits,
+            RequestPriority::Interactive => self.interactive_limits,
+        };
+        let mut update_rx = self.agent_controller_pool.subscribe_to_updates();
+        let (dispatched_agent_tx, mut dispatched_agent_rx) = oneshot::channel();
+
         {
-            return Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent));
-        }
+            let mut buffered_request_queue = self.lock_buffered_request_queue();
+
+            // Requests that are already buffered get the free slots first
+            buffered_request_queue.dispatch(|dispatch_criteria| {
+                self.agent_controller_pool
+                    .take_agent_controller(dispatch_criteria)
+            });
+
+            // Quick path: a slot is still available, no buffering needed.
+            if let Some(dispatched_agent) = self
+                .agent_controller_pool
+                .take_agent_controller(dispatch_criteria)
+            {
+                self.metrics_registry.buffer_wait_seconds.observe(0.0);
+
+                return Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent));
+            }
+
+            // Slot is busy — we would need to wait. Reject if the buffer is full
+            // (max_buffered_requests == 0 means buffering is disabled entirely).
+            if buffered_request_queue.count(dispatch_criteria.priority)
+                >= usize::try_from(limits.max_buffered_requests).unwrap_or(0)
+            {
+                return Ok(BufferedRequestAgentWaitResult::BufferOverflow);
+            }
 
-        // Slot is busy — we would need to wait. Reject if the buffer is full
-        // (max_buffered_requests == 0 means buffering is disabled entirely).
-        if self.buffered_request_counter.get() >= self.max_buffered_requests {
-            return Ok(BufferedRequestAgentWaitResult::BufferOverflow);
+            buffered_request_queue.push(
+                dispatch_criteria.clone(),
+                fair_share_tenant,
+                dispatched_agent_tx,
+            );
         }
 
         let _buffered_request_count_guard = self.buffered_request_counter.increment_with_guard();
-        let agent_controller_pool = self.agent_controller_pool.clone();
-        let mut update_rx = agent_controller_pool.subscribe_to_updates();
+        let buffered_at = Instant::now();
 
-        match timeout(self.buffered_request_timeout, async {
+        let wait_result = timeout(limits.buffered_request_timeout, async {
             loop {
-                if let Some(dispatched_agent) =
-                    agent_controller_pool.take_least_busy_agent_controller()
-                {
-                    return Ok::<_, anyhow::Error>(BufferedRequestAgentWaitResult::Found(
-                        dispatched_agent,
-                    ));
+                tokio::select! {
+                    dispatched_agent = &mut dispatched_agent_rx => {
+                        return Ok::<_, anyhow::Error>(dispatched_agent?);
+                    }
+                    changed = update_rx.changed() => {
+                        changed?;
+
+                        self.lock_buffered_request_queue().dispatch(|dispatch_criteria| {
+                            self.agent_controller_pool
+                                .take_agent_controller(dispatch_criteria)
+                        });
+                    }
                 }
-
-                update_rx.changed().await?;
             }
         })
-        .await
-        {
-            Ok(inner_result) => Ok(inner_result?),
-            Err(timeout_err) => Ok(BufferedRequestAgentWaitResult::Timeout(timeout_err.into())),
+        .await;
+
+        self.metrics_registry
+            .buffer_wait_seconds
+            .observe(buffered_at.elapsed().as_secs_f64());
+
+        match wait_result {
+            Ok(inner_result) => Ok(BufferedRequestAgentWaitResult::Found(inner_result?)),
+            Err(timeout_err) => match dispatched_agent_rx.try_recv() {
+                // The slot was handed out right as the timeout elapsed
+                Ok(dispatched_agent) => Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent)),
+                Err(_) => Ok(BufferedRequestAgentWaitResult::Timeout(timeout_err.into())),
+            },
         }
     }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn lock_buffered_request_queue(&self) -> MutexGuard<'_, BufferedRequestQueue> {
+        self.buffered_request_queue
+            .lock()
+            .expect("Poisoned lock on buffered request queue")
+    }
 }
 
 impl ProducesSnapshot for BufferedRequestManager {
@@ -96,16 +168,19 @@ impl SubscribesToUpdates for BufferedRequestManager {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
+    const LIMITS: BufferedRequestLimits = BufferedRequestLimits {
+        buffered_request_timeout: Duration::from_millis(10),
+        max_buffered_requests: 10,
+    };
+
     #[tokio::test]
     async fn counter_increment_wakes_subscribed_waiter() -> Result<()> {
         let pool = Arc::new(AgentControllerPool::default());
-        let manager = Arc::new(BufferedRequestManager::new(
-            pool,
-            Duration::from_secs(1),
-            10,
-        ));
+        let manager = Arc::new(BufferedRequestManager::new(pool, LIMITS, LIMITS));
 
         let mut update_rx = manager.subscribe_to_updates();
 
@@ -118,4 +193,39 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn batch_requests_have_their_own_buffer_limit() -> Result<()> {
+        let pool = Arc::new(AgentControllerPool::default());
+        let manager = BufferedRequestManager::new(
+            pool,
+            BufferedRequestLimits {
+                max_buffered_requests: 0,
+                ..LIMITS
+            },
+            LIMITS,
+        );
+
+        assert!(matches!(
+            manager
+                .wait_for_available_agent(
+                    &DispatchCriteria {
+                        priority: RequestPriority::Batch,
+                        ..DispatchCriteria::default()
+                    },
+                    None
+                )
+                .await?,
+            BufferedRequestAgentWaitResult::BufferOverflow
+        ));
+        assert!(matches!(
+            manager
+                .wait_for_available_agent(&DispatchCriteria::default(), None)
+                .await?,
+            BufferedRequestAgentWaitResult::Timeout(_)
+        ));
+        assert_eq!(manager.buffered_request_counter.get(), 0);
+
+        Ok(())
+    }
 }
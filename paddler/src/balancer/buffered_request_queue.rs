@@ -0,0 +1,249 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use paddler_types::request_priority::RequestPriority;
+use tokio::sync::oneshot;
+
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::dispatched_agent::DispatchedAgent;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+
+/// Virtual time a request takes up from the share of a tenant with the weight of 1
+const FAIR_SHARE_REQUEST_COST: u64 = 1 << 32;
+
+struct BufferedRequest {
+    dispatch_criteria: DispatchCriteria,
+    dispatched_agent_tx: oneshot::Sender<DispatchedAgent>,
+    start_tag: u64,
+}
+
+/// Requests waiting for a free slot, in the order they are going to get one: higher priority
+/// first, then by the fair share of their tenants (start-time fair queuing), then first come,
+/// first served. Requests without a tenant share the queue as if they were a single tenant.
+///
+/// Requests stop waiting by dropping the receiving end of their channel.
+#[derive(Default)]
+pub struct BufferedRequestQueue {
+    buffered_requests: Vec<BufferedRequest>,
+    /// Virtual time at which the share taken by the last request of each tenant ends
+    tenant_finish_tags: BTreeMap<(RequestPriority, Option<String>), u64>,
+    virtual_times: BTreeMap<RequestPriority, u64>,
+}
+
+impl BufferedRequestQueue {
+    pub fn count(&self, priority: RequestPriority) -> usize {
+        self.buffered_requests
+            .iter()
+            .filter(|buffered_request| {
+                buffered_request.dispatch_criteria.priority == priority
+                    && !buffered_request.dispatched_agent_tx.is_closed()
+            })
+            .count()
+    }
+
+    /// Requests that cannot get a slot (for example because their model pool is busy) do not
+    /// hold back the requests behind them.
+    pub fn dispatch<TTakeAgentController>(
+        &mut self,
+        mut take_agent_controller: TTakeAgentController,
+    ) where
+        TTakeAgentController: FnMut(&DispatchCriteria) -> Option<DispatchedAgent>,
+    {
+        self.buffered_requests
+            .retain(|buffered_request| !buffered_request.dispatched_agent_tx.is_closed());
+
+        let mut index = 0;
+
+        while let Some(buffered_request) = self.buffered_requests.get(index) {
+            let Some(dispatched_agent) = take_agent_controller(&buffered_request.dispatch_criteria)
+            else {
+                index += 1;
+
+                continue;
+            };
+
+            let BufferedRequest {
+                dispatch_criteria,
+                dispatched_agent_tx,
+                start_tag,
+            } = self.buffered_requests.remove(index);
+
+            // Requests that stopped waiting in the meantime drop the agent, which frees the slot
+            if dispatched_agent_tx.send(dispatched_agent).is_ok() {
+                self.advance_virtual_time(dispatch_criteria.priority, start_tag);
+            }
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        dispatch_criteria: DispatchCriteria,
+        fair_share_tenant: Option<FairShareTenant>,
+        dispatched_agent_tx: oneshot::Sender<DispatchedAgent>,
+    ) {
+        let priority = dispatch_criteria.priority;
+        let (tenant_name, weight) = fair_share_tenant.map_or((None, 1), |fair_share_tenant| {
+            (
+                Some(fair_share_tenant.name),
+                fair_share_tenant.weight.max(1),
+            )
+        });
+        let virtual_time = self.virtual_times.get(&priority).copied().unwrap_or(0);
+        let finish_tag = self
+            .tenant_finish_tags
+            .entry((priority, tenant_name))
+            .or_insert(0);
+        let start_tag = (*finish_tag).max(virtual_time);
+
+        *finish_tag = start_tag.saturating_add(FAIR_SHARE_REQUEST_COST / u64::from(weight));
+
+        let position = self.buffered_requests.partition_point(|buffered_request| {
+            (
+                Reverse(buffered_request.dispatch_criteria.priority),
+                buffered_request.start_tag,
+            ) <= (Reverse(priority), start_tag)
+        });
+
+        self.buffered_requests.insert(
+            position,
+            BufferedRequest {
+                dispatch_criteria,
+                dispatched_agent_tx,
+                start_tag,
+            },
+        );
+    }
+
+    fn advance_virtual_time(&mut self, priority: RequestPriority, start_tag: u64) {
+        let virtual_time = self.virtual_times.entry(priority).or_insert(0);
+
+        *virtual_time = (*virtual_time).max(start_tag);
+
+        let virtual_time = *virtual_time;
+
+        // Tenants whose share ended before the virtual time start from the virtual time anyway
+        self.tenant_finish_tags
+            .retain(|(tenant_priority, _), finish_tag| {
+                *tenant_priority != priority || *finish_tag > virtual_time
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(
+        buffered_request_queue: &mut BufferedRequestQueue,
+        marker: i32,
+        priority: RequestPriority,
+        fair_share_tenant: Option<FairShareTenant>,
+    ) -> oneshot::Receiver<DispatchedAgent> {
+        let (dispatched_agent_tx, dispatched_agent_rx) = oneshot::channel();
+
+        buffered_request_queue.push(
+            DispatchCriteria {
+                estimated_tokens: marker,
+                priority,
+                ..DispatchCriteria::default()
+            },
+            fair_share_tenant,
+            dispatched_agent_tx,
+        );
+
+        dispatched_agent_rx
+    }
+
+    fn dispatch_order(buffered_request_queue: &mut BufferedRequestQueue) -> Vec<i32> {
+        let mut markers = vec![];
+
+        buffered_request_queue.dispatch(|dispatch_criteria| {
+            markers.push(dispatch_criteria.estimated_tokens);
+
+            None
+        });
+
+        markers
+    }
+
+    fn tenant(name: &str, weight: u32) -> Option<FairShareTenant> {
+        Some(FairShareTenant {
+            name: name.to_owned(),
+            weight,
+        })
+    }
+
+    #[test]
+    fn dispatches_interactive_requests_before_batch_requests() {
+        let mut buffered_request_queue = BufferedRequestQueue::default();
+        let _receivers = [
+            push(&mut buffered_request_queue, 1, RequestPriority::Batch, None),
+            push(
+                &mut buffered_request_queue,
+                2,
+                RequestPriority::Interactive,
+                None,
+            ),
+            push(&mut buffered_request_queue, 3, RequestPriority::Batch, None),
+            push(
+                &mut buffered_request_queue,
+                4,
+                RequestPriority::Interactive,
+                None,
+            ),
+        ];
+
+        assert_eq!(
+            dispatch_order(&mut buffered_request_queue),
+            vec![2, 4, 1, 3]
+        );
+        assert_eq!(buffered_request_queue.count(RequestPriority::Batch), 2);
+    }
+
+    #[test]
+    fn shares_queue_between_tenants_by_weight() {
+        let mut buffered_request_queue = BufferedRequestQueue::default();
+        let mut receivers = vec![];
+
+        for marker in 1..=4 {
+            receivers.push(push(
+                &mut buffered_request_queue,
+                marker,
+                RequestPriority::Interactive,
+                tenant("nightly", 2),
+            ));
+        }
+
+        for marker in 11..=12 {
+            receivers.push(push(
+                &mut buffered_request_queue,
+                marker,
+                RequestPriority::Interactive,
+                tenant("chat", 1),
+            ));
+        }
+
+        assert_eq!(
+            dispatch_order(&mut buffered_request_queue),
+            vec![1, 11, 2, 3, 12, 4]
+        );
+    }
+
+    #[test]
+    fn abandoned_request_is_not_dispatched() {
+        let mut buffered_request_queue = BufferedRequestQueue::default();
+
+        drop(push(
+            &mut buffered_request_queue,
+            1,
+            RequestPriority::Interactive,
+            None,
+        ));
+
+        assert_eq!(
+            buffered_request_queue.count(RequestPriority::Interactive),
+            0
+        );
+        assert!(dispatch_order(&mut buffered_request_queue).is_empty());
+    }
+}
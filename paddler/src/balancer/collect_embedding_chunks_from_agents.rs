@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use futures::future::join_all;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use tokio_stream::StreamExt as _;
+
+use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::balancer::unbounded_stream_from_agent::unbounded_stream_from_agent;
+
+/// Sends every chunk of an embedding batch to the agents at once and waits until all of them are
+/// served. The collector keeps what the agents produce; the first error any chunk ended with is
+/// returned.
+pub async fn collect_embedding_chunks_from_agents<TTransformsOutgoingMessage>(
+    buffered_request_manager: &Arc<BufferedRequestManager>,
+    fair_share_tenant: Option<&FairShareTenant>,
+    inference_service_configuration: &InferenceServiceConfiguration,
+    chunks: impl IntoIterator<Item = GenerateEmbeddingBatchParams>,
+    collector: &TTransformsOutgoingMessage,
+) -> Option<String>
+where
+    TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
+{
+    let chunks_results: Vec<Vec<TransformResult>> = join_all(chunks.into_iter().map(|chunk| {
+        unbounded_stream_from_agent(
+            buffered_request_manager.clone(),
+            fair_share_tenant.cloned(),
+            inference_service_configuration.clone(),
+            chunk,
+            collector.clone(),
+        )
+        .collect()
+    }))
+    .await;
+
+    chunks_results
+        .into_iter()
+        .flatten()
+        .find_map(|result| match result {
+            TransformResult::Error(error_json) => Some(error_json),
+            TransformResult::Chunk(_) | TransformResult::Discard => None,
+        })
+}
@@ -0,0 +1,7 @@
+use actix_web::HttpResponse;
+
+use crate::balancer::compatibility::anthropic_service::anthropic_error_json::anthropic_error_json;
+
+pub fn anthropic_bad_request(message: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(anthropic_error_json("invalid_request_error", message))
+}
@@ -0,0 +1,11 @@
+use serde_json::json;
+
+pub fn anthropic_error_json(error_type: &str, message: &str) -> serde_json::Value {
+    json!({
+        "type": "error",
+        "error": {
+            "type": error_type,
+            "message": message
+        }
+    })
+}
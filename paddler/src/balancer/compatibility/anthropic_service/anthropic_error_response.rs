@@ -0,0 +1,43 @@
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+
+/// Status the Anthropic API responds with when it is overloaded; it has no named constant
+const OVERLOADED_STATUS: u16 = 529;
+
+/// Responds with the status the Anthropic API uses for the type of the error; anything the
+/// agents failed at is a server error
+pub fn anthropic_error_response(error_json: String) -> HttpResponse {
+    let error_type = serde_json::from_str::<serde_json::Value>(&error_json)
+        .ok()
+        .and_then(|error| error["error"]["type"].as_str().map(ToOwned::to_owned));
+    let status = match error_type.as_deref() {
+        Some("invalid_request_error") => StatusCode::BAD_REQUEST,
+        Some("overloaded_error") => {
+            StatusCode::from_u16(OVERLOADED_STATUS).unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
+        }
+        Some("timeout_error") => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balancer::compatibility::anthropic_service::anthropic_error_json::anthropic_error_json;
+
+    fn status_of(error_type: &str) -> StatusCode {
+        anthropic_error_response(anthropic_error_json(error_type, "message").to_string()).status()
+    }
+
+    #[test]
+    fn maps_error_types_to_statuses() {
+        assert_eq!(status_of("invalid_request_error"), StatusCode::BAD_REQUEST);
+        assert_eq!(status_of("overloaded_error").as_u16(), OVERLOADED_STATUS);
+        assert_eq!(status_of("timeout_error"), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(status_of("api_error"), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,4 @@
+/// Server-sent event, named after the `type` of its data, as Anthropic SDKs expect both
+pub fn anthropic_event(event_type: &str, data: &serde_json::Value) -> String {
+    format!("event: {event_type}\ndata: {data}\n\n")
+}
@@ -0,0 +1,11 @@
+use actix_web::HttpResponse;
+
+use crate::balancer::compatibility::anthropic_service::anthropic_error_json::anthropic_error_json;
+use crate::balancer::model_not_found::ModelNotFound;
+
+pub fn anthropic_model_not_found(model_not_found: &ModelNotFound) -> HttpResponse {
+    HttpResponse::NotFound().json(anthropic_error_json(
+        "not_found_error",
+        &model_not_found.to_string(),
+    ))
+}
@@ -0,0 +1,13 @@
+use paddler_types::finish_reason::FinishReason;
+
+pub const fn anthropic_stop_reason(
+    finish_reason: &FinishReason,
+    has_tool_calls: bool,
+) -> &'static str {
+    match finish_reason {
+        FinishReason::Eos | FinishReason::StopSequence(_) if has_tool_calls => "tool_use",
+        FinishReason::Cancelled | FinishReason::Eos => "end_turn",
+        FinishReason::Evicted | FinishReason::MaxTokens => "max_tokens",
+        FinishReason::StopSequence(_) => "stop_sequence",
+    }
+}
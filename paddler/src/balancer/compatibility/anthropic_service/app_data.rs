@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use crate::balancer::api_key_registry::ApiKeyRegistry;
+use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::inference_service::configuration::Configuration;
+
+pub struct AppData {
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    pub buffered_request_manager: Arc<BufferedRequestManager>,
+    pub inference_service_configuration: Configuration,
+}
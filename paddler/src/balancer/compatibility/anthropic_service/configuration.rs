@@ -0,0 +1,8 @@
+use std::net::SocketAddr;
+
+#[derive(Clone)]
+pub struct Configuration {
+    pub addr: SocketAddr,
+    /// Requests have to bear an API key with the inference scope
+    pub require_api_key: bool,
+}
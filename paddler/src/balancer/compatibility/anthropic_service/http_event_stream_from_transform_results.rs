@@ -0,0 +1,29 @@
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::http::header;
+use bytes::Bytes;
+use futures::Stream;
+use futures::stream::StreamExt;
+
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+
+/// Chunks are already framed as server-sent events, errors are framed as `error` events.
+pub fn http_event_stream_from_transform_results<TStream>(transform_results: TStream) -> HttpResponse
+where
+    TStream: Stream<Item = TransformResult> + 'static,
+{
+    let stream = transform_results.filter_map(|transform_result| async move {
+        match transform_result {
+            TransformResult::Chunk(chunk) => Some(Ok::<_, Error>(Bytes::from(chunk))),
+            TransformResult::Error(error) => Some(Ok::<_, Error>(Bytes::from(format!(
+                "event: error\ndata: {error}\n\n"
+            )))),
+            TransformResult::Discard => None,
+        }
+    });
+
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream)
+}
@@ -0,0 +1,58 @@
+use paddler_types::tool_call::ToolCall;
+use serde_json::json;
+
+pub enum AnthropicContentBlock {
+    Text(String),
+    Thinking(String),
+    ToolUse(ToolCall),
+}
+
+impl AnthropicContentBlock {
+    pub const fn block_type(&self) -> &'static str {
+        match self {
+            Self::Text(_) => "text",
+            Self::Thinking(_) => "thinking",
+            Self::ToolUse(_) => "tool_use",
+        }
+    }
+
+    /// Block as it is announced in `content_block_start`, before any delta fills it in
+    pub fn start_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(_) => json!({"type": "text", "text": ""}),
+            Self::Thinking(_) => json!({"type": "thinking", "thinking": "", "signature": ""}),
+            Self::ToolUse(tool_call) => json!({
+                "type": "tool_use",
+                "id": tool_call.id,
+                "name": tool_call.name,
+                "input": {}
+            }),
+        }
+    }
+
+    pub fn delta_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(text) => json!({"type": "text_delta", "text": text}),
+            Self::Thinking(thinking) => json!({"type": "thinking_delta", "thinking": thinking}),
+            Self::ToolUse(tool_call) => json!({
+                "type": "input_json_delta",
+                "partial_json": tool_call.arguments.to_string()
+            }),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(text) => json!({"type": "text", "text": text}),
+            Self::Thinking(thinking) => {
+                json!({"type": "thinking", "thinking": thinking, "signature": ""})
+            }
+            Self::ToolUse(tool_call) => json!({
+                "type": "tool_use",
+                "id": tool_call.id,
+                "name": tool_call.name,
+                "input": tool_call.arguments
+            }),
+        }
+    }
+}
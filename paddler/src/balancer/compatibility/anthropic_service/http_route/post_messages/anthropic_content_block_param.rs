@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::anthropic_image_source::AnthropicImageSource;
+use super::anthropic_message_content::AnthropicMessageContent;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicContentBlockParam {
+    Image {
+        source: AnthropicImageSource,
+    },
+    /// Chat templates drop the reasoning of earlier turns anyway
+    RedactedThinking,
+    Text {
+        text: String,
+    },
+    Thinking,
+    ToolResult {
+        #[serde(default)]
+        content: Option<AnthropicMessageContent>,
+        tool_use_id: String,
+    },
+    ToolUse {
+        id: String,
+        input: Value,
+        name: String,
+    },
+}
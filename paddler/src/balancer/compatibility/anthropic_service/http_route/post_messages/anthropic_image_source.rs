@@ -0,0 +1,26 @@
+use paddler_types::image_url::ImageUrl;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicImageSource {
+    Base64 {
+        data: String,
+        media_type: String,
+    },
+    /// Passed on as is, although agents only decode images embedded in the request
+    Url {
+        url: String,
+    },
+}
+
+impl From<AnthropicImageSource> for ImageUrl {
+    fn from(anthropic_image_source: AnthropicImageSource) -> Self {
+        match anthropic_image_source {
+            AnthropicImageSource::Base64 { data, media_type } => Self {
+                url: format!("data:{media_type};base64,{data}"),
+            },
+            AnthropicImageSource::Url { url } => Self { url },
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+use super::anthropic_message_content::AnthropicMessageContent;
+
+#[derive(Deserialize)]
+pub struct AnthropicMessage {
+    pub content: AnthropicMessageContent,
+    pub role: String,
+}
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use anyhow::bail;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::conversation_message_content_part::ConversationMessageContentPart;
+use paddler_types::tool_call::ToolCall;
+use serde::Deserialize;
+
+use super::anthropic_content_block_param::AnthropicContentBlockParam;
+
+fn content_from_parts(parts: Vec<ConversationMessageContentPart>) -> ConversationMessageContent {
+    if parts
+        .iter()
+        .all(|part| matches!(part, ConversationMessageContentPart::Text { .. }))
+    {
+        ConversationMessageContent::Text(ConversationMessageContent::Parts(parts).text_content())
+    } else {
+        ConversationMessageContent::Parts(parts)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum AnthropicMessageContent {
+    Blocks(Vec<AnthropicContentBlockParam>),
+    Text(String),
+}
+
+impl AnthropicMessageContent {
+    /// Content of the system prompt and of tool results, which can not hold tool blocks
+    pub fn into_content(self) -> Result<ConversationMessageContent> {
+        let blocks = match self {
+            Self::Blocks(blocks) => blocks,
+            Self::Text(text) => return Ok(ConversationMessageContent::Text(text)),
+        };
+        let mut parts = Vec::with_capacity(blocks.len());
+
+        for block in blocks {
+            match block {
+                AnthropicContentBlockParam::Image { source } => {
+                    parts.push(ConversationMessageContentPart::ImageUrl {
+                        image_url: source.into(),
+                    });
+                }
+                AnthropicContentBlockParam::Text { text } => {
+                    parts.push(ConversationMessageContentPart::Text { text });
+                }
+                AnthropicContentBlockParam::RedactedThinking
+                | AnthropicContentBlockParam::Thinking => {}
+                AnthropicContentBlockParam::ToolResult { .. }
+                | AnthropicContentBlockParam::ToolUse { .. } => {
+                    bail!("tool blocks are only allowed in the content of messages");
+                }
+            }
+        }
+
+        Ok(content_from_parts(parts))
+    }
+
+    /// Tool results become separate `tool` messages that come before the rest of the content,
+    /// because that is how chat templates expect them.
+    pub fn into_conversation_messages(self, role: String) -> Result<Vec<ConversationMessage>> {
+        let blocks = match self {
+            Self::Blocks(blocks) => blocks,
+            Self::Text(text) => {
+                return Ok(vec![ConversationMessage {
+                    content: ConversationMessageContent::Text(text),
+                    role,
+                    tool_call_id: None,
+                    tool_calls: vec![],
+                }]);
+            }
+        };
+        let mut conversation_messages = vec![];
+        let mut parts = vec![];
+        let mut tool_calls = vec![];
+
+        for block in blocks {
+            match block {
+                AnthropicContentBlockParam::Image { source } => {
+                    parts.push(ConversationMessageContentPart::ImageUrl {
+                        image_url: source.into(),
+                    });
+                }
+                AnthropicContentBlockParam::RedactedThinking
+                | AnthropicContentBlockParam::Thinking => {}
+                AnthropicContentBlockParam::Text { text } => {
+                    parts.push(ConversationMessageContentPart::Text { text });
+                }
+                AnthropicContentBlockParam::ToolResult {
+                    content,
+                    tool_use_id,
+                } => conversation_messages.push(ConversationMessage {
+                    content: match content {
+                        Some(content) => content.into_content()?,
+                        None => ConversationMessageContent::Text(String::new()),
+                    },
+                    role: "tool".to_owned(),
+                    tool_call_id: Some(tool_use_id),
+                    tool_calls: vec![],
+                }),
+                AnthropicContentBlockParam::ToolUse { id, input, name } => {
+                    tool_calls.push(ToolCall {
+                        arguments: input,
+                        id,
+                        name,
+                    });
+                }
+            }
+        }
+
+        if !parts.is_empty() || !tool_calls.is_empty() {
+            conversation_messages.push(ConversationMessage {
+                content: content_from_parts(parts),
+                role,
+                tool_call_id: None,
+                tool_calls,
+            });
+        }
+
+        Ok(conversation_messages)
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::Result;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::request_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::truncation_policy::TruncationPolicy;
+use serde::Deserialize;
+
+use super::anthropic_message::AnthropicMessage;
+use super::anthropic_message_content::AnthropicMessageContent;
+use super::anthropic_thinking::AnthropicThinking;
+use super::anthropic_tool::AnthropicTool;
+use super::anthropic_tool_choice::AnthropicToolChoice;
+
+#[derive(Deserialize)]
+pub struct AnthropicMessagesRequestParams {
+    pub max_tokens: i32,
+    pub messages: Vec<AnthropicMessage>,
+    /// Picks the model pool of that name; any value is served by the default model while no
+    /// model pools are configured.
+    pub model: String,
+    #[serde(default)]
+    pub stop_sequences: Vec<String>,
+    #[serde(default)]
+    pub stream: Option<bool>,
+    #[serde(default)]
+    pub system: Option<AnthropicMessageContent>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Models that can reason only do so when it is enabled, like Anthropic models
+    #[serde(default)]
+    pub thinking: Option<AnthropicThinking>,
+    #[serde(default)]
+    pub tool_choice: Option<AnthropicToolChoice>,
+    #[serde(default)]
+    pub tools: Vec<AnthropicTool>,
+    #[serde(default)]
+    pub top_k: Option<i32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Paddler extension, absent from the Anthropic API; see the native conversation history
+    /// parameters
+    #[serde(default)]
+    pub truncation: Option<TruncationPolicy>,
+}
+
+impl AnthropicMessagesRequestParams {
+    pub fn into_paddler_params(
+        self,
+    ) -> Result<ContinueFromConversationHistoryParams<RawParametersSchema>> {
+        let tools = match &self.tool_choice {
+            Some(tool_choice) => tool_choice.select_tools(self.tools)?,
+            None => self.tools,
+        };
+        let sampling = SamplingParameters {
+            temperature: self.temperature,
+            top_k: self.top_k,
+            top_p: self.top_p,
+            ..SamplingParameters::default()
+        };
+        let mut conversation_messages: Vec<ConversationMessage> = vec![];
+
+        if let Some(system) = self.system {
+            conversation_messages.push(ConversationMessage {
+                content: system.into_content()?,
+                role: "system".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
+            });
+        }
+
+        for message in self.messages {
+            conversation_messages.extend(message.content.into_conversation_messages(message.role)?);
+        }
+
+        Ok(ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(conversation_messages),
+            enable_thinking: matches!(self.thinking, Some(AnthropicThinking::Enabled)),
+            grammar: None,
+            logprobs: None,
+            max_tokens: self.max_tokens,
+            model: Some(self.model),
+            priority: RequestPriority::Interactive,
+            sampling: if sampling == SamplingParameters::default() {
+                None
+            } else {
+                Some(sampling)
+            },
+            session_id: None,
+            stop: self.stop_sequences,
+            tools: tools.into_iter().map(Into::into).collect(),
+            truncation: self.truncation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::conversation_message_content::ConversationMessageContent;
+    use paddler_types::tool_call::ToolCall;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn system_prompt_comes_first() -> Result<()> {
+        let anthropic_params: AnthropicMessagesRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "max_tokens": 100,
+            "system": [{"type": "text", "text": "Be brief."}],
+            "messages": [{"role": "user", "content": "hi"}],
+            "top_k": 40
+        }))?;
+
+        let paddler_params = anthropic_params.into_paddler_params()?;
+        let messages = &paddler_params.conversation_history.messages;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(
+            messages[0].content,
+            ConversationMessageContent::Text("Be brief.".to_owned())
+        );
+        assert_eq!(paddler_params.max_tokens, 100);
+        assert_eq!(
+            paddler_params.sampling,
+            Some(SamplingParameters {
+                top_k: Some(40),
+                ..SamplingParameters::default()
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn passes_truncation_extension_through() -> Result<()> {
+        let anthropic_params: AnthropicMessagesRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "max_tokens": 100,
+            "messages": [{"role": "user", "content": "hi"}],
+            "truncation": "MiddleOut"
+        }))?;
+
+        assert_eq!(
+            anthropic_params.into_paddler_params()?.truncation,
+            Some(TruncationPolicy::MiddleOut)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn base64_image_becomes_data_uri() -> Result<()> {
+        let anthropic_params: AnthropicMessagesRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "max_tokens": 100,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "abc"}},
+                    {"type": "text", "text": "What is this?"}
+                ]
+            }]
+        }))?;
+
+        let paddler_params = anthropic_params.into_paddler_params()?;
+        let image_urls = paddler_params.conversation_history.messages[0]
+            .content
+            .image_urls();
+
+        assert_eq!(image_urls.len(), 1);
+        assert_eq!(image_urls[0].url, "data:image/png;base64,abc");
+
+        Ok(())
+    }
+
+    #[test]
+    fn tool_blocks_become_tool_calls_and_tool_messages() -> Result<()> {
+        let anthropic_params: AnthropicMessagesRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "max_tokens": 100,
+            "messages": [
+                {"role": "user", "content": "What is the weather in Paris?"},
+                {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "I should check.", "signature": ""},
+                    {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {"city": "Paris"}}
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "Sunny"},
+                    {"type": "text", "text": "Thanks!"}
+                ]}
+            ],
+            "tools": [
+                {"name": "get_weather", "input_schema": {"type": "object", "properties": {"city": {"type": "string"}}}},
+                {"name": "get_time", "input_schema": {"type": "object", "properties": {}}}
+            ],
+            "tool_choice": {"type": "tool", "name": "get_weather"}
+        }))?;
+
+        let paddler_params = anthropic_params.into_paddler_params()?;
+        let messages = &paddler_params.conversation_history.messages;
+
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[1].tool_calls,
+            vec![ToolCall {
+                arguments: json!({"city": "Paris"}),
+                id: "toolu_1".to_owned(),
+                name: "get_weather".to_owned(),
+            }]
+        );
+        assert_eq!(messages[2].role, "tool");
+        assert_eq!(messages[2].tool_call_id.as_deref(), Some("toolu_1"));
+        assert_eq!(
+            messages[2].content,
+            ConversationMessageContent::Text("Sunny".to_owned())
+        );
+        assert_eq!(messages[3].role, "user");
+        assert_eq!(paddler_params.tools.len(), 1);
+
+        Ok(())
+    }
+}
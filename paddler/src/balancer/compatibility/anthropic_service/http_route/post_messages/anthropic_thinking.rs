@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+/// The budget is not enforced, reasoning counts towards `max_tokens` like any other output
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicThinking {
+    Disabled,
+    Enabled,
+}
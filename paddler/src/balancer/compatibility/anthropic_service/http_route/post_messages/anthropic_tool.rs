@@ -0,0 +1,29 @@
+use paddler_types::request_params::continue_from_conversation_history_params::tool::Tool;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::FunctionCall;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct AnthropicTool {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub input_schema: Option<RawParametersSchema>,
+    pub name: String,
+}
+
+impl From<AnthropicTool> for Tool<RawParametersSchema> {
+    fn from(anthropic_tool: AnthropicTool) -> Self {
+        Self::Function(FunctionCall {
+            function: Function {
+                name: anthropic_tool.name,
+                description: anthropic_tool.description.unwrap_or_default(),
+                parameters: anthropic_tool
+                    .input_schema
+                    .map_or(Parameters::Empty, Parameters::Schema),
+            },
+        })
+    }
+}
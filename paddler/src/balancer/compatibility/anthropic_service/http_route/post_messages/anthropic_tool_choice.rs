@@ -0,0 +1,37 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+
+use super::anthropic_tool::AnthropicTool;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicToolChoice {
+    Any,
+    Auto,
+    None,
+    Tool { name: String },
+}
+
+impl AnthropicToolChoice {
+    /// Chat templates have no notion of a tool choice, so it is applied by narrowing down the
+    /// tools the model gets to see. `any` still leaves the decision to the model.
+    pub fn select_tools(&self, tools: Vec<AnthropicTool>) -> Result<Vec<AnthropicTool>> {
+        match self {
+            Self::Any | Self::Auto => Ok(tools),
+            Self::None => Ok(vec![]),
+            Self::Tool { name } => {
+                let selected_tools: Vec<AnthropicTool> = tools
+                    .into_iter()
+                    .filter(|tool| tool.name == *name)
+                    .collect();
+
+                if selected_tools.is_empty() {
+                    bail!("tool_choice names tool '{name}' which is not one of the tools");
+                }
+
+                Ok(selected_tools)
+            }
+        }
+    }
+}
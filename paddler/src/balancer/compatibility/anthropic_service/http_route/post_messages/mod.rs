@@ -0,0 +1,740 @@
+mod anthropic_content_block;
+mod anthropic_content_block_param;
+mod anthropic_image_source;
+mod anthropic_message;
+mod anthropic_message_content;
+mod anthropic_messages_request_params;
+mod anthropic_thinking;
+mod anthropic_tool;
+mod anthropic_tool_choice;
+
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::post;
+use actix_web::web;
+use actix_web::web::ReqData;
+use async_trait::async_trait;
+use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::token_usage::TokenUsage;
+use paddler_types::validates::Validates;
+use serde_json::json;
+use tokio_stream::StreamExt as _;
+
+use self::anthropic_content_block::AnthropicContentBlock;
+use self::anthropic_messages_request_params::AnthropicMessagesRequestParams;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::compatibility::anthropic_service::anthropic_bad_request::anthropic_bad_request;
+use crate::balancer::compatibility::anthropic_service::anthropic_error_json::anthropic_error_json;
+use crate::balancer::compatibility::anthropic_service::anthropic_error_response::anthropic_error_response;
+use crate::balancer::compatibility::anthropic_service::anthropic_event::anthropic_event;
+use crate::balancer::compatibility::anthropic_service::anthropic_model_not_found::anthropic_model_not_found;
+use crate::balancer::compatibility::anthropic_service::anthropic_stop_reason::anthropic_stop_reason;
+use crate::balancer::compatibility::anthropic_service::app_data::AppData;
+use crate::balancer::compatibility::anthropic_service::http_event_stream_from_transform_results::http_event_stream_from_transform_results;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+use crate::balancer::unbounded_stream_from_agent::unbounded_stream_from_agent;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+fn anthropic_stop_sequence(finish_reason: &FinishReason) -> Option<&str> {
+    match finish_reason {
+        FinishReason::StopSequence(stop_sequence) => Some(stop_sequence),
+        FinishReason::Cancelled
+        | FinishReason::Eos
+        | FinishReason::Evicted
+        | FinishReason::MaxTokens => None,
+    }
+}
+
+fn anthropic_usage_json(token_usage: &TokenUsage) -> serde_json::Value {
+    json!({
+        "input_tokens": token_usage.prompt_tokens,
+        "output_tokens": token_usage.completion_tokens
+    })
+}
+
+/// The answer, the reasoning and the tool calls are turned into content blocks by the
+/// transformers, so only the failures are left to transform.
+fn transform_unexpected_message(message: OutgoingMessage) -> TransformResult {
+    match message {
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::ChatTemplateError(description)
+                    | GeneratedTokenResult::GrammarIncompatibleWithThinking(description)
+                    | GeneratedTokenResult::GrammarRejectedModelOutput(description)
+                    | GeneratedTokenResult::GrammarInitializationFailed(description)
+                    | GeneratedTokenResult::GrammarSyntaxError(description)
+                    | GeneratedTokenResult::ImageDecodingFailed(description)
+                    | GeneratedTokenResult::InvalidSamplingParameters(description)
+                    | GeneratedTokenResult::MultimodalNotSupported(description)
+                    | GeneratedTokenResult::SamplerError(description),
+                ),
+            ..
+        })
+        | OutgoingMessage::Error(ErrorEnvelope {
+            error: paddler_types::jsonrpc::Error { description, .. },
+            ..
+        }) => TransformResult::Error(anthropic_error_json("api_error", &description).to_string()),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(GeneratedTokenResult::ContextOverflow(
+                    context_overflow,
+                )),
+            ..
+        }) => TransformResult::Error(
+            anthropic_error_json("invalid_request_error", &context_overflow.to_string())
+                .to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Timeout,
+            ..
+        }) => TransformResult::Error(
+            anthropic_error_json("timeout_error", "request timed out").to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::TooManyBufferedRequests,
+            ..
+        }) => TransformResult::Error(
+            anthropic_error_json("overloaded_error", "too many buffered requests").to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Embedding(_) | OutgoingResponse::Rerank(_),
+            ..
+        }) => TransformResult::Error(
+            anthropic_error_json(
+                "invalid_request_error",
+                "unexpected embedding response in messages",
+            )
+            .to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::Done(_)
+                    | GeneratedTokenResult::ReasoningToken(_)
+                    | GeneratedTokenResult::Seed(_)
+                    | GeneratedTokenResult::Token(_)
+                    | GeneratedTokenResult::TokenLogprobs(_)
+                    | GeneratedTokenResult::ToolCall(_)
+                    | GeneratedTokenResult::Usage(_),
+                ),
+            ..
+        }) => TransformResult::Discard,
+    }
+}
+
+#[derive(Default)]
+struct AnthropicStreamState {
+    content_blocks_count: usize,
+    has_tool_use: bool,
+    /// Index and type of the block that the next delta of the same type extends
+    open_content_block: Option<(usize, &'static str)>,
+    token_usage: Option<TokenUsage>,
+}
+
+impl AnthropicStreamState {
+    fn stop_open_content_block(&mut self) -> String {
+        match self.open_content_block.take() {
+            Some((index, _)) => anthropic_event(
+                "content_block_stop",
+                &json!({
+                    "type": "content_block_stop",
+                    "index": index
+                }),
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Tool calls arrive whole, so their block is stopped right after its only delta
+    fn content_block_events(&mut self, content_block: &AnthropicContentBlock) -> String {
+        let mut events = String::new();
+        let index = match self.open_content_block {
+            Some((index, block_type)) if block_type == content_block.block_type() => index,
+            _ => {
+                let index = self.content_blocks_count;
+
+                events.push_str(&self.stop_open_content_block());
+                events.push_str(&anthropic_event(
+                    "content_block_start",
+                    &json!({
+                        "type": "content_block_start",
+                        "index": index,
+                        "content_block": content_block.start_json()
+                    }),
+                ));
+                self.content_blocks_count += 1;
+                self.open_content_block = Some((index, content_block.block_type()));
+
+                index
+            }
+        };
+
+        events.push_str(&anthropic_event(
+            "content_block_delta",
+            &json!({
+                "type": "content_block_delta",
+                "index": index,
+                "delta": content_block.delta_json()
+            }),
+        ));
+
+        if matches!(content_block, AnthropicContentBlock::ToolUse(_)) {
+            self.has_tool_use = true;
+            events.push_str(&self.stop_open_content_block());
+        }
+
+        events
+    }
+}
+
+/// Every chunk is a series of server-sent events. `message_start` is sent by the handler before
+/// the agent generates anything.
+#[derive(Clone, Default)]
+struct AnthropicStreamingResponseTransformer {
+    state: Arc<RwLock<AnthropicStreamState>>,
+}
+
+impl AnthropicStreamingResponseTransformer {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn content_block_events(&self, content_block: &AnthropicContentBlock) -> String {
+        let mut lock = self
+            .state
+            .write()
+            .expect("Failed to acquire write lock on stream state");
+
+        lock.content_block_events(content_block)
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn finish_events(&self, finish_reason: &FinishReason) -> String {
+        let mut lock = self
+            .state
+            .write()
+            .expect("Failed to acquire write lock on stream state");
+
+        let mut events = lock.stop_open_content_block();
+
+        events.push_str(&anthropic_event(
+            "message_delta",
+            &json!({
+                "type": "message_delta",
+                "delta": {
+                    "stop_reason": anthropic_stop_reason(finish_reason, lock.has_tool_use),
+                    "stop_sequence": anthropic_stop_sequence(finish_reason)
+                },
+                "usage": anthropic_usage_json(&lock.token_usage.clone().unwrap_or_default())
+            }),
+        ));
+        events.push_str(&anthropic_event(
+            "message_stop",
+            &json!({
+                "type": "message_stop"
+            }),
+        ));
+
+        events
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn set_token_usage(&self, token_usage: TokenUsage) {
+        let mut lock = self
+            .state
+            .write()
+            .expect("Failed to acquire write lock on stream state");
+
+        lock.token_usage = Some(token_usage);
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for AnthropicStreamingResponseTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => Ok(TransformResult::Chunk(self.finish_events(&finish_reason))),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => {
+                self.set_token_usage(token_usage);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(
+                self.content_block_events(&AnthropicContentBlock::Text(token)),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ReasoningToken(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(
+                self.content_block_events(&AnthropicContentBlock::Thinking(token)),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ToolCall(tool_call)),
+                ..
+            }) => Ok(TransformResult::Chunk(self.content_block_events(
+                &AnthropicContentBlock::ToolUse(tool_call),
+            ))),
+            message => Ok(transform_unexpected_message(message)),
+        }
+    }
+}
+
+/// Clones share the content blocks, the stop reason and the token usage, so they can be read
+/// back once the whole response is collected.
+#[derive(Clone, Default)]
+struct AnthropicCombinedResponseTransformer {
+    content_blocks: Arc<RwLock<Vec<AnthropicContentBlock>>>,
+    finish_reason: Arc<RwLock<Option<FinishReason>>>,
+    token_usage: Arc<RwLock<Option<TokenUsage>>>,
+}
+
+impl AnthropicCombinedResponseTransformer {
+    /// Consecutive tokens of the same type are joined into one block
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_content_block(&self, content_block: AnthropicContentBlock) {
+        let mut lock = self
+            .content_blocks
+            .write()
+            .expect("Failed to acquire write lock on content blocks");
+
+        let content_block = match (lock.last_mut(), content_block) {
+            (Some(AnthropicContentBlock::Text(text)), AnthropicContentBlock::Text(token))
+            | (
+                Some(AnthropicContentBlock::Thinking(text)),
+                AnthropicContentBlock::Thinking(token),
+            ) => {
+                text.push_str(&token);
+
+                return;
+            }
+            (_, content_block) => content_block,
+        };
+
+        lock.push(content_block);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_finish_reason(&self) -> Option<FinishReason> {
+        let lock = self
+            .finish_reason
+            .read()
+            .expect("Failed to acquire read lock on finish reason");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_token_usage(&self) -> Option<TokenUsage> {
+        let lock = self
+            .token_usage
+            .read()
+            .expect("Failed to acquire read lock on token usage");
+
+        lock.clone()
+    }
+
+    /// Returns the JSON of the blocks and whether any of them is a tool use
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn content_json(&self) -> (Vec<serde_json::Value>, bool) {
+        let lock = self
+            .content_blocks
+            .read()
+            .expect("Failed to acquire read lock on content blocks");
+
+        (
+            lock.iter().map(AnthropicContentBlock::to_json).collect(),
+            lock.iter()
+                .any(|content_block| matches!(content_block, AnthropicContentBlock::ToolUse(_))),
+        )
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn set_finish_reason(&self, finish_reason: FinishReason) {
+        let mut lock = self
+            .finish_reason
+            .write()
+            .expect("Failed to acquire write lock on finish reason");
+
+        *lock = Some(finish_reason);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn set_token_usage(&self, token_usage: TokenUsage) {
+        let mut lock = self
+            .token_usage
+            .write()
+            .expect("Failed to acquire write lock on token usage");
+
+        *lock = Some(token_usage);
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for AnthropicCombinedResponseTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => self.set_finish_reason(finish_reason),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => self.set_token_usage(token_usage),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => self.add_content_block(AnthropicContentBlock::Text(token)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ReasoningToken(token)),
+                ..
+            }) => self.add_content_block(AnthropicContentBlock::Thinking(token)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ToolCall(tool_call)),
+                ..
+            }) => self.add_content_block(AnthropicContentBlock::ToolUse(tool_call)),
+            message => return Ok(transform_unexpected_message(message)),
+        }
+
+        Ok(TransformResult::Discard)
+    }
+}
+
+#[post("/v1/messages")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    anthropic_params: web::Json<AnthropicMessagesRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let anthropic_params = anthropic_params.into_inner();
+    let model = anthropic_params.model.clone();
+    let stream = anthropic_params.stream.unwrap_or(false);
+    let paddler_params = match anthropic_params
+        .into_paddler_params()
+        .and_then(Validates::validate)
+    {
+        Ok(paddler_params) => paddler_params,
+        Err(err) => return Ok(anthropic_bad_request(&err.to_string())),
+    };
+
+    if let Some(sampling) = &paddler_params.sampling
+        && let Err(err) = sampling.clone().validate()
+    {
+        return Ok(anthropic_bad_request(&err.to_string()));
+    }
+
+    if let Err(model_not_found) = app_data
+        .buffered_request_manager
+        .ensure_model_is_served(paddler_params.model.as_deref())
+    {
+        return Ok(anthropic_model_not_found(&model_not_found));
+    }
+
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            paddler_params.dispatch_criteria().estimated_tokens,
+        )?;
+    }
+
+    let message_id = format!("msg_{}", nanoid!());
+
+    if stream {
+        let message_start = anthropic_event(
+            "message_start",
+            &json!({
+                "type": "message_start",
+                "message": {
+                    "id": message_id,
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [],
+                    "model": model,
+                    "stop_reason": null,
+                    "stop_sequence": null,
+                    "usage": anthropic_usage_json(&TokenUsage::default())
+                }
+            }),
+        );
+
+        return Ok(http_event_stream_from_transform_results(
+            futures::stream::iter([TransformResult::Chunk(message_start)]).chain(
+                unbounded_stream_from_agent(
+                    app_data.buffered_request_manager.clone(),
+                    fair_share_tenant,
+                    app_data.inference_service_configuration.clone(),
+                    paddler_params,
+                    AnthropicStreamingResponseTransformer::default(),
+                ),
+            ),
+        ));
+    }
+
+    let transformer = AnthropicCombinedResponseTransformer::default();
+    let results: Vec<TransformResult> = unbounded_stream_from_agent(
+        app_data.buffered_request_manager.clone(),
+        fair_share_tenant,
+        app_data.inference_service_configuration.clone(),
+        paddler_params,
+        transformer.clone(),
+    )
+    .collect()
+    .await;
+
+    if let Some(TransformResult::Error(error_json)) = results
+        .iter()
+        .find(|result| matches!(result, TransformResult::Error(_)))
+    {
+        return Ok(anthropic_error_response(error_json.clone()));
+    }
+
+    let (content, has_tool_use) = transformer.content_json();
+    let finish_reason = transformer.get_finish_reason().unwrap_or(FinishReason::Eos);
+
+    Ok(HttpResponse::Ok().json(json!({
+        "id": message_id,
+        "type": "message",
+        "role": "assistant",
+        "model": model,
+        "content": content,
+        "stop_reason": anthropic_stop_reason(&finish_reason, has_tool_use),
+        "stop_sequence": anthropic_stop_sequence(&finish_reason),
+        "usage": anthropic_usage_json(&transformer.get_token_usage().unwrap_or_default())
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use anyhow::Result;
+    use paddler_types::context_overflow::ContextOverflow;
+    use paddler_types::tool_call::ToolCall;
+
+    use super::*;
+
+    fn make_token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::GeneratedToken(token_result),
+        })
+    }
+
+    fn event_data(chunk: &str) -> Result<Vec<serde_json::Value>> {
+        chunk
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| Ok(serde_json::from_str(data)?))
+            .collect()
+    }
+
+    async fn transform_chunk(
+        transformer: &AnthropicStreamingResponseTransformer,
+        token_result: GeneratedTokenResult,
+    ) -> Result<String> {
+        match transformer
+            .transform(make_token_message(token_result))
+            .await?
+        {
+            TransformResult::Chunk(chunk) => Ok(chunk),
+            TransformResult::Discard | TransformResult::Error(_) => {
+                anyhow::bail!("expected TransformResult::Chunk")
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn streaming_opens_new_block_when_content_type_changes() -> Result<()> {
+        let transformer = AnthropicStreamingResponseTransformer::default();
+
+        let thinking_chunk = transform_chunk(
+            &transformer,
+            GeneratedTokenResult::ReasoningToken("hmm".to_owned()),
+        )
+        .await?;
+        let text_chunk =
+            transform_chunk(&transformer, GeneratedTokenResult::Token("Hi".to_owned())).await?;
+        let next_text_chunk =
+            transform_chunk(&transformer, GeneratedTokenResult::Token("!".to_owned())).await?;
+
+        assert_eq!(
+            event_data(&thinking_chunk)?,
+            vec![
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": "", "signature": ""}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "hmm"}}),
+            ]
+        );
+        assert_eq!(
+            event_data(&text_chunk)?,
+            vec![
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+                json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Hi"}}),
+            ]
+        );
+        assert_eq!(
+            event_data(&next_text_chunk)?,
+            vec![
+                json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "!"}}),
+            ]
+        );
+        assert!(text_chunk.starts_with("event: content_block_stop\n"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_tool_call_ends_with_tool_use_stop_reason() -> Result<()> {
+        let transformer = AnthropicStreamingResponseTransformer::default();
+
+        let tool_use_chunk = transform_chunk(
+            &transformer,
+            GeneratedTokenResult::ToolCall(ToolCall {
+                arguments: json!({"city": "Paris"}),
+                id: "toolu_1".to_owned(),
+                name: "get_weather".to_owned(),
+            }),
+        )
+        .await?;
+
+        transformer
+            .transform(make_token_message(GeneratedTokenResult::Usage(
+                TokenUsage {
+                    completion_tokens: 7,
+                    prompt_tokens: 11,
+                },
+            )))
+            .await?;
+
+        let done_chunk =
+            transform_chunk(&transformer, GeneratedTokenResult::Done(FinishReason::Eos)).await?;
+
+        assert_eq!(
+            event_data(&tool_use_chunk)?,
+            vec![
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "{\"city\":\"Paris\"}"}}),
+                json!({"type": "content_block_stop", "index": 0}),
+            ]
+        );
+        assert_eq!(
+            event_data(&done_chunk)?,
+            vec![
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use", "stop_sequence": null}, "usage": {"input_tokens": 11, "output_tokens": 7}}),
+                json!({"type": "message_stop"}),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_joins_consecutive_tokens_into_blocks() -> Result<()> {
+        let transformer = AnthropicCombinedResponseTransformer::default();
+
+        for token_result in [
+            GeneratedTokenResult::ReasoningToken("Let me ".to_owned()),
+            GeneratedTokenResult::ReasoningToken("think.".to_owned()),
+            GeneratedTokenResult::Token("Hello ".to_owned()),
+            GeneratedTokenResult::Token("world".to_owned()),
+            GeneratedTokenResult::Done(FinishReason::StopSequence("END".to_owned())),
+        ] {
+            transformer
+                .transform(make_token_message(token_result))
+                .await?;
+        }
+
+        let (content, has_tool_use) = transformer.content_json();
+
+        assert_eq!(
+            content,
+            vec![
+                json!({"type": "thinking", "thinking": "Let me think.", "signature": ""}),
+                json!({"type": "text", "text": "Hello world"}),
+            ]
+        );
+        assert!(!has_tool_use);
+        assert_eq!(
+            transformer.get_finish_reason(),
+            Some(FinishReason::StopSequence("END".to_owned()))
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn timeout_is_reported_as_anthropic_error() -> Result<()> {
+        let transformer = AnthropicStreamingResponseTransformer::default();
+
+        let result = transformer
+            .transform(OutgoingMessage::Response(ResponseEnvelope {
+                request_id: "test-request".to_owned(),
+                response: OutgoingResponse::Timeout,
+            }))
+            .await?;
+
+        let TransformResult::Error(error_json) = result else {
+            anyhow::bail!("expected TransformResult::Error");
+        };
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&error_json)?,
+            json!({"type": "error", "error": {"type": "timeout_error", "message": "request timed out"}})
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn context_overflow_responds_with_bad_request() -> Result<()> {
+        let result = AnthropicCombinedResponseTransformer::default()
+            .transform(make_token_message(GeneratedTokenResult::ContextOverflow(
+                ContextOverflow {
+                    context_size: 16,
+                    max_tokens: 8,
+                    prompt_tokens: 32,
+                },
+            )))
+            .await?;
+
+        let TransformResult::Error(error_json) = result else {
+            anyhow::bail!("expected TransformResult::Error");
+        };
+
+        assert_eq!(
+            anthropic_error_response(error_json).status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        Ok(())
+    }
+}
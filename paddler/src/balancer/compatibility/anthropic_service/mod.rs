@@ -0,0 +1,91 @@
+mod anthropic_bad_request;
+mod anthropic_error_json;
+mod anthropic_error_response;
+mod anthropic_event;
+mod anthropic_model_not_found;
+mod anthropic_stop_reason;
+pub mod app_data;
+pub mod configuration;
+mod http_event_stream_from_transform_results;
+pub mod http_route;
+
+use std::sync::Arc;
+
+use actix_web::App;
+use actix_web::HttpServer;
+use actix_web::middleware::from_fn;
+use actix_web::web::Data;
+use anyhow::Result;
+use async_trait::async_trait;
+use paddler_types::api_key_scope::ApiKeyScope;
+use tokio_util::sync::CancellationToken;
+
+use crate::balancer::api_key_guard::ApiKeyGuard;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
+use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::compatibility::anthropic_service::app_data::AppData;
+use crate::balancer::compatibility::anthropic_service::configuration::Configuration as AnthropicServiceConfiguration;
+use crate::balancer::http_route as common_http_route;
+use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::create_cors_middleware::create_cors_middleware;
+use crate::service::Service;
+
+pub struct AnthropicService {
+    pub anthropic_service_configuration: AnthropicServiceConfiguration,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    pub buffered_request_manager: Arc<BufferedRequestManager>,
+    pub inference_service_configuration: InferenceServiceConfiguration,
+}
+
+#[async_trait]
+impl Service for AnthropicService {
+    fn name(&self) -> &'static str {
+        "balancer::compatibility::anthropic_service"
+    }
+
+    async fn run(&mut self, shutdown: CancellationToken) -> Result<()> {
+        let cors_allowed_hosts = self
+            .inference_service_configuration
+            .cors_allowed_hosts
+            .clone();
+        let cors_allowed_hosts_arc = Arc::new(cors_allowed_hosts);
+
+        let app_data = Data::new(AppData {
+            api_key_registry: self.api_key_registry.clone(),
+            buffered_request_manager: self.buffered_request_manager.clone(),
+            inference_service_configuration: self.inference_service_configuration.clone(),
+        });
+
+        let api_key_guard = ApiKeyGuard {
+            accepts_api_key_header: true,
+            api_key_registry: self.api_key_registry.clone(),
+            public_path_prefixes: &["/health"],
+            require_api_key: self.anthropic_service_configuration.require_api_key,
+            scope: ApiKeyScope::Inference,
+        };
+
+        #[expect(clippy::expect_used, reason = "server bind failure is unrecoverable")]
+        HttpServer::new(move || {
+            let api_key_guard = api_key_guard.clone();
+
+            App::new()
+                .wrap(from_fn(move |service_request, next| {
+                    api_key_guard.clone().authorize(service_request, next)
+                }))
+                .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
+                .app_data(app_data.clone())
+                .configure(common_http_route::get_health::register)
+                .configure(http_route::post_messages::register)
+        })
+        .shutdown_signal(async move {
+            shutdown.cancelled().await;
+        })
+        .disable_signals()
+        .bind(self.anthropic_service_configuration.addr)
+        .expect("Unable to bind server to address")
+        .run()
+        .await?;
+
+        Ok(())
+    }
+}
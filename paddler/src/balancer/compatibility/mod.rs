@@ -1 +1,2 @@
+pub mod anthropic_service;
 pub mod openai_service;
@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
+use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::inference_service::configuration::Configuration;
+use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 
 pub struct AppData {
+    pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: Configuration,
 }
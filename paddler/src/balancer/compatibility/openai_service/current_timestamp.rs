@@ -0,0 +1,13 @@
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+#[expect(
+    clippy::expect_used,
+    reason = "system time before UNIX_EPOCH means we are moving back in time"
+)]
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_secs()
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::web;
+use serde_json::json;
+
+use crate::balancer::compatibility::openai_service::app_data::AppData;
+use crate::balancer::compatibility::openai_service::current_timestamp::current_timestamp;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Agents serving a model pool are listed under the name of the pool, so it can be used as the
+/// `model` of the requests. Agents serving the default model are listed under the name of their
+/// model file; while no model pools are configured, requests naming any model are served by them.
+fn openai_model_id(model_path: &str, model_pool: Option<String>) -> String {
+    model_pool.unwrap_or_else(|| {
+        Path::new(model_path).file_stem().map_or_else(
+            || model_path.to_owned(),
+            |file_stem| file_stem.to_string_lossy().into_owned(),
+        )
+    })
+}
+
+#[get("/v1/models")]
+async fn respond(app_data: web::Data<AppData>) -> Result<HttpResponse, Error> {
+    let mut model_ids = BTreeSet::new();
+
+    for entry in &app_data.agent_controller_pool.agents {
+        let agent_controller = entry.value();
+
+        // Agents without a model path have not loaded their model yet
+        if let Some(model_path) = agent_controller.get_model_path() {
+            model_ids.insert(openai_model_id(
+                &model_path,
+                agent_controller.get_model_pool(),
+            ));
+        }
+    }
+
+    let created = current_timestamp();
+
+    Ok(HttpResponse::Ok().json(json!({
+        "object": "list",
+        "data": model_ids
+            .into_iter()
+            .map(|model_id| json!({
+                "id": model_id,
+                "object": "model",
+                "created": created,
+                "owned_by": "paddler"
+            }))
+            .collect::<Vec<_>>()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_default_model_under_its_file_name() {
+        assert_eq!(
+            openai_model_id("/models/Qwen3-0.6B-Q8_0.gguf", None),
+            "Qwen3-0.6B-Q8_0"
+        );
+    }
+
+    #[test]
+    fn lists_pooled_model_under_its_pool() {
+        assert_eq!(
+            openai_model_id("/models/Qwen3-0.6B-Q8_0.gguf", Some("small".to_owned())),
+            "small"
+        );
+    }
+}
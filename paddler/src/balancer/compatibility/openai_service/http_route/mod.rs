@@ -1 +1,5 @@
+pub mod get_models;
 pub mod post_chat_completions;
+pub mod post_completions;
+pub mod post_embeddings;
+pub mod post_rerank;
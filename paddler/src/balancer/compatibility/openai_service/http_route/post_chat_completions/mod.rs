@@ -0,0 +1,1552 @@
+mod openai_completion_request_params;
+mod openai_message;
+mod openai_response_format;
+mod openai_tool;
+mod openai_tool_call;
+mod openai_tool_choice;
+
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicUsize;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::post;
+use actix_web::web;
+use actix_web::web::ReqData;
+use async_trait::async_trait;
+use futures::future::join_all;
+use futures::stream::select_all;
+use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::request_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::token_logprobs::TokenLogprobs;
+use paddler_types::tool_call::ToolCall;
+use paddler_types::validates::Validates;
+use serde_json::json;
+use tokio_stream::StreamExt as _;
+
+use self::openai_completion_request_params::OpenAICompletionRequestParams;
+use crate::atomic_value::AtomicValue;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::compatibility::openai_service::app_data::AppData;
+use crate::balancer::compatibility::openai_service::current_timestamp::current_timestamp;
+use crate::balancer::compatibility::openai_service::openai_bad_request::openai_bad_request;
+use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+use crate::balancer::compatibility::openai_service::openai_error_response::openai_error_response;
+use crate::balancer::compatibility::openai_service::openai_finish_reason::openai_finish_reason;
+use crate::balancer::compatibility::openai_service::openai_logprob_json::openai_logprob_json;
+use crate::balancer::compatibility::openai_service::openai_model_not_found::openai_model_not_found;
+use crate::balancer::compatibility::openai_service::openai_usage_json::openai_usage_json;
+use crate::balancer::compatibility::openai_service::shared_token_usage::SharedTokenUsage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::http_stream_from_transform_results::http_stream_from_transform_results;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+use crate::balancer::unbounded_stream_from_agent::unbounded_stream_from_agent;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+fn openai_tool_call_json(tool_call: &ToolCall) -> serde_json::Value {
+    json!({
+        "id": tool_call.id,
+        "type": "function",
+        "function": {
+            "name": tool_call.name,
+            "arguments": tool_call.arguments.to_string()
+        }
+    })
+}
+
+/// Choices sampled with the same seed would all come out the same, so each one gets the seed that
+/// follows the seed of the previous choice. Agents pick a random seed for every choice when there
+/// is none.
+fn choice_params(
+    paddler_params: &ContinueFromConversationHistoryParams<ValidatedParametersSchema>,
+    seed: Option<u32>,
+    choice_index: usize,
+) -> ContinueFromConversationHistoryParams<ValidatedParametersSchema> {
+    let Some(seed) = seed else {
+        return paddler_params.clone();
+    };
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "choice seeds only have to differ from each other, so they may wrap around"
+    )]
+    let choice_seed = seed.wrapping_add(choice_index as u32);
+
+    ContinueFromConversationHistoryParams {
+        sampling: Some(SamplingParameters {
+            seed: Some(choice_seed),
+            ..paddler_params.sampling.clone().unwrap_or_default()
+        }),
+        ..paddler_params.clone()
+    }
+}
+
+/// Everything but the generated content ends the choice with an error
+fn transform_unexpected_message(message: OutgoingMessage) -> TransformResult {
+    match message {
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::ChatTemplateError(description)
+                    | GeneratedTokenResult::GrammarIncompatibleWithThinking(description)
+                    | GeneratedTokenResult::GrammarRejectedModelOutput(description)
+                    | GeneratedTokenResult::GrammarInitializationFailed(description)
+                    | GeneratedTokenResult::GrammarSyntaxError(description)
+                    | GeneratedTokenResult::ImageDecodingFailed(description)
+                    | GeneratedTokenResult::InvalidSamplingParameters(description)
+                    | GeneratedTokenResult::MultimodalNotSupported(description)
+                    | GeneratedTokenResult::SamplerError(description),
+                ),
+            ..
+        })
+        | OutgoingMessage::Error(ErrorEnvelope {
+            error: paddler_types::jsonrpc::Error { description, .. },
+            ..
+        }) => TransformResult::Error(openai_error_json("server_error", &description).to_string()),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(GeneratedTokenResult::ContextOverflow(
+                    context_overflow,
+                )),
+            ..
+        }) => TransformResult::Error(
+            openai_error_json("invalid_request_error", &context_overflow.to_string()).to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Timeout,
+            ..
+        }) => TransformResult::Error(openai_error_json("timeout", "request timed out").to_string()),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::TooManyBufferedRequests,
+            ..
+        }) => TransformResult::Error(
+            openai_error_json("rate_limit_error", "too many buffered requests").to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Embedding(_) | OutgoingResponse::Rerank(_),
+            ..
+        }) => TransformResult::Error(
+            openai_error_json(
+                "invalid_request_error",
+                "unexpected embedding response in chat completions",
+            )
+            .to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::Done(_)
+                    | GeneratedTokenResult::ReasoningToken(_)
+                    | GeneratedTokenResult::Seed(_)
+                    | GeneratedTokenResult::Token(_)
+                    | GeneratedTokenResult::TokenLogprobs(_)
+                    | GeneratedTokenResult::ToolCall(_)
+                    | GeneratedTokenResult::Usage(_),
+                ),
+            ..
+        }) => TransformResult::Discard,
+    }
+}
+
+#[derive(Clone)]
+struct OpenAIStreamingResponseTransformer {
+    choice_index: usize,
+    /// Shared by the chunks of every choice
+    completion_id: String,
+    /// Adds a chunk with the token usage and no choices after the last choice finishes
+    include_usage: bool,
+    model: String,
+    system_fingerprint: String,
+    token_usage: SharedTokenUsage,
+    /// Tool calls of this choice are numbered in the order they were generated
+    tool_calls_count: Arc<AtomicValue<AtomicUsize>>,
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAIStreamingResponseTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => {
+                let finish_chunk = serde_json::to_string(&json!({
+                    "id": self.completion_id,
+                    "object": "chat.completion.chunk",
+                    "created": current_timestamp(),
+                    "model": self.model,
+                    "system_fingerprint": self.system_fingerprint,
+                    "choices": [
+                        {
+                            "index": self.choice_index,
+                            "delta": {},
+                            "logprobs": null,
+                            "finish_reason": openai_finish_reason(
+                                &finish_reason,
+                                self.tool_calls_count.get() > 0
+                            )
+                        }
+                    ]
+                }))?;
+
+                if !self.include_usage || !self.token_usage.finish_choice() {
+                    return Ok(TransformResult::Chunk(finish_chunk));
+                }
+
+                let usage_chunk = serde_json::to_string(&json!({
+                    "id": self.completion_id,
+                    "object": "chat.completion.chunk",
+                    "created": current_timestamp(),
+                    "model": self.model,
+                    "system_fingerprint": self.system_fingerprint,
+                    "choices": [],
+                    "usage": openai_usage_json(
+                        &self.token_usage.get_token_usage().unwrap_or_default()
+                    )
+                }))?;
+
+                Ok(TransformResult::Chunk(format!(
+                    "{finish_chunk}\n{usage_chunk}"
+                )))
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => {
+                self.token_usage.add_token_usage(token_usage);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Seed(_)),
+                ..
+            }) => Ok(TransformResult::Discard),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(serde_json::to_string(&json!({
+                "id": self.completion_id,
+                "object": "chat.completion.chunk",
+                "created": current_timestamp(),
+                "model": self.model,
+                "system_fingerprint": self.system_fingerprint,
+                "choices": [
+                    {
+                        "index": self.choice_index,
+                        "delta": {
+                            "role": "assistant",
+                            "content": token,
+                        },
+                        "logprobs": null,
+                        "finish_reason": null
+                    }
+                ]
+            }))?)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprobs(
+                        token_logprobs,
+                    )),
+                ..
+            }) => Ok(TransformResult::Chunk(serde_json::to_string(&json!({
+                "id": self.completion_id,
+                "object": "chat.completion.chunk",
+                "created": current_timestamp(),
+                "model": self.model,
+                "system_fingerprint": self.system_fingerprint,
+                "choices": [
+                    {
+                        "index": self.choice_index,
+                        "delta": {},
+                        "logprobs": {
+                            "content": [openai_logprob_json(&token_logprobs)],
+                            "refusal": null
+                        },
+                        "finish_reason": null
+                    }
+                ]
+            }))?)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ReasoningToken(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(serde_json::to_string(&json!({
+                "id": self.completion_id,
+                "object": "chat.completion.chunk",
+                "created": current_timestamp(),
+                "model": self.model,
+                "system_fingerprint": self.system_fingerprint,
+                "choices": [
+                    {
+                        "index": self.choice_index,
+                        "delta": {
+                            "role": "assistant",
+                            "reasoning_content": token,
+                        },
+                        "logprobs": null,
+                        "finish_reason": null
+                    }
+                ]
+            }))?)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ToolCall(tool_call)),
+                ..
+            }) => {
+                let mut tool_call_json = openai_tool_call_json(&tool_call);
+
+                tool_call_json["index"] = json!(self.tool_calls_count.get());
+                self.tool_calls_count.increment_by(1);
+
+                Ok(TransformResult::Chunk(serde_json::to_string(&json!({
+                    "id": self.completion_id,
+                    "object": "chat.completion.chunk",
+                    "created": current_timestamp(),
+                    "model": self.model,
+                    "system_fingerprint": self.system_fingerprint,
+                    "choices": [
+                        {
+                            "index": self.choice_index,
+                            "delta": {
+                                "role": "assistant",
+                                "tool_calls": [tool_call_json],
+                            },
+                            "logprobs": null,
+                            "finish_reason": null
+                        }
+                    ]
+                }))?))
+            }
+            message => Ok(transform_unexpected_message(message)),
+        }
+    }
+}
+
+/// Clones share the finish reason, the reasoning, the logprobs, the token usage and the tool
+/// calls, so they can be read back once the whole response is collected.
+#[derive(Clone, Default)]
+struct OpenAICombinedResponseTransformer {
+    finish_reason: Arc<RwLock<Option<FinishReason>>>,
+    reasoning_content: Arc<RwLock<String>>,
+    token_logprobs: Arc<RwLock<Vec<TokenLogprobs>>>,
+    token_usage: SharedTokenUsage,
+    tool_calls: Arc<RwLock<Vec<ToolCall>>>,
+}
+
+impl OpenAICombinedResponseTransformer {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_reasoning_content(&self, token: &str) {
+        let mut lock = self
+            .reasoning_content
+            .write()
+            .expect("Failed to acquire write lock on reasoning content");
+
+        lock.push_str(token);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_token_logprobs(&self, token_logprobs: TokenLogprobs) {
+        let mut lock = self
+            .token_logprobs
+            .write()
+            .expect("Failed to acquire write lock on token logprobs");
+
+        lock.push(token_logprobs);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_tool_call(&self, tool_call: ToolCall) {
+        let mut lock = self
+            .tool_calls
+            .write()
+            .expect("Failed to acquire write lock on tool calls");
+
+        lock.push(tool_call);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_finish_reason(&self) -> Option<FinishReason> {
+        let lock = self
+            .finish_reason
+            .read()
+            .expect("Failed to acquire read lock on finish reason");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_reasoning_content(&self) -> String {
+        let lock = self
+            .reasoning_content
+            .read()
+            .expect("Failed to acquire read lock on reasoning content");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_token_logprobs(&self) -> Vec<TokenLogprobs> {
+        let lock = self
+            .token_logprobs
+            .read()
+            .expect("Failed to acquire read lock on token logprobs");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_tool_calls(&self) -> Vec<ToolCall> {
+        let lock = self
+            .tool_calls
+            .read()
+            .expect("Failed to acquire read lock on tool calls");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn set_finish_reason(&self, finish_reason: FinishReason) {
+        let mut lock = self
+            .finish_reason
+            .write()
+            .expect("Failed to acquire write lock on finish reason");
+
+        *lock = Some(finish_reason);
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAICombinedResponseTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => {
+                self.set_finish_reason(finish_reason);
+
+                Ok(TransformResult::Chunk(String::new()))
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => {
+                self.token_usage.add_token_usage(token_usage);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Seed(_)),
+                ..
+            }) => Ok(TransformResult::Discard),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(token)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ReasoningToken(token)),
+                ..
+            }) => {
+                self.add_reasoning_content(&token);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprobs(
+                        token_logprobs,
+                    )),
+                ..
+            }) => {
+                self.add_token_logprobs(token_logprobs);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::ToolCall(tool_call)),
+                ..
+            }) => {
+                self.add_tool_call(tool_call);
+
+                Ok(TransformResult::Discard)
+            }
+            message => Ok(transform_unexpected_message(message)),
+        }
+    }
+}
+
+#[post("/v1/chat/completions")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    openai_params: web::Json<OpenAICompletionRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let openai_params = openai_params.into_inner();
+    let choices_count = match openai_params.choices_count() {
+        Ok(choices_count) => choices_count,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+    let include_usage = openai_params.include_usage();
+    let model = openai_params.model.clone();
+    let stream = openai_params.stream.unwrap_or(false);
+    let paddler_params = match openai_params
+        .into_paddler_params()
+        .and_then(Validates::validate)
+    {
+        Ok(paddler_params) => paddler_params,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+
+    if let Some(sampling) = &paddler_params.sampling
+        && let Err(err) = sampling.clone().validate()
+    {
+        return Ok(openai_bad_request(&err.to_string()));
+    }
+
+    if let Err(model_not_found) = app_data
+        .buffered_request_manager
+        .ensure_model_is_served(paddler_params.model.as_deref())
+    {
+        return Ok(openai_model_not_found(&model_not_found));
+    }
+
+    let fair_share_tenant = api_key
+        .as_deref()
+        .and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            paddler_params
+                .dispatch_criteria()
+                .estimated_tokens
+                .saturating_mul(i32::try_from(choices_count).unwrap_or(i32::MAX)),
+        )?;
+    }
+
+    let seed = paddler_params
+        .sampling
+        .as_ref()
+        .and_then(|sampling| sampling.seed)
+        .or_else(|| {
+            app_data
+                .balancer_applicable_state_holder
+                .get_balancer_applicable_state()
+                .and_then(|balancer_applicable_state| {
+                    balancer_applicable_state
+                        .agent_desired_state_for(paddler_params.model.as_deref())
+                        .inference_parameters
+                        .seed
+                })
+        });
+    let choices_params: Vec<_> = (0..choices_count)
+        .map(|choice_index| choice_params(&paddler_params, seed, choice_index))
+        .collect();
+
+    let completion_id = nanoid!();
+    let logprobs_requested = paddler_params.logprobs.is_some();
+    let token_usage = SharedTokenUsage::new(choices_count);
+
+    if stream {
+        let system_fingerprint = nanoid!();
+
+        return Ok(http_stream_from_transform_results(select_all(
+            choices_params
+                .into_iter()
+                .enumerate()
+                .map(|(choice_index, choice_params)| {
+                    Box::pin(unbounded_stream_from_agent(
+                        app_data.buffered_request_manager.clone(),
+                        fair_share_tenant.clone(),
+                        app_data.inference_service_configuration.clone(),
+                        choice_params,
+                        OpenAIStreamingResponseTransformer {
+                            choice_index,
+                            completion_id: completion_id.clone(),
+                            include_usage,
+                            model: model.clone(),
+                            system_fingerprint: system_fingerprint.clone(),
+                            token_usage: token_usage.clone(),
+                            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+                        },
+                    ))
+                }),
+        )));
+    }
+
+    let transformers: Vec<OpenAICombinedResponseTransformer> = (0..choices_count)
+        .map(|_| OpenAICombinedResponseTransformer {
+            finish_reason: Arc::default(),
+            reasoning_content: Arc::default(),
+            token_logprobs: Arc::default(),
+            token_usage: token_usage.clone(),
+            tool_calls: Arc::default(),
+        })
+        .collect();
+    let choices_results: Vec<Vec<TransformResult>> =
+        join_all(choices_params.into_iter().zip(transformers.iter()).map(
+            |(choice_params, transformer)| {
+                unbounded_stream_from_agent(
+                    app_data.buffered_request_manager.clone(),
+                    fair_share_tenant.clone(),
+                    app_data.inference_service_configuration.clone(),
+                    choice_params,
+                    transformer.clone(),
+                )
+                .collect()
+            },
+        ))
+        .await;
+
+    if let Some(TransformResult::Error(error_json)) = choices_results
+        .iter()
+        .flatten()
+        .find(|result| matches!(result, TransformResult::Error(_)))
+    {
+        return Ok(openai_error_response(error_json.clone()));
+    }
+
+    let choices: Vec<serde_json::Value> = choices_results
+        .into_iter()
+        .zip(transformers.iter())
+        .enumerate()
+        .map(|(choice_index, (results, transformer))| {
+            let combined_response: String = results
+                .into_iter()
+                .filter_map(|result| match result {
+                    TransformResult::Chunk(content) => Some(content),
+                    TransformResult::Discard | TransformResult::Error(_) => None,
+                })
+                .collect();
+            let reasoning_content = transformer.get_reasoning_content();
+            let tool_calls = transformer.get_tool_calls();
+            let mut message = json!({
+              "role": "assistant",
+              "content": combined_response,
+              "refusal": null,
+              "annotations": []
+            });
+
+            if !reasoning_content.is_empty() {
+                message["reasoning_content"] = json!(reasoning_content);
+            }
+
+            if !tool_calls.is_empty() {
+                if combined_response.is_empty() {
+                    message["content"] = serde_json::Value::Null;
+                }
+
+                message["tool_calls"] = tool_calls.iter().map(openai_tool_call_json).collect();
+            }
+
+            let logprobs = if logprobs_requested {
+                json!({
+                  "content": transformer
+                    .get_token_logprobs()
+                    .iter()
+                    .map(openai_logprob_json)
+                    .collect::<Vec<_>>(),
+                  "refusal": null
+                })
+            } else {
+                serde_json::Value::Null
+            };
+
+            json!({
+              "index": choice_index,
+              "message": message,
+              "logprobs": logprobs,
+              "finish_reason": transformer
+                .get_finish_reason()
+                .as_ref()
+                .map_or("stop", |finish_reason| {
+                    openai_finish_reason(finish_reason, !tool_calls.is_empty())
+                })
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(json!({
+      "id": completion_id,
+      "object": "chat.completion",
+      "created": current_timestamp(),
+      "model": model,
+      "choices": choices,
+      "usage": openai_usage_json(&token_usage.get_token_usage().unwrap_or_default()),
+      "service_tier": "default"
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+
+    use anyhow::Result;
+    use paddler_types::finish_reason::FinishReason;
+    use paddler_types::generated_token_result::GeneratedTokenResult;
+    use paddler_types::inference_client::Message as OutgoingMessage;
+    use paddler_types::inference_client::Response as OutgoingResponse;
+    use paddler_types::jsonrpc::ErrorEnvelope;
+    use paddler_types::jsonrpc::ResponseEnvelope;
+    use paddler_types::request_params::ContinueFromConversationHistoryParams;
+    use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+    use paddler_types::token_logprob::TokenLogprob;
+    use paddler_types::token_logprobs::TokenLogprobs;
+    use paddler_types::token_usage::TokenUsage;
+    use paddler_types::tool_call::ToolCall;
+    use paddler_types::validates::Validates as _;
+    use serde_json::json;
+
+    use super::OpenAICombinedResponseTransformer;
+    use super::OpenAIStreamingResponseTransformer;
+    use super::choice_params;
+    use crate::atomic_value::AtomicValue;
+    use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+    use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+    use crate::balancer::compatibility::openai_service::shared_token_usage::SharedTokenUsage;
+
+    #[test]
+    fn choices_get_consecutive_seeds() -> Result<()> {
+        let params: ContinueFromConversationHistoryParams<RawParametersSchema> =
+            serde_json::from_value(json!({
+                "add_generation_prompt": true,
+                "conversation_history": [{"content": "hi", "role": "user"}],
+                "enable_thinking": false,
+                "max_tokens": 50,
+                "sampling": {"temperature": 0.5},
+            }))?;
+        let params = params.validate()?;
+
+        let seeds: Vec<Option<u32>> = (0..3)
+            .map(|choice_index| {
+                choice_params(&params, Some(u32::MAX), choice_index)
+                    .sampling
+                    .and_then(|sampling| sampling.seed)
+            })
+            .collect();
+
+        assert_eq!(seeds, vec![Some(u32::MAX), Some(0), Some(1)]);
+        assert_eq!(
+            choice_params(&params, Some(7), 1)
+                .sampling
+                .and_then(|sampling| sampling.temperature),
+            Some(0.5)
+        );
+        assert_eq!(choice_params(&params, None, 1), params);
+
+        Ok(())
+    }
+
+    fn make_token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::GeneratedToken(token_result),
+        })
+    }
+
+    fn make_token_logprobs() -> TokenLogprobs {
+        let token_logprob = TokenLogprob {
+            bytes: b"hello".to_vec(),
+            id: 42,
+            logprob: -0.5,
+            token: "hello".to_owned(),
+        };
+
+        TokenLogprobs {
+            sampled: token_logprob.clone(),
+            top_logprobs: vec![token_logprob],
+        }
+    }
+
+    fn make_error_message(code: i32, description: &str) -> OutgoingMessage {
+        OutgoingMessage::Error(ErrorEnvelope {
+            request_id: "test-request".to_owned(),
+            error: paddler_types::jsonrpc::Error {
+                code,
+                description: description.to_owned(),
+            },
+        })
+    }
+
+    fn make_response_message(response: OutgoingResponse) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            request_id: "test-request".to_owned(),
+            response,
+        })
+    }
+
+    fn assert_chunk_contains(result: &TransformResult, expected: &str) -> Result<()> {
+        let TransformResult::Chunk(content) = result else {
+            anyhow::bail!("expected TransformResult::Chunk, got TransformResult::Error");
+        };
+
+        assert!(
+            content.contains(expected),
+            "chunk does not contain '{expected}': {content}"
+        );
+
+        Ok(())
+    }
+
+    fn assert_error_contains(result: &TransformResult, expected: &str) -> Result<()> {
+        let TransformResult::Error(content) = result else {
+            anyhow::bail!("expected TransformResult::Error, got TransformResult::Chunk");
+        };
+
+        assert!(
+            content.contains(expected),
+            "error does not contain '{expected}': {content}"
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_token_emits_content_delta() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::Token("hello".to_owned()));
+        let result = transformer.transform(message).await?;
+
+        assert_chunk_contains(&result, "\"content\":\"hello\"")?;
+        assert_chunk_contains(&result, "\"role\":\"assistant\"")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_done_emits_stop_finish_reason() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::Done(FinishReason::Eos));
+        let result = transformer.transform(message).await?;
+
+        assert_chunk_contains(&result, "\"finish_reason\":\"stop\"")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_done_at_max_tokens_emits_length_finish_reason() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::Done(FinishReason::MaxTokens));
+        let result = transformer.transform(message).await?;
+
+        assert_chunk_contains(&result, "\"finish_reason\":\"length\"")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_token_logprobs_emit_logprobs_content() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message =
+            make_token_message(GeneratedTokenResult::TokenLogprobs(make_token_logprobs()));
+        let TransformResult::Chunk(chunk) = transformer.transform(message).await? else {
+            anyhow::bail!("expected TransformResult::Chunk");
+        };
+        let chunk: serde_json::Value = serde_json::from_str(&chunk)?;
+
+        assert_eq!(
+            chunk["choices"][0]["logprobs"]["content"],
+            json!([{
+                "token": "hello",
+                "logprob": -0.5,
+                "bytes": [104, 101, 108, 108, 111],
+                "top_logprobs": [{
+                    "token": "hello",
+                    "logprob": -0.5,
+                    "bytes": [104, 101, 108, 108, 111]
+                }]
+            }])
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_token_logprobs_are_kept_for_the_response() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message =
+            make_token_message(GeneratedTokenResult::TokenLogprobs(make_token_logprobs()));
+        let result = transformer.transform(message).await?;
+
+        assert!(matches!(result, TransformResult::Discard));
+        assert_eq!(
+            transformer.get_token_logprobs(),
+            vec![make_token_logprobs()]
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_token_returns_content() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::Token("hello".to_owned()));
+        let result = transformer.transform(message).await?;
+
+        assert!(matches!(result, TransformResult::Chunk(ref content) if content == "hello"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_done_returns_empty_chunk() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::Done(FinishReason::StopSequence(
+            "###".to_owned(),
+        )));
+        let result = transformer.transform(message).await?;
+
+        assert!(matches!(result, TransformResult::Chunk(ref content) if content.is_empty()));
+        assert_eq!(
+            transformer.get_finish_reason(),
+            Some(FinishReason::StopSequence("###".to_owned()))
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_done_appends_usage_chunk_when_requested() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: true,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let usage_result = transformer
+            .transform(make_token_message(GeneratedTokenResult::Usage(
+                TokenUsage {
+                    completion_tokens: 3,
+                    prompt_tokens: 12,
+                },
+            )))
+            .await?;
+
+        assert!(matches!(usage_result, TransformResult::Discard));
+
+        let result = transformer
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::Eos,
+            )))
+            .await?;
+
+        assert_chunk_contains(&result, "\"finish_reason\":\"stop\"")?;
+        assert_chunk_contains(&result, "\"total_tokens\":15")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_usage_chunk_waits_for_every_choice() -> Result<()> {
+        let token_usage = SharedTokenUsage::new(2);
+        let make_transformer = |choice_index| OpenAIStreamingResponseTransformer {
+            choice_index,
+            completion_id: "test-completion".to_owned(),
+            include_usage: true,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: token_usage.clone(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+        let first_choice = make_transformer(0);
+        let second_choice = make_transformer(1);
+
+        for transformer in [&first_choice, &second_choice] {
+            transformer
+                .transform(make_token_message(GeneratedTokenResult::Usage(
+                    TokenUsage {
+                        completion_tokens: 3,
+                        prompt_tokens: 12,
+                    },
+                )))
+                .await?;
+        }
+
+        let first_result = first_choice
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::Eos,
+            )))
+            .await?;
+
+        assert_chunk_contains(&first_result, "\"index\":0")?;
+        assert!(
+            matches!(first_result, TransformResult::Chunk(ref content) if !content.contains("usage"))
+        );
+
+        let second_result = second_choice
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::Eos,
+            )))
+            .await?;
+
+        assert_chunk_contains(&second_result, "\"id\":\"test-completion\"")?;
+        assert_chunk_contains(&second_result, "\"index\":1")?;
+        assert_chunk_contains(&second_result, "\"total_tokens\":18")?;
+
+        Ok(())
+    }
+
+    fn make_tool_call_message() -> OutgoingMessage {
+        make_token_message(GeneratedTokenResult::ToolCall(ToolCall {
+            arguments: json!({"city": "Paris"}),
+            id: "call_1".to_owned(),
+            name: "get_weather".to_owned(),
+        }))
+    }
+
+    #[actix_web::test]
+    async fn streaming_tool_call_emits_tool_calls_delta() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        transformer.transform(make_tool_call_message()).await?;
+
+        let result = transformer.transform(make_tool_call_message()).await?;
+
+        assert_chunk_contains(&result, "\"index\":1")?;
+        assert_chunk_contains(&result, "\"id\":\"call_1\"")?;
+        assert_chunk_contains(&result, "\"arguments\":\"{\\\"city\\\":\\\"Paris\\\"}\"")?;
+
+        let done_result = transformer
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::Eos,
+            )))
+            .await?;
+
+        assert_chunk_contains(&done_result, "\"finish_reason\":\"tool_calls\"")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_tool_call_is_kept_for_the_response() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let result = transformer.transform(make_tool_call_message()).await?;
+
+        assert!(matches!(result, TransformResult::Discard));
+        assert_eq!(transformer.get_tool_calls().len(), 1);
+        assert_eq!(transformer.get_tool_calls()[0].name, "get_weather");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_reasoning_token_emits_reasoning_content_delta() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::ReasoningToken("hmm".to_owned()));
+        let result = transformer.transform(message).await?;
+
+        assert_chunk_contains(&result, "\"reasoning_content\":\"hmm\"")?;
+        assert!(
+            matches!(result, TransformResult::Chunk(ref content) if !content.contains("\"content\""))
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_reasoning_is_kept_apart_from_the_content() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        for token in ["Let me ", "think."] {
+            let result = transformer
+                .transform(make_token_message(GeneratedTokenResult::ReasoningToken(
+                    token.to_owned(),
+                )))
+                .await?;
+
+            assert!(matches!(result, TransformResult::Discard));
+        }
+
+        assert_eq!(transformer.get_reasoning_content(), "Let me think.");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_usage_is_kept_for_the_response() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::Usage(TokenUsage {
+            completion_tokens: 3,
+            prompt_tokens: 12,
+        }));
+        let result = transformer.transform(message).await?;
+
+        assert!(matches!(result, TransformResult::Discard));
+        assert_eq!(
+            transformer.token_usage.get_token_usage(),
+            Some(TokenUsage {
+                completion_tokens: 3,
+                prompt_tokens: 12,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_error_message_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_error_message(500, "internal server error");
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "internal server error")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_error_message_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_error_message(500, "internal server error");
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "internal server error")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_chat_template_error_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::ChatTemplateError(
+            "bad template".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "bad template")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_chat_template_error_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::ChatTemplateError(
+            "bad template".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "bad template")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_timeout_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_response_message(OutgoingResponse::Timeout);
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "request timed out")?;
+        assert_error_contains(&result, "timeout")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_timeout_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_response_message(OutgoingResponse::Timeout);
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "request timed out")?;
+        assert_error_contains(&result, "timeout")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_too_many_buffered_requests_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_response_message(OutgoingResponse::TooManyBufferedRequests);
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "too many buffered requests")?;
+        assert_error_contains(&result, "rate_limit_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_too_many_buffered_requests_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_response_message(OutgoingResponse::TooManyBufferedRequests);
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "too many buffered requests")?;
+        assert_error_contains(&result, "rate_limit_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn openai_error_json_has_correct_structure() -> Result<()> {
+        let error = super::openai_error_json("server_error", "something went wrong");
+
+        assert_eq!(error["error"]["type"], "server_error");
+        assert_eq!(error["error"]["message"], "something went wrong");
+        assert!(error["error"]["param"].is_null());
+        assert!(error["error"]["code"].is_null());
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_image_decoding_failed_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::ImageDecodingFailed(
+            "unsupported format".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "unsupported format")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_image_decoding_failed_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::ImageDecodingFailed(
+            "unsupported format".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "unsupported format")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streaming_multimodal_not_supported_returns_error_variant() -> Result<()> {
+        let transformer = OpenAIStreamingResponseTransformer {
+            choice_index: 0,
+            completion_id: "test-completion".to_owned(),
+            include_usage: false,
+            model: "test-model".to_owned(),
+            system_fingerprint: "test-fingerprint".to_owned(),
+            token_usage: SharedTokenUsage::default(),
+            tool_calls_count: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+        };
+
+        let message = make_token_message(GeneratedTokenResult::MultimodalNotSupported(
+            "model does not support images".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "model does not support images")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn combined_multimodal_not_supported_returns_error_variant() -> Result<()> {
+        let transformer = OpenAICombinedResponseTransformer::default();
+
+        let message = make_token_message(GeneratedTokenResult::MultimodalNotSupported(
+            "model does not support images".to_owned(),
+        ));
+        let result = transformer.transform(message).await?;
+
+        assert_error_contains(&result, "model does not support images")?;
+        assert_error_contains(&result, "server_error")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_text_only_request() -> Result<()> {
+        let input = serde_json::json!({
+            "model": "test-model",
+            "messages": [
+                {"role": "user", "content": "hello"}
+            ]
+        });
+
+        let params: super::OpenAICompletionRequestParams = serde_json::from_value(input)?;
+
+        assert_eq!(params.model, "test-model");
+        assert!(params.stop.is_none());
+        assert_eq!(params.messages.len(), 1);
+        assert_eq!(params.messages[0].role, "user");
+        assert_eq!(params.messages[0].content.text_content(), "hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_single_stop_sequence() -> Result<()> {
+        let input = serde_json::json!({
+            "model": "test-model",
+            "messages": [
+                {"role": "user", "content": "hello"}
+            ],
+            "stop": "###"
+        });
+
+        let params: super::OpenAICompletionRequestParams = serde_json::from_value(input)?;
+
+        assert_eq!(
+            params.stop.map(Vec::<String>::from),
+            Some(vec!["###".to_owned()])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_multimodal_request_with_image() -> Result<()> {
+        let input = serde_json::json!({
+            "model": "vision-model",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": [
+                        {"type": "text", "text": "describe this image"},
+                        {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/4AAQ"}}
+                    ]
+                }
+            ]
+        });
+
+        let params: super::OpenAICompletionRequestParams = serde_json::from_value(input)?;
+
+        assert_eq!(params.messages.len(), 1);
+        assert_eq!(
+            params.messages[0].content.text_content(),
+            "describe this image"
+        );
+
+        let image_urls = params.messages[0].content.image_urls();
+
+        assert_eq!(image_urls.len(), 1);
+        assert_eq!(image_urls[0].url, "data:image/jpeg;base64,/9j/4AAQ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_multi_turn_conversation() -> Result<()> {
+        let input = serde_json::json!({
+            "model": "test-model",
+            "messages": [
+                {"role": "system", "content": "You are a helpful assistant."},
+                {"role": "user", "content": "What is 2+2?"},
+                {"role": "assistant", "content": "4"},
+                {"role": "user", "content": "And 3+3?"}
+            ]
+        });
+
+        let params: super::OpenAICompletionRequestParams = serde_json::from_value(input)?;
+
+        assert_eq!(params.messages.len(), 4);
+        assert_eq!(params.messages[0].role, "system");
+        assert_eq!(params.messages[1].role, "user");
+        assert_eq!(params.messages[2].role, "assistant");
+        assert_eq!(params.messages[3].role, "user");
+
+        Ok(())
+    }
+
+    #[test]
+    fn openai_message_converts_to_conversation_message() -> Result<()> {
+        use paddler_types::conversation_message::ConversationMessage;
+
+        let input = serde_json::json!({
+            "role": "user",
+            "content": [
+                {"type": "text", "text": "OCR this"},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,abc"}}
+            ]
+        });
+
+        let openai_message: super::openai_message::OpenAIMessage = serde_json::from_value(input)?;
+        let conversation_message = ConversationMessage::try_from(&openai_message)?;
+
+        assert_eq!(conversation_message.role, "user");
+        assert_eq!(conversation_message.content.text_content(), "OCR this");
+        assert_eq!(conversation_message.content.image_urls().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn openai_tool_call_messages_convert_to_conversation_messages() -> Result<()> {
+        use paddler_types::conversation_message::ConversationMessage;
+
+        let assistant_message: super::openai_message::OpenAIMessage =
+            serde_json::from_value(serde_json::json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                }]
+            }))?;
+        let tool_message: super::openai_message::OpenAIMessage =
+            serde_json::from_value(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": "call_1",
+                "content": "sunny"
+            }))?;
+
+        let assistant_conversation_message = ConversationMessage::try_from(&assistant_message)?;
+        let tool_conversation_message = ConversationMessage::try_from(&tool_message)?;
+
+        assert_eq!(assistant_conversation_message.content.text_content(), "");
+        assert_eq!(
+            assistant_conversation_message.tool_calls[0].name,
+            "get_weather"
+        );
+        assert_eq!(
+            assistant_conversation_message.tool_calls[0].arguments,
+            serde_json::json!({"city": "Paris"})
+        );
+        assert_eq!(
+            tool_conversation_message.tool_call_id.as_deref(),
+            Some("call_1")
+        );
+
+        Ok(())
+    }
+}
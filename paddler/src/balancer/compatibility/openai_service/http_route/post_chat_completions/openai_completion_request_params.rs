@@ -0,0 +1,305 @@
+use anyhow::Result;
+use anyhow::bail;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::request_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::truncation_policy::TruncationPolicy;
+use serde::Deserialize;
+
+use super::openai_message::OpenAIMessage;
+use super::openai_response_format::OpenAIResponseFormat;
+use super::openai_tool::OpenAITool;
+use super::openai_tool_choice::OpenAIToolChoice;
+use crate::balancer::compatibility::openai_service::openai_stop::OpenAIStop;
+use crate::balancer::compatibility::openai_service::openai_stream_options::OpenAIStreamOptions;
+
+const DEFAULT_MAX_TOKENS: i32 = 2000;
+/// Each choice is a separate request to the agents, so one request cannot flood them
+const MAX_CHOICES: usize = 128;
+/// Same limit as the OpenAI API
+const MAX_TOP_LOGPROBS: u32 = 20;
+
+#[derive(Deserialize)]
+pub struct OpenAICompletionRequestParams {
+    #[serde(default)]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default)]
+    pub logprobs: Option<bool>,
+    #[serde(default)]
+    pub max_completion_tokens: Option<i32>,
+    /// Deprecated by OpenAI in favor of `max_completion_tokens`, but still sent by older clients
+    #[serde(default)]
+    pub max_tokens: Option<i32>,
+    pub messages: Vec<OpenAIMessage>,
+    /// Picks the model pool of that name; any value is served by the default model while no
+    /// model pools are configured.
+    pub model: String,
+    /// Number of choices to generate, each one is a separate request to the agents
+    #[serde(default)]
+    pub n: Option<usize>,
+    #[serde(default)]
+    pub presence_penalty: Option<f32>,
+    #[serde(default)]
+    pub response_format: Option<OpenAIResponseFormat>,
+    #[serde(default)]
+    pub seed: Option<u32>,
+    /// `flex` requests can wait, so they are buffered with the batch priority
+    #[serde(default)]
+    pub service_tier: Option<String>,
+    #[serde(default)]
+    pub stop: Option<OpenAIStop>,
+    #[serde(default)]
+    pub stream: Option<bool>,
+    #[serde(default)]
+    pub stream_options: Option<OpenAIStreamOptions>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub tool_choice: Option<OpenAIToolChoice>,
+    #[serde(default)]
+    pub tools: Vec<OpenAITool>,
+    /// Only allowed along with `logprobs`
+    #[serde(default)]
+    pub top_logprobs: Option<u32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Not in the OpenAI API; lets a conversation that overflows the context be truncated
+    /// instead of failing
+    #[serde(default)]
+    pub truncation: Option<TruncationPolicy>,
+}
+
+impl OpenAICompletionRequestParams {
+    pub fn choices_count(&self) -> Result<usize> {
+        match self.n {
+            Some(0) => bail!("n must be at least 1"),
+            Some(choices_count) if choices_count > MAX_CHOICES => {
+                bail!("n must be at most {MAX_CHOICES}, got {choices_count}")
+            }
+            Some(choices_count) => Ok(choices_count),
+            None => Ok(1),
+        }
+    }
+
+    pub fn include_usage(&self) -> bool {
+        self.stream_options
+            .as_ref()
+            .is_some_and(|stream_options| stream_options.include_usage)
+    }
+
+    fn requested_logprobs(&self) -> Result<Option<u32>> {
+        match (self.logprobs.unwrap_or(false), self.top_logprobs) {
+            (_, Some(top_logprobs)) if top_logprobs > MAX_TOP_LOGPROBS => {
+                bail!("top_logprobs must be at most {MAX_TOP_LOGPROBS}, got {top_logprobs}")
+            }
+            (false, Some(_)) => bail!("top_logprobs requires logprobs to be enabled"),
+            (false, None) => Ok(None),
+            (true, top_logprobs) => Ok(Some(top_logprobs.unwrap_or(0))),
+        }
+    }
+
+    pub fn into_paddler_params(
+        self,
+    ) -> Result<ContinueFromConversationHistoryParams<RawParametersSchema>> {
+        let logprobs = self.requested_logprobs()?;
+        let tools = match &self.tool_choice {
+            Some(tool_choice) => tool_choice.select_tools(self.tools)?,
+            None => self.tools,
+        };
+        let sampling = SamplingParameters {
+            penalty_frequency: self.frequency_penalty,
+            penalty_presence: self.presence_penalty,
+            seed: self.seed,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            ..SamplingParameters::default()
+        };
+
+        Ok(ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(
+                self.messages
+                    .iter()
+                    .map(ConversationMessage::try_from)
+                    .collect::<Result<_>>()?,
+            ),
+            enable_thinking: true,
+            grammar: match self.response_format {
+                Some(response_format) => response_format.into_grammar_constraint()?,
+                None => None,
+            },
+            logprobs,
+            max_tokens: self
+                .max_completion_tokens
+                .or(self.max_tokens)
+                .unwrap_or(DEFAULT_MAX_TOKENS),
+            model: Some(self.model),
+            priority: if self.service_tier.as_deref() == Some("flex") {
+                RequestPriority::Batch
+            } else {
+                RequestPriority::Interactive
+            },
+            sampling: if sampling == SamplingParameters::default() {
+                None
+            } else {
+                Some(sampling)
+            },
+            session_id: None,
+            stop: self.stop.map(Vec::from).unwrap_or_default(),
+            tools: tools.into_iter().map(Into::into).collect(),
+            truncation: self.truncation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::grammar_constraint::GrammarConstraint;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_sampling_and_response_format() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "temperature": 0.2,
+            "top_p": 0.9,
+            "seed": 42,
+            "presence_penalty": 0.5,
+            "frequency_penalty": 0.25,
+            "response_format": {"type": "json_object"},
+            "user": "ignored"
+        }))?;
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(
+            paddler_params.sampling,
+            Some(SamplingParameters {
+                penalty_frequency: Some(0.25),
+                penalty_presence: Some(0.5),
+                seed: Some(42),
+                temperature: Some(0.2),
+                top_p: Some(0.9),
+                ..SamplingParameters::default()
+            })
+        );
+        assert_eq!(
+            paddler_params.grammar,
+            Some(GrammarConstraint::JsonSchema {
+                schema: r#"{"type":"object"}"#.to_owned(),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn named_tool_choice_keeps_only_that_tool() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "What is the weather?"}],
+            "tools": [
+                {"type": "function", "function": {"name": "get_weather", "parameters": {"type": "object", "properties": {"city": {"type": "string"}}}}},
+                {"type": "function", "function": {"name": "get_time"}}
+            ],
+            "tool_choice": {"type": "function", "function": {"name": "get_weather"}}
+        }))?;
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params.tools.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn flex_service_tier_is_buffered_as_batch() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "service_tier": "flex"
+        }))?;
+
+        assert_eq!(
+            openai_params.into_paddler_params()?.priority,
+            RequestPriority::Batch
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn passes_truncation_extension_through() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "truncation": "DropOldestMessages"
+        }))?;
+
+        assert_eq!(
+            openai_params.into_paddler_params()?.truncation,
+            Some(TruncationPolicy::DropOldestMessages)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_choices_are_rejected() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [],
+            "n": 0
+        }))?;
+
+        assert!(openai_params.choices_count().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn too_many_choices_are_rejected() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [],
+            "n": MAX_CHOICES + 1
+        }))?;
+
+        assert!(openai_params.choices_count().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn maps_logprobs_to_top_logprobs_count() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "logprobs": true,
+            "top_logprobs": 3
+        }))?;
+
+        assert_eq!(openai_params.into_paddler_params()?.logprobs, Some(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn top_logprobs_without_logprobs_are_rejected() -> Result<()> {
+        let openai_params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "top_logprobs": 3
+        }))?;
+
+        assert!(openai_params.into_paddler_params().is_err());
+
+        Ok(())
+    }
+}
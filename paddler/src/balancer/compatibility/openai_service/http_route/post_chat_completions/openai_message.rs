@@ -0,0 +1,42 @@
+use anyhow::Error;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::tool_call::ToolCall;
+use serde::Deserialize;
+
+use super::openai_tool_call::OpenAIToolCall;
+
+#[derive(Deserialize)]
+/// Although fields are same as in Paddler's conversation message for the moment,
+/// it would be better if this struct stayed independent from ours just in case
+/// to avoid any potential side effects in the future.
+pub struct OpenAIMessage {
+    /// Assistant messages that only call tools come with `null` content
+    #[serde(default)]
+    pub content: Option<ConversationMessageContent>,
+    pub role: String,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<OpenAIToolCall>,
+}
+
+impl TryFrom<&OpenAIMessage> for ConversationMessage {
+    type Error = Error;
+
+    fn try_from(openai_message: &OpenAIMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            content: openai_message
+                .content
+                .clone()
+                .unwrap_or_else(|| ConversationMessageContent::Text(String::new())),
+            role: openai_message.role.clone(),
+            tool_call_id: openai_message.tool_call_id.clone(),
+            tool_calls: openai_message
+                .tool_calls
+                .iter()
+                .map(ToolCall::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
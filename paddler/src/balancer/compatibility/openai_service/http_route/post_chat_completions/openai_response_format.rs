@@ -0,0 +1,73 @@
+use anyhow::Result;
+use paddler_types::grammar_constraint::GrammarConstraint;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+
+#[derive(Deserialize)]
+pub struct OpenAIJsonSchema {
+    pub schema: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type")]
+pub enum OpenAIResponseFormat {
+    JsonObject,
+    JsonSchema { json_schema: OpenAIJsonSchema },
+    Text,
+}
+
+impl OpenAIResponseFormat {
+    pub fn into_grammar_constraint(self) -> Result<Option<GrammarConstraint>> {
+        let schema = match self {
+            Self::JsonObject => json!({ "type": "object" }),
+            Self::JsonSchema { json_schema } => json_schema.schema,
+            Self::Text => return Ok(None),
+        };
+
+        Ok(Some(GrammarConstraint::JsonSchema {
+            schema: serde_json::to_string(&schema)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_schema_becomes_json_schema_grammar() -> Result<()> {
+        let response_format: OpenAIResponseFormat = serde_json::from_value(json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "answer",
+                "schema": {"type": "object", "properties": {"answer": {"type": "string"}}},
+                "strict": true
+            }
+        }))?;
+
+        let Some(GrammarConstraint::JsonSchema { schema }) =
+            response_format.into_grammar_constraint()?
+        else {
+            anyhow::bail!("expected a JSON schema grammar");
+        };
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&schema)?,
+            json!({"type": "object", "properties": {"answer": {"type": "string"}}})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn text_has_no_grammar() -> Result<()> {
+        let response_format: OpenAIResponseFormat =
+            serde_json::from_value(json!({"type": "text"}))?;
+
+        assert!(response_format.into_grammar_constraint()?.is_none());
+
+        Ok(())
+    }
+}
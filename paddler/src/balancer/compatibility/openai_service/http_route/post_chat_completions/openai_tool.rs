@@ -0,0 +1,46 @@
+use paddler_types::request_params::continue_from_conversation_history_params::tool::Tool;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::FunctionCall;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct OpenAIFunction {
+    #[serde(default)]
+    pub description: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub parameters: Option<RawParametersSchema>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum OpenAITool {
+    #[serde(rename = "function")]
+    Function { function: OpenAIFunction },
+}
+
+impl OpenAITool {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Function { function } => &function.name,
+        }
+    }
+}
+
+impl From<OpenAITool> for Tool<RawParametersSchema> {
+    fn from(openai_tool: OpenAITool) -> Self {
+        match openai_tool {
+            OpenAITool::Function { function } => Self::Function(FunctionCall {
+                function: Function {
+                    name: function.name,
+                    description: function.description.unwrap_or_default(),
+                    parameters: function
+                        .parameters
+                        .map_or(Parameters::Empty, Parameters::Schema),
+                },
+            }),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::Context as _;
+use anyhow::Error;
+use paddler_types::tool_call::ToolCall;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct OpenAIToolCallFunction {
+    /// Serialized JSON, as OpenAI sends it back to the client
+    pub arguments: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum OpenAIToolCall {
+    #[serde(rename = "function")]
+    Function {
+        function: OpenAIToolCallFunction,
+        id: String,
+    },
+}
+
+impl TryFrom<&OpenAIToolCall> for ToolCall {
+    type Error = Error;
+
+    fn try_from(openai_tool_call: &OpenAIToolCall) -> Result<Self, Self::Error> {
+        match openai_tool_call {
+            OpenAIToolCall::Function { function, id } => Ok(Self {
+                arguments: serde_json::from_str(&function.arguments)
+                    .with_context(|| format!("Arguments of tool call '{id}' are not valid JSON"))?,
+                id: id.clone(),
+                name: function.name.clone(),
+            }),
+        }
+    }
+}
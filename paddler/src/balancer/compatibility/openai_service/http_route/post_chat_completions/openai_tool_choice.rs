@@ -0,0 +1,51 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+
+use super::openai_tool::OpenAITool;
+
+#[derive(Deserialize)]
+pub struct OpenAINamedFunction {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenAIToolChoiceMode {
+    Auto,
+    None,
+    Required,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIToolChoice {
+    Mode(OpenAIToolChoiceMode),
+    Named { function: OpenAINamedFunction },
+}
+
+impl OpenAIToolChoice {
+    /// Chat templates have no notion of a tool choice, so it is applied by narrowing down the
+    /// tools the model gets to see. `required` still leaves the decision to the model.
+    pub fn select_tools(&self, tools: Vec<OpenAITool>) -> Result<Vec<OpenAITool>> {
+        match self {
+            Self::Mode(OpenAIToolChoiceMode::Auto | OpenAIToolChoiceMode::Required) => Ok(tools),
+            Self::Mode(OpenAIToolChoiceMode::None) => Ok(vec![]),
+            Self::Named { function } => {
+                let selected_tools: Vec<OpenAITool> = tools
+                    .into_iter()
+                    .filter(|tool| tool.name() == function.name)
+                    .collect();
+
+                if selected_tools.is_empty() {
+                    bail!(
+                        "tool_choice names function '{}' which is not one of the tools",
+                        function.name
+                    );
+                }
+
+                Ok(selected_tools)
+            }
+        }
+    }
+}
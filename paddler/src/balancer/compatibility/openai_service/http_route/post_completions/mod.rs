@@ -0,0 +1,615 @@
+mod openai_prompt;
+mod openai_text_completion_request_params;
+
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicUsize;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::post;
+use actix_web::web;
+use actix_web::web::ReqData;
+use async_trait::async_trait;
+use futures::future::join_all;
+use futures::stream::select_all;
+use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::token_logprobs::TokenLogprobs;
+use paddler_types::validates::Validates as _;
+use serde_json::json;
+use tokio_stream::StreamExt as _;
+
+use self::openai_text_completion_request_params::OpenAITextCompletionRequestParams;
+use crate::atomic_value::AtomicValue;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::compatibility::openai_service::app_data::AppData;
+use crate::balancer::compatibility::openai_service::current_timestamp::current_timestamp;
+use crate::balancer::compatibility::openai_service::openai_bad_request::openai_bad_request;
+use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+use crate::balancer::compatibility::openai_service::openai_error_response::openai_error_response;
+use crate::balancer::compatibility::openai_service::openai_finish_reason::openai_finish_reason;
+use crate::balancer::compatibility::openai_service::openai_model_not_found::openai_model_not_found;
+use crate::balancer::compatibility::openai_service::openai_text_completion_logprobs_json::openai_text_completion_logprobs_json;
+use crate::balancer::compatibility::openai_service::openai_usage_json::openai_usage_json;
+use crate::balancer::compatibility::openai_service::shared_token_usage::SharedTokenUsage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::http_stream_from_transform_results::http_stream_from_transform_results;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+use crate::balancer::unbounded_stream_from_agent::unbounded_stream_from_agent;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Raw prompts are completed without reasoning markers or tool parsing, so apart from the
+/// failures only the tokens, their logprobs, the usage and the finish reason are left to transform.
+fn transform_unexpected_message(message: OutgoingMessage) -> TransformResult {
+    match message {
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::ChatTemplateError(description)
+                    | GeneratedTokenResult::GrammarIncompatibleWithThinking(description)
+                    | GeneratedTokenResult::GrammarRejectedModelOutput(description)
+                    | GeneratedTokenResult::GrammarInitializationFailed(description)
+                    | GeneratedTokenResult::GrammarSyntaxError(description)
+                    | GeneratedTokenResult::ImageDecodingFailed(description)
+                    | GeneratedTokenResult::InvalidSamplingParameters(description)
+                    | GeneratedTokenResult::MultimodalNotSupported(description)
+                    | GeneratedTokenResult::SamplerError(description),
+                ),
+            ..
+        })
+        | OutgoingMessage::Error(ErrorEnvelope {
+            error: paddler_types::jsonrpc::Error { description, .. },
+            ..
+        }) => TransformResult::Error(openai_error_json("server_error", &description).to_string()),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(GeneratedTokenResult::ContextOverflow(
+                    context_overflow,
+                )),
+            ..
+        }) => TransformResult::Error(
+            openai_error_json("invalid_request_error", &context_overflow.to_string()).to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Timeout,
+            ..
+        }) => TransformResult::Error(openai_error_json("timeout", "request timed out").to_string()),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::TooManyBufferedRequests,
+            ..
+        }) => TransformResult::Error(
+            openai_error_json("rate_limit_error", "too many buffered requests").to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Embedding(_) | OutgoingResponse::Rerank(_),
+            ..
+        }) => TransformResult::Error(
+            openai_error_json(
+                "invalid_request_error",
+                "unexpected embedding response in completions",
+            )
+            .to_string(),
+        ),
+        OutgoingMessage::Response(ResponseEnvelope {
+            response:
+                OutgoingResponse::GeneratedToken(
+                    GeneratedTokenResult::Done(_)
+                    | GeneratedTokenResult::ReasoningToken(_)
+                    | GeneratedTokenResult::Seed(_)
+                    | GeneratedTokenResult::Token(_)
+                    | GeneratedTokenResult::TokenLogprobs(_)
+                    | GeneratedTokenResult::ToolCall(_)
+                    | GeneratedTokenResult::Usage(_),
+                ),
+            ..
+        }) => TransformResult::Discard,
+    }
+}
+
+#[derive(Clone)]
+struct OpenAITextCompletionStreamingTransformer {
+    choice_index: usize,
+    /// Shared by the chunks of every choice
+    completion_id: String,
+    /// Adds a chunk with the token usage and no choices after the last choice finishes
+    include_usage: bool,
+    model: String,
+    /// Characters of the choice text streamed so far, including the echoed prompt
+    text_offset: Arc<AtomicValue<AtomicUsize>>,
+    token_usage: SharedTokenUsage,
+}
+
+impl OpenAITextCompletionStreamingTransformer {
+    fn logprobs_chunk(&self, token_logprobs: TokenLogprobs) -> serde_json::Value {
+        let mut chunk = self.text_chunk("", None);
+
+        chunk["choices"][0]["logprobs"] =
+            openai_text_completion_logprobs_json(&[token_logprobs], self.text_offset.get());
+
+        chunk
+    }
+
+    fn text_chunk(&self, text: &str, finish_reason: Option<&str>) -> serde_json::Value {
+        json!({
+            "id": self.completion_id,
+            "object": "text_completion",
+            "created": current_timestamp(),
+            "model": self.model,
+            "choices": [
+                {
+                    "text": text,
+                    "index": self.choice_index,
+                    "logprobs": null,
+                    "finish_reason": finish_reason
+                }
+            ]
+        })
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAITextCompletionStreamingTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => {
+                let finish_chunk = serde_json::to_string(
+                    &self.text_chunk("", Some(openai_finish_reason(&finish_reason, false))),
+                )?;
+
+                if !self.include_usage || !self.token_usage.finish_choice() {
+                    return Ok(TransformResult::Chunk(finish_chunk));
+                }
+
+                let usage_chunk = serde_json::to_string(&json!({
+                    "id": self.completion_id,
+                    "object": "text_completion",
+                    "created": current_timestamp(),
+                    "model": self.model,
+                    "choices": [],
+                    "usage": openai_usage_json(
+                        &self.token_usage.get_token_usage().unwrap_or_default()
+                    )
+                }))?;
+
+                Ok(TransformResult::Chunk(format!(
+                    "{finish_chunk}\n{usage_chunk}"
+                )))
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => {
+                self.token_usage.add_token_usage(token_usage);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => {
+                self.text_offset.increment_by(token.chars().count());
+
+                Ok(TransformResult::Chunk(serde_json::to_string(
+                    &self.text_chunk(&token, None),
+                )?))
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprobs(
+                        token_logprobs,
+                    )),
+                ..
+            }) => Ok(TransformResult::Chunk(serde_json::to_string(
+                &self.logprobs_chunk(token_logprobs),
+            )?)),
+            message => Ok(transform_unexpected_message(message)),
+        }
+    }
+}
+
+/// Clones share the finish reason, the logprobs and the token usage, so they can be read back
+/// once the whole response is collected.
+#[derive(Clone)]
+struct OpenAITextCompletionCombinedTransformer {
+    finish_reason: Arc<RwLock<Option<FinishReason>>>,
+    token_logprobs: Arc<RwLock<Vec<TokenLogprobs>>>,
+    token_usage: SharedTokenUsage,
+}
+
+impl OpenAITextCompletionCombinedTransformer {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_token_logprobs(&self, token_logprobs: TokenLogprobs) {
+        let mut lock = self
+            .token_logprobs
+            .write()
+            .expect("Failed to acquire write lock on token logprobs");
+
+        lock.push(token_logprobs);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_finish_reason(&self) -> Option<FinishReason> {
+        let lock = self
+            .finish_reason
+            .read()
+            .expect("Failed to acquire read lock on finish reason");
+
+        lock.clone()
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn set_finish_reason(&self, finish_reason: FinishReason) {
+        let mut lock = self
+            .finish_reason
+            .write()
+            .expect("Failed to acquire write lock on finish reason");
+
+        *lock = Some(finish_reason);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn get_token_logprobs(&self) -> Vec<TokenLogprobs> {
+        let lock = self
+            .token_logprobs
+            .read()
+            .expect("Failed to acquire read lock on token logprobs");
+
+        lock.clone()
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAITextCompletionCombinedTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(finish_reason)),
+                ..
+            }) => {
+                self.set_finish_reason(finish_reason);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Usage(token_usage)),
+                ..
+            }) => {
+                self.token_usage.add_token_usage(token_usage);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+                ..
+            }) => Ok(TransformResult::Chunk(token)),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprobs(
+                        token_logprobs,
+                    )),
+                ..
+            }) => {
+                self.add_token_logprobs(token_logprobs);
+
+                Ok(TransformResult::Discard)
+            }
+            message => Ok(transform_unexpected_message(message)),
+        }
+    }
+}
+
+#[post("/v1/completions")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    openai_params: web::Json<OpenAITextCompletionRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let openai_params = openai_params.into_inner();
+    let echo = openai_params.echo;
+    let include_usage = openai_params.include_usage();
+    let model = openai_params.model.clone();
+    let stream = openai_params.stream.unwrap_or(false);
+    let paddler_params_list = match openai_params.into_paddler_params() {
+        Ok(paddler_params_list) => paddler_params_list,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+
+    // Every prompt shares the same sampling parameters
+    if let Some(sampling) = paddler_params_list
+        .first()
+        .and_then(|paddler_params| paddler_params.sampling.clone())
+        && let Err(err) = sampling.validate()
+    {
+        return Ok(openai_bad_request(&err.to_string()));
+    }
+
+    if let Err(model_not_found) = app_data
+        .buffered_request_manager
+        .ensure_model_is_served(Some(model.as_str()))
+    {
+        return Ok(openai_model_not_found(&model_not_found));
+    }
+
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            paddler_params_list
+                .iter()
+                .map(|paddler_params| paddler_params.dispatch_criteria().estimated_tokens)
+                .fold(0, i32::saturating_add),
+        )?;
+    }
+
+    let completion_id = nanoid!();
+    let token_usage = SharedTokenUsage::with_distinct_prompts(paddler_params_list.len());
+
+    if stream {
+        let mut choice_streams = vec![];
+
+        for (choice_index, paddler_params) in paddler_params_list.into_iter().enumerate() {
+            let transformer = OpenAITextCompletionStreamingTransformer {
+                choice_index,
+                completion_id: completion_id.clone(),
+                include_usage,
+                model: model.clone(),
+                text_offset: Arc::new(AtomicValue::<AtomicUsize>::new(if echo {
+                    paddler_params.raw_prompt.chars().count()
+                } else {
+                    0
+                })),
+                token_usage: token_usage.clone(),
+            };
+            let echo_chunk = if echo {
+                Some(TransformResult::Chunk(
+                    transformer
+                        .text_chunk(&paddler_params.raw_prompt, None)
+                        .to_string(),
+                ))
+            } else {
+                None
+            };
+
+            choice_streams.push(Box::pin(futures::stream::iter(echo_chunk).chain(
+                unbounded_stream_from_agent(
+                    app_data.buffered_request_manager.clone(),
+                    fair_share_tenant.clone(),
+                    app_data.inference_service_configuration.clone(),
+                    paddler_params,
+                    transformer,
+                ),
+            )));
+        }
+
+        return Ok(http_stream_from_transform_results(select_all(
+            choice_streams,
+        )));
+    }
+
+    let transformers: Vec<OpenAITextCompletionCombinedTransformer> = paddler_params_list
+        .iter()
+        .map(|_| OpenAITextCompletionCombinedTransformer {
+            finish_reason: Arc::default(),
+            token_logprobs: Arc::default(),
+            token_usage: token_usage.clone(),
+        })
+        .collect();
+    let choices_results: Vec<Vec<TransformResult>> =
+        join_all(paddler_params_list.iter().zip(transformers.iter()).map(
+            |(paddler_params, transformer)| {
+                unbounded_stream_from_agent(
+                    app_data.buffered_request_manager.clone(),
+                    fair_share_tenant.clone(),
+                    app_data.inference_service_configuration.clone(),
+                    paddler_params.clone(),
+                    transformer.clone(),
+                )
+                .collect()
+            },
+        ))
+        .await;
+
+    if let Some(TransformResult::Error(error_json)) = choices_results
+        .iter()
+        .flatten()
+        .find(|result| matches!(result, TransformResult::Error(_)))
+    {
+        return Ok(openai_error_response(error_json.clone()));
+    }
+
+    let choices: Vec<serde_json::Value> = choices_results
+        .into_iter()
+        .zip(paddler_params_list.iter().zip(transformers.iter()))
+        .enumerate()
+        .map(|(choice_index, (results, (paddler_params, transformer)))| {
+            let completion: String = results
+                .into_iter()
+                .filter_map(|result| match result {
+                    TransformResult::Chunk(content) => Some(content),
+                    TransformResult::Discard | TransformResult::Error(_) => None,
+                })
+                .collect();
+            let (text, text_offset) = if echo {
+                (
+                    format!("{}{completion}", paddler_params.raw_prompt),
+                    paddler_params.raw_prompt.chars().count(),
+                )
+            } else {
+                (completion, 0)
+            };
+            let logprobs = if paddler_params.logprobs.is_some() {
+                openai_text_completion_logprobs_json(&transformer.get_token_logprobs(), text_offset)
+            } else {
+                serde_json::Value::Null
+            };
+
+            json!({
+              "text": text,
+              "index": choice_index,
+              "logprobs": logprobs,
+              "finish_reason": transformer
+                .get_finish_reason()
+                .as_ref()
+                .map_or("stop", |finish_reason| openai_finish_reason(finish_reason, false))
+            })
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(json!({
+      "id": completion_id,
+      "object": "text_completion",
+      "created": current_timestamp(),
+      "model": model,
+      "choices": choices,
+      "usage": openai_usage_json(&token_usage.get_token_usage().unwrap_or_default())
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use paddler_types::token_logprob::TokenLogprob;
+    use paddler_types::token_usage::TokenUsage;
+
+    use super::*;
+
+    fn make_token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::GeneratedToken(token_result),
+        })
+    }
+
+    fn make_streaming_transformer(
+        choice_index: usize,
+        token_usage: SharedTokenUsage,
+    ) -> OpenAITextCompletionStreamingTransformer {
+        OpenAITextCompletionStreamingTransformer {
+            choice_index,
+            completion_id: "test-completion".to_owned(),
+            include_usage: true,
+            model: "test-model".to_owned(),
+            text_offset: Arc::new(AtomicValue::<AtomicUsize>::new(0)),
+            token_usage,
+        }
+    }
+
+    #[actix_web::test]
+    async fn streams_tokens_as_text_completion_chunks() -> Result<()> {
+        let transformer = make_streaming_transformer(1, SharedTokenUsage::default());
+
+        let TransformResult::Chunk(chunk) = transformer
+            .transform(make_token_message(GeneratedTokenResult::Token(
+                "Hello".to_owned(),
+            )))
+            .await?
+        else {
+            anyhow::bail!("expected TransformResult::Chunk");
+        };
+        let chunk: serde_json::Value = serde_json::from_str(&chunk)?;
+
+        assert_eq!(chunk["object"], "text_completion");
+        assert_eq!(chunk["choices"][0]["text"], "Hello");
+        assert_eq!(chunk["choices"][0]["index"], 1);
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streams_logprobs_at_the_offset_of_their_token() -> Result<()> {
+        let transformer = make_streaming_transformer(0, SharedTokenUsage::default());
+        let token_logprob = TokenLogprob {
+            bytes: b" world".to_vec(),
+            id: 1917,
+            logprob: -0.5,
+            token: " world".to_owned(),
+        };
+
+        transformer
+            .transform(make_token_message(GeneratedTokenResult::Token(
+                "Hello".to_owned(),
+            )))
+            .await?;
+
+        let TransformResult::Chunk(chunk) = transformer
+            .transform(make_token_message(GeneratedTokenResult::TokenLogprobs(
+                TokenLogprobs {
+                    sampled: token_logprob.clone(),
+                    top_logprobs: vec![token_logprob],
+                },
+            )))
+            .await?
+        else {
+            anyhow::bail!("expected TransformResult::Chunk");
+        };
+        let chunk: serde_json::Value = serde_json::from_str(&chunk)?;
+
+        assert_eq!(chunk["choices"][0]["text"], "");
+        assert_eq!(
+            chunk["choices"][0]["logprobs"],
+            json!({
+                "tokens": [" world"],
+                "token_logprobs": [-0.5],
+                "top_logprobs": [{" world": -0.5}],
+                "text_offset": [5],
+            })
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn usage_chunk_adds_up_prompt_tokens_of_every_prompt() -> Result<()> {
+        let token_usage = SharedTokenUsage::with_distinct_prompts(2);
+        let first_choice = make_streaming_transformer(0, token_usage.clone());
+        let second_choice = make_streaming_transformer(1, token_usage);
+
+        for (transformer, prompt_tokens) in [(&first_choice, 4), (&second_choice, 6)] {
+            transformer
+                .transform(make_token_message(GeneratedTokenResult::Usage(
+                    TokenUsage {
+                        completion_tokens: 2,
+                        prompt_tokens,
+                    },
+                )))
+                .await?;
+        }
+
+        first_choice
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::MaxTokens,
+            )))
+            .await?;
+
+        let TransformResult::Chunk(chunk) = second_choice
+            .transform(make_token_message(GeneratedTokenResult::Done(
+                FinishReason::Eos,
+            )))
+            .await?
+        else {
+            anyhow::bail!("expected TransformResult::Chunk");
+        };
+
+        assert!(chunk.contains("\"prompt_tokens\":10"));
+        assert!(chunk.contains("\"completion_tokens\":4"));
+
+        Ok(())
+    }
+}
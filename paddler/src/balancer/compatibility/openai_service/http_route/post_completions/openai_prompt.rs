@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIPrompt {
+    Multiple(Vec<String>),
+    Single(String),
+}
+
+impl From<OpenAIPrompt> for Vec<String> {
+    fn from(openai_prompt: OpenAIPrompt) -> Self {
+        match openai_prompt {
+            OpenAIPrompt::Multiple(prompts) => prompts,
+            OpenAIPrompt::Single(prompt) => vec![prompt],
+        }
+    }
+}
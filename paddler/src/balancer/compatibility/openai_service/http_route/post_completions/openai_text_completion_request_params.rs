@@ -0,0 +1,184 @@
+use anyhow::Result;
+use anyhow::bail;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::truncation_policy::TruncationPolicy;
+use serde::Deserialize;
+
+use super::openai_prompt::OpenAIPrompt;
+use crate::balancer::compatibility::openai_service::openai_stop::OpenAIStop;
+use crate::balancer::compatibility::openai_service::openai_stream_options::OpenAIStreamOptions;
+
+/// Same default as the OpenAI API
+const DEFAULT_MAX_TOKENS: i32 = 16;
+/// Same limit as the OpenAI API
+const MAX_LOGPROBS: u32 = 5;
+
+#[derive(Deserialize)]
+pub struct OpenAITextCompletionRequestParams {
+    /// Returns the prompt in front of the completion
+    #[serde(default)]
+    pub echo: bool,
+    #[serde(default)]
+    pub frequency_penalty: Option<f32>,
+    /// Number of most likely alternatives to list along with the logprob of every token
+    #[serde(default)]
+    pub logprobs: Option<u32>,
+    #[serde(default)]
+    pub max_tokens: Option<i32>,
+    /// Picks the model pool of that name; any value is served by the default model while no
+    /// model pools are configured.
+    pub model: String,
+    #[serde(default)]
+    pub presence_penalty: Option<f32>,
+    /// Each prompt is completed as a separate choice
+    pub prompt: OpenAIPrompt,
+    #[serde(default)]
+    pub seed: Option<u32>,
+    /// `flex` requests can wait, so they are buffered with the batch priority
+    #[serde(default)]
+    pub service_tier: Option<String>,
+    #[serde(default)]
+    pub stop: Option<OpenAIStop>,
+    #[serde(default)]
+    pub stream: Option<bool>,
+    #[serde(default)]
+    pub stream_options: Option<OpenAIStreamOptions>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Not in the OpenAI API; lets a prompt that overflows the context have its middle cut out
+    /// instead of failing
+    #[serde(default)]
+    pub truncation: Option<TruncationPolicy>,
+}
+
+impl OpenAITextCompletionRequestParams {
+    pub fn include_usage(&self) -> bool {
+        self.stream_options
+            .as_ref()
+            .is_some_and(|stream_options| stream_options.include_usage)
+    }
+
+    pub fn into_paddler_params(self) -> Result<Vec<ContinueFromRawPromptParams>> {
+        let prompts = Vec::<String>::from(self.prompt);
+
+        if prompts.is_empty() {
+            bail!("prompt must not be empty");
+        }
+
+        if let Some(logprobs) = self.logprobs
+            && logprobs > MAX_LOGPROBS
+        {
+            bail!("logprobs must be at most {MAX_LOGPROBS}, got {logprobs}");
+        }
+
+        let sampling = SamplingParameters {
+            penalty_frequency: self.frequency_penalty,
+            penalty_presence: self.presence_penalty,
+            seed: self.seed,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            ..SamplingParameters::default()
+        };
+        let paddler_params = ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: self.logprobs,
+            max_tokens: self.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            model: Some(self.model),
+            priority: if self.service_tier.as_deref() == Some("flex") {
+                RequestPriority::Batch
+            } else {
+                RequestPriority::Interactive
+            },
+            raw_prompt: String::new(),
+            sampling: if sampling == SamplingParameters::default() {
+                None
+            } else {
+                Some(sampling)
+            },
+            stop: self.stop.map(Vec::from).unwrap_or_default(),
+            truncation: self.truncation,
+        };
+
+        Ok(prompts
+            .into_iter()
+            .map(|raw_prompt| ContinueFromRawPromptParams {
+                raw_prompt,
+                ..paddler_params.clone()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_each_prompt_to_raw_prompt_request() -> Result<()> {
+        let openai_params: OpenAITextCompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "prompt": ["Once upon a time", "The capital of France is"],
+            "max_tokens": 5,
+            "stop": "\n",
+            "echo": true
+        }))?;
+
+        assert!(openai_params.echo);
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params.len(), 2);
+        assert_eq!(paddler_params[1].raw_prompt, "The capital of France is");
+        assert_eq!(paddler_params[1].max_tokens, 5);
+        assert_eq!(paddler_params[1].stop, vec!["\n".to_owned()]);
+        assert_eq!(paddler_params[1].model.as_deref(), Some("test-model"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn defaults_to_openai_max_tokens() -> Result<()> {
+        let openai_params: OpenAITextCompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "prompt": "Hello"
+        }))?;
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params[0].max_tokens, DEFAULT_MAX_TOKENS);
+        assert_eq!(paddler_params[0].sampling, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_empty_prompt_list() -> Result<()> {
+        let openai_params: OpenAITextCompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "prompt": []
+        }))?;
+
+        assert!(openai_params.into_paddler_params().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_more_logprobs_than_openai_allows() -> Result<()> {
+        let openai_params: OpenAITextCompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "prompt": "Hello",
+            "logprobs": MAX_LOGPROBS + 1
+        }))?;
+
+        assert!(openai_params.into_paddler_params().is_err());
+
+        Ok(())
+    }
+}
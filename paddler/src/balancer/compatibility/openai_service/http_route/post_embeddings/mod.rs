@@ -0,0 +1,321 @@
+mod openai_embedding_input;
+mod openai_embedding_request_params;
+mod openai_encoding_format;
+
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::post;
+use actix_web::web;
+use actix_web::web::ReqData;
+use anyhow::Result;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use paddler_types::api_key::ApiKey;
+use paddler_types::embedding::Embedding;
+use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::normalization::l2;
+use serde_json::json;
+
+use self::openai_embedding_request_params::OpenAIEmbeddingRequestParams;
+use self::openai_encoding_format::OpenAIEncodingFormat;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::collect_embedding_chunks_from_agents::collect_embedding_chunks_from_agents;
+use crate::balancer::compatibility::openai_service::app_data::AppData;
+use crate::balancer::compatibility::openai_service::openai_bad_request::openai_bad_request;
+use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+use crate::balancer::compatibility::openai_service::openai_error_response::openai_error_response;
+use crate::balancer::compatibility::openai_service::openai_model_not_found::openai_model_not_found;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::provides_dispatch_criteria::CHARACTERS_PER_TOKEN_APPROXIMATELY;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Clones share the embeddings, so they can be read back once every chunk of the batch is
+/// collected.
+#[derive(Clone, Default)]
+struct OpenAIEmbeddingCollector {
+    embeddings: Arc<RwLock<Vec<Embedding>>>,
+}
+
+impl OpenAIEmbeddingCollector {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_embedding(&self, embedding: Embedding) {
+        let mut lock = self
+            .embeddings
+            .write()
+            .expect("Failed to acquire write lock on embeddings");
+
+        lock.push(embedding);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn take_embeddings(&self) -> Vec<Embedding> {
+        let mut lock = self
+            .embeddings
+            .write()
+            .expect("Failed to acquire write lock on embeddings");
+
+        std::mem::take(&mut *lock)
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAIEmbeddingCollector {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Embedding(embedding)),
+                ..
+            }) => {
+                self.add_embedding(embedding);
+
+                Ok(TransformResult::Discard)
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Done),
+                ..
+            }) => Ok(TransformResult::Discard),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Error(description)),
+                ..
+            })
+            | OutgoingMessage::Error(ErrorEnvelope {
+                error: paddler_types::jsonrpc::Error { description, .. },
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("server_error", &description).to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Timeout,
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("timeout", "request timed out").to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::TooManyBufferedRequests,
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("rate_limit_error", "too many buffered requests").to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(_),
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json(
+                    "invalid_request_error",
+                    "unexpected generated token in embeddings",
+                )
+                .to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Rerank(_),
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json(
+                    "invalid_request_error",
+                    "unexpected rerank response in embeddings",
+                )
+                .to_string(),
+            )),
+        }
+    }
+}
+
+/// Embeddings shortened to fewer dimensions are no longer unit length, so they are normalized
+/// again.
+fn openai_embedding_json(
+    embedding: &[f32],
+    dimensions: Option<usize>,
+    encoding_format: OpenAIEncodingFormat,
+    index: usize,
+) -> Result<serde_json::Value> {
+    let embedding = match dimensions {
+        Some(dimensions) if dimensions > embedding.len() => {
+            return Err(anyhow!(
+                "dimensions must not exceed {} for this model",
+                embedding.len()
+            ));
+        }
+        Some(dimensions) if dimensions < embedding.len() => l2(&embedding[..dimensions]),
+        _ => embedding.to_vec(),
+    };
+
+    Ok(json!({
+        "object": "embedding",
+        "index": index,
+        "embedding": match encoding_format {
+            OpenAIEncodingFormat::Base64 => json!(BASE64_STANDARD.encode(
+                embedding
+                    .iter()
+                    .flat_map(|value| value.to_le_bytes())
+                    .collect::<Vec<u8>>()
+            )),
+            OpenAIEncodingFormat::Float => json!(embedding),
+        }
+    }))
+}
+
+#[post("/v1/embeddings")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    openai_params: web::Json<OpenAIEmbeddingRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let openai_params = openai_params.into_inner();
+    let dimensions = openai_params.dimensions;
+    let encoding_format = openai_params.encoding_format;
+    let model = openai_params.model.clone();
+    let paddler_params = match openai_params.into_paddler_params() {
+        Ok(paddler_params) => paddler_params,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+
+    if let Err(model_not_found) = app_data
+        .buffered_request_manager
+        .ensure_model_is_served(paddler_params.model.as_deref())
+    {
+        return Ok(openai_model_not_found(&model_not_found));
+    }
+
+    let Some(balancer_applicable_state) = app_data
+        .balancer_applicable_state_holder
+        .get_balancer_applicable_state()
+    else {
+        return Ok(HttpResponse::ServiceUnavailable().json(openai_error_json(
+            "server_error",
+            "Balancer applicable state is not yet set",
+        )));
+    };
+    let agent_desired_state =
+        balancer_applicable_state.agent_desired_state_for(paddler_params.model.as_deref());
+
+    if !agent_desired_state.inference_parameters.enable_embeddings {
+        return Ok(HttpResponse::NotImplemented().json(openai_error_json(
+            "invalid_request_error",
+            "Embedding generation is not enabled in the inference parameters",
+        )));
+    }
+
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            paddler_params.dispatch_criteria().estimated_tokens,
+        )?;
+    }
+
+    let collector = OpenAIEmbeddingCollector::default();
+    if let Some(error_json) = collect_embedding_chunks_from_agents(
+        &app_data.buffered_request_manager,
+        fair_share_tenant.as_ref(),
+        &app_data.inference_service_configuration,
+        paddler_params.chunk_by_input_size(
+            agent_desired_state.inference_parameters.batch_n_tokens
+                * CHARACTERS_PER_TOKEN_APPROXIMATELY,
+        ),
+        &collector,
+    )
+    .await
+    {
+        return Ok(openai_error_response(error_json));
+    }
+
+    let mut indexed_embeddings: Vec<(usize, Embedding)> = collector
+        .take_embeddings()
+        .into_iter()
+        .filter_map(|embedding| {
+            let index = embedding.source_document_id.parse().ok()?;
+
+            Some((index, embedding))
+        })
+        .collect();
+
+    indexed_embeddings.sort_by_key(|(index, _)| *index);
+
+    if indexed_embeddings.len() != paddler_params.input_batch.len() {
+        return Ok(HttpResponse::InternalServerError().json(openai_error_json(
+            "server_error",
+            "agents did not return an embedding for every input",
+        )));
+    }
+
+    let prompt_tokens: usize = indexed_embeddings
+        .iter()
+        .map(|(_, embedding)| embedding.input_tokens)
+        .sum();
+    let data = match indexed_embeddings
+        .iter()
+        .map(|(index, embedding)| {
+            openai_embedding_json(&embedding.embedding, dimensions, encoding_format, *index)
+        })
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(data) => data,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+
+    Ok(HttpResponse::Ok().json(json!({
+        "object": "list",
+        "data": data,
+        "model": model,
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "total_tokens": prompt_tokens
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_embedding_is_normalized_again() -> Result<()> {
+        let embedding_json =
+            openai_embedding_json(&[3.0, 4.0], Some(1), OpenAIEncodingFormat::Float, 2)?;
+
+        assert_eq!(
+            embedding_json,
+            json!({"object": "embedding", "index": 2, "embedding": [1.0]})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn base64_embedding_is_little_endian_f32() -> Result<()> {
+        let embedding_json =
+            openai_embedding_json(&[1.0, -2.0], None, OpenAIEncodingFormat::Base64, 0)?;
+
+        let encoded = embedding_json["embedding"]
+            .as_str()
+            .ok_or_else(|| anyhow!("base64 embedding is not a string"))?;
+        let decoded = BASE64_STANDARD.decode(encoded)?;
+
+        assert_eq!(
+            decoded,
+            [1.0_f32.to_le_bytes(), (-2.0_f32).to_le_bytes()].concat()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_more_dimensions_than_the_model_has() {
+        assert!(openai_embedding_json(&[1.0], Some(2), OpenAIEncodingFormat::Float, 0).is_err());
+    }
+}
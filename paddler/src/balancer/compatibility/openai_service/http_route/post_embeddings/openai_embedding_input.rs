@@ -0,0 +1,46 @@
+use paddler_types::embedding_input_document::EmbeddingInputDocument;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIEmbeddingInput {
+    Multiple(Vec<String>),
+    MultipleTokenized(Vec<Vec<i32>>),
+    Single(String),
+    Tokenized(Vec<i32>),
+}
+
+impl OpenAIEmbeddingInput {
+    /// Documents are identified by their position in the input, which is the index of their
+    /// embedding in the response.
+    pub fn into_input_documents(self) -> Vec<EmbeddingInputDocument> {
+        let tokenized_document = |(index, tokens): (usize, Vec<i32>)| EmbeddingInputDocument {
+            content: String::new(),
+            id: index.to_string(),
+            tokens: Some(tokens),
+        };
+
+        match self {
+            Self::Multiple(contents) => contents
+                .into_iter()
+                .enumerate()
+                .map(|(index, content)| EmbeddingInputDocument {
+                    content,
+                    id: index.to_string(),
+                    tokens: None,
+                })
+                .collect(),
+            Self::MultipleTokenized(tokens_list) => tokens_list
+                .into_iter()
+                .enumerate()
+                .map(tokenized_document)
+                .collect(),
+            Self::Single(content) => vec![EmbeddingInputDocument {
+                content,
+                id: "0".to_owned(),
+                tokens: None,
+            }],
+            Self::Tokenized(tokens) => vec![tokenized_document((0, tokens))],
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use anyhow::bail;
+use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
+use serde::Deserialize;
+
+use super::openai_embedding_input::OpenAIEmbeddingInput;
+use super::openai_encoding_format::OpenAIEncodingFormat;
+
+#[derive(Deserialize)]
+pub struct OpenAIEmbeddingRequestParams {
+    /// Embeddings are cut down to that many leading dimensions and normalized again
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    #[serde(default)]
+    pub encoding_format: OpenAIEncodingFormat,
+    pub input: OpenAIEmbeddingInput,
+    /// Picks the model pool of that name; any value is served by the default model while no
+    /// model pools are configured.
+    pub model: String,
+}
+
+impl OpenAIEmbeddingRequestParams {
+    pub fn into_paddler_params(self) -> Result<GenerateEmbeddingBatchParams> {
+        if self.dimensions == Some(0) {
+            bail!("dimensions must be at least 1");
+        }
+
+        let input_batch = self.input.into_input_documents();
+
+        if input_batch.is_empty() {
+            bail!("input must not be empty");
+        }
+
+        if input_batch.iter().any(|document| {
+            document
+                .tokens
+                .as_ref()
+                .map_or(document.content.is_empty(), Vec::is_empty)
+        }) {
+            bail!("input must not contain empty strings or token arrays");
+        }
+
+        Ok(GenerateEmbeddingBatchParams {
+            input_batch,
+            model: Some(self.model),
+            normalization_method: EmbeddingNormalizationMethod::L2,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_string_input_to_l2_normalized_document() -> Result<()> {
+        let openai_params: OpenAIEmbeddingRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "input": "The food was delicious",
+            "user": "ignored"
+        }))?;
+
+        assert_eq!(openai_params.encoding_format, OpenAIEncodingFormat::Float);
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params.input_batch.len(), 1);
+        assert_eq!(
+            paddler_params.input_batch[0].content,
+            "The food was delicious"
+        );
+        assert_eq!(paddler_params.input_batch[0].id, "0");
+        assert!(matches!(
+            paddler_params.normalization_method,
+            EmbeddingNormalizationMethod::L2
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn maps_token_arrays_to_tokenized_documents() -> Result<()> {
+        let openai_params: OpenAIEmbeddingRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "input": [[1, 2, 3], [4, 5]],
+            "encoding_format": "base64"
+        }))?;
+
+        assert_eq!(openai_params.encoding_format, OpenAIEncodingFormat::Base64);
+
+        let paddler_params = openai_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params.input_batch.len(), 2);
+        assert_eq!(paddler_params.input_batch[1].id, "1");
+        assert_eq!(paddler_params.input_batch[1].tokens, Some(vec![4, 5]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_empty_input() -> Result<()> {
+        let openai_params: OpenAIEmbeddingRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "input": ["hello", ""]
+        }))?;
+
+        assert!(openai_params.into_paddler_params().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAIEncodingFormat {
+    /// Little-endian `f32` values, base64 encoded
+    Base64,
+    #[default]
+    Float,
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+/// Cohere takes documents as plain strings, Jina also accepts them as objects
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum CohereRerankDocument {
+    Object { text: String },
+    Text(String),
+}
+
+impl CohereRerankDocument {
+    pub fn into_text(self) -> String {
+        match self {
+            Self::Object { text } | Self::Text(text) => text,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use paddler_types::request_params::RerankParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rerank_document::RerankDocument;
+use paddler_types::validates::Validates as _;
+use serde::Deserialize;
+
+use super::cohere_rerank_document::CohereRerankDocument;
+
+#[derive(Deserialize)]
+pub struct CohereRerankRequestParams {
+    pub documents: Vec<CohereRerankDocument>,
+    /// Picks the model pool of that name; any value is served by the default model while no
+    /// model pools are configured.
+    pub model: String,
+    pub query: String,
+    /// Includes the text of every document in its result
+    #[serde(default)]
+    pub return_documents: bool,
+    #[serde(default)]
+    pub top_n: Option<usize>,
+}
+
+impl CohereRerankRequestParams {
+    /// Documents are identified by their position in the request, which is the index of their
+    /// result in the response. Results are always sorted from the most relevant document.
+    pub fn into_paddler_params(self) -> Result<RerankParams> {
+        RerankParams {
+            documents: self
+                .documents
+                .into_iter()
+                .enumerate()
+                .map(|(index, document)| RerankDocument {
+                    content: document.into_text(),
+                    id: index.to_string(),
+                })
+                .collect(),
+            model: Some(self.model),
+            priority: RequestPriority::Interactive,
+            query: self.query,
+            sort_by_score: true,
+            top_n: self.top_n,
+        }
+        .validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_documents_of_either_shape_in_order() -> Result<()> {
+        let cohere_params: CohereRerankRequestParams = serde_json::from_value(json!({
+            "model": "reranker",
+            "query": "What is the capital of France?",
+            "documents": ["Berlin is in Germany", {"text": "Paris is in France"}],
+            "top_n": 1
+        }))?;
+
+        assert!(!cohere_params.return_documents);
+
+        let paddler_params = cohere_params.into_paddler_params()?;
+
+        assert_eq!(paddler_params.documents[0].content, "Berlin is in Germany");
+        assert_eq!(paddler_params.documents[1].content, "Paris is in France");
+        assert_eq!(paddler_params.documents[1].id, "1");
+        assert_eq!(paddler_params.top_n, Some(1));
+        assert!(paddler_params.is_sorted());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_empty_documents() -> Result<()> {
+        let cohere_params: CohereRerankRequestParams = serde_json::from_value(json!({
+            "model": "reranker",
+            "query": "query",
+            "documents": []
+        }))?;
+
+        assert!(cohere_params.into_paddler_params().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+mod cohere_rerank_document;
+mod cohere_rerank_request_params;
+
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::post;
+use actix_web::web;
+use actix_web::web::ReqData;
+use anyhow::Result;
+use async_trait::async_trait;
+use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
+use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::pooling_type::PoolingType;
+use paddler_types::request_params::RerankParams;
+use paddler_types::rerank_score::RerankScore;
+use serde_json::json;
+
+use self::cohere_rerank_request_params::CohereRerankRequestParams;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::collect_embedding_chunks_from_agents::collect_embedding_chunks_from_agents;
+use crate::balancer::compatibility::openai_service::app_data::AppData;
+use crate::balancer::compatibility::openai_service::openai_bad_request::openai_bad_request;
+use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+use crate::balancer::compatibility::openai_service::openai_error_response::openai_error_response;
+use crate::balancer::compatibility::openai_service::openai_model_not_found::openai_model_not_found;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::provides_dispatch_criteria::CHARACTERS_PER_TOKEN_APPROXIMATELY;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Clones share the scores, so they can be ranked once every chunk of the batch is scored.
+#[derive(Clone, Default)]
+struct CohereRerankScoreCollector {
+    scores: Arc<RwLock<Vec<RerankScore>>>,
+}
+
+impl CohereRerankScoreCollector {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn add_score(&self, score: RerankScore) {
+        let mut lock = self
+            .scores
+            .write()
+            .expect("Failed to acquire write lock on rerank scores");
+
+        lock.push(score);
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn take_scores(&self) -> Vec<RerankScore> {
+        let mut lock = self
+            .scores
+            .write()
+            .expect("Failed to acquire write lock on rerank scores");
+
+        std::mem::take(&mut *lock)
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for CohereRerankScoreCollector {
+    async fn transform(&self, message: OutgoingMessage) -> anyhow::Result<TransformResult> {
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Embedding(embedding)),
+                ..
+            }) => match RerankScore::try_from(embedding) {
+                Ok(score) => {
+                    self.add_score(score);
+
+                    Ok(TransformResult::Discard)
+                }
+                Err(err) => Ok(TransformResult::Error(
+                    openai_error_json("server_error", &format!("{err:#}")).to_string(),
+                )),
+            },
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Done),
+                ..
+            }) => Ok(TransformResult::Discard),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Error(description)),
+                ..
+            })
+            | OutgoingMessage::Error(ErrorEnvelope {
+                error: paddler_types::jsonrpc::Error { description, .. },
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("server_error", &description).to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Timeout,
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("timeout", "request timed out").to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::TooManyBufferedRequests,
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json("rate_limit_error", "too many buffered requests").to_string(),
+            )),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::GeneratedToken(_) | OutgoingResponse::Rerank(_),
+                ..
+            }) => Ok(TransformResult::Error(
+                openai_error_json(
+                    "invalid_request_error",
+                    "unexpected response to a rerank request",
+                )
+                .to_string(),
+            )),
+        }
+    }
+}
+
+/// Cohere and Jina both identify results by the index of their document in the request
+fn cohere_rerank_results_json(
+    params: &RerankParams,
+    scores: Vec<RerankScore>,
+    return_documents: bool,
+) -> Vec<serde_json::Value> {
+    params
+        .rank(scores)
+        .into_iter()
+        .filter_map(|score| {
+            let index: usize = score.document_id.parse().ok()?;
+            let mut result = json!({
+                "index": index,
+                "relevance_score": score.score,
+            });
+
+            if return_documents {
+                result["document"] = json!({
+                    "text": params.documents.get(index)?.content,
+                });
+            }
+
+            Some(result)
+        })
+        .collect()
+}
+
+#[post("/v1/rerank")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    cohere_params: web::Json<CohereRerankRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let cohere_params = cohere_params.into_inner();
+    let model = cohere_params.model.clone();
+    let return_documents = cohere_params.return_documents;
+    let paddler_params = match cohere_params.into_paddler_params() {
+        Ok(paddler_params) => paddler_params,
+        Err(err) => return Ok(openai_bad_request(&err.to_string())),
+    };
+
+    if let Err(model_not_found) = app_data
+        .buffered_request_manager
+        .ensure_model_is_served(paddler_params.model.as_deref())
+    {
+        return Ok(openai_model_not_found(&model_not_found));
+    }
+
+    let Some(balancer_applicable_state) = app_data
+        .balancer_applicable_state_holder
+        .get_balancer_applicable_state()
+    else {
+        return Ok(HttpResponse::ServiceUnavailable().json(openai_error_json(
+            "server_error",
+            "Balancer applicable state is not yet set",
+        )));
+    };
+    let agent_desired_state =
+        balancer_applicable_state.agent_desired_state_for(paddler_params.model.as_deref());
+
+    if !agent_desired_state.inference_parameters.enable_embeddings
+        || agent_desired_state.inference_parameters.pooling_type != PoolingType::Rank
+    {
+        return Ok(HttpResponse::NotImplemented().json(openai_error_json(
+            "invalid_request_error",
+            "Reranking requires embeddings to be enabled with the rank pooling type in the inference parameters",
+        )));
+    }
+
+    let embedding_batch_params = paddler_params.to_embedding_batch_params();
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            embedding_batch_params.dispatch_criteria().estimated_tokens,
+        )?;
+    }
+
+    let collector = CohereRerankScoreCollector::default();
+    if let Some(error_json) = collect_embedding_chunks_from_agents(
+        &app_data.buffered_request_manager,
+        fair_share_tenant.as_ref(),
+        &app_data.inference_service_configuration,
+        embedding_batch_params.chunk_by_input_size(
+            agent_desired_state.inference_parameters.batch_n_tokens
+                * CHARACTERS_PER_TOKEN_APPROXIMATELY,
+        ),
+        &collector,
+    )
+    .await
+    {
+        return Ok(openai_error_response(error_json));
+    }
+
+    let scores = collector.take_scores();
+
+    if scores.len() != paddler_params.documents.len() {
+        return Ok(HttpResponse::InternalServerError().json(openai_error_json(
+            "server_error",
+            "agents did not return a score for every document",
+        )));
+    }
+
+    let total_tokens: usize = scores.iter().map(|score| score.input_tokens).sum();
+
+    Ok(HttpResponse::Ok().json(json!({
+        "id": format!("rerank-{}", nanoid!()),
+        "model": model,
+        "results": cohere_rerank_results_json(&paddler_params, scores, return_documents),
+        "usage": {
+            "total_tokens": total_tokens
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::request_priority::RequestPriority;
+    use paddler_types::rerank_document::RerankDocument;
+
+    use super::*;
+
+    fn make_params(top_n: Option<usize>) -> RerankParams {
+        RerankParams {
+            documents: vec![
+                RerankDocument {
+                    content: "Berlin is in Germany".to_owned(),
+                    id: "0".to_owned(),
+                },
+                RerankDocument {
+                    content: "Paris is in France".to_owned(),
+                    id: "1".to_owned(),
+                },
+            ],
+            model: None,
+            priority: RequestPriority::Interactive,
+            query: "What is the capital of France?".to_owned(),
+            sort_by_score: true,
+            top_n,
+        }
+    }
+
+    fn score(document_id: &str, score: f32) -> RerankScore {
+        RerankScore {
+            document_id: document_id.to_owned(),
+            input_tokens: 10,
+            score,
+        }
+    }
+
+    #[test]
+    fn results_are_ranked_and_indexed_by_document_position() {
+        let results = cohere_rerank_results_json(
+            &make_params(None),
+            vec![score("0", -2.0), score("1", 4.0)],
+            false,
+        );
+
+        assert_eq!(
+            results,
+            vec![
+                json!({"index": 1, "relevance_score": 4.0}),
+                json!({"index": 0, "relevance_score": -2.0}),
+            ]
+        );
+    }
+
+    #[test]
+    fn results_include_documents_on_request() {
+        let results = cohere_rerank_results_json(
+            &make_params(Some(1)),
+            vec![score("0", -2.0), score("1", 4.0)],
+            true,
+        );
+
+        assert_eq!(
+            results,
+            vec![json!({
+                "index": 1,
+                "relevance_score": 4.0,
+                "document": {"text": "Paris is in France"}
+            })]
+        );
+    }
+}
@@ -1,25 +1,46 @@
 pub mod app_data;
 pub mod configuration;
+mod current_timestamp;
 pub mod http_route;
+mod openai_bad_request;
+mod openai_error_json;
+mod openai_error_response;
+mod openai_finish_reason;
+mod openai_logprob_json;
+mod openai_model_not_found;
+mod openai_stop;
+mod openai_stream_options;
+mod openai_text_completion_logprobs_json;
+mod openai_usage_json;
+mod shared_token_usage;
 
 use std::sync::Arc;
 
 use actix_web::App;
 use actix_web::HttpServer;
+use actix_web::middleware::from_fn;
 use actix_web::web::Data;
 use anyhow::Result;
 use async_trait::async_trait;
+use paddler_types::api_key_scope::ApiKeyScope;
 use tokio_util::sync::CancellationToken;
 
+use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::balancer::api_key_guard::ApiKeyGuard;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::compatibility::openai_service::app_data::AppData;
 use crate::balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use crate::balancer::http_route as common_http_route;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::create_cors_middleware::create_cors_middleware;
 use crate::service::Service;
 
 pub struct OpenAIService {
+    pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub openai_service_configuration: OpenAIServiceConfiguration,
@@ -39,17 +60,37 @@ impl Service for OpenAIService {
         let cors_allowed_hosts_arc = Arc::new(cors_allowed_hosts);
 
         let app_data = Data::new(AppData {
+            agent_controller_pool: self.agent_controller_pool.clone(),
+            api_key_registry: self.api_key_registry.clone(),
+            balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             inference_service_configuration: self.inference_service_configuration.clone(),
         });
 
+        let api_key_guard = ApiKeyGuard {
+            accepts_api_key_header: false,
+            api_key_registry: self.api_key_registry.clone(),
+            public_path_prefixes: &["/health"],
+            require_api_key: self.openai_service_configuration.require_api_key,
+            scope: ApiKeyScope::Inference,
+        };
+
         #[expect(clippy::expect_used, reason = "server bind failure is unrecoverable")]
         HttpServer::new(move || {
+            let api_key_guard = api_key_guard.clone();
+
             App::new()
+                .wrap(from_fn(move |service_request, next| {
+                    api_key_guard.clone().authorize(service_request, next)
+                }))
                 .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                 .app_data(app_data.clone())
                 .configure(common_http_route::get_health::register)
+                .configure(http_route::get_models::register)
                 .configure(http_route::post_chat_completions::register)
+                .configure(http_route::post_completions::register)
+                .configure(http_route::post_embeddings::register)
+                .configure(http_route::post_rerank::register)
         })
         .shutdown_signal(async move {
             shutdown.cancelled().await;
@@ -0,0 +1,7 @@
+use actix_web::HttpResponse;
+
+use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+
+pub fn openai_bad_request(message: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(openai_error_json("invalid_request_error", message))
+}
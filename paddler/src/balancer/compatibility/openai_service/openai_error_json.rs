@@ -0,0 +1,12 @@
+use serde_json::json;
+
+pub fn openai_error_json(error_type: &str, message: &str) -> serde_json::Value {
+    json!({
+        "error": {
+            "message": message,
+            "type": error_type,
+            "param": null,
+            "code": null
+        }
+    })
+}
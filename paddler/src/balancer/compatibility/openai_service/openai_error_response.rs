@@ -0,0 +1,38 @@
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+
+/// Responds with the status the OpenAI API uses for the type of the error; anything the agents
+/// failed at is a server error
+pub fn openai_error_response(error_json: String) -> HttpResponse {
+    let error_type = serde_json::from_str::<serde_json::Value>(&error_json)
+        .ok()
+        .and_then(|error| error["error"]["type"].as_str().map(ToOwned::to_owned));
+    let status = match error_type.as_deref() {
+        Some("invalid_request_error") => StatusCode::BAD_REQUEST,
+        Some("rate_limit_error") => StatusCode::TOO_MANY_REQUESTS,
+        Some("timeout") => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balancer::compatibility::openai_service::openai_error_json::openai_error_json;
+
+    fn status_of(error_type: &str) -> StatusCode {
+        openai_error_response(openai_error_json(error_type, "message").to_string()).status()
+    }
+
+    #[test]
+    fn maps_error_types_to_statuses() {
+        assert_eq!(status_of("invalid_request_error"), StatusCode::BAD_REQUEST);
+        assert_eq!(status_of("rate_limit_error"), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status_of("timeout"), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(status_of("server_error"), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
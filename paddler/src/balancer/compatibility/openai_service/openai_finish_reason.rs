@@ -0,0 +1,12 @@
+use paddler_types::finish_reason::FinishReason;
+
+pub const fn openai_finish_reason(
+    finish_reason: &FinishReason,
+    has_tool_calls: bool,
+) -> &'static str {
+    match finish_reason {
+        FinishReason::Eos | FinishReason::StopSequence(_) if has_tool_calls => "tool_calls",
+        FinishReason::Cancelled | FinishReason::Eos | FinishReason::StopSequence(_) => "stop",
+        FinishReason::Evicted | FinishReason::MaxTokens => "length",
+    }
+}
@@ -0,0 +1,24 @@
+use paddler_types::token_logprob::TokenLogprob;
+use paddler_types::token_logprobs::TokenLogprobs;
+use serde_json::json;
+
+fn openai_top_logprob_json(token_logprob: &TokenLogprob) -> serde_json::Value {
+    json!({
+        "token": token_logprob.token,
+        "logprob": token_logprob.logprob,
+        "bytes": token_logprob.bytes,
+    })
+}
+
+/// Entry of the `logprobs.content` list of a chat completion choice
+pub fn openai_logprob_json(token_logprobs: &TokenLogprobs) -> serde_json::Value {
+    let mut logprob_json = openai_top_logprob_json(&token_logprobs.sampled);
+
+    logprob_json["top_logprobs"] = token_logprobs
+        .top_logprobs
+        .iter()
+        .map(openai_top_logprob_json)
+        .collect();
+
+    logprob_json
+}
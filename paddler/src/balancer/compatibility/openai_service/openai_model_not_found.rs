@@ -0,0 +1,15 @@
+use actix_web::HttpResponse;
+use serde_json::json;
+
+use crate::balancer::model_not_found::ModelNotFound;
+
+pub fn openai_model_not_found(model_not_found: &ModelNotFound) -> HttpResponse {
+    HttpResponse::NotFound().json(json!({
+        "error": {
+            "message": model_not_found.to_string(),
+            "type": "invalid_request_error",
+            "param": "model",
+            "code": "model_not_found"
+        }
+    }))
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIStop {
+    Multiple(Vec<String>),
+    Single(String),
+}
+
+impl From<OpenAIStop> for Vec<String> {
+    fn from(openai_stop: OpenAIStop) -> Self {
+        match openai_stop {
+            OpenAIStop::Multiple(stop_sequences) => stop_sequences,
+            OpenAIStop::Single(stop_sequence) => vec![stop_sequence],
+        }
+    }
+}
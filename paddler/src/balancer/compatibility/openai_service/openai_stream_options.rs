@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct OpenAIStreamOptions {
+    #[serde(default)]
+    pub include_usage: bool,
+}
@@ -0,0 +1,81 @@
+use paddler_types::token_logprobs::TokenLogprobs;
+use serde_json::json;
+
+/// Legacy completions list the logprobs column by column. Text offsets count the characters
+/// of the choice text that come before each token, starting at `text_offset`.
+pub fn openai_text_completion_logprobs_json(
+    token_logprobs_list: &[TokenLogprobs],
+    mut text_offset: usize,
+) -> serde_json::Value {
+    let mut text_offsets = Vec::with_capacity(token_logprobs_list.len());
+
+    for token_logprobs in token_logprobs_list {
+        text_offsets.push(text_offset);
+        text_offset += token_logprobs.sampled.token.chars().count();
+    }
+
+    json!({
+        "tokens": token_logprobs_list
+            .iter()
+            .map(|token_logprobs| &token_logprobs.sampled.token)
+            .collect::<Vec<_>>(),
+        "token_logprobs": token_logprobs_list
+            .iter()
+            .map(|token_logprobs| token_logprobs.sampled.logprob)
+            .collect::<Vec<_>>(),
+        "top_logprobs": token_logprobs_list
+            .iter()
+            .map(|token_logprobs| {
+                token_logprobs
+                    .top_logprobs
+                    .iter()
+                    .map(|top_logprob| (top_logprob.token.clone(), json!(top_logprob.logprob)))
+                    .collect::<serde_json::Map<_, _>>()
+            })
+            .collect::<Vec<_>>(),
+        "text_offset": text_offsets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::token_logprob::TokenLogprob;
+
+    use super::*;
+
+    fn token_logprob(token: &str, logprob: f32) -> TokenLogprob {
+        TokenLogprob {
+            bytes: token.as_bytes().to_vec(),
+            id: 1,
+            logprob,
+            token: token.to_owned(),
+        }
+    }
+
+    #[test]
+    fn lists_logprobs_column_by_column() {
+        let logprobs_json = openai_text_completion_logprobs_json(
+            &[
+                TokenLogprobs {
+                    sampled: token_logprob("Hello", -0.5),
+                    top_logprobs: vec![token_logprob("Hello", -0.5), token_logprob("Hi", -1.0)],
+                },
+                TokenLogprobs {
+                    sampled: token_logprob(" world", -0.25),
+                    top_logprobs: vec![token_logprob(" world", -0.25)],
+                },
+            ],
+            3,
+        );
+
+        assert_eq!(
+            logprobs_json,
+            json!({
+                "tokens": ["Hello", " world"],
+                "token_logprobs": [-0.5, -0.25],
+                "top_logprobs": [{"Hello": -0.5, "Hi": -1.0}, {" world": -0.25}],
+                "text_offset": [3, 8],
+            })
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use paddler_types::token_usage::TokenUsage;
+use serde_json::json;
+
+pub fn openai_usage_json(token_usage: &TokenUsage) -> serde_json::Value {
+    json!({
+        "prompt_tokens": token_usage.prompt_tokens,
+        "completion_tokens": token_usage.completion_tokens,
+        "total_tokens": token_usage.total_tokens(),
+        "prompt_tokens_details": {
+            "cached_tokens": 0,
+            "audio_tokens": 0
+        },
+        "completion_tokens_details": {
+            "reasoning_tokens": 0,
+            "audio_tokens": 0,
+            "accepted_prediction_tokens": 0,
+            "rejected_prediction_tokens": 0
+        }
+    })
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use paddler_types::token_usage::TokenUsage;
+
+#[derive(Default)]
+struct ChoicesTokenUsage {
+    choices_finished: usize,
+    token_usage: Option<TokenUsage>,
+}
+
+/// Usage of each choice arrives right before its finish reason, so clones share it and add it
+/// up until the last choice finishes.
+#[derive(Clone)]
+pub struct SharedTokenUsage {
+    /// Choices generated from different prompts add up their prompt tokens as well
+    adds_up_prompt_tokens: bool,
+    choices_count: usize,
+    choices_token_usage: Arc<RwLock<ChoicesTokenUsage>>,
+}
+
+impl SharedTokenUsage {
+    /// Every choice is generated from the same prompt, so only the completion tokens add up
+    pub fn new(choices_count: usize) -> Self {
+        Self {
+            adds_up_prompt_tokens: false,
+            choices_count,
+            choices_token_usage: Arc::new(RwLock::new(ChoicesTokenUsage::default())),
+        }
+    }
+
+    pub fn with_distinct_prompts(choices_count: usize) -> Self {
+        Self {
+            adds_up_prompt_tokens: true,
+            ..Self::new(choices_count)
+        }
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn add_token_usage(&self, token_usage: TokenUsage) {
+        let mut lock = self
+            .choices_token_usage
+            .write()
+            .expect("Failed to acquire write lock on token usage");
+
+        let current_token_usage = lock.token_usage.clone().unwrap_or_default();
+
+        lock.token_usage = Some(TokenUsage {
+            completion_tokens: current_token_usage.completion_tokens
+                + token_usage.completion_tokens,
+            prompt_tokens: if self.adds_up_prompt_tokens {
+                current_token_usage.prompt_tokens + token_usage.prompt_tokens
+            } else {
+                token_usage.prompt_tokens
+            },
+        });
+    }
+
+    /// Returns true once every choice has finished
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn finish_choice(&self) -> bool {
+        let mut lock = self
+            .choices_token_usage
+            .write()
+            .expect("Failed to acquire write lock on token usage");
+
+        lock.choices_finished += 1;
+
+        lock.choices_finished >= self.choices_count
+    }
+
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    pub fn get_token_usage(&self) -> Option<TokenUsage> {
+        let lock = self
+            .choices_token_usage
+            .read()
+            .expect("Failed to acquire read lock on token usage");
+
+        lock.token_usage.clone()
+    }
+}
+
+impl Default for SharedTokenUsage {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
@@ -0,0 +1,67 @@
+use dashmap::DashMap;
+
+/// Forgetting an affinity only costs the agent a cold KV cache, so the table is simply reset
+/// instead of tracking recency.
+const MAX_REMEMBERED_AFFINITIES: usize = 65_536;
+
+#[derive(Default)]
+pub struct DispatchAffinity {
+    agent_ids: DashMap<u64, String>,
+}
+
+impl DispatchAffinity {
+    pub fn forget_agent(&self, agent_id: &str) {
+        self.agent_ids
+            .retain(|_, remembered_agent_id| remembered_agent_id != agent_id);
+    }
+
+    #[must_use]
+    pub fn get_agent_id(&self, affinity_key: u64) -> Option<String> {
+        self.agent_ids
+            .get(&affinity_key)
+            .map(|entry| entry.value().clone())
+    }
+
+    pub fn remember(&self, affinity_key: u64, agent_id: &str) {
+        if self.agent_ids.len() >= MAX_REMEMBERED_AFFINITIES
+            && !self.agent_ids.contains_key(&affinity_key)
+        {
+            self.agent_ids.clear();
+        }
+
+        self.agent_ids.insert(affinity_key, agent_id.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forgets_only_affinities_of_removed_agent() {
+        let dispatch_affinity = DispatchAffinity::default();
+
+        dispatch_affinity.remember(1, "agent-a");
+        dispatch_affinity.remember(2, "agent-b");
+        dispatch_affinity.forget_agent("agent-a");
+
+        assert_eq!(dispatch_affinity.get_agent_id(1), None);
+        assert_eq!(
+            dispatch_affinity.get_agent_id(2),
+            Some("agent-b".to_owned())
+        );
+    }
+
+    #[test]
+    fn remembers_most_recent_agent() {
+        let dispatch_affinity = DispatchAffinity::default();
+
+        dispatch_affinity.remember(1, "agent-a");
+        dispatch_affinity.remember(1, "agent-b");
+
+        assert_eq!(
+            dispatch_affinity.get_agent_id(1),
+            Some("agent-b".to_owned())
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use paddler_types::request_priority::RequestPriority;
+
+#[derive(Clone, Debug, Default)]
+pub struct DispatchCriteria {
+    /// Requests with the same key prefer the agent that served the key last
+    pub affinity_key: Option<u64>,
+    /// Rough number of tokens (prompt and completion budget) the request is going to occupy
+    pub estimated_tokens: i32,
+    /// Requests are only dispatched to agents serving the model pool of this name
+    pub model: Option<String>,
+    pub priority: RequestPriority,
+    /// Embedding requests are only dispatched to agents that have embeddings enabled
+    pub requires_embeddings: bool,
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+
+pub struct LeastBusy;
+
+impl OrdersDispatchCandidates for LeastBusy {
+    fn dispatch_strategy(&self) -> DispatchStrategy {
+        DispatchStrategy::LeastBusy
+    }
+
+    fn order_candidates(
+        &self,
+        candidates: &mut [Arc<AgentController>],
+        _dispatch_criteria: &DispatchCriteria,
+    ) {
+        candidates.sort_by_key(|agent| agent.slots_processing.get());
+    }
+}
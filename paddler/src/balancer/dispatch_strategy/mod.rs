@@ -0,0 +1,29 @@
+mod least_busy;
+mod least_outstanding_tokens;
+mod power_of_two_choices;
+mod round_robin;
+mod weighted;
+
+use std::sync::Arc;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+use self::least_busy::LeastBusy;
+use self::least_outstanding_tokens::LeastOutstandingTokens;
+use self::power_of_two_choices::PowerOfTwoChoices;
+use self::round_robin::RoundRobin;
+use self::weighted::Weighted;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+
+#[must_use]
+pub fn create_dispatch_strategy(
+    dispatch_strategy: DispatchStrategy,
+) -> Arc<dyn OrdersDispatchCandidates> {
+    match dispatch_strategy {
+        DispatchStrategy::LeastBusy => Arc::new(LeastBusy),
+        DispatchStrategy::LeastOutstandingTokens => Arc::new(LeastOutstandingTokens),
+        DispatchStrategy::PowerOfTwoChoices => Arc::new(PowerOfTwoChoices),
+        DispatchStrategy::RoundRobin => Arc::new(RoundRobin::default()),
+        DispatchStrategy::Weighted => Arc::new(Weighted),
+    }
+}
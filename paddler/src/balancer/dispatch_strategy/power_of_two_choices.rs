@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+use rand::seq::SliceRandom as _;
+
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+
+pub struct PowerOfTwoChoices;
+
+impl OrdersDispatchCandidates for PowerOfTwoChoices {
+    fn dispatch_strategy(&self) -> DispatchStrategy {
+        DispatchStrategy::PowerOfTwoChoices
+    }
+
+    fn order_candidates(
+        &self,
+        candidates: &mut [Arc<AgentController>],
+        _dispatch_criteria: &DispatchCriteria,
+    ) {
+        candidates.shuffle(&mut rand::rng());
+
+        if candidates.len() < 2 {
+            return;
+        }
+
+        if candidates[1].slots_processing.get() < candidates[0].slots_processing.get() {
+            candidates.swap(0, 1);
+        }
+
+        // If neither of the two picks has a free slot, fall back to the least busy of the rest
+        candidates[2..].sort_by_key(|agent| agent.slots_processing.get());
+    }
+}
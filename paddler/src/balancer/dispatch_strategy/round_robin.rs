@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+
+#[derive(Default)]
+pub struct RoundRobin {
+    next_turn: AtomicUsize,
+}
+
+impl OrdersDispatchCandidates for RoundRobin {
+    fn dispatch_strategy(&self) -> DispatchStrategy {
+        DispatchStrategy::RoundRobin
+    }
+
+    fn order_candidates(
+        &self,
+        candidates: &mut [Arc<AgentController>],
+        _dispatch_criteria: &DispatchCriteria,
+    ) {
+        if candidates.is_empty() {
+            return;
+        }
+
+        candidates.sort_by(|left, right| left.id.cmp(&right.id));
+        candidates.rotate_left(self.next_turn.fetch_add(1, Ordering::Relaxed) % candidates.len());
+    }
+}
@@ -0,0 +1,38 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+use crate::balancer::orders_dispatch_candidates::OrdersDispatchCandidates;
+
+fn effective_weight(agent: &AgentController) -> i64 {
+    i64::from(agent.weight.max(1))
+}
+
+fn compare_weighted_load(left: &Arc<AgentController>, right: &Arc<AgentController>) -> Ordering {
+    // slots_processing / weight, compared without dividing
+    let left_load = i64::from(left.slots_processing.get()) * effective_weight(right);
+    let right_load = i64::from(right.slots_processing.get()) * effective_weight(left);
+
+    left_load
+        .cmp(&right_load)
+        .then_with(|| effective_weight(right).cmp(&effective_weight(left)))
+}
+
+pub struct Weighted;
+
+impl OrdersDispatchCandidates for Weighted {
+    fn dispatch_strategy(&self) -> DispatchStrategy {
+        DispatchStrategy::Weighted
+    }
+
+    fn order_candidates(
+        &self,
+        candidates: &mut [Arc<AgentController>],
+        _dispatch_criteria: &DispatchCriteria,
+    ) {
+        candidates.sort_by(compare_weighted_load);
+    }
+}
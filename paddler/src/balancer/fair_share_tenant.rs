@@ -0,0 +1,19 @@
+use paddler_types::api_key::ApiKey;
+
+/// Owner of buffered requests that gets its own share of the queue
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FairShareTenant {
+    pub name: String,
+    pub weight: u32,
+}
+
+impl FairShareTenant {
+    /// Keys without a fair share weight do not form a tenant of their own
+    #[must_use]
+    pub fn from_api_key(api_key: &ApiKey) -> Option<Self> {
+        api_key.fair_share_weight.map(|fair_share_weight| Self {
+            name: api_key.name.clone(),
+            weight: fair_share_weight,
+        })
+    }
+}
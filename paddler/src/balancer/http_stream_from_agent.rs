@@ -1,56 +1,41 @@
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use actix_web::Error;
 use actix_web::HttpResponse;
-use actix_web::http::header;
-use bytes::Bytes;
-use futures::stream::StreamExt;
 use paddler_types::inference_client::Response as OutgoingResponse;
 use paddler_types::streamable_result::StreamableResult;
 
 use crate::agent::jsonrpc::Request as AgentJsonRpcRequest;
 use crate::balancer::agent_controller::AgentController;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
-use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::handles_agent_streaming_response::HandlesAgentStreamingResponse;
+use crate::balancer::http_stream_from_transform_results::http_stream_from_transform_results;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::balancer::manages_senders::ManagesSenders;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria;
+use crate::balancer::reports_request_outcome::ReportsRequestOutcome;
 use crate::balancer::unbounded_stream_from_agent::unbounded_stream_from_agent;
 
 pub fn http_stream_from_agent<TParams, TTransformsOutgoingMessage>(
     buffered_request_manager: Arc<BufferedRequestManager>,
+    fair_share_tenant: Option<FairShareTenant>,
     inference_service_configuration: InferenceServiceConfiguration,
     params: TParams,
     transformer: TTransformsOutgoingMessage,
 ) -> HttpResponse
 where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Debug + Into<AgentJsonRpcRequest> + ProvidesDispatchCriteria + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
-    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
+    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + ReportsRequestOutcome + StreamableResult,
     TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
 {
-    let stream = unbounded_stream_from_agent(
+    http_stream_from_transform_results(unbounded_stream_from_agent(
         buffered_request_manager,
+        fair_share_tenant,
         inference_service_configuration,
         params,
         transformer,
-    )
-    .filter_map(|transform_result| async move {
-        match transform_result {
-            TransformResult::Chunk(chunk) => {
-                Some(Ok::<_, Error>(Bytes::from(format!("{chunk}\n"))))
-            }
-            TransformResult::Error(error) => {
-                Some(Ok::<_, Error>(Bytes::from(format!("{error}\n"))))
-            }
-            TransformResult::Discard => None,
-        }
-    });
-
-    HttpResponse::Ok()
-        .insert_header(header::ContentType::json())
-        .insert_header((header::CACHE_CONTROL, "no-cache"))
-        .streaming(stream)
+    ))
 }
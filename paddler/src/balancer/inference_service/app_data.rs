@@ -2,11 +2,13 @@ use std::sync::Arc;
 
 use tokio_util::sync::CancellationToken;
 
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::inference_service::configuration::Configuration;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 
 pub struct AppData {
+    pub api_key_registry: Arc<ApiKeyRegistry>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: Configuration,
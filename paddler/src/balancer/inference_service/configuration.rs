@@ -6,4 +6,6 @@ pub struct Configuration {
     pub addr: SocketAddr,
     pub cors_allowed_hosts: Vec<String>,
     pub inference_item_timeout: Duration,
+    /// Requests have to bear an API key with the inference scope
+    pub require_api_key: bool,
 }
@@ -1,4 +1,5 @@
 pub mod post_continue_from_conversation_history;
 pub mod post_continue_from_raw_prompt;
 pub mod post_generate_embedding_batch;
+pub mod post_rerank;
 pub mod ws_inference_socket;
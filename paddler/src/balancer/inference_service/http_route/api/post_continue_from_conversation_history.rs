@@ -3,14 +3,18 @@ use actix_web::Responder;
 use actix_web::error::ErrorBadRequest;
 use actix_web::post;
 use actix_web::web;
+use actix_web::web::ReqData;
 
+use paddler_types::api_key::ApiKey;
 use paddler_types::request_params::ContinueFromConversationHistoryParams;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
 use paddler_types::validates::Validates as _;
 
 use crate::balancer::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::http_stream_from_agent::http_stream_from_agent;
 use crate::balancer::inference_service::app_data::AppData;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
 
 pub fn register(cfg: &mut web::ServiceConfig) {
     cfg.service(respond);
@@ -18,20 +22,37 @@ pub fn register(cfg: &mut web::ServiceConfig) {
 
 #[post("/api/v1/continue_from_conversation_history")]
 async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
     app_data: web::Data<AppData>,
     params: web::Json<ContinueFromConversationHistoryParams<RawParametersSchema>>,
 ) -> Result<impl Responder, Error> {
+    let validated_params = match params.into_inner().validate() {
+        Ok(validated_params) => validated_params,
+        Err(validation_error) => {
+            return Err(ErrorBadRequest(format!(
+                "Invalid request parameters: {validation_error}"
+            )));
+        }
+    };
+
+    app_data
+        .buffered_request_manager
+        .ensure_model_is_served(validated_params.model.as_deref())?;
+
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            validated_params.dispatch_criteria().estimated_tokens,
+        )?;
+    }
+
     Ok(http_stream_from_agent(
         app_data.buffered_request_manager.clone(),
+        fair_share_tenant,
         app_data.inference_service_configuration.clone(),
-        match params.into_inner().validate() {
-            Ok(validated_params) => validated_params,
-            Err(validation_error) => {
-                return Err(ErrorBadRequest(format!(
-                    "Invalid request parameters: {validation_error}"
-                )));
-            }
-        },
+        validated_params,
         IdentityTransformer::new(),
     ))
 }
@@ -7,21 +7,19 @@ use actix_web::http::header;
 use actix_web::post;
 use actix_web::rt;
 use actix_web::web;
+use actix_web::web::ReqData;
 use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::StreamExt;
-use log::error;
 use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
 use paddler_types::embedding_result::EmbeddingResult;
 use paddler_types::inference_client::Message as OutgoingMessage;
 use paddler_types::inference_client::Response as OutgoingResponse;
-use paddler_types::jsonrpc::Error as JsonRpcError;
-use paddler_types::jsonrpc::ErrorEnvelope;
 use paddler_types::jsonrpc::ResponseEnvelope;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
 use tokio::sync::mpsc;
-use tokio::task::JoinSet;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio_util::sync::CancellationToken;
 
@@ -29,13 +27,14 @@ use crate::balancer::chunk_forwarding_session_controller::ChunkForwardingSession
 use crate::balancer::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
 use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::inference_service::app_data::AppData;
-use crate::balancer::request_from_agent::request_from_agent;
+use crate::balancer::provides_dispatch_criteria::CHARACTERS_PER_TOKEN_APPROXIMATELY;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+use crate::balancer::spawn_embedding_chunk_requests::spawn_embedding_chunk_requests;
 use crate::cancellation_token_stream_guard::CancellationTokenStreamGuard;
 use crate::controls_session::ControlsSession as _;
 
-const CHARACTERS_PER_TOKEN_APPROXIMATELY: usize = 3;
-
 #[derive(Clone)]
 struct EmbeddingChunkBodyTransformer;
 
@@ -62,16 +61,24 @@ pub fn register(cfg: &mut web::ServiceConfig) {
 
 #[post("/api/v1/generate_embedding_batch")]
 async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
     app_data: web::Data<AppData>,
     params: web::Json<GenerateEmbeddingBatchParams>,
 ) -> Result<impl Responder, Error> {
+    app_data
+        .buffered_request_manager
+        .ensure_model_is_served(params.model.as_deref())?;
+
     let balancer_applicable_state_holder = app_data.balancer_applicable_state_holder.clone();
-    let Some(agent_desired_state) = balancer_applicable_state_holder.get_agent_desired_state()
+    let Some(balancer_applicable_state) =
+        balancer_applicable_state_holder.get_balancer_applicable_state()
     else {
         return Err(ErrorServiceUnavailable(
             "Balancer applicable state is not yet set",
         ));
     };
+    let agent_desired_state =
+        balancer_applicable_state.agent_desired_state_for(params.model.as_deref());
 
     if !agent_desired_state.inference_parameters.enable_embeddings {
         return Err(ErrorNotImplemented(
@@ -79,51 +86,31 @@ async fn respond(
         ));
     }
 
+    let fair_share_tenant = api_key
+        .as_deref()
+        .and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data
+            .api_key_registry
+            .charge_tokens(&api_key, params.dispatch_criteria().estimated_tokens)?;
+    }
+
     let connection_close = CancellationToken::new();
     let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
 
-    let mut chunk_tasks: JoinSet<()> = JoinSet::new();
-
-    for batch in params.chunk_by_input_size(
-        agent_desired_state.inference_parameters.batch_n_tokens
-            * CHARACTERS_PER_TOKEN_APPROXIMATELY,
-    ) {
-        let buffered_request_manager_clone = app_data.buffered_request_manager.clone();
-        let chunk_tx_clone = chunk_tx.clone();
-        let connection_close_clone = connection_close.clone();
-        let inference_service_configuration_clone =
-            app_data.inference_service_configuration.clone();
-
-        chunk_tasks.spawn(async move {
-            let request_id: String = nanoid!();
-            let mut session_controller = ChunkForwardingSessionController::new(
-                chunk_tx_clone,
-                EmbeddingChunkBodyTransformer,
-            );
-
-            if let Err(err) = request_from_agent(
-                buffered_request_manager_clone,
-                connection_close_clone,
-                inference_service_configuration_clone,
-                batch,
-                request_id.clone(),
-                session_controller.clone(),
-            )
-            .await
-            {
-                error!("Failed to handle request: {err}");
-                session_controller
-                    .send_response_safe(OutgoingMessage::Error(ErrorEnvelope {
-                        request_id: request_id.clone(),
-                        error: JsonRpcError {
-                            code: 500,
-                            description: format!("Request {request_id} failed: {err}"),
-                        },
-                    }))
-                    .await;
-            }
-        });
-    }
+    let mut chunk_tasks = spawn_embedding_chunk_requests(
+        &app_data.buffered_request_manager,
+        &chunk_tx,
+        &connection_close,
+        fair_share_tenant.as_ref(),
+        &app_data.inference_service_configuration,
+        params.chunk_by_input_size(
+            agent_desired_state.inference_parameters.batch_n_tokens
+                * CHARACTERS_PER_TOKEN_APPROXIMATELY,
+        ),
+        &EmbeddingChunkBodyTransformer,
+    );
 
     let final_done_chunk_tx = chunk_tx.clone();
 
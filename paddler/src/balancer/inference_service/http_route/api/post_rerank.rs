@@ -0,0 +1,282 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use actix_web::error::ErrorBadRequest;
+use actix_web::error::ErrorNotImplemented;
+use actix_web::error::ErrorServiceUnavailable;
+use actix_web::http::header;
+use actix_web::post;
+use actix_web::rt;
+use actix_web::web;
+use actix_web::web::ReqData;
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::StreamExt;
+use nanoid::nanoid;
+use paddler_types::api_key::ApiKey;
+use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::inference_client::Response as OutgoingResponse;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::pooling_type::PoolingType;
+use paddler_types::request_params::RerankParams;
+use paddler_types::rerank_result::RerankResult;
+use paddler_types::rerank_score::RerankScore;
+use paddler_types::validates::Validates as _;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+use crate::balancer::chunk_forwarding_session_controller::ChunkForwardingSessionController;
+use crate::balancer::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::inference_service::app_data::AppData;
+use crate::balancer::provides_dispatch_criteria::CHARACTERS_PER_TOKEN_APPROXIMATELY;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria as _;
+use crate::balancer::spawn_embedding_chunk_requests::spawn_embedding_chunk_requests;
+use crate::cancellation_token_stream_guard::CancellationTokenStreamGuard;
+use crate::controls_session::ControlsSession as _;
+
+/// Turns the embeddings of query and document pairs into relevance scores. When the scores are
+/// sorted, clones share them, so they can be ranked once every chunk is scored.
+#[derive(Clone)]
+struct RerankChunkBodyTransformer {
+    collected_scores: Option<Arc<RwLock<Vec<RerankScore>>>>,
+}
+
+#[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+fn collect_score(collected_scores: &RwLock<Vec<RerankScore>>, score: RerankScore) {
+    collected_scores
+        .write()
+        .expect("Failed to acquire write lock on rerank scores")
+        .push(score);
+}
+
+impl RerankChunkBodyTransformer {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    fn take_collected_scores(&self) -> Vec<RerankScore> {
+        self.collected_scores
+            .as_ref()
+            .map_or_else(Vec::new, |collected_scores| {
+                std::mem::take(
+                    &mut *collected_scores
+                        .write()
+                        .expect("Failed to acquire write lock on rerank scores"),
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for RerankChunkBodyTransformer {
+    async fn transform(&self, message: OutgoingMessage) -> Result<TransformResult> {
+        let message = match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response: OutgoingResponse::Embedding(EmbeddingResult::Done),
+                ..
+            }) => return Ok(TransformResult::Discard),
+            OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::Embedding(EmbeddingResult::Embedding(embedding)),
+            }) => OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::Rerank(match RerankScore::try_from(embedding) {
+                    Ok(score) => {
+                        if let Some(collected_scores) = &self.collected_scores {
+                            collect_score(collected_scores, score);
+
+                            return Ok(TransformResult::Discard);
+                        }
+
+                        RerankResult::Score(score)
+                    }
+                    Err(err) => RerankResult::Error(format!("{err:#}")),
+                }),
+            }),
+            OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::Embedding(EmbeddingResult::Error(description)),
+            }) => OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::Rerank(RerankResult::Error(description)),
+            }),
+            message => message,
+        };
+
+        let serialized = serde_json::to_string(&message)?;
+
+        Ok(TransformResult::Chunk(serialized))
+    }
+}
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[post("/api/v1/rerank")]
+async fn respond(
+    api_key: Option<ReqData<ApiKey>>,
+    app_data: web::Data<AppData>,
+    params: web::Json<RerankParams>,
+) -> Result<impl Responder, Error> {
+    let params = params.into_inner().validate().map_err(ErrorBadRequest)?;
+
+    app_data
+        .buffered_request_manager
+        .ensure_model_is_served(params.model.as_deref())?;
+
+    let balancer_applicable_state_holder = app_data.balancer_applicable_state_holder.clone();
+    let Some(balancer_applicable_state) =
+        balancer_applicable_state_holder.get_balancer_applicable_state()
+    else {
+        return Err(ErrorServiceUnavailable(
+            "Balancer applicable state is not yet set",
+        ));
+    };
+    let agent_desired_state =
+        balancer_applicable_state.agent_desired_state_for(params.model.as_deref());
+
+    if !agent_desired_state.inference_parameters.enable_embeddings
+        || agent_desired_state.inference_parameters.pooling_type != PoolingType::Rank
+    {
+        return Err(ErrorNotImplemented(
+            "Reranking requires embeddings to be enabled with the rank pooling type in the inference parameters",
+        ));
+    }
+
+    let embedding_batch_params = params.to_embedding_batch_params();
+    let fair_share_tenant = api_key.as_deref().and_then(FairShareTenant::from_api_key);
+
+    if let Some(api_key) = api_key {
+        app_data.api_key_registry.charge_tokens(
+            &api_key,
+            embedding_batch_params.dispatch_criteria().estimated_tokens,
+        )?;
+    }
+
+    let connection_close = CancellationToken::new();
+    let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
+    let transformer = RerankChunkBodyTransformer {
+        collected_scores: params.is_sorted().then(Arc::default),
+    };
+
+    let mut chunk_tasks = spawn_embedding_chunk_requests(
+        &app_data.buffered_request_manager,
+        &chunk_tx,
+        &connection_close,
+        fair_share_tenant.as_ref(),
+        &app_data.inference_service_configuration,
+        embedding_batch_params.chunk_by_input_size(
+            agent_desired_state.inference_parameters.batch_n_tokens
+                * CHARACTERS_PER_TOKEN_APPROXIMATELY,
+        ),
+        &transformer,
+    );
+
+    let final_chunk_tx = chunk_tx.clone();
+
+    rt::spawn(async move {
+        while chunk_tasks.join_next().await.is_some() {}
+
+        let final_request_id: String = nanoid!();
+        let mut final_session =
+            ChunkForwardingSessionController::new(final_chunk_tx, IdentityTransformer::new());
+
+        for score in params.rank(transformer.take_collected_scores()) {
+            final_session
+                .send_response_safe(OutgoingMessage::Response(ResponseEnvelope {
+                    request_id: final_request_id.clone(),
+                    response: OutgoingResponse::Rerank(RerankResult::Score(score)),
+                }))
+                .await;
+        }
+
+        final_session
+            .send_response_safe(OutgoingMessage::Response(ResponseEnvelope {
+                request_id: final_request_id,
+                response: OutgoingResponse::Rerank(RerankResult::Done),
+            }))
+            .await;
+    });
+
+    drop(chunk_tx);
+
+    let stream =
+        CancellationTokenStreamGuard::new(UnboundedReceiverStream::new(chunk_rx), connection_close)
+            .filter_map(|transform_result| async move {
+                match transform_result {
+                    TransformResult::Chunk(content) | TransformResult::Error(content) => {
+                        Some(Ok::<_, Error>(Bytes::from(format!("{content}\n"))))
+                    }
+                    TransformResult::Discard => None,
+                }
+            });
+
+    Ok(HttpResponse::Ok()
+        .insert_header(header::ContentType::json())
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::embedding::Embedding;
+    use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+
+    use super::*;
+
+    fn embedding_message(document_id: &str, score: f32) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            request_id: "request".to_owned(),
+            response: OutgoingResponse::Embedding(EmbeddingResult::Embedding(Embedding {
+                embedding: vec![score],
+                input_tokens: 3,
+                normalization_method: EmbeddingNormalizationMethod::None,
+                pooling_type: PoolingType::Rank,
+                source_document_id: document_id.to_owned(),
+            })),
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_scores_when_unsorted() -> Result<()> {
+        let transformer = RerankChunkBodyTransformer {
+            collected_scores: None,
+        };
+
+        let TransformResult::Chunk(chunk) =
+            transformer.transform(embedding_message("doc", 0.5)).await?
+        else {
+            anyhow::bail!("score was not streamed");
+        };
+
+        assert!(chunk.contains(r#""Rerank":{"Score":{"document_id":"doc""#));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn collects_scores_when_sorted() -> Result<()> {
+        let transformer = RerankChunkBodyTransformer {
+            collected_scores: Some(Arc::default()),
+        };
+
+        let chunk_transformer = transformer.clone();
+
+        assert!(matches!(
+            chunk_transformer
+                .transform(embedding_message("doc", 0.5))
+                .await?,
+            TransformResult::Discard
+        ));
+        assert_eq!(transformer.take_collected_scores().len(), 1);
+
+        Ok(())
+    }
+}
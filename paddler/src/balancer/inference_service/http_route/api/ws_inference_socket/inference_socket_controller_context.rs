@@ -1,9 +1,38 @@
 use std::sync::Arc;
 
+use paddler_types::api_key::ApiKey;
+
+use crate::balancer::api_key_registry::ApiKeyRegistry;
+use crate::balancer::api_key_rejection::ApiKeyRejection;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria;
 
 pub struct InferenceSocketControllerContext {
+    /// Key the socket was opened with, if the service requires one
+    pub api_key: Option<ApiKey>,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: InferenceServiceConfiguration,
 }
+
+impl InferenceSocketControllerContext {
+    pub fn charge_tokens(
+        &self,
+        params: &impl ProvidesDispatchCriteria,
+    ) -> Result<(), ApiKeyRejection> {
+        match &self.api_key {
+            Some(api_key) => self
+                .api_key_registry
+                .charge_tokens(api_key, params.dispatch_criteria().estimated_tokens),
+            None => Ok(()),
+        }
+    }
+
+    pub fn fair_share_tenant(&self) -> Option<FairShareTenant> {
+        self.api_key
+            .as_ref()
+            .and_then(FairShareTenant::from_api_key)
+    }
+}
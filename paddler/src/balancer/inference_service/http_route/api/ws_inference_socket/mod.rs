@@ -5,7 +5,9 @@ use std::sync::Arc;
 use actix_web::rt;
 use actix_web::Error;
 use actix_web::HttpRequest;
+use actix_web::HttpMessage as _;
 use actix_web::HttpResponse;
+use actix_web::ResponseError;
 use actix_web::get;
 use actix_web::web::Data;
 use actix_web::web::Payload;
@@ -13,6 +15,7 @@ use actix_web::web::ServiceConfig;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
+use paddler_types::api_key::ApiKey;
 use paddler_types::inference_client::Message as OutgoingMessage;
 use paddler_types::inference_server::Message as InferenceServerMessage;
 use paddler_types::inference_server::Request as InferenceServerRequest;
@@ -24,11 +27,13 @@ use paddler_types::validates::Validates as _;
 use tokio_util::sync::CancellationToken;
 
 use self::inference_socket_controller_context::InferenceSocketControllerContext;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::inference_service::app_data::AppData;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::balancer::request_from_agent::request_from_agent;
 use crate::continuation_decision::ContinuationDecision;
+use crate::controls_session::ControlsSession as _;
 use crate::controls_websocket_endpoint::ControlsWebSocketEndpoint;
 use crate::websocket_session_controller::WebSocketSessionController;
 
@@ -36,6 +41,8 @@ type InferenceJsonRpcMessage = InferenceServerMessage<RawParametersSchema>;
 type InferenceJsonRpcRequest = InferenceServerRequest<RawParametersSchema>;
 
 struct InferenceSocketController {
+    api_key: Option<ApiKey>,
+    api_key_registry: Arc<ApiKeyRegistry>,
     buffered_request_manager: Arc<BufferedRequestManager>,
     inference_service_configuration: InferenceServiceConfiguration,
 }
@@ -48,6 +55,8 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
 
     fn create_context(&self) -> Self::Context {
         InferenceSocketControllerContext {
+            api_key: self.api_key.clone(),
+            api_key_registry: self.api_key_registry.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             inference_service_configuration: self.inference_service_configuration.clone(),
         }
@@ -57,7 +66,7 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
         connection_close: CancellationToken,
         context: Arc<Self::Context>,
         deserialized_message: Self::IncomingMessage,
-        websocket_session_controller: WebSocketSessionController<Self::OutgoingMessage>,
+        mut websocket_session_controller: WebSocketSessionController<Self::OutgoingMessage>,
     ) -> Result<ContinuationDecision> {
         match deserialized_message {
             InferenceJsonRpcMessage::Error(ErrorEnvelope {
@@ -79,10 +88,31 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
             }) => {
                 let validated_params = conversation_history_params.validate()?;
 
+                if let Err(model_not_found) = context
+                    .buffered_request_manager
+                    .ensure_model_is_served(validated_params.model.as_deref())
+                {
+                    send_rejection(
+                        &model_not_found,
+                        request_id,
+                        &mut websocket_session_controller,
+                    )
+                    .await;
+
+                    return Ok(ContinuationDecision::Continue);
+                }
+
+                if let Err(rejection) = context.charge_tokens(&validated_params) {
+                    send_rejection(&rejection, request_id, &mut websocket_session_controller).await;
+
+                    return Ok(ContinuationDecision::Continue);
+                }
+
                 rt::spawn(async move {
                     if let Err(err) = request_from_agent(
                         context.buffered_request_manager.clone(),
                         connection_close,
+                        context.fair_share_tenant(),
                         context.inference_service_configuration.clone(),
                         validated_params,
                         request_id.clone(),
@@ -100,10 +130,33 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
                 id: request_id,
                 request: InferenceJsonRpcRequest::ContinueFromRawPrompt(raw_prompt_params),
             }) => {
+                let raw_prompt_params = raw_prompt_params.validate()?;
+
+                if let Err(model_not_found) = context
+                    .buffered_request_manager
+                    .ensure_model_is_served(raw_prompt_params.model.as_deref())
+                {
+                    send_rejection(
+                        &model_not_found,
+                        request_id,
+                        &mut websocket_session_controller,
+                    )
+                    .await;
+
+                    return Ok(ContinuationDecision::Continue);
+                }
+
+                if let Err(rejection) = context.charge_tokens(&raw_prompt_params) {
+                    send_rejection(&rejection, request_id, &mut websocket_session_controller).await;
+
+                    return Ok(ContinuationDecision::Continue);
+                }
+
                 rt::spawn(async move {
                     if let Err(err) = request_from_agent(
                         context.buffered_request_manager.clone(),
                         connection_close,
+                        context.fair_share_tenant(),
                         context.inference_service_configuration.clone(),
                         raw_prompt_params,
                         request_id.clone(),
@@ -121,6 +174,22 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
     }
 }
 
+async fn send_rejection(
+    rejection: &impl ResponseError,
+    request_id: String,
+    websocket_session_controller: &mut WebSocketSessionController<OutgoingMessage>,
+) {
+    websocket_session_controller
+        .send_response_safe(OutgoingMessage::Error(ErrorEnvelope {
+            request_id,
+            error: JsonRpcError {
+                code: i32::from(rejection.status_code().as_u16()),
+                description: rejection.to_string(),
+            },
+        }))
+        .await;
+}
+
 #[get("/api/v1/inference_socket")]
 #[expect(
     clippy::future_not_send,
@@ -132,6 +201,8 @@ async fn respond(
     http_request: HttpRequest,
 ) -> Result<HttpResponse, Error> {
     let inference_socket_controller = InferenceSocketController {
+        api_key: http_request.extensions().get::<ApiKey>().cloned(),
+        api_key_registry: app_data.api_key_registry.clone(),
         buffered_request_manager: app_data.buffered_request_manager.clone(),
         inference_service_configuration: app_data.inference_service_configuration.clone(),
     };
@@ -6,11 +6,15 @@ use std::sync::Arc;
 
 use actix_web::App;
 use actix_web::HttpServer;
+use actix_web::middleware::from_fn;
 use actix_web::web::Data;
 use anyhow::Result;
 use async_trait::async_trait;
+use paddler_types::api_key_scope::ApiKeyScope;
 use tokio_util::sync::CancellationToken;
 
+use crate::balancer::api_key_guard::ApiKeyGuard;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::http_route as common_http_route;
 use crate::balancer::inference_service::app_data::AppData;
@@ -22,6 +26,7 @@ use crate::create_cors_middleware::create_cors_middleware;
 use crate::service::Service;
 
 pub struct InferenceService {
+    pub api_key_registry: Arc<ApiKeyRegistry>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub configuration: InferenceServiceConfiguration,
@@ -47,21 +52,36 @@ impl Service for InferenceService {
         let cors_allowed_hosts_arc = Arc::new(cors_allowed_hosts);
 
         let app_data = Data::new(AppData {
+            api_key_registry: self.api_key_registry.clone(),
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             inference_service_configuration: self.configuration.clone(),
             shutdown: shutdown.clone(),
         });
 
+        let api_key_guard = ApiKeyGuard {
+            accepts_api_key_header: false,
+            api_key_registry: self.api_key_registry.clone(),
+            public_path_prefixes: &["/health"],
+            require_api_key: self.configuration.require_api_key,
+            scope: ApiKeyScope::Inference,
+        };
+
         #[expect(clippy::expect_used, reason = "server bind failure is unrecoverable")]
         HttpServer::new(move || {
+            let api_key_guard = api_key_guard.clone();
+
             App::new()
+                .wrap(from_fn(move |service_request, next| {
+                    api_key_guard.clone().authorize(service_request, next)
+                }))
                 .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                 .app_data(app_data.clone())
                 .configure(common_http_route::get_health::register)
                 .configure(http_route::api::post_continue_from_conversation_history::register)
                 .configure(http_route::api::post_continue_from_raw_prompt::register)
                 .configure(http_route::api::post_generate_embedding_batch::register)
+                .configure(http_route::api::post_rerank::register)
                 .configure(http_route::api::ws_inference_socket::register)
         })
         .shutdown_signal(async move {
@@ -1,8 +1,10 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio_util::sync::CancellationToken;
 
 use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use crate::balancer::embedding_sender_collection::EmbeddingSenderCollection;
@@ -13,6 +15,9 @@ use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 
 pub struct AppData {
     pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub agent_join_secret: Option<String>,
+    pub agent_reconnect_grace_period: Duration,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
@@ -1,7 +1,18 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub struct Configuration {
     pub addr: SocketAddr,
+    /// Agents have to present this secret when they register
+    pub agent_join_secret: Option<String>,
+    /// How long the balancer holds the in-flight requests of a disconnected agent, waiting for
+    /// it to reconnect, before it fails them and forgets the agent
+    pub agent_reconnect_grace_period: Duration,
+    /// Secret accepted with the management scope on top of the stored API keys, so that the
+    /// first keys can be created when the service requires one
+    pub bootstrap_api_key: Option<String>,
     pub cors_allowed_hosts: Vec<String>,
+    /// Requests have to bear an API key with the management scope
+    pub require_api_key: bool,
 }
@@ -0,0 +1,28 @@
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::get;
+use actix_web::web;
+use paddler_types::redacted_api_key::RedactedApiKey;
+
+use crate::balancer::management_service::app_data::AppData;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Lists the keys without their secrets, which are only ever sent to the balancer
+#[get("/api/v1/api_keys")]
+async fn respond(app_data: web::Data<AppData>) -> Result<impl Responder, Error> {
+    let api_keys = app_data
+        .state_database
+        .read_api_keys()
+        .await
+        .map_err(ErrorInternalServerError)?
+        .into_iter()
+        .map(RedactedApiKey::from)
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(api_keys))
+}
@@ -1,10 +1,12 @@
 pub mod get_agents;
 pub mod get_agents_stream;
+pub mod get_api_keys;
 pub mod get_balancer_applicable_state;
 pub mod get_balancer_desired_state;
 pub mod get_buffered_requests;
 pub mod get_buffered_requests_stream;
 pub mod get_chat_template_override;
 pub mod get_model_metadata;
+pub mod put_api_keys;
 pub mod put_balancer_desired_state;
 pub mod ws_agent_socket;
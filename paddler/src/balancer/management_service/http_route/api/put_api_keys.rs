@@ -0,0 +1,34 @@
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use actix_web::error::ErrorBadRequest;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::put;
+use actix_web::web;
+use paddler_types::api_key::ApiKey;
+use paddler_types::validates::Validates as _;
+
+use crate::balancer::management_service::app_data::AppData;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+/// Replaces all the API keys; usage of keys that are kept counts towards their limits as before
+#[put("/api/v1/api_keys")]
+async fn respond(
+    app_data: web::Data<AppData>,
+    api_keys: web::Json<Vec<ApiKey>>,
+) -> Result<impl Responder, Error> {
+    let api_keys = api_keys.into_inner().validate().map_err(ErrorBadRequest)?;
+
+    app_data
+        .state_database
+        .store_api_keys(&api_keys)
+        .await
+        .map_err(ErrorInternalServerError)?;
+
+    app_data.api_key_registry.set_api_keys(api_keys);
+
+    Ok(HttpResponse::NoContent().finish())
+}
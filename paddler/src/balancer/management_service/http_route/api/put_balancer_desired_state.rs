@@ -19,11 +19,8 @@ async fn respond(
     app_data: web::Data<AppData>,
     balancer_desired_state: web::Json<BalancerDesiredState>,
 ) -> Result<impl Responder, Error> {
-    let balancer_desired_state_inner = balancer_desired_state.into_inner();
-
-    balancer_desired_state_inner
-        .inference_parameters
-        .clone()
+    let balancer_desired_state_inner = balancer_desired_state
+        .into_inner()
         .validate()
         .map_err(ErrorBadRequest)?;
 
@@ -1,8 +1,13 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use log::error;
 use log::info;
+use tokio::runtime::Handle;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 
+use crate::balancer::agent_controller::AgentController;
 use crate::balancer::agent_controller_pool::AgentControllerPool;
 use crate::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use crate::balancer::embedding_sender_collection::EmbeddingSenderCollection;
@@ -11,24 +16,81 @@ use crate::balancer::model_metadata_sender_collection::ModelMetadataSenderCollec
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 
 pub struct AgentSocketControllerContext {
+    pub agent_connection_id: String,
     pub agent_controller_pool: Arc<AgentControllerPool>,
     pub agent_id: String,
+    pub agent_join_secret: Option<String>,
+    pub agent_reconnect_grace_period: Duration,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
     pub embedding_sender_collection: Arc<EmbeddingSenderCollection>,
     pub generate_tokens_sender_collection: Arc<GenerateTokensSenderCollection>,
     pub model_metadata_sender_collection: Arc<ModelMetadataSenderCollection>,
+    pub shutdown: CancellationToken,
+}
+
+impl AgentSocketControllerContext {
+    /// Controller of the agent, as long as it still talks through this connection
+    pub fn get_attached_agent_controller(&self) -> Option<Arc<AgentController>> {
+        self.agent_controller_pool
+            .get_agent_controller(&self.agent_id)
+            .filter(|agent_controller| {
+                agent_controller.get_connection().id == self.agent_connection_id
+            })
+    }
+
+    pub fn remove_agent_controller(&self, agent_controller: &AgentController) {
+        remove_agent_controller(&self.agent_controller_pool, agent_controller);
+    }
 }
 
 impl Drop for AgentSocketControllerContext {
     fn drop(&mut self) {
-        if let Err(err) = self
-            .agent_controller_pool
-            .remove_agent_controller(&self.agent_id)
-        {
-            error!("Failed to remove agent: {err}");
-        }
-
-        info!("Removed agent: {}", self.agent_id);
+        let Some(agent_controller) = self.get_attached_agent_controller() else {
+            return;
+        };
+
+        let runtime_handle = match Handle::try_current() {
+            Ok(runtime_handle) if !self.shutdown.is_cancelled() => runtime_handle,
+            _ => {
+                self.remove_agent_controller(&agent_controller);
+
+                return;
+            }
+        };
+
+        // Dispatch skips the agent from now on
+        self.agent_controller_pool.signal_update();
+
+        info!(
+            "Agent {} disconnected, waiting {:?} for it to reconnect",
+            self.agent_id, self.agent_reconnect_grace_period
+        );
+
+        let agent_connection_id = self.agent_connection_id.clone();
+        let agent_controller_pool = self.agent_controller_pool.clone();
+        let agent_reconnect_grace_period = self.agent_reconnect_grace_period;
+
+        runtime_handle.spawn(async move {
+            sleep(agent_reconnect_grace_period).await;
+
+            if agent_controller.get_connection().id == agent_connection_id {
+                remove_agent_controller(&agent_controller_pool, &agent_controller);
+            }
+        });
+    }
+}
+
+fn remove_agent_controller(
+    agent_controller_pool: &AgentControllerPool,
+    agent_controller: &AgentController,
+) {
+    if let Err(err) = agent_controller_pool.remove_agent_controller(&agent_controller.id) {
+        error!("Failed to remove agent: {err}");
     }
+
+    // Fails the requests that are still waiting for the agent
+    agent_controller.connection_close.cancel();
+
+    info!("Removed agent: {}", agent_controller.id);
 }
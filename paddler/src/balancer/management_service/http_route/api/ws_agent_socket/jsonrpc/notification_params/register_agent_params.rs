@@ -5,6 +5,11 @@ use serde::Serialize;
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RegisterAgentParams {
+    #[serde(default)]
+    pub join_secret: Option<String>,
+    #[serde(default)]
+    pub model_pool: Option<String>,
     pub name: Option<String>,
     pub slot_aggregated_status_snapshot: SlotAggregatedStatusSnapshot,
+    pub weight: u32,
 }
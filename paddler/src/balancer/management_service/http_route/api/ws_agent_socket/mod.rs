@@ -6,6 +6,7 @@ use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
 
 use actix_web::Error;
 use actix_web::HttpRequest;
@@ -16,15 +17,21 @@ use actix_web::web::Data;
 use actix_web::web::Path;
 use actix_web::web::Payload;
 use actix_web::web::ServiceConfig;
+use actix_ws::CloseCode;
+use actix_ws::CloseReason;
 use actix_ws::Session;
 use anyhow::Context;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
 use log::info;
+use nanoid::nanoid;
+use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::jsonrpc::ResponseEnvelope;
 use paddler_types::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
 use serde::Deserialize;
+use subtle::ConstantTimeEq as _;
+use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
@@ -38,6 +45,7 @@ use crate::agent::jsonrpc::Notification as AgentJsonRpcNotification;
 use crate::agent::jsonrpc::Response as AgentJsonRpcResponse;
 use crate::agent::jsonrpc::notification_params::VersionParams;
 use crate::atomic_value::AtomicValue;
+use crate::balancer::agent_connection::AgentConnection;
 use crate::balancer::agent_controller::AgentController;
 use crate::balancer::agent_controller_pool::AgentControllerPool;
 use crate::balancer::agent_controller_update_result::AgentControllerUpdateResult;
@@ -50,7 +58,6 @@ use crate::balancer::model_metadata_sender_collection::ModelMetadataSenderCollec
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::continuation_decision::ContinuationDecision;
 use crate::continuation_stop_parameters::ContinuationStopParameters;
-use crate::controls_session::ControlsSession as _;
 use crate::controls_websocket_endpoint::ControlsWebSocketEndpoint;
 use crate::sets_desired_state::SetsDesiredState as _;
 use crate::websocket_session_controller::WebSocketSessionController;
@@ -62,11 +69,14 @@ pub fn register(cfg: &mut ServiceConfig) {
 struct AgentSocketController {
     agent_controller_pool: Arc<AgentControllerPool>,
     agent_id: String,
+    agent_join_secret: Option<String>,
+    agent_reconnect_grace_period: Duration,
     balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
     embedding_sender_collection: Arc<EmbeddingSenderCollection>,
     generate_tokens_sender_collection: Arc<GenerateTokensSenderCollection>,
     model_metadata_sender_collection: Arc<ModelMetadataSenderCollection>,
+    shutdown: CancellationToken,
 }
 
 #[async_trait]
@@ -77,8 +87,11 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
 
     fn create_context(&self) -> Self::Context {
         AgentSocketControllerContext {
+            agent_connection_id: nanoid!(),
             agent_controller_pool: self.agent_controller_pool.clone(),
             agent_id: self.agent_id.clone(),
+            agent_join_secret: self.agent_join_secret.clone(),
+            agent_reconnect_grace_period: self.agent_reconnect_grace_period,
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             chat_template_override_sender_collection: self
                 .chat_template_override_sender_collection
@@ -86,6 +99,7 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
             embedding_sender_collection: self.embedding_sender_collection.clone(),
             generate_tokens_sender_collection: self.generate_tokens_sender_collection.clone(),
             model_metadata_sender_collection: self.model_metadata_sender_collection.clone(),
+            shutdown: self.shutdown.clone(),
         }
     }
 
@@ -104,6 +118,11 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
             ManagementJsonRpcMessage::Notification(
                 ManagementJsonRpcNotification::DeregisterAgent,
             ) => {
+                // The agent is shutting down, so there is nothing to wait for
+                if let Some(agent_controller) = context.get_attached_agent_controller() {
+                    context.remove_agent_controller(&agent_controller);
+                }
+
                 connection_close.cancel();
 
                 return Ok(ContinuationDecision::Stop(ContinuationStopParameters {
@@ -112,95 +131,191 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
             }
             ManagementJsonRpcMessage::Notification(
                 ManagementJsonRpcNotification::RegisterAgent(RegisterAgentParams {
+                    join_secret,
+                    model_pool,
                     name,
-                    slot_aggregated_status_snapshot:
-                        SlotAggregatedStatusSnapshot {
-                            desired_slots_total,
-                            download_current,
-                            download_filename,
-                            download_total,
-                            issues,
-                            model_path,
-                            slots_processing,
-                            slots_total,
-                            state_application_status,
-                            uses_chat_template_override,
-                            version,
-                        },
+                    slot_aggregated_status_snapshot,
+                    weight,
                 }),
             ) => {
-                let (agent_message_tx, mut agent_message_rx) =
-                    mpsc::unbounded_channel::<AgentJsonRpcMessage>();
-                let agent_controller = Arc::new(AgentController {
-                    agent_message_tx,
-                    chat_template_override_sender_collection: context
-                        .chat_template_override_sender_collection
-                        .clone(),
-                    connection_close: connection_close.clone(),
-                    desired_slots_total: AtomicValue::<AtomicI32>::new(desired_slots_total),
-                    download_current: AtomicValue::<AtomicUsize>::new(download_current),
-                    download_filename: RwLock::new(download_filename),
-                    download_total: AtomicValue::<AtomicUsize>::new(download_total),
-                    embedding_sender_collection: context.embedding_sender_collection.clone(),
-                    generate_tokens_sender_collection: context
-                        .generate_tokens_sender_collection
-                        .clone(),
-                    model_metadata_sender_collection: context
-                        .model_metadata_sender_collection
-                        .clone(),
-                    id: context.agent_id.clone(),
-                    issues: RwLock::new(issues),
-                    model_path: RwLock::new(model_path),
-                    name,
-                    newest_update_version: AtomicValue::<AtomicI32>::new(version),
-                    slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
-                    slots_total: AtomicValue::<AtomicI32>::new(slots_total),
-                    state_application_status_code: AtomicValue::<AtomicI32>::new(
-                        state_application_status as i32,
-                    ),
-                    uses_chat_template_override: AtomicValue::<AtomicBool>::new(
-                        uses_chat_template_override,
-                    ),
-                });
+                if let Some(agent_join_secret) = &context.agent_join_secret
+                    && !join_secret.as_ref().is_some_and(|join_secret| {
+                        bool::from(join_secret.as_bytes().ct_eq(agent_join_secret.as_bytes()))
+                    })
+                {
+                    error!(
+                        "Agent {} presented an invalid join secret",
+                        context.agent_id
+                    );
 
-                context
+                    return Ok(ContinuationDecision::Stop(ContinuationStopParameters {
+                        close_reason: Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some("Invalid agent join secret".to_owned()),
+                        }),
+                    }));
+                }
+
+                let agent_connection = AgentConnection {
+                    close: connection_close.clone(),
+                    id: context.agent_connection_id.clone(),
+                };
+
+                let agent_controller = if let Some(agent_controller) = context
                     .agent_controller_pool
-                    .register_agent_controller(context.agent_id.clone(), agent_controller.clone())
-                    .context("Unable to register agent controller")?;
+                    .get_agent_controller(&context.agent_id)
+                {
+                    agent_controller.attach_connection(agent_connection);
+                    agent_controller.update_from_slot_aggregated_status_snapshot(
+                        slot_aggregated_status_snapshot,
+                    );
+                    context.agent_controller_pool.signal_update();
 
-                if let Some(desired_state) = context
+                    info!("Reconnected agent: {}", context.agent_id);
+
+                    agent_controller
+                } else {
+                    let SlotAggregatedStatusSnapshot {
+                        accepts_embeddings,
+                        desired_slots_total,
+                        download_current,
+                        download_filename,
+                        download_total,
+                        issues,
+                        model_path,
+                        prefix_cache_hits,
+                        prefix_cache_misses,
+                        slots_processing,
+                        slots_total,
+                        state_application_count,
+                        state_application_status,
+                        uses_chat_template_override,
+                        version,
+                    } = slot_aggregated_status_snapshot;
+                    let (agent_message_tx, agent_message_rx) =
+                        mpsc::unbounded_channel::<AgentJsonRpcMessage>();
+                    let agent_controller = Arc::new(AgentController {
+                        accepts_embeddings: AtomicValue::<AtomicBool>::new(accepts_embeddings),
+                        agent_message_rx: Mutex::new(agent_message_rx),
+                        agent_message_tx,
+                        chat_template_override_sender_collection: context
+                            .chat_template_override_sender_collection
+                            .clone(),
+                        connection: RwLock::new(agent_connection),
+                        connection_close: CancellationToken::new(),
+                        desired_slots_total: AtomicValue::<AtomicI32>::new(desired_slots_total),
+                        desired_state: RwLock::new(None),
+                        download_current: AtomicValue::<AtomicUsize>::new(download_current),
+                        download_filename: RwLock::new(download_filename),
+                        download_total: AtomicValue::<AtomicUsize>::new(download_total),
+                        embedding_sender_collection: context.embedding_sender_collection.clone(),
+                        generate_tokens_sender_collection: context
+                            .generate_tokens_sender_collection
+                            .clone(),
+                        model_metadata_sender_collection: context
+                            .model_metadata_sender_collection
+                            .clone(),
+                        id: context.agent_id.clone(),
+                        issues: RwLock::new(issues),
+                        model_path: RwLock::new(model_path),
+                        model_pool: RwLock::new(None),
+                        name,
+                        newest_update_version: AtomicValue::<AtomicI32>::new(version),
+                        outstanding_tokens: AtomicValue::<AtomicI32>::new(0),
+                        prefix_cache_hits: AtomicValue::<AtomicUsize>::new(prefix_cache_hits),
+                        prefix_cache_misses: AtomicValue::<AtomicUsize>::new(prefix_cache_misses),
+                        requested_model_pool: model_pool,
+                        slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
+                        slots_total: AtomicValue::<AtomicI32>::new(slots_total),
+                        state_application_count: AtomicValue::<AtomicUsize>::new(
+                            state_application_count,
+                        ),
+                        state_application_status_code: AtomicValue::<AtomicI32>::new(
+                            state_application_status as i32,
+                        ),
+                        unsent_agent_message: Mutex::new(None),
+                        uses_chat_template_override: AtomicValue::<AtomicBool>::new(
+                            uses_chat_template_override,
+                        ),
+                        weight,
+                    });
+
+                    context
+                        .agent_controller_pool
+                        .assign_model_pool(&agent_controller);
+                    context
+                        .agent_controller_pool
+                        .register_agent_controller(
+                            context.agent_id.clone(),
+                            agent_controller.clone(),
+                        )
+                        .context("Unable to register agent controller")?;
+
+                    info!("Registered agent: {}", context.agent_id);
+
+                    agent_controller
+                };
+
+                if let Some(balancer_applicable_state) = context
                     .balancer_applicable_state_holder
-                    .get_agent_desired_state()
+                    .get_balancer_applicable_state()
                 {
                     agent_controller
-                        .set_desired_state(desired_state)
+                        .set_desired_state(
+                            balancer_applicable_state
+                                .agent_desired_state_for(
+                                    agent_controller.get_model_pool().as_deref(),
+                                )
+                                .clone(),
+                        )
                         .await
                         .context("Unable to set desired state")?;
                 }
 
-                info!("Registered agent: {}", context.agent_id);
-
                 let forwarder_close = connection_close.clone();
 
                 rt::spawn(async move {
+                    // Waits until the forwarder of the previous connection lets go of the queue
+                    let mut agent_message_rx = agent_controller.agent_message_rx.lock().await;
+                    let mut unsent_agent_message =
+                        agent_controller.unsent_agent_message.lock().await;
+
                     loop {
-                        tokio::select! {
-                            () = forwarder_close.cancelled() => {
-                                break;
-                            }
-                            result = agent_message_rx.recv() => {
-                                if let Some(message) = result {
-                                    websocket_session_controller
-                                        .send_response(message)
-                                        .await
-                                        .unwrap_or_else(|err| {
-                                            error!("Error sending response: {err}");
-                                        });
-                                } else {
-                                    info!("Session channel closed for agent: {}", context.agent_id);
+                        let serialized_message = if let Some(serialized_message) =
+                            unsent_agent_message.take()
+                        {
+                            serialized_message
+                        } else {
+                            tokio::select! {
+                                biased;
+
+                                () = forwarder_close.cancelled() => {
                                     break;
                                 }
+                                result = agent_message_rx.recv() => {
+                                    if let Some(message) = result {
+                                        match serde_json::to_string(&message) {
+                                            Ok(serialized_message) => serialized_message,
+                                            Err(err) => {
+                                                error!("Error serializing response: {err}");
+                                                continue;
+                                            }
+                                        }
+                                    } else {
+                                        info!("Session channel closed for agent: {}", context.agent_id);
+                                        break;
+                                    }
+                                }
                             }
+                        };
+
+                        if let Err(err) = websocket_session_controller
+                            .send_serialized_response(serialized_message.clone())
+                            .await
+                        {
+                            error!("Error sending response: {err}");
+                            unsent_agent_message.replace(serialized_message);
+                            break;
                         }
                     }
                 });
@@ -256,6 +371,14 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
                 request_id,
                 response: AgentJsonRpcResponse::GeneratedToken(generated_token_envelope),
             }) => {
+                if let GeneratedTokenResult::Usage(token_usage) = &generated_token_envelope {
+                    context
+                        .agent_controller_pool
+                        .metrics_registry
+                        .token_usage_counter
+                        .record(token_usage);
+                }
+
                 context
                     .generate_tokens_sender_collection
                     .forward_response_safe(request_id, generated_token_envelope)
@@ -320,6 +443,8 @@ async fn respond(
     let agent_socket_controller = AgentSocketController {
         agent_controller_pool: app_data.agent_controller_pool.clone(),
         agent_id: path_params.agent_id.clone(),
+        agent_join_secret: app_data.agent_join_secret.clone(),
+        agent_reconnect_grace_period: app_data.agent_reconnect_grace_period,
         balancer_applicable_state_holder: app_data.balancer_applicable_state_holder.clone(),
         chat_template_override_sender_collection: app_data
             .chat_template_override_sender_collection
@@ -327,6 +452,7 @@ async fn respond(
         embedding_sender_collection: app_data.embedding_sender_collection.clone(),
         generate_tokens_sender_collection: app_data.generate_tokens_sender_collection.clone(),
         model_metadata_sender_collection: app_data.model_metadata_sender_collection.clone(),
+        shutdown: app_data.shutdown.clone(),
     };
 
     agent_socket_controller.respond(payload, req, app_data.shutdown.clone())
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::fmt::Write as _;
 
 use actix_web::HttpResponse;
 use actix_web::Responder;
@@ -7,13 +8,56 @@ use actix_web::web::Data;
 use actix_web::web::ServiceConfig;
 use indoc::formatdoc;
 
+use crate::balancer::agent_controller_pool::AgentControllerPool;
 use crate::balancer::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
 use crate::balancer::management_service::app_data::AppData;
+use crate::balancer::metrics_registry::escape_label_value;
 
 pub fn register(cfg: &mut ServiceConfig) {
     cfg.service(respond);
 }
 
+fn write_agent_slots(
+    agent_controller_pool: &AgentControllerPool,
+    output: &mut String,
+    statsd_prefix: &str,
+) -> std::fmt::Result {
+    let mut agent_slots_processing = String::new();
+    let mut agent_slots_total = String::new();
+
+    for entry in &agent_controller_pool.agents {
+        let agent = entry.value();
+        let labels = format!(
+            "agent_id=\"{}\",agent_name=\"{}\",model_pool=\"{}\"",
+            escape_label_value(&agent.id),
+            escape_label_value(agent.name.as_deref().unwrap_or_default()),
+            escape_label_value(&agent.get_model_pool().unwrap_or_default()),
+        );
+
+        writeln!(
+            agent_slots_processing,
+            "{statsd_prefix}agent_slots_processing{{{labels}}} {}",
+            agent.slots_processing.get()
+        )?;
+        writeln!(
+            agent_slots_total,
+            "{statsd_prefix}agent_slots_total{{{labels}}} {}",
+            agent.slots_total.get()
+        )?;
+    }
+
+    output.push_str(&formatdoc! {"
+        # HELP {statsd_prefix}agent_slots_processing Number of processing slots of each agent
+        # TYPE {statsd_prefix}agent_slots_processing gauge
+        {agent_slots_processing}
+        # HELP {statsd_prefix}agent_slots_total Number of total slots of each agent
+        # TYPE {statsd_prefix}agent_slots_total gauge
+        {agent_slots_total}
+    "});
+
+    Ok(())
+}
+
 #[get("/metrics")]
 async fn respond(app_data: Data<AppData>) -> Result<impl Responder, Box<dyn Error>> {
     let AgentControllerPoolTotalSlots {
@@ -26,7 +70,7 @@ async fn respond(app_data: Data<AppData>) -> Result<impl Responder, Box<dyn Erro
         .get();
     let statsd_prefix = app_data.statsd_prefix.clone();
 
-    let metrics_response = formatdoc! {"
+    let mut metrics_response = formatdoc! {"
         # HELP {statsd_prefix}slots_processing Number of processing slots
         # TYPE {statsd_prefix}slots_processing gauge
         {statsd_prefix}slots_processing {slots_processing}
@@ -38,8 +82,19 @@ async fn respond(app_data: Data<AppData>) -> Result<impl Responder, Box<dyn Erro
         # HELP {statsd_prefix}requests_buffered Number of buffered requests
         # TYPE {statsd_prefix}requests_buffered gauge
         {statsd_prefix}requests_buffered {buffered_requests_count}
+
     "};
 
+    write_agent_slots(
+        &app_data.agent_controller_pool,
+        &mut metrics_response,
+        &statsd_prefix,
+    )?;
+    app_data
+        .agent_controller_pool
+        .metrics_registry
+        .write_prometheus(&mut metrics_response, &statsd_prefix)?;
+
     Ok(HttpResponse::Ok()
         .content_type("text/plain; version=0.0.4; charset=utf-8; escaping=values")
         .body(metrics_response))
@@ -6,12 +6,16 @@ use std::sync::Arc;
 
 use actix_web::App;
 use actix_web::HttpServer;
+use actix_web::middleware::from_fn;
 use actix_web::web::Data;
 use anyhow::Result;
 use async_trait::async_trait;
+use paddler_types::api_key_scope::ApiKeyScope;
 use tokio_util::sync::CancellationToken;
 
 use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::balancer::api_key_guard::ApiKeyGuard;
+use crate::balancer::api_key_registry::ApiKeyRegistry;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use crate::balancer::embedding_sender_collection::EmbeddingSenderCollection;
@@ -29,6 +33,7 @@ use crate::service::Service;
 
 pub struct ManagementService {
     pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub api_key_registry: Arc<ApiKeyRegistry>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
@@ -61,6 +66,9 @@ impl Service for ManagementService {
 
         let app_data = Data::new(AppData {
             agent_controller_pool: self.agent_controller_pool.clone(),
+            agent_join_secret: self.configuration.agent_join_secret.clone(),
+            agent_reconnect_grace_period: self.configuration.agent_reconnect_grace_period,
+            api_key_registry: self.api_key_registry.clone(),
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             chat_template_override_sender_collection: self
@@ -74,13 +82,27 @@ impl Service for ManagementService {
             statsd_prefix: self.statsd_prefix.clone(),
         });
 
+        let api_key_guard = ApiKeyGuard {
+            accepts_api_key_header: false,
+            api_key_registry: self.api_key_registry.clone(),
+            public_path_prefixes: &["/health", "/api/v1/agent_socket/"],
+            require_api_key: self.configuration.require_api_key,
+            scope: ApiKeyScope::Management,
+        };
+
         #[expect(clippy::expect_used, reason = "server bind failure is unrecoverable")]
         HttpServer::new(move || {
+            let api_key_guard = api_key_guard.clone();
+
             App::new()
+                .wrap(from_fn(move |service_request, next| {
+                    api_key_guard.clone().authorize(service_request, next)
+                }))
                 .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                 .app_data(app_data.clone())
                 .configure(common_http_route::get_health::register)
                 .configure(http_route::api::get_agents::register)
+                .configure(http_route::api::get_api_keys::register)
                 .configure(http_route::api::get_agents_stream::register)
                 .configure(http_route::api::get_balancer_applicable_state::register)
                 .configure(http_route::api::get_balancer_desired_state::register)
@@ -88,6 +110,7 @@ impl Service for ManagementService {
                 .configure(http_route::api::get_buffered_requests_stream::register)
                 .configure(http_route::api::get_chat_template_override::register)
                 .configure(http_route::api::get_model_metadata::register)
+                .configure(http_route::api::put_api_keys::register)
                 .configure(http_route::api::put_balancer_desired_state::register)
                 .configure(http_route::api::ws_agent_socket::register)
                 .configure(http_route::get_metrics::register)
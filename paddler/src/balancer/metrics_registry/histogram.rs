@@ -0,0 +1,116 @@
+use std::fmt::Write as _;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Cumulative histogram with fixed bucket boundaries, safe to observe from many tasks at once
+pub struct Histogram {
+    bucket_counts: Vec<AtomicU64>,
+    bucket_upper_bounds: &'static [f64],
+    count: AtomicU64,
+    sum_bits: AtomicU64,
+}
+
+impl Histogram {
+    #[must_use]
+    pub fn new(bucket_upper_bounds: &'static [f64]) -> Self {
+        Self {
+            bucket_counts: bucket_upper_bounds
+                .iter()
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            bucket_upper_bounds,
+            count: AtomicU64::new(0),
+            sum_bits: AtomicU64::new(0.0_f64.to_bits()),
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    pub fn observe(&self, value: f64) {
+        for (bucket_count, upper_bound) in self
+            .bucket_counts
+            .iter()
+            .zip(self.bucket_upper_bounds.iter())
+        {
+            if value <= *upper_bound {
+                bucket_count.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        self.count.fetch_add(1, Ordering::SeqCst);
+
+        // `fetch_update` only fails when the closure returns `None`, which it never does
+        let _ = self
+            .sum_bits
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |sum_bits| {
+                Some((f64::from_bits(sum_bits) + value).to_bits())
+            });
+    }
+
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum_bits.load(Ordering::SeqCst))
+    }
+
+    pub fn write_prometheus(
+        &self,
+        output: &mut String,
+        name: &str,
+        help: &str,
+    ) -> std::fmt::Result {
+        writeln!(output, "# HELP {name} {help}")?;
+        writeln!(output, "# TYPE {name} histogram")?;
+
+        for (bucket_count, upper_bound) in self
+            .bucket_counts
+            .iter()
+            .zip(self.bucket_upper_bounds.iter())
+        {
+            writeln!(
+                output,
+                "{name}_bucket{{le=\"{upper_bound}\"}} {}",
+                bucket_count.load(Ordering::SeqCst)
+            )?;
+        }
+
+        let count = self.count();
+
+        writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {count}")?;
+        writeln!(output, "{name}_sum {}", self.sum())?;
+        writeln!(output, "{name}_count {count}")?;
+        writeln!(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_cumulative() -> std::fmt::Result {
+        let histogram = Histogram::new(&[0.5, 1.0, 5.0]);
+
+        histogram.observe(0.25);
+        histogram.observe(0.75);
+        histogram.observe(10.0);
+
+        let mut output = String::new();
+
+        histogram.write_prometheus(&mut output, "latency_seconds", "Request latency")?;
+
+        assert_eq!(
+            output,
+            "# HELP latency_seconds Request latency\n\
+            # TYPE latency_seconds histogram\n\
+            latency_seconds_bucket{le=\"0.5\"} 1\n\
+            latency_seconds_bucket{le=\"1\"} 2\n\
+            latency_seconds_bucket{le=\"5\"} 2\n\
+            latency_seconds_bucket{le=\"+Inf\"} 3\n\
+            latency_seconds_sum 11\n\
+            latency_seconds_count 3\n\n"
+        );
+
+        Ok(())
+    }
+}
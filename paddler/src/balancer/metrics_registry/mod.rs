@@ -0,0 +1,171 @@
+pub mod histogram;
+pub mod request_outcome;
+
+use std::fmt::Write as _;
+use std::sync::atomic::AtomicUsize;
+
+use dashmap::DashMap;
+use paddler_types::token_usage::TokenUsage;
+
+use crate::atomic_value::AtomicValue;
+use crate::balancer::metrics_registry::histogram::Histogram;
+use crate::balancer::metrics_registry::request_outcome::RequestOutcome;
+use crate::balancer::token_usage_counter::TokenUsageCounter;
+
+const BUFFER_WAIT_SECONDS_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+const GENERATED_TOKENS_PER_SECOND_BUCKETS: &[f64] = &[
+    1.0, 5.0, 10.0, 20.0, 30.0, 50.0, 75.0, 100.0, 150.0, 200.0, 300.0, 500.0,
+];
+const REQUEST_DURATION_SECONDS_BUCKETS: &[f64] = &[
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+const TIME_TO_FIRST_TOKEN_SECONDS_BUCKETS: &[f64] =
+    &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Request level metrics of the balancer, exposed both on `/metrics` and through statsd
+pub struct MetricsRegistry {
+    pub buffer_wait_seconds: Histogram,
+    pub generated_tokens_per_second: Histogram,
+    pub request_duration_seconds: Histogram,
+    requests_finished: DashMap<RequestOutcome, AtomicValue<AtomicUsize>>,
+    pub time_to_first_token_seconds: Histogram,
+    pub token_usage_counter: TokenUsageCounter,
+}
+
+impl MetricsRegistry {
+    pub fn record_request_outcome(&self, request_outcome: RequestOutcome) {
+        if let Some(requests_finished) = self.requests_finished.get(&request_outcome) {
+            requests_finished.increment_by(1);
+        }
+    }
+
+    pub fn requests_finished(&self, request_outcome: RequestOutcome) -> usize {
+        self.requests_finished
+            .get(&request_outcome)
+            .map_or(0, |requests_finished| requests_finished.get())
+    }
+
+    pub fn write_prometheus(&self, output: &mut String, prefix: &str) -> std::fmt::Result {
+        let TokenUsage {
+            completion_tokens,
+            prompt_tokens,
+        } = self.token_usage_counter.get();
+
+        writeln!(
+            output,
+            "# HELP {prefix}prompt_tokens_total Number of prompt tokens of the finished requests"
+        )?;
+        writeln!(output, "# TYPE {prefix}prompt_tokens_total counter")?;
+        writeln!(output, "{prefix}prompt_tokens_total {prompt_tokens}")?;
+        writeln!(output)?;
+
+        writeln!(
+            output,
+            "# HELP {prefix}completion_tokens_total Number of tokens generated for the finished requests"
+        )?;
+        writeln!(output, "# TYPE {prefix}completion_tokens_total counter")?;
+        writeln!(
+            output,
+            "{prefix}completion_tokens_total {completion_tokens}"
+        )?;
+        writeln!(output)?;
+
+        writeln!(
+            output,
+            "# HELP {prefix}requests_finished_total Number of finished requests by outcome"
+        )?;
+        writeln!(output, "# TYPE {prefix}requests_finished_total counter")?;
+
+        for request_outcome in RequestOutcome::ALL {
+            writeln!(
+                output,
+                "{prefix}requests_finished_total{{outcome=\"{}\"}} {}",
+                request_outcome.label(),
+                self.requests_finished(request_outcome)
+            )?;
+        }
+
+        writeln!(output)?;
+
+        self.time_to_first_token_seconds.write_prometheus(
+            output,
+            &format!("{prefix}time_to_first_token_seconds"),
+            "Time from receiving a request to streaming its first generated token",
+        )?;
+        self.request_duration_seconds.write_prometheus(
+            output,
+            &format!("{prefix}request_duration_seconds"),
+            "Time from receiving a request to sending its last response",
+        )?;
+        self.generated_tokens_per_second.write_prometheus(
+            output,
+            &format!("{prefix}generated_tokens_per_second"),
+            "Generation speed of the finished requests, measured after their first token",
+        )?;
+        self.buffer_wait_seconds.write_prometheus(
+            output,
+            &format!("{prefix}buffer_wait_seconds"),
+            "Time requests spent waiting for a free slot",
+        )
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        let requests_finished = DashMap::new();
+
+        for request_outcome in RequestOutcome::ALL {
+            requests_finished.insert(request_outcome, AtomicValue::<AtomicUsize>::new(0));
+        }
+
+        Self {
+            buffer_wait_seconds: Histogram::new(BUFFER_WAIT_SECONDS_BUCKETS),
+            generated_tokens_per_second: Histogram::new(GENERATED_TOKENS_PER_SECOND_BUCKETS),
+            request_duration_seconds: Histogram::new(REQUEST_DURATION_SECONDS_BUCKETS),
+            requests_finished,
+            time_to_first_token_seconds: Histogram::new(TIME_TO_FIRST_TOKEN_SECONDS_BUCKETS),
+            token_usage_counter: TokenUsageCounter::default(),
+        }
+    }
+}
+
+/// Escapes a value so it can be placed inside a quoted Prometheus label
+#[must_use]
+pub fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_requests_by_outcome() -> std::fmt::Result {
+        let metrics_registry = MetricsRegistry::default();
+
+        metrics_registry.record_request_outcome(RequestOutcome::Done);
+        metrics_registry.record_request_outcome(RequestOutcome::Done);
+        metrics_registry.record_request_outcome(RequestOutcome::Timeout);
+
+        let mut output = String::new();
+
+        metrics_registry.write_prometheus(&mut output, "paddler_")?;
+
+        assert!(output.contains("paddler_requests_finished_total{outcome=\"done\"} 2\n"));
+        assert!(output.contains("paddler_requests_finished_total{outcome=\"timeout\"} 1\n"));
+        assert!(output.contains("paddler_requests_finished_total{outcome=\"evicted\"} 0\n"));
+        assert!(output.contains("paddler_time_to_first_token_seconds_count 0\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -0,0 +1,42 @@
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RequestOutcome {
+    /// Request was rejected because the buffer was full
+    BufferOverflow,
+    /// Client went away or asked to stop before the request finished
+    Cancelled,
+    /// Request finished normally
+    Done,
+    /// Request failed for any other reason
+    Error,
+    /// Agent ran out of KV cache space and dropped the request
+    Evicted,
+    /// Grammar could not be initialized or rejected the model output
+    GrammarError,
+    /// Request waited too long for a slot or for the next token
+    Timeout,
+}
+
+impl RequestOutcome {
+    pub const ALL: [Self; 7] = [
+        Self::BufferOverflow,
+        Self::Cancelled,
+        Self::Done,
+        Self::Error,
+        Self::Evicted,
+        Self::GrammarError,
+        Self::Timeout,
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::BufferOverflow => "buffer_overflow",
+            Self::Cancelled => "cancelled",
+            Self::Done => "done",
+            Self::Error => "error",
+            Self::Evicted => "evicted",
+            Self::GrammarError => "grammar_error",
+            Self::Timeout => "timeout",
+        }
+    }
+}
@@ -1,34 +1,56 @@
+pub mod agent_connection;
 pub mod agent_controller;
 pub mod agent_controller_pool;
 mod agent_controller_pool_total_slots;
 pub mod agent_controller_slot_guard;
 pub mod agent_controller_update_result;
+pub mod api_key_guard;
+pub mod api_key_registry;
+pub mod api_key_rejection;
+mod api_key_usage_window;
 mod buffered_request_agent_wait_result;
 mod buffered_request_count_guard;
 mod buffered_request_counter;
+pub mod buffered_request_limits;
 pub mod buffered_request_manager;
+mod buffered_request_queue;
 pub mod chat_template_override_sender_collection;
 mod chunk_forwarding_session_controller;
+mod collect_embedding_chunks_from_agents;
 pub mod compatibility;
 mod controls_manages_senders_endpoint;
+mod dispatch_affinity;
+pub mod dispatch_criteria;
+pub mod dispatch_strategy;
 pub mod dispatched_agent;
 pub mod embedding_sender_collection;
+pub mod fair_share_tenant;
 pub mod generate_tokens_sender_collection;
 mod handles_agent_streaming_response;
 mod http_route;
 mod http_stream_from_agent;
+mod http_stream_from_transform_results;
 pub mod inference_service;
 pub mod management_service;
 mod manages_senders;
 mod manages_senders_controller;
+pub mod metrics_registry;
 pub mod model_metadata_sender_collection;
+pub mod model_not_found;
+pub mod model_rollout;
+pub mod orders_dispatch_candidates;
+mod provides_dispatch_criteria;
 pub mod reconciliation_service;
+mod reports_request_outcome;
 mod request_from_agent;
+mod request_metrics_recorder;
 #[cfg(feature = "web_admin_panel")]
 mod response;
+mod spawn_embedding_chunk_requests;
 pub mod state_database;
 pub mod state_database_type;
 pub mod statsd_service;
+pub mod token_usage_counter;
 mod unbounded_stream_from_agent;
 #[cfg(feature = "web_admin_panel")]
 pub mod web_admin_panel_service;
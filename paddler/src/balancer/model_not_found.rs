@@ -0,0 +1,22 @@
+use actix_web::HttpResponse;
+use actix_web::ResponseError;
+use actix_web::http::StatusCode;
+use serde_json::json;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Model '{model}' is not served by any of the model pools")]
+pub struct ModelNotFound {
+    pub model: String,
+}
+
+impl ResponseError for ModelNotFound {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(json!({
+            "error": self.to_string(),
+        }))
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::bail;
+use log::error;
+use log::info;
+use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
+use tokio::task::JoinHandle;
+
+use crate::agent_desired_state::AgentDesiredState;
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::sets_desired_state::SetsDesiredState as _;
+use crate::subscribes_to_updates::SubscribesToUpdates as _;
+
+/// Switches agents to their new desired state one at a time, so that the others keep serving
+/// while each of them reloads. An agent that cannot apply the new state stops the rollout, which
+/// leaves the remaining agents on the previous one. Dropping it stops the rollout where it is.
+pub struct ModelRollout {
+    task: JoinHandle<()>,
+}
+
+impl ModelRollout {
+    #[must_use]
+    pub fn spawn(
+        agent_controller_pool: Arc<AgentControllerPool>,
+        pending_agent_states: Vec<(Arc<AgentController>, AgentDesiredState)>,
+    ) -> Self {
+        let task = tokio::spawn(async move {
+            let agents_count = pending_agent_states.len();
+
+            for (switched_agents_count, (agent_controller, desired_state)) in
+                pending_agent_states.into_iter().enumerate()
+            {
+                if let Err(err) =
+                    switch_agent(&agent_controller_pool, &agent_controller, desired_state).await
+                {
+                    error!(
+                        "Stopped the rollout with {switched_agents_count} of {agents_count} agents switched to the new state: {err}"
+                    );
+
+                    return;
+                }
+
+                info!(
+                    "Rolled out the new state to {} of {agents_count} agents",
+                    switched_agents_count + 1
+                );
+            }
+
+            info!("Rolled out the new state to all agents");
+        });
+
+        Self { task }
+    }
+}
+
+impl Drop for ModelRollout {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn switch_agent(
+    agent_controller_pool: &AgentControllerPool,
+    agent_controller: &AgentController,
+    desired_state: AgentDesiredState,
+) -> Result<()> {
+    if agent_controller.connection_close.is_cancelled() {
+        return Ok(());
+    }
+
+    let mut update_rx = agent_controller_pool.subscribe_to_updates();
+    let state_application_count = agent_controller.state_application_count.get();
+    let has_failed_previous_state = has_failed_to_apply_state(agent_controller);
+
+    agent_controller.set_desired_state(desired_state).await?;
+
+    // It is not serving anything, so there is nothing to wait for
+    if has_failed_previous_state {
+        return Ok(());
+    }
+
+    info!(
+        "Waiting for agent {} to apply the new state",
+        agent_controller.id
+    );
+
+    loop {
+        if agent_controller.state_application_count.get() > state_application_count
+            && agent_controller.state_application_status_code.get()
+                == AgentStateApplicationStatus::Applied as i32
+        {
+            return Ok(());
+        }
+
+        if has_failed_to_apply_state(agent_controller) {
+            bail!(
+                "Agent {} failed to apply the new state",
+                agent_controller.id
+            );
+        }
+
+        tokio::select! {
+            () = agent_controller.connection_close.cancelled() => return Ok(()),
+            changed = update_rx.changed() => changed?,
+        }
+    }
+}
+
+/// Agents stuck retrying are treated the same as the ones that gave up, since they may never
+/// get there
+fn has_failed_to_apply_state(agent_controller: &AgentController) -> bool {
+    matches!(
+        AgentStateApplicationStatus::try_from(agent_controller.state_application_status_code.get()),
+        Ok(AgentStateApplicationStatus::AttemptedAndNotAppliable
+            | AgentStateApplicationStatus::Stuck)
+    )
+}
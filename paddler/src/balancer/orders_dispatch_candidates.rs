@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+use crate::balancer::agent_controller::AgentController;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+
+pub trait OrdersDispatchCandidates: Send + Sync {
+    fn dispatch_strategy(&self) -> DispatchStrategy;
+
+    /// Candidates that come first are tried first; the ones without a free slot are skipped.
+    fn order_candidates(
+        &self,
+        candidates: &mut [Arc<AgentController>],
+        dispatch_criteria: &DispatchCriteria,
+    );
+}
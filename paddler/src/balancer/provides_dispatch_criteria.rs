@@ -0,0 +1,255 @@
+use std::hash::DefaultHasher;
+use std::hash::Hash as _;
+use std::hash::Hasher as _;
+
+use paddler_types::request_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::request_priority::RequestPriority;
+
+use crate::balancer::dispatch_criteria::DispatchCriteria;
+
+pub const CHARACTERS_PER_TOKEN_APPROXIMATELY: usize = 3;
+
+/// Raw prompts grow with every turn, so only their beginning identifies the conversation.
+const RAW_PROMPT_AFFINITY_PREFIX_CHARACTERS: usize = 512;
+
+fn estimate_tokens(characters: usize, max_tokens: i32) -> i32 {
+    i32::try_from(characters / CHARACTERS_PER_TOKEN_APPROXIMATELY)
+        .unwrap_or(i32::MAX)
+        .saturating_add(max_tokens.max(0))
+}
+
+pub trait ProvidesDispatchCriteria {
+    fn dispatch_criteria(&self) -> DispatchCriteria;
+}
+
+impl ProvidesDispatchCriteria for ContinueFromConversationHistoryParams<ValidatedParametersSchema> {
+    fn dispatch_criteria(&self) -> DispatchCriteria {
+        let messages = &self.conversation_history.messages;
+        let characters = messages
+            .iter()
+            .map(|message| message.content.text_content().len())
+            .sum();
+
+        let mut hasher = DefaultHasher::new();
+
+        if let Some(session_id) = &self.session_id {
+            "session".hash(&mut hasher);
+            session_id.hash(&mut hasher);
+        } else {
+            // System prompt and the opening user message stay the same on every turn
+            let opening_messages = messages
+                .iter()
+                .position(|message| message.role != "system")
+                .map_or(messages.len(), |first_turn_index| first_turn_index + 1);
+
+            if opening_messages == 0 {
+                return DispatchCriteria {
+                    affinity_key: None,
+                    estimated_tokens: estimate_tokens(characters, self.max_tokens),
+                    model: self.model.clone(),
+                    priority: self.priority,
+                    requires_embeddings: false,
+                };
+            }
+
+            "conversation".hash(&mut hasher);
+
+            for message in &messages[..opening_messages] {
+                message.role.hash(&mut hasher);
+                message.content.text_content().hash(&mut hasher);
+            }
+        }
+
+        DispatchCriteria {
+            affinity_key: Some(hasher.finish()),
+            estimated_tokens: estimate_tokens(characters, self.max_tokens),
+            model: self.model.clone(),
+            priority: self.priority,
+            requires_embeddings: false,
+        }
+    }
+}
+
+impl ProvidesDispatchCriteria for ContinueFromRawPromptParams {
+    fn dispatch_criteria(&self) -> DispatchCriteria {
+        let mut hasher = DefaultHasher::new();
+
+        "raw_prompt".hash(&mut hasher);
+
+        for character in self
+            .raw_prompt
+            .chars()
+            .take(RAW_PROMPT_AFFINITY_PREFIX_CHARACTERS)
+        {
+            character.hash(&mut hasher);
+        }
+
+        DispatchCriteria {
+            affinity_key: Some(hasher.finish()),
+            estimated_tokens: estimate_tokens(self.raw_prompt.len(), self.max_tokens),
+            model: self.model.clone(),
+            priority: self.priority,
+            requires_embeddings: false,
+        }
+    }
+}
+
+impl ProvidesDispatchCriteria for GenerateEmbeddingBatchParams {
+    fn dispatch_criteria(&self) -> DispatchCriteria {
+        let rerank_query_characters = self.rerank_query.as_ref().map_or(0, String::len);
+        let characters = self
+            .input_batch
+            .iter()
+            .filter(|document| document.tokens.is_none())
+            .map(|document| document.content.len())
+            .sum::<usize>()
+            + rerank_query_characters * self.input_batch.len();
+        let tokens = self
+            .input_batch
+            .iter()
+            .filter_map(|document| document.tokens.as_ref())
+            .map(Vec::len)
+            .sum();
+
+        DispatchCriteria {
+            affinity_key: None,
+            estimated_tokens: estimate_tokens(characters, 0)
+                .saturating_add(i32::try_from(tokens).unwrap_or(i32::MAX)),
+            model: self.model.clone(),
+            priority: self.priority,
+            requires_embeddings: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_types::conversation_history::ConversationHistory;
+    use paddler_types::conversation_message::ConversationMessage;
+    use paddler_types::conversation_message_content::ConversationMessageContent;
+    use paddler_types::embedding_input_document::EmbeddingInputDocument;
+    use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+
+    use super::*;
+
+    fn message(role: &str, text: &str) -> ConversationMessage {
+        ConversationMessage {
+            content: ConversationMessageContent::Text(text.to_owned()),
+            role: role.to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
+        }
+    }
+
+    fn conversation(
+        messages: Vec<ConversationMessage>,
+        session_id: Option<&str>,
+    ) -> ContinueFromConversationHistoryParams<ValidatedParametersSchema> {
+        ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(messages),
+            enable_thinking: false,
+            grammar: None,
+            logprobs: None,
+            max_tokens: 100,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: session_id.map(ToOwned::to_owned),
+            stop: vec![],
+            tools: vec![],
+            truncation: None,
+        }
+    }
+
+    #[test]
+    fn estimate_includes_completion_budget() {
+        assert_eq!(estimate_tokens(30, 100), 110);
+    }
+
+    #[test]
+    fn estimate_ignores_negative_completion_budget() {
+        assert_eq!(estimate_tokens(30, -1), 10);
+    }
+
+    #[test]
+    fn next_turn_of_conversation_keeps_affinity_key() {
+        let first_turn = conversation(
+            vec![message("system", "Be brief."), message("user", "Hi")],
+            None,
+        );
+        let second_turn = conversation(
+            vec![
+                message("system", "Be brief."),
+                message("user", "Hi"),
+                message("assistant", "Hello!"),
+                message("user", "How are you?"),
+            ],
+            None,
+        );
+
+        assert!(first_turn.dispatch_criteria().affinity_key.is_some());
+        assert_eq!(
+            first_turn.dispatch_criteria().affinity_key,
+            second_turn.dispatch_criteria().affinity_key
+        );
+    }
+
+    #[test]
+    fn different_conversations_get_different_affinity_keys() {
+        let first = conversation(vec![message("user", "Hi")], None);
+        let second = conversation(vec![message("user", "Hello")], None);
+
+        assert_ne!(
+            first.dispatch_criteria().affinity_key,
+            second.dispatch_criteria().affinity_key
+        );
+    }
+
+    #[test]
+    fn session_id_takes_precedence_over_messages() {
+        let first = conversation(vec![message("user", "Hi")], Some("session-1"));
+        let second = conversation(vec![message("user", "Hello")], Some("session-1"));
+
+        assert_eq!(
+            first.dispatch_criteria().affinity_key,
+            second.dispatch_criteria().affinity_key
+        );
+    }
+
+    #[test]
+    fn embeddings_have_no_affinity() {
+        let params = GenerateEmbeddingBatchParams {
+            input_batch: vec![],
+            model: None,
+            normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
+        };
+
+        assert_eq!(params.dispatch_criteria().affinity_key, None);
+        assert!(params.dispatch_criteria().requires_embeddings);
+    }
+
+    #[test]
+    fn reranking_estimates_the_query_for_every_document() {
+        let document = |id: &str| EmbeddingInputDocument {
+            content: "abc".to_owned(),
+            id: id.to_owned(),
+            tokens: None,
+        };
+        let params = GenerateEmbeddingBatchParams {
+            input_batch: vec![document("1"), document("2")],
+            model: None,
+            normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: Some("xyz".to_owned()),
+        };
+
+        // (3 + 3) characters for each of the 2 documents
+        assert_eq!(params.dispatch_criteria().estimated_tokens, 4);
+    }
+}
@@ -4,6 +4,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio::sync::broadcast;
 use tokio::time::Duration;
 use tokio::time::MissedTickBehavior;
@@ -11,27 +12,50 @@ use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 
 use crate::balancer::agent_controller_pool::AgentControllerPool;
+use crate::balancer::model_rollout::ModelRollout;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::converts_to_applicable_state::ConvertsToApplicableState as _;
 use crate::service::Service;
-use crate::sets_desired_state::SetsDesiredState as _;
 
 pub struct ReconciliationService {
     pub agent_controller_pool: Arc<AgentControllerPool>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub balancer_desired_state: BalancerDesiredState,
     pub balancer_desired_state_rx: broadcast::Receiver<BalancerDesiredState>,
+    pub default_dispatch_strategy: DispatchStrategy,
     pub is_converted_to_applicable_state: bool,
+    pub model_rollout: Option<ModelRollout>,
 }
 
 impl ReconciliationService {
     pub async fn convert_to_applicable_state(&mut self) -> Result<()> {
+        // Agents the previous rollout did not get to are compared against the new state instead
+        self.model_rollout = None;
+
+        self.agent_controller_pool.set_dispatch_strategy(
+            self.balancer_desired_state
+                .dispatch_strategy
+                .unwrap_or(self.default_dispatch_strategy),
+        );
+
         if let Some(balancer_applicable_state) =
             self.balancer_desired_state.to_applicable_state(()).await?
         {
-            self.agent_controller_pool
-                .set_desired_state(balancer_applicable_state.agent_desired_state.clone())
+            let pending_agent_states = self
+                .agent_controller_pool
+                .apply_balancer_applicable_state(
+                    &balancer_applicable_state,
+                    self.balancer_desired_state.rollout_strategy,
+                )
                 .await?;
+
+            if !pending_agent_states.is_empty() {
+                self.model_rollout = Some(ModelRollout::spawn(
+                    self.agent_controller_pool.clone(),
+                    pending_agent_states,
+                ));
+            }
+
             self.balancer_applicable_state_holder
                 .set_balancer_applicable_state(Some(balancer_applicable_state));
         }
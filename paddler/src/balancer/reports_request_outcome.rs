@@ -0,0 +1,80 @@
+use paddler_types::embedding_result::EmbeddingResult;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+
+use crate::balancer::metrics_registry::request_outcome::RequestOutcome;
+
+pub trait ReportsRequestOutcome {
+    fn is_generated_token(&self) -> bool;
+
+    /// Outcome of the request this result finishes, `None` if more results follow
+    fn request_outcome(&self) -> Option<RequestOutcome>;
+}
+
+impl ReportsRequestOutcome for EmbeddingResult {
+    fn is_generated_token(&self) -> bool {
+        false
+    }
+
+    fn request_outcome(&self) -> Option<RequestOutcome> {
+        match self {
+            Self::Done => Some(RequestOutcome::Done),
+            Self::Embedding(_) => None,
+            Self::Error(_) => Some(RequestOutcome::Error),
+        }
+    }
+}
+
+impl ReportsRequestOutcome for GeneratedTokenResult {
+    fn is_generated_token(&self) -> bool {
+        matches!(
+            self,
+            Self::ReasoningToken(_) | Self::Token(_) | Self::ToolCall(_)
+        )
+    }
+
+    fn request_outcome(&self) -> Option<RequestOutcome> {
+        match self {
+            Self::Done(FinishReason::Cancelled) => Some(RequestOutcome::Cancelled),
+            Self::Done(FinishReason::Evicted) => Some(RequestOutcome::Evicted),
+            Self::Done(_) => Some(RequestOutcome::Done),
+            Self::GrammarIncompatibleWithThinking(_)
+            | Self::GrammarInitializationFailed(_)
+            | Self::GrammarRejectedModelOutput(_)
+            | Self::GrammarSyntaxError(_) => Some(RequestOutcome::GrammarError),
+            Self::ChatTemplateError(_)
+            | Self::ContextOverflow(_)
+            | Self::ImageDecodingFailed(_)
+            | Self::InvalidSamplingParameters(_)
+            | Self::MultimodalNotSupported(_)
+            | Self::SamplerError(_) => Some(RequestOutcome::Error),
+            Self::ReasoningToken(_)
+            | Self::Seed(_)
+            | Self::Token(_)
+            | Self::TokenLogprobs(_)
+            | Self::ToolCall(_)
+            | Self::Usage(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicted_generation_is_reported_separately() {
+        assert_eq!(
+            GeneratedTokenResult::Done(FinishReason::Evicted).request_outcome(),
+            Some(RequestOutcome::Evicted)
+        );
+    }
+
+    #[test]
+    fn grammar_failures_are_grammar_errors() {
+        assert_eq!(
+            GeneratedTokenResult::GrammarRejectedModelOutput("err".to_owned()).request_outcome(),
+            Some(RequestOutcome::GrammarError)
+        );
+    }
+}
@@ -18,16 +18,23 @@ use crate::agent::jsonrpc::Request as AgentJsonRpcRequest;
 use crate::balancer::agent_controller::AgentController;
 use crate::balancer::buffered_request_agent_wait_result::BufferedRequestAgentWaitResult;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::dispatch_criteria::DispatchCriteria;
 use crate::balancer::dispatched_agent::DispatchedAgent;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::balancer::manages_senders::ManagesSenders;
 use crate::balancer::manages_senders_controller::ManagesSendersController;
+use crate::balancer::metrics_registry::request_outcome::RequestOutcome;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria;
+use crate::balancer::reports_request_outcome::ReportsRequestOutcome;
+use crate::balancer::request_metrics_recorder::RequestMetricsRecorder;
 use crate::controls_session::ControlsSession;
 
 pub async fn request_from_agent<TControlsSession, TParams>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     connection_close: CancellationToken,
+    fair_share_tenant: Option<FairShareTenant>,
     inference_service_configuration: InferenceServiceConfiguration,
     params: TParams,
     request_id: String,
@@ -35,13 +42,19 @@ pub async fn request_from_agent<TControlsSession, TParams>(
 ) -> Result<()>
 where
     TControlsSession: ControlsSession<OutgoingMessage>,
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send,
+    TParams: Debug + Into<AgentJsonRpcRequest> + ProvidesDispatchCriteria + Send,
     AgentController: HandlesAgentStreamingResponse<TParams>,
-    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
+    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + ReportsRequestOutcome + StreamableResult,
 {
+    let request_metrics_recorder =
+        RequestMetricsRecorder::new(buffered_request_manager.metrics_registry.clone());
+
     match wait_for_agent_controller(
         buffered_request_manager.clone(),
         connection_close.clone(),
+        params.dispatch_criteria(),
+        fair_share_tenant,
+        &request_metrics_recorder,
         request_id.clone(),
         &mut session_controller,
     )
@@ -57,6 +70,8 @@ where
                 Err(err) => {
                     error!("Failed to handle request {request_id:?}: {err}");
 
+                    request_metrics_recorder.finish(RequestOutcome::Error);
+
                     respond_with_error(
                         JsonRpcError {
                             code: 500,
@@ -76,6 +91,7 @@ where
                 connection_close,
                 inference_service_configuration,
                 receive_response_controller,
+                request_metrics_recorder,
                 request_id,
                 session_controller,
             )
@@ -92,13 +108,15 @@ async fn forward_responses_stream<TControlsSession, TManagesSenders>(
     connection_close: CancellationToken,
     inference_service_configuration: InferenceServiceConfiguration,
     mut receive_response_controller: ManagesSendersController<TManagesSenders>,
+    mut request_metrics_recorder: RequestMetricsRecorder,
     request_id: String,
     mut session_controller: TControlsSession,
 ) -> Result<()>
 where
     TControlsSession: ControlsSession<OutgoingMessage>,
     TManagesSenders: ManagesSenders + Send + Sync,
-    TManagesSenders::Value: Debug + Into<OutgoingResponse> + Send + StreamableResult,
+    TManagesSenders::Value:
+        Debug + Into<OutgoingResponse> + ReportsRequestOutcome + Send + StreamableResult,
 {
     debug!("Found available agent controller for request: {request_id:?}");
 
@@ -109,6 +127,8 @@ where
             () = agent_connection_close.cancelled() => {
                 error!("Agent controller connection closed");
 
+                request_metrics_recorder.finish(RequestOutcome::Error);
+
                 respond_with_error(
                     JsonRpcError {
                         code: 502,
@@ -121,6 +141,8 @@ where
                 break;
             }
             () = connection_close.cancelled() => {
+                request_metrics_recorder.finish(RequestOutcome::Cancelled);
+
                 agent_controller.stop_responding_to(request_id.clone()).await.unwrap_or_else(|err| {
                     error!("Failed to stop request {request_id:?}: {err}");
                 });
@@ -135,6 +157,8 @@ where
                     Consider increasing --inference-item-timeout if the model needs more time to process the prompt."
                 );
 
+                request_metrics_recorder.finish(RequestOutcome::Timeout);
+
                 respond_with_error(
                     JsonRpcError {
                         code: 504,
@@ -157,6 +181,9 @@ where
                 match response {
                     Some(response) => {
                         let is_done = response.is_done();
+                        let request_outcome = response.request_outcome();
+
+                        request_metrics_recorder.observe_result(&response);
 
                         let send_succeeded = send_response_to_client(
                             agent_controller.clone(),
@@ -165,11 +192,24 @@ where
                             &mut session_controller,
                         ).await;
 
-                        if is_done || !send_succeeded {
+                        if !send_succeeded {
+                            request_metrics_recorder.finish(RequestOutcome::Cancelled);
+
                             break;
                         }
+
+                        if is_done {
+                            request_metrics_recorder
+                                .finish(request_outcome.unwrap_or(RequestOutcome::Done));
+
+                            break;
+                        }
+                    }
+                    None => {
+                        request_metrics_recorder.finish(RequestOutcome::Error);
+
+                        break;
                     }
-                    None => break,
                 }
             }
         }
@@ -231,6 +271,9 @@ where
 async fn wait_for_agent_controller<TControlsSession>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     connection_close: CancellationToken,
+    dispatch_criteria: DispatchCriteria,
+    fair_share_tenant: Option<FairShareTenant>,
+    request_metrics_recorder: &RequestMetricsRecorder,
     request_id: String,
     session_controller: &mut TControlsSession,
 ) -> Result<Option<DispatchedAgent>>
@@ -243,14 +286,18 @@ where
         () = connection_close.cancelled() => {
             debug!("Connection close signal received, stopping GenerateTokens loop.");
 
+            request_metrics_recorder.finish(RequestOutcome::Cancelled);
+
             Ok(None)
         },
-        buffered_request_agent_wait_result = buffered_request_manager.wait_for_available_agent() => {
+        buffered_request_agent_wait_result = buffered_request_manager.wait_for_available_agent(&dispatch_criteria, fair_share_tenant) => {
             match buffered_request_agent_wait_result {
                 Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent)) => Ok(Some(dispatched_agent)),
                 Ok(BufferedRequestAgentWaitResult::BufferOverflow) => {
                     warn!("Too many buffered requests, dropping request: {request_id:?}");
 
+                    request_metrics_recorder.finish(RequestOutcome::BufferOverflow);
+
                     respond_with_error(
                         JsonRpcError {
                             code: 503,
@@ -265,6 +312,8 @@ where
                 Ok(BufferedRequestAgentWaitResult::Timeout(err)) => {
                     warn!("Buffered request {request_id:?} timed out: {err:?}");
 
+                    request_metrics_recorder.finish(RequestOutcome::Timeout);
+
                     respond_with_error(
                         JsonRpcError {
                             code: 504,
@@ -279,6 +328,8 @@ where
                 Err(err) => {
                     error!("Error while waiting for available agent controller for GenerateTokens request: {err}");
 
+                    request_metrics_recorder.finish(RequestOutcome::Error);
+
                     respond_with_error(
                         JsonRpcError {
                             code: 500,
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::balancer::metrics_registry::MetricsRegistry;
+use crate::balancer::metrics_registry::request_outcome::RequestOutcome;
+use crate::balancer::reports_request_outcome::ReportsRequestOutcome;
+
+/// Follows a single request from the moment the balancer received it until it finishes
+pub struct RequestMetricsRecorder {
+    first_token_at: Option<Instant>,
+    generated_tokens: usize,
+    metrics_registry: Arc<MetricsRegistry>,
+    received_at: Instant,
+}
+
+impl RequestMetricsRecorder {
+    #[must_use]
+    pub fn new(metrics_registry: Arc<MetricsRegistry>) -> Self {
+        Self {
+            first_token_at: None,
+            generated_tokens: 0,
+            metrics_registry,
+            received_at: Instant::now(),
+        }
+    }
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "token counts are far below the f64 mantissa limit"
+    )]
+    pub fn finish(&self, request_outcome: RequestOutcome) {
+        self.metrics_registry
+            .record_request_outcome(request_outcome);
+        self.metrics_registry
+            .request_duration_seconds
+            .observe(self.received_at.elapsed().as_secs_f64());
+
+        // The first token only marks the start of the generation, so it is not part of the rate
+        if let Some(first_token_at) = self.first_token_at
+            && self.generated_tokens > 1
+        {
+            let generation_seconds = first_token_at.elapsed().as_secs_f64();
+
+            if generation_seconds > 0.0 {
+                self.metrics_registry
+                    .generated_tokens_per_second
+                    .observe((self.generated_tokens - 1) as f64 / generation_seconds);
+            }
+        }
+    }
+
+    pub fn observe_result<TResult: ReportsRequestOutcome>(&mut self, result: &TResult) {
+        if !result.is_generated_token() {
+            return;
+        }
+
+        if self.first_token_at.is_none() {
+            self.first_token_at = Some(Instant::now());
+            self.metrics_registry
+                .time_to_first_token_seconds
+                .observe(self.received_at.elapsed().as_secs_f64());
+        }
+
+        self.generated_tokens += 1;
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use log::error;
+use nanoid::nanoid;
+use paddler_types::inference_client::Message as OutgoingMessage;
+use paddler_types::jsonrpc::Error as JsonRpcError;
+use paddler_types::jsonrpc::ErrorEnvelope;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::chunk_forwarding_session_controller::ChunkForwardingSessionController;
+use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
+use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::balancer::request_from_agent::request_from_agent;
+use crate::controls_session::ControlsSession as _;
+
+/// Sends every chunk of an embedding batch to the agents at once and forwards what they respond
+/// with to `chunk_tx`. The returned tasks finish once every chunk is served.
+pub fn spawn_embedding_chunk_requests<TTransformsOutgoingMessage>(
+    buffered_request_manager: &Arc<BufferedRequestManager>,
+    chunk_tx: &mpsc::UnboundedSender<TransformResult>,
+    connection_close: &CancellationToken,
+    fair_share_tenant: Option<&FairShareTenant>,
+    inference_service_configuration: &InferenceServiceConfiguration,
+    chunks: impl IntoIterator<Item = GenerateEmbeddingBatchParams>,
+    transformer: &TTransformsOutgoingMessage,
+) -> JoinSet<()>
+where
+    TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
+{
+    let mut chunk_tasks: JoinSet<()> = JoinSet::new();
+
+    for chunk in chunks {
+        let buffered_request_manager_clone = buffered_request_manager.clone();
+        let chunk_tx_clone = chunk_tx.clone();
+        let connection_close_clone = connection_close.clone();
+        let fair_share_tenant_clone = fair_share_tenant.cloned();
+        let inference_service_configuration_clone = inference_service_configuration.clone();
+        let transformer_clone = transformer.clone();
+
+        chunk_tasks.spawn(async move {
+            let request_id: String = nanoid!();
+            let mut session_controller =
+                ChunkForwardingSessionController::new(chunk_tx_clone, transformer_clone);
+
+            if let Err(err) = request_from_agent(
+                buffered_request_manager_clone,
+                connection_close_clone,
+                fair_share_tenant_clone,
+                inference_service_configuration_clone,
+                chunk,
+                request_id.clone(),
+                session_controller.clone(),
+            )
+            .await
+            {
+                error!("Failed to handle request: {err}");
+                session_controller
+                    .send_response_safe(OutgoingMessage::Error(ErrorEnvelope {
+                        request_id: request_id.clone(),
+                        error: JsonRpcError {
+                            code: 500,
+                            description: format!("Request {request_id} failed: {err}"),
+                        },
+                    }))
+                    .await;
+            }
+        });
+    }
+
+    chunk_tasks
+}
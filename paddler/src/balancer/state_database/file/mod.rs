@@ -6,6 +6,7 @@ use anyhow::Context;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::warn;
+use paddler_types::api_key::ApiKey;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
@@ -64,11 +65,13 @@ impl File {
             .await
             .context("Failed to store default state")?;
 
+        self.balancer_desired_state_notify_tx
+            .send(schema.balancer_desired_state.clone())?;
+
         Ok(schema)
     }
 
     async fn store_schema(&self, schema: &Schema) -> Result<()> {
-        let balancer_desired_state = schema.balancer_desired_state.clone();
         let _lock = self.write_lock.write().await;
 
         let serialized_schema = serde_json::to_string_pretty(schema)?;
@@ -77,9 +80,6 @@ impl File {
         file.write_all(serialized_schema.as_bytes()).await?;
         file.sync_all().await?;
 
-        self.balancer_desired_state_notify_tx
-            .send(balancer_desired_state)?;
-
         Ok(())
     }
 
@@ -100,6 +100,14 @@ impl File {
 
 #[async_trait]
 impl StateDatabase for File {
+    async fn read_api_keys(&self) -> Result<Vec<ApiKey>> {
+        Ok(self
+            .read_schema_from_file()
+            .await
+            .context("Unable to read state from file")?
+            .api_keys)
+    }
+
     async fn read_balancer_desired_state(&self) -> Result<BalancerDesiredState> {
         Ok(self
             .read_schema_from_file()
@@ -108,6 +116,13 @@ impl StateDatabase for File {
             .balancer_desired_state)
     }
 
+    async fn store_api_keys(&self, api_keys: &[ApiKey]) -> Result<()> {
+        self.update_schema(|schema| {
+            schema.api_keys = api_keys.to_vec();
+        })
+        .await
+    }
+
     async fn store_balancer_desired_state(
         &self,
         balancer_desired_state: &BalancerDesiredState,
@@ -115,6 +130,11 @@ impl StateDatabase for File {
         self.update_schema(|schema| {
             schema.balancer_desired_state = balancer_desired_state.clone();
         })
-        .await
+        .await?;
+
+        self.balancer_desired_state_notify_tx
+            .send(balancer_desired_state.clone())?;
+
+        Ok(())
     }
 }
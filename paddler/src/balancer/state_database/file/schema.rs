@@ -1,3 +1,4 @@
+use paddler_types::api_key::ApiKey;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use serde::Deserialize;
 use serde::Serialize;
@@ -9,6 +10,8 @@ fn default_version() -> String {
 #[derive(Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Schema {
+    #[serde(default)]
+    pub api_keys: Vec<ApiKey>,
     pub balancer_desired_state: BalancerDesiredState,
     #[serde(default = "default_version")]
     pub version: String,
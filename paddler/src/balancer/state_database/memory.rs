@@ -2,12 +2,14 @@ use std::sync::RwLock;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use paddler_types::api_key::ApiKey;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use tokio::sync::broadcast;
 
 use super::StateDatabase;
 
 pub struct Memory {
+    api_keys: RwLock<Vec<ApiKey>>,
     balancer_desired_state: RwLock<BalancerDesiredState>,
     balancer_desired_state_notify_tx: broadcast::Sender<BalancerDesiredState>,
 }
@@ -19,6 +21,7 @@ impl Memory {
         initial_desired_state: BalancerDesiredState,
     ) -> Self {
         Self {
+            api_keys: RwLock::new(Vec::new()),
             balancer_desired_state: RwLock::new(initial_desired_state),
             balancer_desired_state_notify_tx,
         }
@@ -27,6 +30,15 @@ impl Memory {
 
 #[async_trait]
 impl StateDatabase for Memory {
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    async fn read_api_keys(&self) -> Result<Vec<ApiKey>> {
+        Ok(self
+            .api_keys
+            .read()
+            .expect("Failed to acquire read lock")
+            .clone())
+    }
+
     #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     async fn read_balancer_desired_state(&self) -> Result<BalancerDesiredState> {
         Ok(self
@@ -36,6 +48,13 @@ impl StateDatabase for Memory {
             .clone())
     }
 
+    #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
+    async fn store_api_keys(&self, api_keys: &[ApiKey]) -> Result<()> {
+        *self.api_keys.write().expect("Failed to acquire write lock") = api_keys.to_vec();
+
+        Ok(())
+    }
+
     #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     async fn store_balancer_desired_state(&self, state: &BalancerDesiredState) -> Result<()> {
         {
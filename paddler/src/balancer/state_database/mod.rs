@@ -3,6 +3,7 @@ mod memory;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use paddler_types::api_key::ApiKey;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 
 pub use self::file::File;
@@ -10,17 +11,27 @@ pub use self::memory::Memory;
 
 #[async_trait]
 pub trait StateDatabase: Send + Sync {
+    async fn read_api_keys(&self) -> Result<Vec<ApiKey>>;
+
     async fn read_balancer_desired_state(&self) -> Result<BalancerDesiredState>;
 
+    async fn store_api_keys(&self, api_keys: &[ApiKey]) -> Result<()>;
+
     async fn store_balancer_desired_state(&self, state: &BalancerDesiredState) -> Result<()>;
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+
     use anyhow::Result;
     use paddler_types::agent_desired_model::AgentDesiredModel;
+    use paddler_types::api_key_rate_limit::ApiKeyRateLimit;
+    use paddler_types::api_key_scope::ApiKeyScope;
     use paddler_types::chat_template::ChatTemplate;
     use paddler_types::inference_parameters::InferenceParameters;
+    use paddler_types::rollout_strategy::RolloutStrategy;
     use tempfile::NamedTempFile;
     use tokio::sync::broadcast;
 
@@ -29,9 +40,13 @@ mod tests {
     async fn subtest_store_desired_state<TDatabase: StateDatabase>(db: &TDatabase) -> Result<()> {
         let desired_state = BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent("test_model_path".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         };
 
@@ -44,6 +59,27 @@ mod tests {
         Ok(())
     }
 
+    async fn subtest_store_api_keys<TDatabase: StateDatabase>(db: &TDatabase) -> Result<()> {
+        let api_keys = vec![ApiKey {
+            fair_share_weight: None,
+            name: "test_key".to_owned(),
+            rate_limit: ApiKeyRateLimit {
+                requests_per_minute: Some(60),
+                tokens_per_minute: None,
+            },
+            scopes: BTreeSet::from([ApiKeyScope::Inference]),
+            secret: "test_secret".to_owned(),
+        }];
+
+        assert!(db.read_api_keys().await?.is_empty());
+
+        db.store_api_keys(&api_keys).await?;
+
+        assert_eq!(db.read_api_keys().await?, api_keys);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_file_database() -> Result<()> {
         let (balancer_desired_state_tx, _balancer_desired_state_rx) = broadcast::channel(100);
@@ -51,6 +87,7 @@ mod tests {
         let db = File::new(balancer_desired_state_tx, tempfile.path().to_path_buf());
 
         subtest_store_desired_state(&db).await?;
+        subtest_store_api_keys(&db).await?;
 
         Ok(())
     }
@@ -61,6 +98,7 @@ mod tests {
         let db = Memory::new(balancer_desired_state_tx, BalancerDesiredState::default());
 
         subtest_store_desired_state(&db).await?;
+        subtest_store_api_keys(&db).await?;
 
         Ok(())
     }
@@ -76,9 +114,13 @@ mod tests {
         };
         let desired_state = BalancerDesiredState {
             chat_template_override: Some(chat_template.clone()),
+            dispatch_strategy: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent("test_model_path".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: true,
         };
 
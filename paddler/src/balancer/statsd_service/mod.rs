@@ -1,14 +1,17 @@
 pub mod configuration;
+mod reported_counters;
 
 use std::net::UdpSocket;
 use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use cadence::Counted;
 use cadence::Gauged;
 use cadence::StatsdClient;
 use cadence::UdpMetricSink;
 use log::error;
+use paddler_types::token_usage::TokenUsage;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
@@ -16,7 +19,10 @@ use tokio_util::sync::CancellationToken;
 use crate::balancer::agent_controller_pool::AgentControllerPool;
 use crate::balancer::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
 use crate::balancer::buffered_request_manager::BufferedRequestManager;
+use crate::balancer::metrics_registry::histogram::Histogram;
+use crate::balancer::metrics_registry::request_outcome::RequestOutcome;
 use crate::balancer::statsd_service::configuration::Configuration as StatsdServiceConfiguration;
+use crate::balancer::statsd_service::reported_counters::ReportedCounters;
 use crate::service::Service;
 
 pub struct StatsdService {
@@ -27,16 +33,126 @@ pub struct StatsdService {
 
 impl StatsdService {
     #[expect(clippy::cast_sign_loss, reason = "slot counts are always non-negative")]
-    fn report_metrics(&self, client: &StatsdClient) -> Result<()> {
+    fn report_agent_slots(&self, client: &StatsdClient) -> Result<()> {
+        for entry in &self.agent_controller_pool.agents {
+            let agent = entry.value();
+            let agent_name = agent.name.clone().unwrap_or_default();
+            let model_pool = agent.get_model_pool().unwrap_or_default();
+
+            client
+                .gauge_with_tags(
+                    "agent_slots_processing",
+                    agent.slots_processing.get() as u64,
+                )
+                .with_tag("agent_id", &agent.id)
+                .with_tag("agent_name", &agent_name)
+                .with_tag("model_pool", &model_pool)
+                .try_send()?;
+            client
+                .gauge_with_tags("agent_slots_total", agent.slots_total.get() as u64)
+                .with_tag("agent_id", &agent.id)
+                .with_tag("agent_name", &agent_name)
+                .with_tag("model_pool", &model_pool)
+                .try_send()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends how many values were observed since the last report as a counter, and their mean as a
+    /// gauge, since statsd has no counters of fractional values to send the sum with
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "observation counts per reporting interval are far below 2^52"
+    )]
+    fn report_histogram(
+        client: &StatsdClient,
+        reported_counters: &mut ReportedCounters,
+        name: &str,
+        histogram: &Histogram,
+    ) -> Result<()> {
+        let count_key = format!("{name}_count");
+        let count_delta = reported_counters.count_delta(&count_key, histogram.count());
+        let sum_delta = reported_counters.sum_delta(&format!("{name}_sum"), histogram.sum());
+
+        client.count(&count_key, count_delta)?;
+
+        if count_delta > 0 {
+            client.gauge(&format!("{name}_mean"), sum_delta / count_delta as f64)?;
+        }
+
+        Ok(())
+    }
+
+    #[expect(clippy::cast_sign_loss, reason = "slot counts are always non-negative")]
+    fn report_metrics(
+        &self,
+        client: &StatsdClient,
+        reported_counters: &mut ReportedCounters,
+    ) -> Result<()> {
         let AgentControllerPoolTotalSlots {
             slots_processing,
             slots_total,
         } = self.agent_controller_pool.total_slots();
         let requests_buffered = self.buffered_request_manager.buffered_request_counter.get();
+        let metrics_registry = &self.agent_controller_pool.metrics_registry;
+        let TokenUsage {
+            completion_tokens,
+            prompt_tokens,
+        } = metrics_registry.token_usage_counter.get();
 
         client.gauge("slots_processing", slots_processing as u64)?;
         client.gauge("slots_total", slots_total as u64)?;
         client.gauge("requests_buffered", requests_buffered as u64)?;
+        client.count(
+            "prompt_tokens_total",
+            reported_counters.count_delta("prompt_tokens_total", prompt_tokens as u64),
+        )?;
+        client.count(
+            "completion_tokens_total",
+            reported_counters.count_delta("completion_tokens_total", completion_tokens as u64),
+        )?;
+
+        for request_outcome in RequestOutcome::ALL {
+            let requests_finished = metrics_registry.requests_finished(request_outcome) as u64;
+
+            client
+                .count_with_tags(
+                    "requests_finished_total",
+                    reported_counters.count_delta(
+                        &format!("requests_finished_total:{}", request_outcome.label()),
+                        requests_finished,
+                    ),
+                )
+                .with_tag("outcome", request_outcome.label())
+                .try_send()?;
+        }
+
+        Self::report_histogram(
+            client,
+            reported_counters,
+            "time_to_first_token_seconds",
+            &metrics_registry.time_to_first_token_seconds,
+        )?;
+        Self::report_histogram(
+            client,
+            reported_counters,
+            "request_duration_seconds",
+            &metrics_registry.request_duration_seconds,
+        )?;
+        Self::report_histogram(
+            client,
+            reported_counters,
+            "generated_tokens_per_second",
+            &metrics_registry.generated_tokens_per_second,
+        )?;
+        Self::report_histogram(
+            client,
+            reported_counters,
+            "buffer_wait_seconds",
+            &metrics_registry.buffer_wait_seconds,
+        )?;
+        self.report_agent_slots(client)?;
         client.flush()?;
 
         Ok(())
@@ -57,6 +173,7 @@ impl Service for StatsdService {
             .with_error_handler(|err| error!("Statsd error: {err}"))
             .build();
 
+        let mut reported_counters = ReportedCounters::default();
         let mut ticker = interval(self.configuration.statsd_reporting_interval);
 
         ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
@@ -65,7 +182,7 @@ impl Service for StatsdService {
             tokio::select! {
                 () = shutdown.cancelled() => break Ok(()),
                 _ = ticker.tick() => {
-                    if let Err(err) = self.report_metrics(&client) {
+                    if let Err(err) = self.report_metrics(&client, &mut reported_counters) {
                         error!("Failed to report metrics: {err}");
                     }
                 }
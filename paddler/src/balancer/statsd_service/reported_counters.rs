@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+/// Values the cumulative metrics had when they were last reported. Statsd counters add up what
+/// they receive, so only what the metrics grew by since then is sent.
+#[derive(Default)]
+pub struct ReportedCounters {
+    counts: HashMap<String, u64>,
+    sums: HashMap<String, f64>,
+}
+
+impl ReportedCounters {
+    pub fn count_delta(&mut self, key: &str, count: u64) -> u64 {
+        let reported_count = self.counts.insert(key.to_owned(), count).unwrap_or(0);
+
+        count.saturating_sub(reported_count)
+    }
+
+    pub fn sum_delta(&mut self, key: &str, sum: f64) -> f64 {
+        let reported_sum = self.sums.insert(key.to_owned(), sum).unwrap_or(0.0);
+
+        (sum - reported_sum).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_growth_since_last_report() {
+        let mut reported_counters = ReportedCounters::default();
+
+        assert_eq!(reported_counters.count_delta("requests", 5), 5);
+        assert_eq!(reported_counters.count_delta("requests", 8), 3);
+        assert_eq!(reported_counters.count_delta("requests", 8), 0);
+        assert_eq!(reported_counters.count_delta("tokens", 2), 2);
+    }
+
+    #[test]
+    fn reports_growth_of_sums() {
+        let mut reported_counters = ReportedCounters::default();
+
+        assert!((reported_counters.sum_delta("duration", 1.5) - 1.5).abs() < f64::EPSILON);
+        assert!((reported_counters.sum_delta("duration", 4.0) - 2.5).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::AtomicUsize;
+
+use paddler_types::token_usage::TokenUsage;
+
+use crate::atomic_value::AtomicValue;
+
+/// Tokens consumed by all the requests finished since the balancer started
+pub struct TokenUsageCounter {
+    completion_tokens: AtomicValue<AtomicUsize>,
+    prompt_tokens: AtomicValue<AtomicUsize>,
+}
+
+impl TokenUsageCounter {
+    pub fn get(&self) -> TokenUsage {
+        TokenUsage {
+            completion_tokens: self.completion_tokens.get(),
+            prompt_tokens: self.prompt_tokens.get(),
+        }
+    }
+
+    pub fn record(&self, token_usage: &TokenUsage) {
+        self.completion_tokens
+            .increment_by(token_usage.completion_tokens);
+        self.prompt_tokens.increment_by(token_usage.prompt_tokens);
+    }
+}
+
+impl Default for TokenUsageCounter {
+    fn default() -> Self {
+        Self {
+            completion_tokens: AtomicValue::<AtomicUsize>::new(0),
+            prompt_tokens: AtomicValue::<AtomicUsize>::new(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_recorded_usage() {
+        let token_usage_counter = TokenUsageCounter::default();
+
+        token_usage_counter.record(&TokenUsage {
+            completion_tokens: 5,
+            prompt_tokens: 20,
+        });
+        token_usage_counter.record(&TokenUsage {
+            completion_tokens: 7,
+            prompt_tokens: 3,
+        });
+
+        assert_eq!(
+            token_usage_counter.get(),
+            TokenUsage {
+                completion_tokens: 12,
+                prompt_tokens: 23,
+            }
+        );
+    }
+}
@@ -20,23 +20,27 @@ use crate::balancer::buffered_request_manager::BufferedRequestManager;
 use crate::balancer::chunk_forwarding_session_controller::ChunkForwardingSessionController;
 use crate::balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::balancer::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::balancer::fair_share_tenant::FairShareTenant;
 use crate::balancer::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::balancer::manages_senders::ManagesSenders;
+use crate::balancer::provides_dispatch_criteria::ProvidesDispatchCriteria;
+use crate::balancer::reports_request_outcome::ReportsRequestOutcome;
 use crate::balancer::request_from_agent::request_from_agent;
 use crate::cancellation_token_stream_guard::CancellationTokenStreamGuard;
 use crate::controls_session::ControlsSession as _;
 
 pub fn unbounded_stream_from_agent<TParams, TTransformsOutgoingMessage>(
     buffered_request_manager: Arc<BufferedRequestManager>,
+    fair_share_tenant: Option<FairShareTenant>,
     inference_service_configuration: InferenceServiceConfiguration,
     params: TParams,
     transformer: TTransformsOutgoingMessage,
 ) -> impl Stream<Item = TransformResult>
 where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Debug + Into<AgentJsonRpcRequest> + ProvidesDispatchCriteria + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
-    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
+    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + ReportsRequestOutcome + StreamableResult,
     TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
 {
     let request_id: String = nanoid!();
@@ -53,6 +57,7 @@ where
             if let Err(err) = request_from_agent(
                 buffered_request_manager.clone(),
                 connection_close,
+                fair_share_tenant,
                 inference_service_configuration.clone(),
                 params,
                 request_id.clone(),
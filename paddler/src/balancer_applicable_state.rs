@@ -1,6 +1,19 @@
+use std::collections::BTreeMap;
+
 use crate::agent_desired_state::AgentDesiredState;
 
 #[derive(Clone, Debug)]
 pub struct BalancerApplicableState {
     pub agent_desired_state: AgentDesiredState,
+    pub model_pool_agent_desired_states: BTreeMap<String, AgentDesiredState>,
+}
+
+impl BalancerApplicableState {
+    /// Agents that are not assigned to any named pool serve the default model
+    #[must_use]
+    pub fn agent_desired_state_for(&self, model_pool: Option<&str>) -> &AgentDesiredState {
+        model_pool
+            .and_then(|model_pool| self.model_pool_agent_desired_states.get(model_pool))
+            .unwrap_or(&self.agent_desired_state)
+    }
 }
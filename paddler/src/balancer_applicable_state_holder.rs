@@ -66,6 +66,8 @@ impl SubscribesToUpdates for BalancerApplicableStateHolder {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use anyhow::Result;
     use paddler_types::agent_desired_model::AgentDesiredModel;
     use paddler_types::inference_parameters::InferenceParameters;
@@ -81,7 +83,9 @@ mod tests {
                 inference_parameters: InferenceParameters::default(),
                 model: AgentDesiredModel::None,
                 multimodal_projection: AgentDesiredModel::None,
+                prefetch_model: AgentDesiredModel::None,
             },
+            model_pool_agent_desired_states: BTreeMap::new(),
         }
     }
 
@@ -16,6 +16,11 @@ impl ConvertsToApplicableState for BalancerDesiredState {
     ) -> Result<Option<Self::ApplicableState>> {
         Ok(Some(BalancerApplicableState {
             agent_desired_state: self.to_agent_desired_state(),
+            model_pool_agent_desired_states: self
+                .model_pools
+                .iter()
+                .map(|(name, model_pool)| (name.clone(), model_pool.to_agent_desired_state()))
+                .collect(),
         }))
     }
 }
@@ -51,6 +51,8 @@ mod tests {
     use paddler_types::chat_template::ChatTemplate;
     use paddler_types::chat_template_message::ChatTemplateMessage;
     use paddler_types::chat_template_message_content::ChatTemplateMessageContent;
+    use paddler_types::chat_template_tool_call::ChatTemplateToolCall;
+    use paddler_types::tool_call::ToolCall;
 
     use crate::chat_template_renderer::ChatTemplateRenderer;
 
@@ -98,10 +100,14 @@ mod tests {
             ChatTemplateMessage {
                 content: ChatTemplateMessageContent::Text("hi".to_owned()),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             },
             ChatTemplateMessage {
                 content: ChatTemplateMessageContent::Text("hello".to_owned()),
                 role: "assistant".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             },
         ];
 
@@ -112,6 +118,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn renders_tool_calls_and_tool_results() -> Result<()> {
+        let template = ChatTemplate {
+            content: "{% for message in messages %}{% for tool_call in message.tool_calls %}{{ tool_call.id }}:{{ tool_call.function.name }}{{ tool_call.function.arguments | tojson }}\n{% endfor %}{% if message.tool_call_id %}{{ message.tool_call_id }}={{ message.content }}\n{% endif %}{% endfor %}".to_owned(),
+        };
+        let renderer = ChatTemplateRenderer::new(template)?;
+        let messages = vec![
+            ChatTemplateMessage {
+                content: ChatTemplateMessageContent::Text(String::new()),
+                role: "assistant".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![ChatTemplateToolCall::from(&ToolCall {
+                    arguments: serde_json::json!({"city": "Paris"}),
+                    id: "call_1".to_owned(),
+                    name: "get_weather".to_owned(),
+                })],
+            },
+            ChatTemplateMessage {
+                content: ChatTemplateMessageContent::Text("sunny".to_owned()),
+                role: "tool".to_owned(),
+                tool_call_id: Some("call_1".to_owned()),
+                tool_calls: vec![],
+            },
+        ];
+
+        let result = renderer.render(context! { messages => messages })?;
+
+        assert_eq!(
+            result,
+            "call_1:get_weather{\"city\":\"Paris\"}\ncall_1=sunny\n"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn add_generation_prompt_branch_changes_output() -> Result<()> {
         let template = ChatTemplate {
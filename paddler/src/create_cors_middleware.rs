@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use actix_cors::Cors;
 use actix_web::http::header;
+use actix_web::http::header::HeaderName;
 
 pub fn create_cors_middleware(allowed_hosts: &Arc<Vec<String>>) -> Cors {
     let mut cors = Cors::default()
@@ -10,6 +11,8 @@ pub fn create_cors_middleware(allowed_hosts: &Arc<Vec<String>>) -> Cors {
             header::ACCEPT,
             header::AUTHORIZATION,
             header::CONTENT_TYPE,
+            HeaderName::from_static("anthropic-version"),
+            HeaderName::from_static("x-api-key"),
         ])
         .max_age(3600);
 
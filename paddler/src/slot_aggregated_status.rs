@@ -17,14 +17,18 @@ use crate::produces_snapshot::ProducesSnapshot;
 use crate::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct SlotAggregatedStatus {
+    accepts_embeddings: AtomicValue<AtomicBool>,
     desired_slots_total: i32,
     download_current: AtomicValue<AtomicUsize>,
     download_filename: RwLock<Option<String>>,
     download_total: AtomicValue<AtomicUsize>,
     issues: DashSet<AgentIssue>,
     model_path: RwLock<Option<String>>,
+    prefix_cache_hits: AtomicValue<AtomicUsize>,
+    prefix_cache_misses: AtomicValue<AtomicUsize>,
     slots_processing: AtomicValue<AtomicI32>,
     slots_total: AtomicValue<AtomicI32>,
+    state_application_count: AtomicValue<AtomicUsize>,
     state_application_status_code: AtomicValue<AtomicI32>,
     update_tx: watch::Sender<()>,
     uses_chat_template_override: AtomicValue<AtomicBool>,
@@ -37,12 +41,16 @@ impl SlotAggregatedStatus {
         let (update_tx, _initial_rx) = watch::channel(());
 
         Self {
+            accepts_embeddings: AtomicValue::<AtomicBool>::new(false),
             desired_slots_total,
             download_current: AtomicValue::<AtomicUsize>::new(0),
             download_filename: RwLock::new(None),
             download_total: AtomicValue::<AtomicUsize>::new(0),
             issues: DashSet::new(),
             model_path: RwLock::new(None),
+            prefix_cache_hits: AtomicValue::<AtomicUsize>::new(0),
+            prefix_cache_misses: AtomicValue::<AtomicUsize>::new(0),
+            state_application_count: AtomicValue::<AtomicUsize>::new(0),
             state_application_status_code: AtomicValue::<AtomicI32>::new(
                 AgentStateApplicationStatus::Fresh as i32,
             ),
@@ -83,12 +91,30 @@ impl SlotAggregatedStatus {
         self.update_tx.send_replace(());
     }
 
+    pub fn increment_state_application_count(&self) {
+        self.state_application_count.increment_by(1);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
     pub fn increment_total_slots(&self) {
         self.slots_total.increment();
         self.version.increment();
         self.update_tx.send_replace(());
     }
 
+    pub fn register_prefix_cache_hit(&self) {
+        self.prefix_cache_hits.increment_by(1);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
+    pub fn register_prefix_cache_miss(&self) {
+        self.prefix_cache_misses.increment_by(1);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
     pub fn register_issue(&self, issue: AgentIssue) {
         if self.issues.insert(issue) {
             self.update_tx.send_replace(());
@@ -158,6 +184,12 @@ impl SlotAggregatedStatus {
         self.update_tx.send_replace(());
     }
 
+    pub fn set_accepts_embeddings(&self, accepts: bool) {
+        self.accepts_embeddings.set(accepts);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
     pub fn set_state_application_status(&self, status: AgentStateApplicationStatus) {
         self.state_application_status_code.set(status as i32);
         self.version.increment();
@@ -201,6 +233,7 @@ impl ProducesSnapshot for SlotAggregatedStatus {
     #[expect(clippy::expect_used, reason = "mutex lock poison is unrecoverable")]
     fn make_snapshot(&self) -> Result<Self::Snapshot> {
         Ok(SlotAggregatedStatusSnapshot {
+            accepts_embeddings: self.accepts_embeddings.get(),
             issues: self.issues.iter().map(|item| item.clone()).collect(),
             desired_slots_total: self.desired_slots_total,
             download_current: self.download_current.get(),
@@ -215,8 +248,11 @@ impl ProducesSnapshot for SlotAggregatedStatus {
                 .read()
                 .expect("Lock poisoned when getting model path")
                 .clone(),
+            prefix_cache_hits: self.prefix_cache_hits.get(),
+            prefix_cache_misses: self.prefix_cache_misses.get(),
             slots_processing: self.slots_processing.get(),
             slots_total: self.slots_total.get(),
+            state_application_count: self.state_application_count.get(),
             state_application_status: self.state_application_status_code.get().try_into()?,
             uses_chat_template_override: self.uses_chat_template_override.get(),
             version: self.version.get(),
@@ -372,6 +408,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn state_application_count_survives_reset() -> Result<()> {
+        let status = SlotAggregatedStatus::new(2);
+
+        status.increment_state_application_count();
+        status.reset();
+        status.increment_state_application_count();
+
+        assert_eq!(status.make_snapshot()?.state_application_count, 2);
+
+        Ok(())
+    }
+
     #[test]
     fn take_slot_and_release_slot() -> Result<()> {
         let status = SlotAggregatedStatus::new(2);
@@ -391,6 +440,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn prefix_cache_hits_and_misses_accumulate() -> Result<()> {
+        let status = SlotAggregatedStatus::new(2);
+
+        status.register_prefix_cache_miss();
+        status.register_prefix_cache_hit();
+        status.register_prefix_cache_hit();
+
+        let snapshot = status.make_snapshot()?;
+
+        assert_eq!(snapshot.prefix_cache_hits, 2);
+        assert_eq!(snapshot.prefix_cache_misses, 1);
+
+        Ok(())
+    }
+
     #[test]
     fn set_download_status_updates_all_fields() -> Result<()> {
         let status = SlotAggregatedStatus::new(2);
@@ -438,6 +503,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_accepts_embeddings() -> Result<()> {
+        let status = SlotAggregatedStatus::new(2);
+        let initial_version = status.make_snapshot()?.version;
+
+        assert!(!status.make_snapshot()?.accepts_embeddings);
+
+        status.set_accepts_embeddings(true);
+
+        let snapshot = status.make_snapshot()?;
+
+        assert!(snapshot.accepts_embeddings);
+        assert!(snapshot.version > initial_version);
+
+        Ok(())
+    }
+
     #[test]
     fn set_uses_chat_template_override() -> Result<()> {
         let status = SlotAggregatedStatus::new(2);
@@ -26,6 +26,12 @@ where
             _marker: PhantomData,
         }
     }
+
+    pub async fn send_serialized_response(&mut self, serialized_message: String) -> Result<()> {
+        self.session.text(serialized_message).await?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -34,10 +40,7 @@ where
     TResponse: RpcMessage + Sync + 'static,
 {
     async fn send_response(&mut self, message: TResponse) -> Result<()> {
-        let serialized_message = serde_json::to_string(&message)?;
-
-        self.session.text(serialized_message).await?;
-
-        Ok(())
+        self.send_serialized_response(serde_json::to_string(&message)?)
+            .await
     }
 }
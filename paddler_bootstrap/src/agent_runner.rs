@@ -1,5 +1,6 @@
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use paddler::slot_aggregated_status::SlotAggregatedStatus;
@@ -12,8 +13,12 @@ use crate::service_thread::ServiceThread;
 pub struct AgentRunnerParams {
     pub agent_name: Option<String>,
     pub cancellation_token: CancellationToken,
+    pub join_secret: Option<String>,
     pub management_address: String,
+    pub model_pool: Option<String>,
+    pub reconnect_grace_period: Duration,
     pub slots: i32,
+    pub weight: u32,
 }
 
 pub struct AgentRunner {
@@ -27,14 +32,26 @@ impl AgentRunner {
         AgentRunnerParams {
             agent_name,
             cancellation_token,
+            join_secret,
             management_address,
+            model_pool,
+            reconnect_grace_period,
             slots,
+            weight,
         }: AgentRunnerParams,
     ) -> Self {
         let BootstrappedAgentHandle {
             service_manager,
             slot_aggregated_status,
-        } = bootstrap_agent(agent_name, &management_address, slots);
+        } = bootstrap_agent(
+            agent_name,
+            join_secret,
+            &management_address,
+            model_pool,
+            reconnect_grace_period,
+            slots,
+            weight,
+        );
 
         let thread = ServiceThread::spawn(cancellation_token, move |task_shutdown| async move {
             service_manager.run_forever(task_shutdown).await
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::compatibility::anthropic_service::configuration::Configuration as AnthropicServiceConfiguration;
 use paddler::balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use paddler::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler::balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
@@ -13,6 +14,7 @@ use paddler::balancer::statsd_service::configuration::Configuration as StatsdSer
 use paddler::balancer::web_admin_panel_service::configuration::Configuration as WebAdminPanelServiceConfiguration;
 use paddler::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
@@ -22,9 +24,13 @@ use crate::bootstrapped_balancer_handle::bootstrap_balancer;
 use crate::service_thread::ServiceThread;
 
 pub struct BalancerRunnerParams {
+    pub anthropic_service_configuration: Option<AnthropicServiceConfiguration>,
+    pub buffered_batch_request_timeout: Duration,
     pub buffered_request_timeout: Duration,
+    pub dispatch_strategy: DispatchStrategy,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub management_service_configuration: ManagementServiceConfiguration,
+    pub max_buffered_batch_requests: i32,
     pub max_buffered_requests: i32,
     pub openai_service_configuration: Option<OpenAIServiceConfiguration>,
     pub cancellation_token: CancellationToken,
@@ -46,9 +52,13 @@ pub struct BalancerRunner {
 impl BalancerRunner {
     pub async fn start(
         BalancerRunnerParams {
+            anthropic_service_configuration,
+            buffered_batch_request_timeout,
             buffered_request_timeout,
+            dispatch_strategy,
             inference_service_configuration,
             management_service_configuration,
+            max_buffered_batch_requests,
             max_buffered_requests,
             openai_service_configuration,
             cancellation_token,
@@ -66,9 +76,13 @@ impl BalancerRunner {
             service_manager,
             state_database,
         } = bootstrap_balancer(BalancerBootstrapConfig {
+            anthropic_service_configuration,
+            buffered_batch_request_timeout,
             buffered_request_timeout,
+            dispatch_strategy,
             inference_service_configuration,
             management_service_configuration,
+            max_buffered_batch_requests,
             max_buffered_requests,
             openai_service_configuration,
             state_database_type,
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use nanoid::nanoid;
 use paddler::agent::continue_from_conversation_history_request::ContinueFromConversationHistoryRequest;
@@ -22,8 +23,12 @@ pub struct BootstrappedAgentHandle {
 
 pub fn bootstrap_agent(
     agent_name: Option<String>,
+    join_secret: Option<String>,
     management_address: &str,
+    model_pool: Option<String>,
+    reconnect_grace_period: Duration,
     slots: i32,
+    weight: u32,
 ) -> BootstrappedAgentHandle {
     let (agent_desired_state_tx, agent_desired_state_rx) =
         mpsc::unbounded_channel::<AgentDesiredState>();
@@ -60,9 +65,12 @@ pub fn bootstrap_agent(
         continue_from_conversation_history_request_tx,
         continue_from_raw_prompt_request_tx,
         generate_embedding_batch_request_tx,
+        join_secret,
         model_metadata_holder,
+        model_pool,
         name: agent_name,
         receive_stream_stopper_collection: Arc::default(),
+        reconnect_grace_period,
         slot_aggregated_status: slot_aggregated_status_manager
             .slot_aggregated_status
             .clone(),
@@ -71,6 +79,7 @@ pub fn bootstrap_agent(
             management_address,
             nanoid!()
         ),
+        weight,
     });
 
     service_manager.add_service(ReconciliationService {
@@ -78,6 +87,7 @@ pub fn bootstrap_agent(
         agent_desired_state: None,
         agent_desired_state_rx,
         is_converted_to_applicable_state: false,
+        model_prefetch: None,
         slot_aggregated_status: slot_aggregated_status_manager
             .slot_aggregated_status
             .clone(),
@@ -2,8 +2,12 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::api_key_registry::ApiKeyRegistry;
+use paddler::balancer::buffered_request_limits::BufferedRequestLimits;
 use paddler::balancer::buffered_request_manager::BufferedRequestManager;
 use paddler::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+use paddler::balancer::compatibility::anthropic_service::AnthropicService;
+use paddler::balancer::compatibility::anthropic_service::configuration::Configuration as AnthropicServiceConfiguration;
 use paddler::balancer::compatibility::openai_service::OpenAIService;
 use paddler::balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use paddler::balancer::embedding_sender_collection::EmbeddingSenderCollection;
@@ -27,12 +31,17 @@ use paddler::balancer::web_admin_panel_service::configuration::Configuration as
 use paddler::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use paddler::service_manager::ServiceManager;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio::sync::broadcast;
 
 pub struct BalancerBootstrapConfig {
+    pub anthropic_service_configuration: Option<AnthropicServiceConfiguration>,
+    pub buffered_batch_request_timeout: Duration,
     pub buffered_request_timeout: Duration,
+    pub dispatch_strategy: DispatchStrategy,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub management_service_configuration: ManagementServiceConfiguration,
+    pub max_buffered_batch_requests: i32,
     pub max_buffered_requests: i32,
     pub openai_service_configuration: Option<OpenAIServiceConfiguration>,
     pub state_database_type: StateDatabaseType,
@@ -52,9 +61,13 @@ pub struct BootstrappedBalancerHandle {
 
 pub async fn bootstrap_balancer(
     BalancerBootstrapConfig {
+        anthropic_service_configuration,
+        buffered_batch_request_timeout,
         buffered_request_timeout,
+        dispatch_strategy,
         inference_service_configuration,
         management_service_configuration,
+        max_buffered_batch_requests,
         max_buffered_requests,
         openai_service_configuration,
         state_database_type,
@@ -66,12 +79,21 @@ pub async fn bootstrap_balancer(
 ) -> anyhow::Result<BootstrappedBalancerHandle> {
     let (balancer_desired_state_tx, balancer_desired_state_rx) = broadcast::channel(100);
 
-    let agent_controller_pool = Arc::new(AgentControllerPool::default());
+    let agent_controller_pool = Arc::new(AgentControllerPool::new(dispatch_strategy));
+    let api_key_registry = Arc::new(ApiKeyRegistry::new(
+        management_service_configuration.bootstrap_api_key.clone(),
+    ));
     let balancer_applicable_state_holder = Arc::new(BalancerApplicableStateHolder::default());
     let buffered_request_manager = Arc::new(BufferedRequestManager::new(
         agent_controller_pool.clone(),
-        buffered_request_timeout,
-        max_buffered_requests,
+        BufferedRequestLimits {
+            buffered_request_timeout: buffered_batch_request_timeout,
+            max_buffered_requests: max_buffered_batch_requests,
+        },
+        BufferedRequestLimits {
+            buffered_request_timeout,
+            max_buffered_requests,
+        },
     ));
     let chat_template_override_sender_collection =
         Arc::new(ChatTemplateOverrideSenderCollection::default());
@@ -89,7 +111,10 @@ pub async fn bootstrap_balancer(
         )),
     };
 
+    api_key_registry.set_api_keys(state_database.read_api_keys().await?);
+
     service_manager.add_service(InferenceService {
+        api_key_registry: api_key_registry.clone(),
         balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
         buffered_request_manager: buffered_request_manager.clone(),
         configuration: inference_service_configuration.clone(),
@@ -99,6 +124,7 @@ pub async fn bootstrap_balancer(
 
     service_manager.add_service(ManagementService {
         agent_controller_pool: agent_controller_pool.clone(),
+        api_key_registry: api_key_registry.clone(),
         balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
         buffered_request_manager: buffered_request_manager.clone(),
         chat_template_override_sender_collection,
@@ -117,11 +143,25 @@ pub async fn bootstrap_balancer(
         balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
         balancer_desired_state: state_database.read_balancer_desired_state().await?,
         balancer_desired_state_rx,
+        default_dispatch_strategy: dispatch_strategy,
         is_converted_to_applicable_state: false,
+        model_rollout: None,
     });
 
+    if let Some(anthropic_configuration) = anthropic_service_configuration {
+        service_manager.add_service(AnthropicService {
+            anthropic_service_configuration: anthropic_configuration,
+            api_key_registry: api_key_registry.clone(),
+            buffered_request_manager: buffered_request_manager.clone(),
+            inference_service_configuration: inference_service_configuration.clone(),
+        });
+    }
+
     if let Some(openai_configuration) = openai_service_configuration {
         service_manager.add_service(OpenAIService {
+            agent_controller_pool: agent_controller_pool.clone(),
+            api_key_registry,
+            balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
             buffered_request_manager: buffered_request_manager.clone(),
             inference_service_configuration,
             openai_service_configuration: openai_configuration,
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::net::TcpListener;
 use std::time::Duration;
@@ -16,7 +17,9 @@ use paddler_bootstrap::balancer_runner::BalancerRunnerParams;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::chat_template::ChatTemplate;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use tempfile::NamedTempFile;
 use tokio::net::TcpStream;
 use tokio::sync::broadcast;
@@ -49,16 +52,25 @@ fn make_balancer_runner_params(
     cancellation_token: CancellationToken,
 ) -> BalancerRunnerParams {
     BalancerRunnerParams {
+        anthropic_service_configuration: None,
+        buffered_batch_request_timeout: Duration::from_secs(10),
         buffered_request_timeout: Duration::from_secs(10),
+        dispatch_strategy: DispatchStrategy::default(),
         inference_service_configuration: InferenceServiceConfiguration {
             addr: inference_addr,
             cors_allowed_hosts: vec![],
             inference_item_timeout: Duration::from_secs(30),
+            require_api_key: false,
         },
         management_service_configuration: ManagementServiceConfiguration {
             addr: management_addr,
+            agent_join_secret: None,
+            agent_reconnect_grace_period: Duration::from_secs(10),
+            bootstrap_api_key: None,
             cors_allowed_hosts: vec![],
+            require_api_key: false,
         },
+        max_buffered_batch_requests: 30,
         max_buffered_requests: 30,
         openai_service_configuration: None,
         cancellation_token,
@@ -78,7 +90,11 @@ fn make_agent_runner_params(
         agent_name: Some("test-agent".to_owned()),
         management_address: management_addr.to_string(),
         cancellation_token,
+        join_secret: None,
+        model_pool: None,
+        reconnect_grace_period: Duration::from_secs(10),
         slots: 1,
+        weight: 1,
     }
 }
 
@@ -166,9 +182,13 @@ async fn balancer_runner_preserves_persisted_desired_state() -> Result<()> {
         chat_template_override: Some(ChatTemplate {
             content: "persisted-chat-template".to_owned(),
         }),
+        dispatch_strategy: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::LocalToAgent("persisted-model".to_owned()),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         use_chat_template_override: true,
     };
 
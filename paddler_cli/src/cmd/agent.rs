@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
@@ -7,21 +9,40 @@ use paddler_bootstrap::agent_runner::AgentRunnerParams;
 use tokio_util::sync::CancellationToken;
 
 use super::handler::Handler;
+use super::value_parser::parse_duration;
 use super::value_parser::parse_socket_addr;
 
 #[derive(Parser)]
 pub struct Agent {
+    #[arg(long)]
+    /// Secret the agent presents when it registers with the management server
+    join_secret: Option<String>,
+
     #[arg(long, value_parser = parse_socket_addr)]
     /// Address of the management server that the agent will connect to
     management_addr: ResolvedSocketAddr,
 
+    #[arg(long)]
+    /// Name of the model pool the agent serves; the balancer picks one when not set
+    model_pool: Option<String>,
+
     #[arg(long)]
     /// Name of the agent (optional)
     name: Option<String>,
 
+    #[arg(long, default_value = "10000", value_parser = parse_duration)]
+    /// How long (in milliseconds) the agent keeps generating responses for in-flight requests
+    /// while it reconnects to the management server. Keep it in line with the balancer's
+    /// --agent-reconnect-grace-period
+    reconnect_grace_period: Duration,
+
     #[arg(long)]
     /// Number of parallel requests of any kind that the agent can handle at once
     slots: i32,
+
+    #[arg(long, default_value = "1")]
+    /// Relative capacity of the agent compared to others, used by the weighted dispatch strategy
+    weight: u32,
 }
 
 #[async_trait]
@@ -31,7 +52,11 @@ impl Handler for Agent {
             agent_name: self.name.clone(),
             management_address: self.management_addr.socket_addr.to_string(),
             cancellation_token: shutdown,
+            join_secret: self.join_secret.clone(),
+            model_pool: self.model_pool.clone(),
+            reconnect_grace_period: self.reconnect_grace_period,
             slots: self.slots,
+            weight: self.weight,
         });
 
         runner.wait_for_completion().await
@@ -3,6 +3,7 @@ use std::time::Duration;
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
+use paddler::balancer::compatibility::anthropic_service::configuration::Configuration as AnthropicServiceConfiguration;
 use paddler::balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use paddler::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler::balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
@@ -15,6 +16,7 @@ use paddler::balancer::web_admin_panel_service::template_data::TemplateData;
 use paddler::resolved_socket_addr::ResolvedSocketAddr;
 use paddler_bootstrap::balancer_runner::BalancerRunner;
 use paddler_bootstrap::balancer_runner::BalancerRunnerParams;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio_util::sync::CancellationToken;
 
 use super::handler::Handler;
@@ -23,15 +25,47 @@ use super::value_parser::parse_socket_addr;
 
 #[derive(Parser)]
 pub struct Balancer {
+    #[arg(long)]
+    /// Secret that agents have to present when they register with the management server
+    agent_join_secret: Option<String>,
+
+    #[arg(long, default_value = "10000", value_parser = parse_duration)]
+    /// How long (in milliseconds) the balancer holds the requests of a disconnected agent, waiting
+    /// for it to reconnect, before it fails them. Keep it in line with the agents'
+    /// --reconnect-grace-period
+    agent_reconnect_grace_period: Duration,
+
+    #[arg(long, default_value = "60000", value_parser = parse_duration)]
+    /// How long a request with the batch priority can stay in the buffer before it is processed.
+    /// Batch requests get a free slot only when no interactive request is waiting for one
+    buffered_batch_request_timeout: Duration,
+
     #[arg(long, default_value = "10000", value_parser = parse_duration)]
-    /// Specifies how long a request can stay in the buffer before it is processed.
+    /// Specifies how long an interactive request can stay in the buffer before it is processed.
     /// If the request stays in the buffer longer than this time, it is rejected with the 504 error
     buffered_request_timeout: Duration,
 
+    #[arg(long, value_parser = parse_socket_addr)]
+    /// Address of the Anthropic-compatible API server (enabled only if this address is specified)
+    compat_anthropic_addr: Option<ResolvedSocketAddr>,
+
+    #[arg(long)]
+    /// Require an API key with the inference scope in the Anthropic-compatible API requests
+    compat_anthropic_require_api_key: bool,
+
     #[arg(long, value_parser = parse_socket_addr)]
     /// Address of the OpenAI-compatible API server (enabled only if this address is specified)
     compat_openai_addr: Option<ResolvedSocketAddr>,
 
+    #[arg(long)]
+    /// Require an API key with the inference scope in the OpenAI-compatible API requests
+    compat_openai_require_api_key: bool,
+
+    #[arg(long, default_value = "least-busy")]
+    /// How the balancer picks an agent for each request, unless the desired state overrides it.
+    /// Supported: least-busy, least-outstanding-tokens, power-of-two-choices, round-robin, weighted
+    dispatch_strategy: DispatchStrategy,
+
     #[arg(long, default_value = "127.0.0.1:8061", value_parser = parse_socket_addr)]
     /// Address of the inference server
     inference_addr: ResolvedSocketAddr,
@@ -47,10 +81,19 @@ pub struct Balancer {
     /// Allowed CORS host for the inference service (can be specified multiple times)
     inference_cors_allowed_hosts: Vec<String>,
 
+    #[arg(long)]
+    /// Require an API key with the inference scope in the inference service requests
+    inference_require_api_key: bool,
+
     #[arg(long, default_value = "127.0.0.1:8060", value_parser = parse_socket_addr)]
     /// This is where you can manage your Paddler setup and the agents connect to
     management_addr: ResolvedSocketAddr,
 
+    #[arg(long)]
+    /// Secret accepted with the management scope on top of the stored API keys, so that the first
+    /// keys can be created with --management-require-api-key. It is neither stored nor listed
+    management_bootstrap_api_key: Option<String>,
+
     #[arg(
         long = "management-cors-allowed-host",
         action = clap::ArgAction::Append
@@ -58,9 +101,19 @@ pub struct Balancer {
     /// Allowed CORS host for the management service (can be specified multiple times)
     management_cors_allowed_hosts: Vec<String>,
 
+    #[arg(long)]
+    /// Require an API key with the management scope in the management service requests.
+    /// Agents authenticate with the join secret instead
+    management_require_api_key: bool,
+
+    #[arg(long, default_value = "100")]
+    /// The maximum number of buffered requests with the batch priority.
+    /// If the buffer is full then new batch requests are rejected with the 503 error
+    max_buffered_batch_requests: i32,
+
     #[arg(long, default_value = "30")]
-    /// The maximum number of buffered requests.
-    /// If the buffer is full then new requests are rejected with the 503 error
+    /// The maximum number of buffered interactive requests.
+    /// If the buffer is full then new interactive requests are rejected with the 503 error
     max_buffered_requests: i32,
 
     #[arg(long, default_value = "memory://")]
@@ -112,20 +165,35 @@ impl Balancer {
 impl Handler for Balancer {
     async fn handle(&self, shutdown: CancellationToken) -> Result<()> {
         let mut runner = BalancerRunner::start(BalancerRunnerParams {
+            anthropic_service_configuration: self.compat_anthropic_addr.clone().map(
+                |compat_anthropic_addr| AnthropicServiceConfiguration {
+                    addr: compat_anthropic_addr.socket_addr,
+                    require_api_key: self.compat_anthropic_require_api_key,
+                },
+            ),
+            buffered_batch_request_timeout: self.buffered_batch_request_timeout,
             buffered_request_timeout: self.buffered_request_timeout,
+            dispatch_strategy: self.dispatch_strategy,
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: self.inference_addr.socket_addr,
                 cors_allowed_hosts: self.inference_cors_allowed_hosts.clone(),
                 inference_item_timeout: self.inference_item_timeout,
+                require_api_key: self.inference_require_api_key,
             },
             management_service_configuration: ManagementServiceConfiguration {
                 addr: self.management_addr.socket_addr,
+                agent_join_secret: self.agent_join_secret.clone(),
+                agent_reconnect_grace_period: self.agent_reconnect_grace_period,
+                bootstrap_api_key: self.management_bootstrap_api_key.clone(),
                 cors_allowed_hosts: self.management_cors_allowed_hosts.clone(),
+                require_api_key: self.management_require_api_key,
             },
+            max_buffered_batch_requests: self.max_buffered_batch_requests,
             max_buffered_requests: self.max_buffered_requests,
             openai_service_configuration: self.compat_openai_addr.clone().map(
                 |compat_openai_addr| OpenAIServiceConfiguration {
                     addr: compat_openai_addr.socket_addr,
+                    require_api_key: self.compat_openai_require_api_key,
                 },
             ),
             cancellation_token: shutdown,
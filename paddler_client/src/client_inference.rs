@@ -8,6 +8,7 @@ use paddler_types::jsonrpc::RequestEnvelope;
 use paddler_types::request_params::ContinueFromConversationHistoryParams;
 use paddler_types::request_params::ContinueFromRawPromptParams;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_params::RerankParams;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 use reqwest::Client;
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -20,6 +21,7 @@ use crate::inference_socket::pool::Pool;
 use crate::stream::ndjson::Ndjson;
 
 pub struct ClientInference<'client> {
+    api_key: Option<&'client str>,
     url: &'client Url,
     http_client: &'client Client,
     inference_socket_pool: OnceLock<Pool>,
@@ -29,11 +31,13 @@ pub struct ClientInference<'client> {
 impl<'client> ClientInference<'client> {
     #[must_use]
     pub const fn new(
+        api_key: Option<&'client str>,
         url: &'client Url,
         http_client: &'client Client,
         inference_socket_pool_size: usize,
     ) -> Self {
         Self {
+            api_key,
             url,
             http_client,
             inference_socket_pool: OnceLock::new(),
@@ -53,8 +57,13 @@ impl<'client> ClientInference<'client> {
     }
 
     fn get_inference_socket_pool(&self) -> &Pool {
-        self.inference_socket_pool
-            .get_or_init(|| Pool::new(self.url.clone(), self.inference_socket_pool_size))
+        self.inference_socket_pool.get_or_init(|| {
+            Pool::new(
+                self.api_key.map(ToOwned::to_owned),
+                self.url.clone(),
+                self.inference_socket_pool_size,
+            )
+        })
     }
 
     pub async fn continue_from_conversation_history(
@@ -152,4 +161,18 @@ impl<'client> ClientInference<'client> {
 
         Ok(Box::pin(stream))
     }
+
+    pub async fn rerank(&self, params: &RerankParams) -> Result<InferenceMessageStream> {
+        let response = self
+            .http_client
+            .post(format_api_url(self.url, "/api/v1/rerank")?)
+            .json(params)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let stream = Ndjson::<InferenceMessage>::from_response(response);
+
+        Ok(Box::pin(stream))
+    }
 }
@@ -1,10 +1,12 @@
 use futures_util::StreamExt;
 use paddler_types::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
 use paddler_types::agent_desired_state::AgentDesiredState;
+use paddler_types::api_key::ApiKey;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::buffered_request_manager_snapshot::BufferedRequestManagerSnapshot;
 use paddler_types::chat_template::ChatTemplate;
 use paddler_types::model_metadata::ModelMetadata;
+use paddler_types::redacted_api_key::RedactedApiKey;
 use reqwest::Client;
 use serde_json::from_str;
 use url::Url;
@@ -48,6 +50,28 @@ impl<'client> ClientManagement<'client> {
         Ok(response.json().await?)
     }
 
+    pub async fn get_api_keys(&self) -> Result<Vec<RedactedApiKey>> {
+        let response = self
+            .http_client
+            .get(format_api_url(self.url, "/api/v1/api_keys")?)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn put_api_keys(&self, api_keys: &[ApiKey]) -> Result<()> {
+        self.http_client
+            .put(format_api_url(self.url, "/api/v1/api_keys")?)
+            .json(api_keys)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
     pub async fn get_balancer_desired_state(&self) -> Result<BalancerDesiredState> {
         let response = self
             .http_client
@@ -10,6 +10,9 @@ pub enum Error {
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(#[from] reqwest::header::InvalidHeaderValue),
+
     #[error("URL parse error: {0}")]
     Url(#[from] url::ParseError),
 
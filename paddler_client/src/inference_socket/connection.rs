@@ -8,6 +8,9 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest as _;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
 use url::Url;
 
 use crate::error::Error;
@@ -25,9 +28,17 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub async fn connect(connection_url: Url) -> Result<Self> {
-        let ws_url = url(connection_url)?;
-        let (ws_stream, _) = connect_async(ws_url.as_str()).await?;
+    pub async fn connect(api_key: Option<&str>, connection_url: Url) -> Result<Self> {
+        let mut request = url(connection_url)?.as_str().into_client_request()?;
+
+        if let Some(api_key) = api_key {
+            request.headers_mut().insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {api_key}"))?,
+            );
+        }
+
+        let (ws_stream, _) = connect_async(request).await?;
         let (ws_write, ws_read) = ws_stream.split();
 
         let pending: PendingRequests = Arc::new(DashMap::new());
@@ -12,6 +12,7 @@ use crate::error::Result;
 use crate::inference_socket::connection::Connection;
 
 pub struct Pool {
+    api_key: Option<String>,
     url: Url,
     connections: Mutex<Vec<Option<Arc<Connection>>>>,
     capacity: usize,
@@ -19,8 +20,9 @@ pub struct Pool {
 }
 
 impl Pool {
-    pub fn new(url: Url, capacity: usize) -> Self {
+    pub fn new(api_key: Option<String>, url: Url, capacity: usize) -> Self {
         Self {
+            api_key,
             url,
             connections: Mutex::new((0..capacity).map(|_| None).collect()),
             capacity,
@@ -80,7 +82,8 @@ impl Pool {
         };
 
         if needs_connect {
-            let new_connection = Connection::connect(self.url.clone()).await?;
+            let new_connection =
+                Connection::connect(self.api_key.as_deref(), self.url.clone()).await?;
             let mut connections = self.connections.lock().await;
             connections[index] = Some(Arc::new(new_connection));
         }
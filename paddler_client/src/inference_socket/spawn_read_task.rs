@@ -22,6 +22,7 @@ fn is_terminal_message(message: &InferenceMessage) -> bool {
         InferenceMessage::Response(envelope) => match &envelope.response {
             Response::Embedding(result) => result.is_done(),
             Response::GeneratedToken(result) => result.is_done(),
+            Response::Rerank(result) => result.is_done(),
             Response::Timeout | Response::TooManyBufferedRequests => true,
         },
     }
@@ -9,6 +9,9 @@ mod inference_socket;
 mod stream;
 
 use reqwest::Client;
+use reqwest::header::AUTHORIZATION;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
 use url::Url;
 
 pub use agents_stream::AgentsStream;
@@ -20,6 +23,7 @@ pub use error::Result;
 pub use inference_message_stream::InferenceMessageStream;
 
 pub struct PaddlerClient {
+    api_key: Option<String>,
     inference_url: Url,
     management_url: Url,
     inference_socket_pool_size: usize,
@@ -30,6 +34,7 @@ impl PaddlerClient {
     #[must_use]
     pub fn new(inference_url: Url, management_url: Url, inference_socket_pool_size: usize) -> Self {
         Self {
+            api_key: None,
             inference_url,
             management_url,
             inference_socket_pool_size,
@@ -37,9 +42,27 @@ impl PaddlerClient {
         }
     }
 
+    /// Sends the key as a bearer token with every HTTP request and inference socket connection
+    pub fn with_api_key(self, api_key: String) -> Result<Self> {
+        let mut authorization = HeaderValue::from_str(&format!("Bearer {api_key}"))?;
+
+        authorization.set_sensitive(true);
+
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert(AUTHORIZATION, authorization);
+
+        Ok(Self {
+            api_key: Some(api_key),
+            http_client: Client::builder().default_headers(default_headers).build()?,
+            ..self
+        })
+    }
+
     #[must_use]
-    pub const fn inference(&self) -> ClientInference<'_> {
+    pub fn inference(&self) -> ClientInference<'_> {
         ClientInference::new(
+            self.api_key.as_deref(),
             &self.inference_url,
             &self.http_client,
             self.inference_socket_pool_size,
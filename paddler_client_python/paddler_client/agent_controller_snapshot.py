@@ -7,6 +7,7 @@ from paddler_client.agent_state_application_status import (
 
 
 class AgentControllerSnapshot(BaseModel):
+    accepts_embeddings: bool = False
     desired_slots_total: int
     download_current: int
     download_filename: str | None = None
@@ -14,7 +15,10 @@ class AgentControllerSnapshot(BaseModel):
     id: str
     issues: list[AgentIssue] = []
     model_path: str | None = None
+    model_pool: str | None = None
     name: str | None = None
+    prefix_cache_hits: int = 0
+    prefix_cache_misses: int = 0
     slots_processing: int
     slots_total: int
     state_application_status: AgentStateApplicationStatus
@@ -0,0 +1,12 @@
+from pydantic import BaseModel, Field
+
+from paddler_client.api_key_rate_limit import ApiKeyRateLimit
+from paddler_client.api_key_scope import ApiKeyScope
+
+
+class ApiKey(BaseModel):
+    fair_share_weight: int | None = None
+    name: str
+    rate_limit: ApiKeyRateLimit = Field(default_factory=ApiKeyRateLimit)
+    scopes: list[ApiKeyScope]
+    secret: str
@@ -0,0 +1,6 @@
+from pydantic import BaseModel
+
+
+class ApiKeyRateLimit(BaseModel):
+    requests_per_minute: int | None = None
+    tokens_per_minute: int | None = None
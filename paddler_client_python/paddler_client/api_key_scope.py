@@ -0,0 +1,6 @@
+from enum import StrEnum
+
+
+class ApiKeyScope(StrEnum):
+    INFERENCE = "Inference"
+    MANAGEMENT = "Management"
@@ -0,0 +1,5 @@
+def authorization_headers(api_key: str | None) -> dict[str, str]:
+    if api_key is None:
+        return {}
+
+    return {"Authorization": f"Bearer {api_key}"}
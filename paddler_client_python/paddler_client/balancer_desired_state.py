@@ -3,6 +3,8 @@ from pydantic import BaseModel, Field
 from paddler_client.agent_desired_model import AgentDesiredModel
 from paddler_client.chat_template import ChatTemplate
 from paddler_client.inference_parameters import InferenceParameters
+from paddler_client.model_pool_desired_state import ModelPoolDesiredState
+from paddler_client.rollout_strategy import RolloutStrategy
 
 
 class BalancerDesiredState(BaseModel):
@@ -11,7 +13,12 @@ class BalancerDesiredState(BaseModel):
         default_factory=InferenceParameters,
     )
     model: AgentDesiredModel = Field(default_factory=AgentDesiredModel.none)
+    model_pools: dict[str, ModelPoolDesiredState] = {}
     multimodal_projection: AgentDesiredModel = Field(
         default_factory=AgentDesiredModel.none,
     )
+    prefetch_model: AgentDesiredModel = Field(
+        default_factory=AgentDesiredModel.none,
+    )
+    rollout_strategy: RolloutStrategy = RolloutStrategy.ALL_AT_ONCE
     use_chat_template_override: bool = False
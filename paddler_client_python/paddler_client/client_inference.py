@@ -5,6 +5,7 @@ from typing import TYPE_CHECKING, Literal, Self
 
 import httpx
 
+from paddler_client.authorization_headers import authorization_headers
 from paddler_client.error import HttpError
 from paddler_client.inference_socket_pool import InferenceSocketPool
 from paddler_client.inference_socket_url import inference_socket_url
@@ -26,6 +27,7 @@ if TYPE_CHECKING:
     )
     from paddler_client.inference_message import InferenceMessage
     from paddler_client.inference_socket_connection import ResponseStream
+    from paddler_client.rerank_params import RerankParams
 
 InferenceRequestVariant = Literal[
     "ContinueFromConversationHistory",
@@ -39,14 +41,17 @@ class ClientInference:
         url: str,
         socket_pool_size: int = 4,
         http_client: httpx.AsyncClient | None = None,
+        api_key: str | None = None,
     ) -> None:
         self._url = url.rstrip("/")
         self._socket_pool_size = socket_pool_size
+        self._headers = authorization_headers(api_key)
         self._http_client = (
             http_client
             if http_client is not None
             else httpx.AsyncClient(timeout=httpx.Timeout(timeout=300.0))
         )
+        self._http_client.headers.update(self._headers)
         self._socket_pool: InferenceSocketPool | None = None
 
     def _get_socket_pool(self) -> InferenceSocketPool:
@@ -55,6 +60,7 @@ class ClientInference:
             self._socket_pool = InferenceSocketPool(
                 url=ws_url,
                 pool_size=self._socket_pool_size,
+                headers=self._headers,
             )
 
         return self._socket_pool
@@ -106,6 +112,16 @@ class ClientInference:
         ):
             yield message
 
+    async def rerank(
+        self,
+        params: RerankParams,
+    ) -> AsyncIterator[InferenceMessage]:
+        async for message in self._stream_ndjson_post(
+            "/api/v1/rerank",
+            params,
+        ):
+            yield message
+
     async def close(self) -> None:
         if self._socket_pool is not None:
             await self._socket_pool.close()
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import json
 from typing import TYPE_CHECKING, Self
 
 import httpx
@@ -7,6 +8,8 @@ import httpx
 from paddler_client.agent_controller_pool_snapshot import (
     AgentControllerPoolSnapshot,
 )
+from paddler_client.api_key import ApiKey
+from paddler_client.authorization_headers import authorization_headers
 from paddler_client.balancer_desired_state import BalancerDesiredState
 from paddler_client.buffered_request_manager_snapshot import (
     BufferedRequestManagerSnapshot,
@@ -14,6 +17,7 @@ from paddler_client.buffered_request_manager_snapshot import (
 from paddler_client.chat_template import ChatTemplate
 from paddler_client.error import HttpError
 from paddler_client.model_metadata import ModelMetadata
+from paddler_client.redacted_api_key import RedactedApiKey
 from paddler_client.stream_sse import stream_sse
 
 if TYPE_CHECKING:
@@ -25,11 +29,13 @@ class ClientManagement:
         self,
         url: str,
         http_client: httpx.AsyncClient | None = None,
+        api_key: str | None = None,
     ) -> None:
         self._url = url.rstrip("/")
         self._http_client = (
             http_client if http_client is not None else httpx.AsyncClient()
         )
+        self._http_client.headers.update(authorization_headers(api_key))
 
     async def get_health(self) -> str:
         response = await self._http_client.get(f"{self._url}/health")
@@ -60,6 +66,30 @@ class ClientManagement:
             async for data in stream_sse(response):
                 yield AgentControllerPoolSnapshot.model_validate_json(data)
 
+    async def get_api_keys(self) -> list[RedactedApiKey]:
+        response = await self._http_client.get(
+            f"{self._url}/api/v1/api_keys",
+        )
+
+        if not response.is_success:
+            raise HttpError(response.status_code, response.text)
+
+        return [
+            RedactedApiKey.model_validate(api_key) for api_key in response.json()
+        ]
+
+    async def put_api_keys(self, api_keys: list[ApiKey]) -> None:
+        response = await self._http_client.put(
+            f"{self._url}/api/v1/api_keys",
+            content=json.dumps(
+                [api_key.model_dump(mode="json") for api_key in api_keys],
+            ),
+            headers={"Content-Type": "application/json"},
+        )
+
+        if not response.is_success:
+            raise HttpError(response.status_code, response.text)
+
     async def get_balancer_desired_state(self) -> BalancerDesiredState:
         response = await self._http_client.get(
             f"{self._url}/api/v1/balancer_desired_state",
@@ -2,7 +2,10 @@ from pydantic import BaseModel
 
 from paddler_client.conversation_message import ConversationMessage
 from paddler_client.grammar_constraint import GrammarConstraint
+from paddler_client.request_priority import RequestPriority
+from paddler_client.sampling_parameters import SamplingParameters
 from paddler_client.tool import Tool
+from paddler_client.truncation_policy import TruncationPolicy
 
 
 class ContinueFromConversationHistoryParams(BaseModel):
@@ -10,5 +13,12 @@ class ContinueFromConversationHistoryParams(BaseModel):
     conversation_history: list[ConversationMessage]
     enable_thinking: bool
     grammar: GrammarConstraint | None = None
+    logprobs: int | None = None
     max_tokens: int
+    model: str | None = None
+    priority: RequestPriority = RequestPriority.INTERACTIVE
+    sampling: SamplingParameters | None = None
+    session_id: str | None = None
+    stop: list[str] = []
     tools: list[Tool] = []
+    truncation: TruncationPolicy | None = None
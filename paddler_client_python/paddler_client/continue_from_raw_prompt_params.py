@@ -1,9 +1,18 @@
 from pydantic import BaseModel
 
 from paddler_client.grammar_constraint import GrammarConstraint
+from paddler_client.request_priority import RequestPriority
+from paddler_client.sampling_parameters import SamplingParameters
+from paddler_client.truncation_policy import TruncationPolicy
 
 
 class ContinueFromRawPromptParams(BaseModel):
     grammar: GrammarConstraint | None = None
+    logprobs: int | None = None
     max_tokens: int
+    model: str | None = None
+    priority: RequestPriority = RequestPriority.INTERACTIVE
     raw_prompt: str
+    sampling: SamplingParameters | None = None
+    stop: list[str] = []
+    truncation: TruncationPolicy | None = None
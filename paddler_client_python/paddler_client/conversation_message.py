@@ -3,6 +3,7 @@ from pydantic import BaseModel
 from paddler_client.conversation_message_content_part import (
     ConversationMessageContentPart,
 )
+from paddler_client.tool_call import ToolCall
 
 ConversationMessageContent = str | list[ConversationMessageContentPart]
 
@@ -10,3 +11,5 @@ ConversationMessageContent = str | list[ConversationMessageContentPart]
 class ConversationMessage(BaseModel):
     content: ConversationMessageContent
     role: str
+    tool_call_id: str | None = None
+    tool_calls: list[ToolCall] | None = None
@@ -0,0 +1,9 @@
+from pydantic import BaseModel
+
+
+class DryParameters(BaseModel):
+    allowed_length: int
+    base: float
+    multiplier: float
+    penalty_last_n: int
+    sequence_breakers: list[str]
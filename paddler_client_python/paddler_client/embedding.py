@@ -10,6 +10,7 @@ class Embedding(BaseModel):
     model_config = ConfigDict(frozen=True)
 
     embedding: list[float]
+    input_tokens: int = 0
     normalization_method: EmbeddingNormalizationMethod
     pooling_type: PoolingType
     source_document_id: str
@@ -4,3 +4,4 @@ from pydantic import BaseModel
 class EmbeddingInputDocument(BaseModel):
     content: str
     id: str
+    tokens: list[int] | None = None
@@ -4,8 +4,12 @@ from paddler_client.embedding_input_document import EmbeddingInputDocument
 from paddler_client.embedding_normalization_method import (
     EmbeddingNormalizationMethod,
 )
+from paddler_client.request_priority import RequestPriority
 
 
 class GenerateEmbeddingBatchParams(BaseModel):
     input_batch: list[EmbeddingInputDocument]
+    model: str | None = None
     normalization_method: EmbeddingNormalizationMethod
+    priority: RequestPriority = RequestPriority.INTERACTIVE
+    rerank_query: str | None = None
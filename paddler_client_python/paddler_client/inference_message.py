@@ -6,10 +6,13 @@ from enum import StrEnum
 from typing import Any
 
 from paddler_client.embedding import Embedding
+from paddler_client.rerank_score import RerankScore
+from paddler_client.token_logprobs import TokenLogprobs
 
 
 class InferenceMessageKind(StrEnum):
     CHAT_TEMPLATE_ERROR = "chat_template_error"
+    CONTEXT_OVERFLOW = "context_overflow"
     DONE = "done"
     EMBEDDING = "embedding"
     EMBEDDING_DONE = "embedding_done"
@@ -19,12 +22,21 @@ class InferenceMessageKind(StrEnum):
     GRAMMAR_REJECTED_MODEL_OUTPUT = "grammar_rejected_model_output"
     GRAMMAR_SYNTAX_ERROR = "grammar_syntax_error"
     IMAGE_DECODING_FAILED = "image_decoding_failed"
+    INVALID_SAMPLING_PARAMETERS = "invalid_sampling_parameters"
     MULTIMODAL_NOT_SUPPORTED = "multimodal_not_supported"
+    REASONING_TOKEN = "reasoning_token"
+    RERANK_DONE = "rerank_done"
+    RERANK_ERROR = "rerank_error"
+    RERANK_SCORE = "rerank_score"
     SAMPLER_ERROR = "sampler_error"
+    SEED = "seed"
     SERVER_ERROR = "server_error"
     TIMEOUT = "timeout"
     TOKEN = "token"
+    TOKEN_LOGPROBS = "token_logprobs"
     TOO_MANY_BUFFERED_REQUESTS = "too_many_buffered_requests"
+    TOOL_CALL = "tool_call"
+    USAGE = "usage"
 
 
 @dataclass(frozen=True)
@@ -32,9 +44,21 @@ class InferenceMessage:
     request_id: str
     kind: InferenceMessageKind
     token: str | None = None
+    token_logprobs: TokenLogprobs | None = None
     embedding_data: Embedding | None = None
+    rerank_score: RerankScore | None = None
     error_message: str | None = None
     error_code: int | None = None
+    finish_reason: str | None = None
+    stop_sequence: str | None = None
+    context_size: int | None = None
+    max_tokens: int | None = None
+    prompt_tokens: int | None = None
+    completion_tokens: int | None = None
+    seed: int | None = None
+    tool_call_id: str | None = None
+    tool_call_name: str | None = None
+    tool_call_arguments: Any = None
 
     @property
     def is_token(self) -> bool:
@@ -47,8 +71,14 @@ class InferenceMessage:
     @property
     def is_terminal(self) -> bool:
         return self.kind not in (
+            InferenceMessageKind.REASONING_TOKEN,
             InferenceMessageKind.TOKEN,
+            InferenceMessageKind.TOKEN_LOGPROBS,
             InferenceMessageKind.EMBEDDING,
+            InferenceMessageKind.RERANK_SCORE,
+            InferenceMessageKind.SEED,
+            InferenceMessageKind.TOOL_CALL,
+            InferenceMessageKind.USAGE,
         )
 
 
@@ -122,6 +152,12 @@ def _parse_response(
             response["Embedding"],
         )
 
+    if "Rerank" in response:
+        return _parse_rerank_result(
+            request_id,
+            response["Rerank"],
+        )
+
     msg = f"Unknown response: {response}"
     raise ValueError(msg)
 
@@ -135,6 +171,7 @@ _GENERATED_TOKEN_ERROR_KINDS: dict[str, InferenceMessageKind] = {
     "GrammarRejectedModelOutput": InferenceMessageKind.GRAMMAR_REJECTED_MODEL_OUTPUT,
     "GrammarSyntaxError": InferenceMessageKind.GRAMMAR_SYNTAX_ERROR,
     "ImageDecodingFailed": InferenceMessageKind.IMAGE_DECODING_FAILED,
+    "InvalidSamplingParameters": InferenceMessageKind.INVALID_SAMPLING_PARAMETERS,
     "MultimodalNotSupported": InferenceMessageKind.MULTIMODAL_NOT_SUPPORTED,
     "SamplerError": InferenceMessageKind.SAMPLER_ERROR,
 }
@@ -144,13 +181,27 @@ def _parse_generated_token_result(
     request_id: str,
     data: str | dict[str, Any],
 ) -> InferenceMessage:
-    if data == "Done":
-        return InferenceMessage(
-            request_id=request_id,
-            kind=InferenceMessageKind.DONE,
-        )
-
     if isinstance(data, dict):
+        if "ContextOverflow" in data:
+            return _parse_context_overflow(request_id, data["ContextOverflow"])
+
+        if "Done" in data:
+            return _parse_done(request_id, data["Done"])
+
+        if "ReasoningToken" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.REASONING_TOKEN,
+                token=data["ReasoningToken"],
+            )
+
+        if "Seed" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.SEED,
+                seed=data["Seed"],
+            )
+
         if "Token" in data:
             return InferenceMessage(
                 request_id=request_id,
@@ -158,6 +209,32 @@ def _parse_generated_token_result(
                 token=data["Token"],
             )
 
+        if "TokenLogprobs" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.TOKEN_LOGPROBS,
+                token_logprobs=TokenLogprobs.model_validate(
+                    data["TokenLogprobs"]
+                ),
+            )
+
+        if "ToolCall" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.TOOL_CALL,
+                tool_call_id=data["ToolCall"]["id"],
+                tool_call_name=data["ToolCall"]["name"],
+                tool_call_arguments=data["ToolCall"]["arguments"],
+            )
+
+        if "Usage" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.USAGE,
+                prompt_tokens=data["Usage"]["prompt_tokens"],
+                completion_tokens=data["Usage"]["completion_tokens"],
+            )
+
         for key, kind in _GENERATED_TOKEN_ERROR_KINDS.items():
             if key in data:
                 return InferenceMessage(
@@ -170,6 +247,50 @@ def _parse_generated_token_result(
     raise ValueError(msg)
 
 
+def _parse_context_overflow(
+    request_id: str,
+    context_overflow: dict[str, Any],
+) -> InferenceMessage:
+    context_size = context_overflow["context_size"]
+    max_tokens = context_overflow["max_tokens"]
+    prompt_tokens = context_overflow["prompt_tokens"]
+
+    return InferenceMessage(
+        request_id=request_id,
+        kind=InferenceMessageKind.CONTEXT_OVERFLOW,
+        error_message=(
+            f"prompt of {prompt_tokens} tokens and up to {max_tokens} generated"
+            f" tokens do not fit in the context of {context_size} tokens"
+        ),
+        context_size=context_size,
+        max_tokens=max_tokens,
+        prompt_tokens=prompt_tokens,
+    )
+
+
+def _parse_done(
+    request_id: str,
+    finish_reason: str | dict[str, Any],
+) -> InferenceMessage:
+    if isinstance(finish_reason, dict) and "StopSequence" in finish_reason:
+        return InferenceMessage(
+            request_id=request_id,
+            kind=InferenceMessageKind.DONE,
+            finish_reason="StopSequence",
+            stop_sequence=finish_reason["StopSequence"],
+        )
+
+    if isinstance(finish_reason, str):
+        return InferenceMessage(
+            request_id=request_id,
+            kind=InferenceMessageKind.DONE,
+            finish_reason=finish_reason,
+        )
+
+    msg = f"Unknown FinishReason: {finish_reason}"
+    raise ValueError(msg)
+
+
 def _parse_embedding_result(
     request_id: str,
     data: str | dict[str, Any],
@@ -199,3 +320,32 @@ def _parse_embedding_result(
 
     msg = f"Unknown EmbeddingResult: {data}"
     raise ValueError(msg)
+
+
+def _parse_rerank_result(
+    request_id: str,
+    data: str | dict[str, Any],
+) -> InferenceMessage:
+    if data == "Done":
+        return InferenceMessage(
+            request_id=request_id,
+            kind=InferenceMessageKind.RERANK_DONE,
+        )
+
+    if isinstance(data, dict):
+        if "Score" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.RERANK_SCORE,
+                rerank_score=RerankScore.model_validate(data["Score"]),
+            )
+
+        if "Error" in data:
+            return InferenceMessage(
+                request_id=request_id,
+                kind=InferenceMessageKind.RERANK_ERROR,
+                error_message=data["Error"],
+            )
+
+    msg = f"Unknown RerankResult: {data}"
+    raise ValueError(msg)
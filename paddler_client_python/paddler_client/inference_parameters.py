@@ -1,19 +1,36 @@
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
+from paddler_client.dry_parameters import DryParameters
+from paddler_client.logit_bias import LogitBias
+from paddler_client.mirostat_parameters import MirostatParameters
 from paddler_client.pooling_type import PoolingType
+from paddler_client.reasoning_markers import ReasoningMarkers
+from paddler_client.sampler_type import DEFAULT_SAMPLER_ORDER, SamplerType
+from paddler_client.xtc_parameters import XtcParameters
 
 
 class InferenceParameters(BaseModel):
     batch_n_tokens: int = 512
     context_size: int = 8192
+    dry: DryParameters | None = None
     enable_embeddings: bool = False
     image_resize_to_fit: int = 1024
+    logit_bias: list[LogitBias] = Field(default_factory=list)
     min_p: float = 0.05
+    mirostat: MirostatParameters | None = None
     penalty_frequency: float = 0.0
     penalty_last_n: int = -1
     penalty_presence: float = 0.8
     penalty_repeat: float = 1.1
     pooling_type: PoolingType = PoolingType.LAST
+    reasoning_markers: ReasoningMarkers | None = None
+    sampler_order: list[SamplerType] = Field(
+        default_factory=lambda: list(DEFAULT_SAMPLER_ORDER),
+    )
+    seed: int | None = None
     temperature: float = 0.8
     top_k: int = 80
+    top_n_sigma: float | None = None
     top_p: float = 0.8
+    typical_p: float | None = None
+    xtc: XtcParameters | None = None
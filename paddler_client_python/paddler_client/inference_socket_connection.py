@@ -46,8 +46,9 @@ class ResponseStream:
 
 
 class InferenceSocketConnection:
-    def __init__(self, url: str) -> None:
+    def __init__(self, url: str, headers: dict[str, str] | None = None) -> None:
         self._url = url
+        self._headers = headers if headers is not None else {}
         self._ws: ClientConnection | None = None
         self._pending: dict[str, asyncio.Queue[InferenceMessage | Exception]] = {}
         self._write_queue: asyncio.Queue[str] = asyncio.Queue()
@@ -60,7 +61,7 @@ class InferenceSocketConnection:
         return self._connected
 
     async def connect(self) -> None:
-        self._ws = await connect(self._url)
+        self._ws = await connect(self._url, additional_headers=self._headers)
         self._connected = True
         self._read_task = asyncio.create_task(self._read_loop())
         self._write_task = asyncio.create_task(self._write_loop())
@@ -14,12 +14,18 @@ logger = logging.getLogger(__name__)
 
 
 class InferenceSocketPool:
-    def __init__(self, url: str, pool_size: int) -> None:
+    def __init__(
+        self,
+        url: str,
+        pool_size: int,
+        headers: dict[str, str] | None = None,
+    ) -> None:
         if pool_size < 1:
             msg = f"pool_size must be >= 1, got {pool_size}"
             raise ValueError(msg)
 
         self._url = url
+        self._headers = headers if headers is not None else {}
         self._pool_size = pool_size
         self._connections: list[InferenceSocketConnection | None] = [None] * pool_size
         self._next_idx = 0
@@ -68,7 +74,7 @@ class InferenceSocketPool:
         if connection is not None:
             await connection.close()
 
-        new_connection = InferenceSocketConnection(self._url)
+        new_connection = InferenceSocketConnection(self._url, self._headers)
         await new_connection.connect()
         self._connections[idx] = new_connection
 
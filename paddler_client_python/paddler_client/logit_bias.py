@@ -0,0 +1,6 @@
+from pydantic import BaseModel
+
+
+class LogitBias(BaseModel):
+    bias: float
+    token: int | str
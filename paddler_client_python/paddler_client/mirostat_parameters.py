@@ -0,0 +1,9 @@
+from pydantic import BaseModel
+
+from paddler_client.mirostat_version import MirostatVersion
+
+
+class MirostatParameters(BaseModel):
+    eta: float
+    tau: float
+    version: MirostatVersion
@@ -0,0 +1,6 @@
+from enum import StrEnum
+
+
+class MirostatVersion(StrEnum):
+    V1 = "V1"
+    V2 = "V2"
@@ -0,0 +1,6 @@
+from pydantic import BaseModel
+
+
+class ReasoningMarkers(BaseModel):
+    end: str
+    start: str
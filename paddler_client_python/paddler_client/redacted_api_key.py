@@ -0,0 +1,11 @@
+from pydantic import BaseModel
+
+from paddler_client.api_key_rate_limit import ApiKeyRateLimit
+from paddler_client.api_key_scope import ApiKeyScope
+
+
+class RedactedApiKey(BaseModel):
+    fair_share_weight: int | None = None
+    name: str
+    rate_limit: ApiKeyRateLimit
+    scopes: list[ApiKeyScope]
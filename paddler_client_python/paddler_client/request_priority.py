@@ -0,0 +1,6 @@
+from enum import StrEnum
+
+
+class RequestPriority(StrEnum):
+    BATCH = "Batch"
+    INTERACTIVE = "Interactive"
@@ -0,0 +1,6 @@
+from pydantic import BaseModel
+
+
+class RerankDocument(BaseModel):
+    content: str
+    id: str
@@ -0,0 +1,13 @@
+from pydantic import BaseModel
+
+from paddler_client.request_priority import RequestPriority
+from paddler_client.rerank_document import RerankDocument
+
+
+class RerankParams(BaseModel):
+    documents: list[RerankDocument]
+    model: str | None = None
+    priority: RequestPriority = RequestPriority.INTERACTIVE
+    query: str
+    sort_by_score: bool = False
+    top_n: int | None = None
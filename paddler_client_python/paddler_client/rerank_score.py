@@ -0,0 +1,9 @@
+from pydantic import BaseModel, ConfigDict
+
+
+class RerankScore(BaseModel):
+    model_config = ConfigDict(frozen=True)
+
+    document_id: str
+    input_tokens: int = 0
+    score: float
@@ -0,0 +1,6 @@
+from enum import StrEnum
+
+
+class RolloutStrategy(StrEnum):
+    ALL_AT_ONCE = "AllAtOnce"
+    AGENT_BY_AGENT = "AgentByAgent"
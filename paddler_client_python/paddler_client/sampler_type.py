@@ -0,0 +1,26 @@
+from enum import StrEnum
+
+
+class SamplerType(StrEnum):
+    DRY = "Dry"
+    MIN_P = "MinP"
+    PENALTIES = "Penalties"
+    TEMPERATURE = "Temperature"
+    TOP_K = "TopK"
+    TOP_N_SIGMA = "TopNSigma"
+    TOP_P = "TopP"
+    TYPICAL_P = "TypicalP"
+    XTC = "Xtc"
+
+
+DEFAULT_SAMPLER_ORDER = [
+    SamplerType.PENALTIES,
+    SamplerType.DRY,
+    SamplerType.TOP_N_SIGMA,
+    SamplerType.TOP_K,
+    SamplerType.TYPICAL_P,
+    SamplerType.TOP_P,
+    SamplerType.MIN_P,
+    SamplerType.XTC,
+    SamplerType.TEMPERATURE,
+]
@@ -0,0 +1,10 @@
+from pydantic import BaseModel, ConfigDict
+
+
+class TokenLogprob(BaseModel):
+    model_config = ConfigDict(frozen=True)
+
+    bytes: list[int]
+    id: int
+    logprob: float
+    token: str
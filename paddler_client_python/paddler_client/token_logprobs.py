@@ -0,0 +1,10 @@
+from pydantic import BaseModel, ConfigDict
+
+from paddler_client.token_logprob import TokenLogprob
+
+
+class TokenLogprobs(BaseModel):
+    model_config = ConfigDict(frozen=True)
+
+    sampled: TokenLogprob
+    top_logprobs: list[TokenLogprob]
@@ -0,0 +1,9 @@
+from typing import Any
+
+from pydantic import BaseModel
+
+
+class ToolCall(BaseModel):
+    arguments: Any
+    id: str
+    name: str
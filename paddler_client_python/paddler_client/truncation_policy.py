@@ -0,0 +1,7 @@
+from enum import StrEnum
+
+
+class TruncationPolicy(StrEnum):
+    DROP_OLDEST_MESSAGES = "DropOldestMessages"
+    MIDDLE_OUT = "MiddleOut"
+    REJECT = "Reject"
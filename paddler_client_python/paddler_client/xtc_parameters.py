@@ -0,0 +1,6 @@
+from pydantic import BaseModel
+
+
+class XtcParameters(BaseModel):
+    probability: float
+    threshold: float
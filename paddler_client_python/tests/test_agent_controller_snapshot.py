@@ -10,6 +10,7 @@ from paddler_client.agent_state_application_status import (
 def test_agent_controller_snapshot_deserialization() -> None:
     snapshot = AgentControllerSnapshot.model_validate(
         {
+            "accepts_embeddings": True,
             "desired_slots_total": 4,
             "download_current": 100,
             "download_filename": "model.gguf",
@@ -25,6 +26,7 @@ def test_agent_controller_snapshot_deserialization() -> None:
         }
     )
 
+    assert snapshot.accepts_embeddings
     assert snapshot.id == "agent-1"
     assert snapshot.download_filename == "model.gguf"
     assert len(snapshot.issues) == 1
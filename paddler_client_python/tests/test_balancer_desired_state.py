@@ -1,5 +1,7 @@
+from paddler_client.agent_desired_model import AgentDesiredModel
 from paddler_client.balancer_desired_state import BalancerDesiredState
 from paddler_client.chat_template import ChatTemplate
+from paddler_client.rollout_strategy import RolloutStrategy
 
 
 def test_balancer_desired_state_defaults() -> None:
@@ -21,3 +23,14 @@ def test_balancer_desired_state_with_chat_template() -> None:
 
     assert dumped["chat_template_override"] == {"content": "{{ messages }}"}
     assert dumped["use_chat_template_override"] is True
+
+
+def test_balancer_desired_state_with_staged_rollout() -> None:
+    state = BalancerDesiredState(
+        prefetch_model=AgentDesiredModel.local_to_agent("/models/next.gguf"),
+        rollout_strategy=RolloutStrategy.AGENT_BY_AGENT,
+    )
+    dumped = state.model_dump(mode="json")
+
+    assert dumped["prefetch_model"] == {"LocalToAgent": "/models/next.gguf"}
+    assert dumped["rollout_strategy"] == "AgentByAgent"
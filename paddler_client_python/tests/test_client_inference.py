@@ -38,7 +38,7 @@ def _make_ndjson_done_response(request_id: str) -> str:
         {
             "Response": {
                 "request_id": request_id,
-                "response": {"GeneratedToken": "Done"},
+                "response": {"GeneratedToken": {"Done": "Eos"}},
             }
         }
     )
@@ -3,6 +3,8 @@ import json
 import httpx
 import pytest
 
+from paddler_client.api_key import ApiKey
+from paddler_client.api_key_scope import ApiKeyScope
 from paddler_client.balancer_desired_state import BalancerDesiredState
 from paddler_client.client_management import ClientManagement
 from paddler_client.error import HttpError
@@ -10,6 +12,7 @@ from paddler_client.error import HttpError
 
 def _agent_snapshot_json() -> dict[str, object]:
     return {
+        "accepts_embeddings": False,
         "desired_slots_total": 4,
         "download_current": 0,
         "download_filename": None,
@@ -150,6 +153,71 @@ async def test_put_balancer_desired_state_sends_json() -> None:
         await client.close()
 
 
+async def test_put_api_keys_sends_bearer_token_and_json() -> None:
+    received_requests: list[httpx.Request] = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        received_requests.append(request)
+
+        return httpx.Response(204)
+
+    transport = httpx.MockTransport(handler)
+    client = ClientManagement(
+        url="http://test:8085",
+        http_client=httpx.AsyncClient(transport=transport),
+        api_key="admin-secret",
+    )
+
+    try:
+        await client.put_api_keys(
+            [
+                ApiKey(
+                    name="ci",
+                    scopes=[ApiKeyScope.INFERENCE],
+                    secret="ci-secret",
+                ),
+            ],
+        )
+        assert received_requests[0].headers["Authorization"] == "Bearer admin-secret"
+        body = json.loads(received_requests[0].content)
+        assert body[0]["scopes"] == ["Inference"]
+        assert body[0]["rate_limit"]["requests_per_minute"] is None
+    finally:
+        await client.close()
+
+
+async def test_get_api_keys_deserializes_keys_without_secrets() -> None:
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(
+            200,
+            json=[
+                {
+                    "fair_share_weight": None,
+                    "name": "ci",
+                    "rate_limit": {
+                        "requests_per_minute": 60,
+                        "tokens_per_minute": None,
+                    },
+                    "scopes": ["Inference"],
+                },
+            ],
+        )
+
+    transport = httpx.MockTransport(handler)
+    client = ClientManagement(
+        url="http://test:8085",
+        http_client=httpx.AsyncClient(transport=transport),
+    )
+
+    try:
+        api_keys = await client.get_api_keys()
+        assert api_keys[0].name == "ci"
+        assert api_keys[0].rate_limit.requests_per_minute == 60
+        assert not hasattr(api_keys[0], "secret")
+    finally:
+        await client.close()
+
+
 async def test_get_buffered_requests_deserializes() -> None:
     def handler(request: httpx.Request) -> httpx.Response:
         return httpx.Response(
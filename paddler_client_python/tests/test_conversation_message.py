@@ -4,11 +4,12 @@ from paddler_client.conversation_message_content_part import (
     TextContentPart,
 )
 from paddler_client.image_url import ImageUrl
+from paddler_client.tool_call import ToolCall
 
 
 def test_conversation_message_text_content() -> None:
     message = ConversationMessage(content="hello", role="user")
-    dumped = message.model_dump(mode="json")
+    dumped = message.model_dump(mode="json", exclude_none=True)
 
     assert dumped == {"content": "hello", "role": "user"}
 
@@ -37,3 +38,28 @@ def test_conversation_message_text_deserialization() -> None:
 
     assert message.content == "hi"
     assert message.role == "assistant"
+
+
+def test_conversation_message_with_tool_calls() -> None:
+    message = ConversationMessage(
+        content="",
+        role="assistant",
+        tool_calls=[
+            ToolCall(arguments={"city": "Paris"}, id="call_1", name="get_weather"),
+        ],
+    )
+    dumped = message.model_dump(mode="json", exclude_none=True)
+
+    assert dumped["tool_calls"] == [
+        {"arguments": {"city": "Paris"}, "id": "call_1", "name": "get_weather"},
+    ]
+    assert "tool_call_id" not in dumped
+
+
+def test_conversation_message_tool_result_deserialization() -> None:
+    message = ConversationMessage.model_validate(
+        {"content": "sunny", "role": "tool", "tool_call_id": "call_1"},
+    )
+
+    assert message.tool_call_id == "call_1"
+    assert message.tool_calls is None
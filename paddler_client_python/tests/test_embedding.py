@@ -6,6 +6,7 @@ def test_embedding_deserialization() -> None:
     embedding = Embedding.model_validate(
         {
             "embedding": [0.1, 0.2, 0.3],
+            "input_tokens": 4,
             "normalization_method": "L2",
             "pooling_type": "Mean",
             "source_document_id": "doc-1",
@@ -13,6 +14,7 @@ def test_embedding_deserialization() -> None:
     )
 
     assert embedding.embedding == [0.1, 0.2, 0.3]
+    assert embedding.input_tokens == 4
     assert embedding.normalization_method.variant == "L2"
     assert embedding.pooling_type == PoolingType.MEAN
     assert embedding.source_document_id == "doc-1"
@@ -5,4 +5,11 @@ def test_embedding_input_document_serialization() -> None:
     doc = EmbeddingInputDocument(content="hello world", id="d1")
     dumped = doc.model_dump(mode="json")
 
-    assert dumped == {"content": "hello world", "id": "d1"}
+    assert dumped == {"content": "hello world", "id": "d1", "tokens": None}
+
+
+def test_tokenized_embedding_input_document_serialization() -> None:
+    doc = EmbeddingInputDocument(content="", id="d1", tokens=[15339, 1917])
+    dumped = doc.model_dump(mode="json", exclude_none=True)
+
+    assert dumped == {"content": "", "id": "d1", "tokens": [15339, 1917]}
@@ -26,16 +26,135 @@ def test_parse_done_response() -> None:
     data = {
         "Response": {
             "request_id": "req-1",
-            "response": {"GeneratedToken": "Done"},
+            "response": {"GeneratedToken": {"Done": "Eos"}},
         }
     }
     message = parse_inference_client_message(data)
 
     assert message.kind == InferenceMessageKind.DONE
+    assert message.finish_reason == "Eos"
     assert message.is_done
     assert message.is_terminal
 
 
+def test_parse_done_on_stop_sequence() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {"GeneratedToken": {"Done": {"StopSequence": "###"}}},
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.is_done
+    assert message.finish_reason == "StopSequence"
+    assert message.stop_sequence == "###"
+
+
+def test_parse_usage() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {
+                    "Usage": {"completion_tokens": 5, "prompt_tokens": 12},
+                },
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.USAGE
+    assert message.prompt_tokens == 12
+    assert message.completion_tokens == 5
+    assert not message.is_terminal
+
+
+def test_parse_seed() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {"Seed": 42},
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.SEED
+    assert message.seed == 42
+    assert not message.is_terminal
+
+
+def test_parse_reasoning_token() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {"GeneratedToken": {"ReasoningToken": "Let me think."}},
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.REASONING_TOKEN
+    assert message.token == "Let me think."
+    assert not message.is_token
+    assert not message.is_terminal
+
+
+def test_parse_tool_call() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {
+                    "ToolCall": {
+                        "arguments": {"city": "Paris"},
+                        "id": "call_1",
+                        "name": "get_weather",
+                    },
+                },
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.TOOL_CALL
+    assert message.tool_call_id == "call_1"
+    assert message.tool_call_name == "get_weather"
+    assert message.tool_call_arguments == {"city": "Paris"}
+    assert not message.is_terminal
+
+
+def test_parse_token_logprobs() -> None:
+    token_logprob = {
+        "bytes": [104, 105],
+        "id": 42,
+        "logprob": -0.5,
+        "token": "hi",
+    }
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {
+                    "TokenLogprobs": {
+                        "sampled": token_logprob,
+                        "top_logprobs": [token_logprob],
+                    },
+                },
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.TOKEN_LOGPROBS
+    assert message.token_logprobs is not None
+    assert message.token_logprobs.sampled.token == "hi"
+    assert message.token_logprobs.top_logprobs[0].logprob == -0.5
+    assert not message.is_token
+    assert not message.is_terminal
+
+
 def test_parse_timeout() -> None:
     data = {
         "Response": {
@@ -91,6 +210,34 @@ def test_parse_chat_template_error() -> None:
     assert message.is_terminal
 
 
+def test_parse_context_overflow() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {
+                    "ContextOverflow": {
+                        "context_size": 4096,
+                        "max_tokens": 512,
+                        "prompt_tokens": 4000,
+                    },
+                },
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.CONTEXT_OVERFLOW
+    assert message.context_size == 4096
+    assert message.max_tokens == 512
+    assert message.prompt_tokens == 4000
+    assert message.error_message == (
+        "prompt of 4000 tokens and up to 512 generated tokens"
+        " do not fit in the context of 4096 tokens"
+    )
+    assert message.is_terminal
+
+
 def test_parse_grammar_incompatible_with_thinking() -> None:
     data = {
         "Response": {
@@ -255,9 +402,47 @@ def test_parse_embedding_error() -> None:
     assert message.is_terminal
 
 
+def test_parse_rerank_score() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "Rerank": {
+                    "Score": {
+                        "document_id": "doc-1",
+                        "input_tokens": 12,
+                        "score": 2.5,
+                    }
+                }
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.RERANK_SCORE
+    assert message.rerank_score is not None
+    assert message.rerank_score.document_id == "doc-1"
+    assert message.rerank_score.score == 2.5
+    assert not message.is_terminal
+
+
+def test_parse_rerank_done() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {"Rerank": "Done"},
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.RERANK_DONE
+    assert message.is_terminal
+
+
 def test_parse_json_string() -> None:
     json_str = (
-        '{"Response": {"request_id": "req-1", "response": {"GeneratedToken": "Done"}}}'
+        '{"Response": {"request_id": "req-1", '
+        '"response": {"GeneratedToken": {"Done": "Eos"}}}}'
     )
     message = parse_inference_client_message(json_str)
 
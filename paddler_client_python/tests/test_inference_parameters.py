@@ -1,5 +1,10 @@
 from paddler_client.inference_parameters import InferenceParameters
+from paddler_client.logit_bias import LogitBias
+from paddler_client.mirostat_parameters import MirostatParameters
+from paddler_client.mirostat_version import MirostatVersion
 from paddler_client.pooling_type import PoolingType
+from paddler_client.reasoning_markers import ReasoningMarkers
+from paddler_client.sampler_type import SamplerType
 
 
 def test_inference_parameters_defaults() -> None:
@@ -17,3 +22,49 @@ def test_inference_parameters_serialization() -> None:
 
     assert dumped["temperature"] == 0.5
     assert dumped["top_k"] == 40
+
+
+def test_inference_parameters_reasoning_markers() -> None:
+    params = InferenceParameters(
+        reasoning_markers=ReasoningMarkers(end="</think>", start="<think>"),
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert InferenceParameters().reasoning_markers is None
+    assert dumped["reasoning_markers"] == {"end": "</think>", "start": "<think>"}
+
+
+def test_inference_parameters_seed() -> None:
+    dumped = InferenceParameters(seed=42).model_dump(mode="json")
+
+    assert InferenceParameters().seed is None
+    assert dumped["seed"] == 42
+
+
+def test_inference_parameters_samplers_are_disabled_by_default() -> None:
+    params = InferenceParameters()
+
+    assert params.dry is None
+    assert params.logit_bias == []
+    assert params.mirostat is None
+    assert params.sampler_order[0] == SamplerType.PENALTIES
+    assert params.sampler_order[-1] == SamplerType.TEMPERATURE
+
+
+def test_inference_parameters_sampler_serialization() -> None:
+    params = InferenceParameters(
+        logit_bias=[
+            LogitBias(bias=-100.0, token=42),
+            LogitBias(bias=2.5, token=" hello"),
+        ],
+        mirostat=MirostatParameters(eta=0.1, tau=5.0, version=MirostatVersion.V2),
+        sampler_order=[SamplerType.TOP_K, SamplerType.TEMPERATURE],
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["logit_bias"] == [
+        {"bias": -100.0, "token": 42},
+        {"bias": 2.5, "token": " hello"},
+    ]
+    assert dumped["mirostat"] == {"eta": 0.1, "tau": 5.0, "version": "V2"}
+    assert dumped["sampler_order"] == ["TopK", "Temperature"]
@@ -16,6 +16,10 @@ from paddler_client.grammar_constraint import (
     GbnfGrammarConstraint,
     JsonSchemaGrammarConstraint,
 )
+from paddler_client.request_priority import RequestPriority
+from paddler_client.rerank_document import RerankDocument
+from paddler_client.rerank_params import RerankParams
+from paddler_client.truncation_policy import TruncationPolicy
 
 
 def test_continue_from_conversation_history_params_serialization() -> None:
@@ -63,6 +67,17 @@ def test_generate_embedding_batch_params_serialization() -> None:
     assert len(dumped["input_batch"]) == 1
     assert dumped["input_batch"][0]["id"] == "doc-1"
     assert dumped["normalization_method"] == "L2"
+    assert dumped["priority"] == "Interactive"
+
+
+def test_batch_priority_serialization() -> None:
+    params = ContinueFromRawPromptParams(
+        max_tokens=10,
+        priority=RequestPriority.BATCH,
+        raw_prompt="Summarize the report",
+    )
+
+    assert params.model_dump(mode="json")["priority"] == "Batch"
 
 
 def test_raw_prompt_params_with_gbnf_grammar() -> None:
@@ -80,6 +95,17 @@ def test_raw_prompt_params_with_gbnf_grammar() -> None:
     assert dumped["grammar"]["grammar"] == 'root ::= "yes" | "no"'
 
 
+def test_raw_prompt_params_with_logprobs() -> None:
+    params = ContinueFromRawPromptParams(
+        logprobs=5,
+        max_tokens=10,
+        raw_prompt="Once upon a time",
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["logprobs"] == 5
+
+
 def test_conversation_history_params_with_json_schema_grammar() -> None:
     params = ContinueFromConversationHistoryParams(
         add_generation_prompt=True,
@@ -96,3 +122,46 @@ def test_conversation_history_params_with_json_schema_grammar() -> None:
 
     assert dumped["grammar"]["type"] == "json_schema"
     assert dumped["grammar"]["schema"] == '{"type": "object"}'
+
+
+def test_conversation_history_params_with_truncation() -> None:
+    params = ContinueFromConversationHistoryParams(
+        add_generation_prompt=True,
+        conversation_history=[
+            ConversationMessage(content="hi", role="user"),
+        ],
+        enable_thinking=False,
+        max_tokens=50,
+        truncation=TruncationPolicy.DROP_OLDEST_MESSAGES,
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["truncation"] == "DropOldestMessages"
+
+
+def test_raw_prompt_params_with_truncation() -> None:
+    params = ContinueFromRawPromptParams(
+        max_tokens=10,
+        raw_prompt="Once upon a time",
+        truncation=TruncationPolicy.MIDDLE_OUT,
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["truncation"] == "MiddleOut"
+
+
+def test_rerank_params_serialization() -> None:
+    params = RerankParams(
+        documents=[
+            RerankDocument(content="Paris is in France", id="paris"),
+        ],
+        query="What is the capital of France?",
+        top_n=1,
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["documents"][0]["id"] == "paris"
+    assert dumped["query"] == "What is the capital of France?"
+    assert dumped["priority"] == "Interactive"
+    assert not dumped["sort_by_score"]
+    assert dumped["top_n"] == 1
@@ -24,7 +24,7 @@ def _make_done_line(request_id: str) -> str:
         {
             "Response": {
                 "request_id": request_id,
-                "response": {"GeneratedToken": "Done"},
+                "response": {"GeneratedToken": {"Done": "Eos"}},
             }
         }
     )
@@ -11,6 +11,7 @@ impl AgentRunningData {
     pub fn apply_status(&mut self, status: SlotAggregatedStatusSnapshot) {
         self.connected = true;
         self.snapshot = AgentControllerSnapshot {
+            accepts_embeddings: status.accepts_embeddings,
             desired_slots_total: status.desired_slots_total,
             download_current: status.download_current,
             download_filename: status.download_filename,
@@ -18,7 +19,10 @@ impl AgentRunningData {
             id: String::new(),
             issues: status.issues,
             model_path: status.model_path,
+            model_pool: None,
             name: self.snapshot.name.clone(),
+            prefix_cache_hits: status.prefix_cache_hits,
+            prefix_cache_misses: status.prefix_cache_misses,
             slots_processing: status.slots_processing,
             slots_total: status.slots_total,
             state_application_status: status.state_application_status,
@@ -36,6 +36,7 @@ use paddler_bootstrap::balancer_runner::BalancerRunner;
 use paddler_bootstrap::balancer_runner::BalancerRunnerParams;
 use paddler_bootstrap::shutdown_signal::wait_for_shutdown_signal;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
@@ -367,7 +368,11 @@ impl App {
                 agent_name,
                 management_address,
                 cancellation_token: cancel,
+                join_secret: None,
+                model_pool: None,
+                reconnect_grace_period: Duration::from_secs(10),
                 slots,
+                weight: 1,
             });
 
             let slot_aggregated_status = runner.slot_aggregated_status.clone();
@@ -464,16 +469,25 @@ impl App {
             });
 
         let params = BalancerRunnerParams {
+            anthropic_service_configuration: None,
+            buffered_batch_request_timeout: buffered_request_timeout,
             buffered_request_timeout,
+            dispatch_strategy: DispatchStrategy::default(),
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: inference_addr,
                 cors_allowed_hosts: vec![],
                 inference_item_timeout: Duration::from_secs(30),
+                require_api_key: false,
             },
             management_service_configuration: ManagementServiceConfiguration {
                 addr: management_addr,
+                agent_join_secret: None,
+                agent_reconnect_grace_period: Duration::from_secs(10),
+                bootstrap_api_key: None,
                 cors_allowed_hosts: vec![],
+                require_api_key: false,
             },
+            max_buffered_batch_requests: max_buffered_requests,
             max_buffered_requests,
             openai_service_configuration: None,
             cancellation_token: cancel,
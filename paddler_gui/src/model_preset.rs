@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::huggingface_model_reference::HuggingFaceModelReference;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ModelPreset {
@@ -53,9 +55,13 @@ impl ModelPreset {
 
         BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: self.inference_parameters.clone(),
             model: AgentDesiredModel::HuggingFace(self.model.clone()),
+            model_pools: BTreeMap::new(),
             multimodal_projection,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         }
     }
@@ -45,6 +45,7 @@ impl RunningBalancerSnapshot {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::collections::BTreeSet;
     use std::sync::Arc;
     use std::sync::RwLock;
@@ -55,6 +56,7 @@ mod tests {
     use anyhow::Result;
     use paddler::agent_desired_state::AgentDesiredState;
     use paddler::atomic_value::AtomicValue;
+    use paddler::balancer::agent_connection::AgentConnection;
     use paddler::balancer::agent_controller::AgentController;
     use paddler::balancer::agent_controller_pool::AgentControllerPool;
     use paddler::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
@@ -66,21 +68,29 @@ mod tests {
     use paddler_types::agent_desired_model::AgentDesiredModel;
     use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
     use paddler_types::inference_parameters::InferenceParameters;
+    use tokio::sync::Mutex;
     use tokio::sync::mpsc;
     use tokio_util::sync::CancellationToken;
 
     use super::*;
 
     fn make_agent_controller(id: &str, name: Option<&str>) -> Arc<AgentController> {
-        let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
+        let (agent_message_tx, agent_message_rx) = mpsc::unbounded_channel();
 
         Arc::new(AgentController {
+            accepts_embeddings: AtomicValue::<AtomicBool>::new(false),
+            agent_message_rx: Mutex::new(agent_message_rx),
             agent_message_tx,
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
             ),
+            connection: RwLock::new(AgentConnection {
+                close: CancellationToken::new(),
+                id: id.to_owned(),
+            }),
             connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(0),
+            desired_state: RwLock::new(None),
             download_current: AtomicValue::<AtomicUsize>::new(0),
             download_filename: RwLock::new(None),
             download_total: AtomicValue::<AtomicUsize>::new(0),
@@ -90,14 +100,21 @@ mod tests {
             issues: RwLock::new(BTreeSet::new()),
             model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
             model_path: RwLock::new(None),
+            model_pool: RwLock::new(None),
             name: name.map(str::to_owned),
             newest_update_version: AtomicValue::<AtomicI32>::new(0),
+            outstanding_tokens: AtomicValue::<AtomicI32>::new(0),
+            prefix_cache_hits: AtomicValue::<AtomicUsize>::new(0),
+            prefix_cache_misses: AtomicValue::<AtomicUsize>::new(0),
+            requested_model_pool: None,
             slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(0),
+            state_application_count: AtomicValue::<AtomicUsize>::new(0),
             state_application_status_code: AtomicValue::<AtomicI32>::new(
                 AgentStateApplicationStatus::Fresh as i32,
             ),
             uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            weight: 1,
         })
     }
 
@@ -108,7 +125,9 @@ mod tests {
                 inference_parameters: InferenceParameters::default(),
                 model: AgentDesiredModel::LocalToAgent("configured_model".to_owned()),
                 multimodal_projection: AgentDesiredModel::None,
+                prefetch_model: AgentDesiredModel::None,
             },
+            model_pool_agent_desired_states: BTreeMap::new(),
         }
     }
 
@@ -72,6 +72,7 @@ impl Screen<JoinBalancerForm> {
                 balancer_address: form_data.balancer_address,
                 connected: false,
                 snapshot: AgentControllerSnapshot {
+                    accepts_embeddings: false,
                     desired_slots_total: 0,
                     download_current: 0,
                     download_filename: None,
@@ -79,7 +80,10 @@ impl Screen<JoinBalancerForm> {
                     id: String::new(),
                     issues: BTreeSet::new(),
                     model_path: None,
+                    model_pool: None,
                     name,
+                    prefix_cache_hits: 0,
+                    prefix_cache_misses: 0,
                     slots_processing: 0,
                     slots_total: 0,
                     state_application_status: AgentStateApplicationStatus::Fresh,
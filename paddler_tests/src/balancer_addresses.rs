@@ -6,6 +6,7 @@ use anyhow::Result;
 use url::Url;
 
 pub struct BalancerAddresses {
+    pub compat_anthropic: SocketAddr,
     pub compat_openai: SocketAddr,
     pub inference: SocketAddr,
     pub management: SocketAddr,
@@ -17,6 +18,8 @@ impl BalancerAddresses {
             TcpListener::bind("127.0.0.1:0").context("failed to reserve inference service port")?;
         let management_listener = TcpListener::bind("127.0.0.1:0")
             .context("failed to reserve management service port")?;
+        let compat_anthropic_listener = TcpListener::bind("127.0.0.1:0")
+            .context("failed to reserve Anthropic-compat service port")?;
         let compat_openai_listener = TcpListener::bind("127.0.0.1:0")
             .context("failed to reserve OpenAI-compat service port")?;
 
@@ -26,6 +29,9 @@ impl BalancerAddresses {
         let management = management_listener
             .local_addr()
             .context("failed to read management listener local address")?;
+        let compat_anthropic = compat_anthropic_listener
+            .local_addr()
+            .context("failed to read Anthropic-compat listener local address")?;
         let compat_openai = compat_openai_listener
             .local_addr()
             .context("failed to read OpenAI-compat listener local address")?;
@@ -33,16 +39,22 @@ impl BalancerAddresses {
         drop((
             inference_listener,
             management_listener,
+            compat_anthropic_listener,
             compat_openai_listener,
         ));
 
         Ok(Self {
+            compat_anthropic,
             compat_openai,
             inference,
             management,
         })
     }
 
+    pub fn compat_anthropic_base_url(&self) -> Result<Url> {
+        Self::base_url_for(self.compat_anthropic)
+    }
+
     pub fn compat_openai_base_url(&self) -> Result<Url> {
         Self::base_url_for(self.compat_openai)
     }
@@ -38,6 +38,9 @@ pub async fn collect_embedding_results(
                         "unexpected generated-token response on an embedding stream"
                     ));
                 }
+                InferenceResponse::Rerank(_) => {
+                    return Err(anyhow!("unexpected rerank response on an embedding stream"));
+                }
                 InferenceResponse::Timeout => {
                     return Err(anyhow!("embedding request timed out on balancer"));
                 }
@@ -39,6 +39,11 @@ pub async fn collect_generated_tokens(
                         "unexpected embedding response on a token-generation stream"
                     ));
                 }
+                InferenceResponse::Rerank(_) => {
+                    return Err(anyhow!(
+                        "unexpected rerank response on a token-generation stream"
+                    ));
+                }
                 InferenceResponse::Timeout => {
                     return Err(anyhow!("inference request timed out on balancer"));
                 }
@@ -0,0 +1,63 @@
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::anyhow;
+use futures_util::StreamExt as _;
+use paddler_types::inference_client::Message as InferenceMessage;
+use paddler_types::inference_client::Response as InferenceResponse;
+use paddler_types::rerank_result::RerankResult;
+use paddler_types::rerank_score::RerankScore;
+
+use crate::collected_rerank_results::CollectedRerankResults;
+use crate::inference_message_stream::InferenceMessageStream;
+
+pub async fn collect_rerank_results(
+    mut stream: InferenceMessageStream,
+) -> Result<CollectedRerankResults> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut saw_done = false;
+    let mut scores: Vec<RerankScore> = Vec::new();
+
+    while let Some(item) = stream.next().await {
+        let message = item.context("rerank stream yielded an error")?;
+
+        match message {
+            InferenceMessage::Response(envelope) => match envelope.response {
+                InferenceResponse::Rerank(RerankResult::Done) => {
+                    saw_done = true;
+
+                    break;
+                }
+                InferenceResponse::Rerank(RerankResult::Error(message)) => {
+                    errors.push(message);
+                }
+                InferenceResponse::Rerank(RerankResult::Score(score)) => {
+                    scores.push(score);
+                }
+                InferenceResponse::Embedding(_) | InferenceResponse::GeneratedToken(_) => {
+                    return Err(anyhow!("unexpected response on a rerank stream"));
+                }
+                InferenceResponse::Timeout => {
+                    return Err(anyhow!("rerank request timed out on balancer"));
+                }
+                InferenceResponse::TooManyBufferedRequests => {
+                    return Err(anyhow!(
+                        "balancer rejected rerank request: too many buffered"
+                    ));
+                }
+            },
+            InferenceMessage::Error(error_envelope) => {
+                return Err(anyhow!(
+                    "rerank stream returned JSON-RPC error code {} ({})",
+                    error_envelope.error.code,
+                    error_envelope.error.description
+                ));
+            }
+        }
+    }
+
+    Ok(CollectedRerankResults {
+        errors,
+        saw_done,
+        scores,
+    })
+}
@@ -0,0 +1,7 @@
+use paddler_types::rerank_score::RerankScore;
+
+pub struct CollectedRerankResults {
+    pub errors: Vec<String>,
+    pub saw_done: bool,
+    pub scores: Vec<RerankScore>,
+}
@@ -4,11 +4,14 @@ use paddler_types::balancer_desired_state::BalancerDesiredState;
 
 pub struct InProcessClusterParams {
     pub agent_name: String,
+    pub agent_reconnect_grace_period: Duration,
+    pub buffered_batch_request_timeout: Duration,
     pub buffered_request_timeout: Duration,
     pub desired_state: BalancerDesiredState,
     pub inference_cors_allowed_hosts: Vec<String>,
     pub inference_item_timeout: Duration,
     pub management_cors_allowed_hosts: Vec<String>,
+    pub max_buffered_batch_requests: i32,
     pub max_buffered_requests: i32,
     pub slots_per_agent: i32,
     pub spawn_agent: bool,
@@ -19,11 +22,14 @@ impl Default for InProcessClusterParams {
     fn default() -> Self {
         Self {
             agent_name: "test-agent".to_owned(),
+            agent_reconnect_grace_period: Duration::from_secs(10),
+            buffered_batch_request_timeout: Duration::from_secs(10),
             buffered_request_timeout: Duration::from_secs(10),
             desired_state: BalancerDesiredState::default(),
             inference_cors_allowed_hosts: Vec::new(),
             inference_item_timeout: Duration::from_secs(30),
             management_cors_allowed_hosts: Vec::new(),
+            max_buffered_batch_requests: 10,
             max_buffered_requests: 10,
             slots_per_agent: 4,
             spawn_agent: true,
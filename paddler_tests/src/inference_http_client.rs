@@ -7,6 +7,7 @@ use paddler_types::inference_client::Message as InferenceMessage;
 use paddler_types::request_params::ContinueFromConversationHistoryParams;
 use paddler_types::request_params::ContinueFromRawPromptParams;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_params::RerankParams;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 use reqwest::Client;
 use url::Url;
@@ -51,6 +52,10 @@ impl InferenceHttpClient {
             .await
     }
 
+    pub async fn post_rerank(&self, params: &RerankParams) -> Result<InferenceMessageStream> {
+        self.post_streaming("api/v1/rerank", params).await
+    }
+
     async fn post_streaming<TBody>(
         &self,
         relative_path: &str,
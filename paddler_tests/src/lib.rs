@@ -8,8 +8,10 @@ pub mod cluster_handle;
 pub mod cluster_handle_params;
 pub mod collect_embedding_results;
 pub mod collect_generated_tokens;
+pub mod collect_rerank_results;
 pub mod collected_embedding_results;
 pub mod collected_generated_tokens;
+pub mod collected_rerank_results;
 pub mod current_test_device;
 pub mod in_process_cluster_params;
 pub mod inference_http_client;
@@ -19,6 +21,7 @@ pub mod make_agent_controller_without_remote_agent;
 pub mod model_card;
 pub mod paddler_command;
 pub mod parse_test_device_value;
+pub mod severable_tcp_proxy;
 pub mod spawn_agent_subprocess;
 pub mod spawn_agent_subprocess_params;
 pub mod start_in_process_cluster;
@@ -28,6 +31,7 @@ pub mod start_in_process_cluster_with_qwen3_5;
 pub mod start_in_process_cluster_with_smolvlm2;
 pub mod start_in_process_embedding_cluster;
 pub mod start_subprocess_cluster;
+pub mod start_subprocess_cluster_with_bge_reranker;
 pub mod start_subprocess_cluster_with_qwen2_5_vl;
 pub mod start_subprocess_cluster_with_qwen3;
 pub mod start_subprocess_cluster_with_qwen3_embedding;
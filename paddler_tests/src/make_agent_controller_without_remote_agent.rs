@@ -6,26 +6,35 @@ use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicUsize;
 
 use paddler::atomic_value::AtomicValue;
+use paddler::balancer::agent_connection::AgentConnection;
 use paddler::balancer::agent_controller::AgentController;
 use paddler::balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use paddler::balancer::embedding_sender_collection::EmbeddingSenderCollection;
 use paddler::balancer::generate_tokens_sender_collection::GenerateTokensSenderCollection;
 use paddler::balancer::model_metadata_sender_collection::ModelMetadataSenderCollection;
 use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
+use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 #[must_use]
 pub fn make_agent_controller_without_remote_agent(id: &str) -> AgentController {
-    let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
+    let (agent_message_tx, agent_message_rx) = mpsc::unbounded_channel();
 
     AgentController {
+        accepts_embeddings: AtomicValue::<AtomicBool>::new(false),
+        agent_message_rx: Mutex::new(agent_message_rx),
         agent_message_tx,
         chat_template_override_sender_collection: Arc::new(
             ChatTemplateOverrideSenderCollection::default(),
         ),
+        connection: RwLock::new(AgentConnection {
+            close: CancellationToken::new(),
+            id: id.to_owned(),
+        }),
         connection_close: CancellationToken::new(),
         desired_slots_total: AtomicValue::<AtomicI32>::new(0),
+        desired_state: RwLock::new(None),
         download_current: AtomicValue::<AtomicUsize>::new(0),
         download_filename: RwLock::new(None),
         download_total: AtomicValue::<AtomicUsize>::new(0),
@@ -35,13 +44,21 @@ pub fn make_agent_controller_without_remote_agent(id: &str) -> AgentController {
         issues: RwLock::new(BTreeSet::new()),
         model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
         model_path: RwLock::new(None),
+        model_pool: RwLock::new(None),
         name: None,
         newest_update_version: AtomicValue::<AtomicI32>::new(0),
+        outstanding_tokens: AtomicValue::<AtomicI32>::new(0),
+        prefix_cache_hits: AtomicValue::<AtomicUsize>::new(0),
+        prefix_cache_misses: AtomicValue::<AtomicUsize>::new(0),
+        requested_model_pool: None,
         slots_processing: AtomicValue::<AtomicI32>::new(0),
         slots_total: AtomicValue::<AtomicI32>::new(0),
+        state_application_count: AtomicValue::<AtomicUsize>::new(0),
         state_application_status_code: AtomicValue::<AtomicI32>::new(
             AgentStateApplicationStatus::Fresh as i32,
         ),
+        unsent_agent_message: Mutex::new(None),
         uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+        weight: 1,
     }
 }
@@ -0,0 +1,15 @@
+use paddler_types::huggingface_model_reference::HuggingFaceModelReference;
+
+use crate::model_card::ModelCard;
+
+#[must_use]
+pub fn bge_reranker_v2_m3() -> ModelCard {
+    ModelCard {
+        gpu_layer_count: 999,
+        reference: HuggingFaceModelReference {
+            filename: "bge-reranker-v2-m3-Q8_0.gguf".to_owned(),
+            repo_id: "gpustack/bge-reranker-v2-m3-GGUF".to_owned(),
+            revision: "main".to_owned(),
+        },
+    }
+}
@@ -1,5 +1,6 @@
 use paddler_types::huggingface_model_reference::HuggingFaceModelReference;
 
+pub mod bge_reranker_v2_m3;
 pub mod nomic_embed_text_v1_5;
 pub mod qwen2_5_vl_3b;
 pub mod qwen2_5_vl_3b_mmproj;
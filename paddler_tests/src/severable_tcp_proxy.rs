@@ -0,0 +1,87 @@
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use tokio::io::copy_bidirectional;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// Forwards TCP connections to the target address and drops all of them on demand, the way a
+/// flaky network would, while still accepting new ones.
+pub struct SeverableTcpProxy {
+    pub addr: SocketAddr,
+    connections_accepted_tx: watch::Sender<usize>,
+    sever_tx: watch::Sender<()>,
+    shutdown: CancellationToken,
+}
+
+impl SeverableTcpProxy {
+    pub async fn start(target_addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (connections_accepted_tx, _initial_connections_accepted_rx) = watch::channel(0);
+        let (sever_tx, _initial_rx) = watch::channel(());
+        let shutdown = CancellationToken::new();
+
+        let accept_connections_accepted_tx = connections_accepted_tx.clone();
+        let accept_sever_tx = sever_tx.clone();
+        let accept_shutdown = shutdown.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = accept_shutdown.cancelled() => break,
+                    accepted = listener.accept() => {
+                        let Ok((mut inbound, _)) = accepted else {
+                            break;
+                        };
+                        let mut sever_rx = accept_sever_tx.subscribe();
+
+                        accept_connections_accepted_tx
+                            .send_modify(|connections_accepted| *connections_accepted += 1);
+
+                        tokio::spawn(async move {
+                            let Ok(mut outbound) = TcpStream::connect(target_addr).await else {
+                                return;
+                            };
+
+                            tokio::select! {
+                                _ = sever_rx.changed() => {}
+                                _ = copy_bidirectional(&mut inbound, &mut outbound) => {}
+                            }
+                        });
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            connections_accepted_tx,
+            sever_tx,
+            shutdown,
+        })
+    }
+
+    /// Resolves once the proxy has accepted that many connections in total, so a client that
+    /// connected again after `sever_connections` can be told apart from one that never left
+    pub async fn until_connections_accepted(&self, connections_count: usize) -> Result<()> {
+        self.connections_accepted_tx
+            .subscribe()
+            .wait_for(|connections_accepted| *connections_accepted >= connections_count)
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn sever_connections(&self) {
+        self.sever_tx.send_replace(());
+    }
+}
+
+impl Drop for SeverableTcpProxy {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
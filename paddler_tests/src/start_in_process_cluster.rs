@@ -1,5 +1,6 @@
 use anyhow::Context as _;
 use anyhow::Result;
+use paddler::balancer::compatibility::anthropic_service::configuration::Configuration as AnthropicServiceConfiguration;
 use paddler::balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use paddler::balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler::balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
@@ -9,6 +10,7 @@ use paddler_bootstrap::agent_runner::AgentRunnerParams;
 use paddler_bootstrap::balancer_runner::BalancerRunner;
 use paddler_bootstrap::balancer_runner::BalancerRunnerParams;
 use paddler_client::PaddlerClient;
+use paddler_types::dispatch_strategy::DispatchStrategy;
 use tokio_util::sync::CancellationToken;
 
 use crate::agents_stream_watcher::AgentsStreamWatcher;
@@ -23,11 +25,14 @@ use crate::wait_until_healthy::wait_until_healthy;
 pub async fn start_in_process_cluster(
     InProcessClusterParams {
         agent_name,
+        agent_reconnect_grace_period,
+        buffered_batch_request_timeout,
         buffered_request_timeout,
         desired_state,
         inference_cors_allowed_hosts,
         inference_item_timeout,
         management_cors_allowed_hosts,
+        max_buffered_batch_requests,
         max_buffered_requests,
         slots_per_agent,
         spawn_agent,
@@ -38,19 +43,32 @@ pub async fn start_in_process_cluster(
     let cancel_token = CancellationToken::new();
 
     let balancer = BalancerRunner::start(BalancerRunnerParams {
+        anthropic_service_configuration: Some(AnthropicServiceConfiguration {
+            addr: addresses.compat_anthropic,
+            require_api_key: false,
+        }),
+        buffered_batch_request_timeout,
         buffered_request_timeout,
+        dispatch_strategy: DispatchStrategy::default(),
         inference_service_configuration: InferenceServiceConfiguration {
             addr: addresses.inference,
             cors_allowed_hosts: inference_cors_allowed_hosts,
             inference_item_timeout,
+            require_api_key: false,
         },
         management_service_configuration: ManagementServiceConfiguration {
             addr: addresses.management,
+            agent_join_secret: None,
+            agent_reconnect_grace_period,
+            bootstrap_api_key: None,
             cors_allowed_hosts: management_cors_allowed_hosts,
+            require_api_key: false,
         },
+        max_buffered_batch_requests,
         max_buffered_requests,
         openai_service_configuration: Some(OpenAIServiceConfiguration {
             addr: addresses.compat_openai,
+            require_api_key: false,
         }),
         cancellation_token: cancel_token.clone(),
         state_database_type: StateDatabaseType::Memory(Box::new(desired_state.clone())),
@@ -90,7 +108,11 @@ pub async fn start_in_process_cluster(
             agent_name: Some(agent_name),
             management_address: addresses.management.to_string(),
             cancellation_token: cancel_token.clone(),
+            join_secret: None,
+            model_pool: None,
+            reconnect_grace_period: agent_reconnect_grace_period,
             slots: slots_per_agent,
+            weight: 1,
         });
 
         agent_runners.push(agent_runner);
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::rollout_strategy::RolloutStrategy;
 
 use crate::cluster_handle::ClusterHandle;
 use crate::current_test_device::current_test_device;
@@ -28,9 +31,13 @@ pub async fn start_in_process_cluster_with_smolvlm2(slots_per_agent: i32) -> Res
         slots_per_agent,
         desired_state: BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
             model: AgentDesiredModel::HuggingFace(primary_reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::HuggingFace(mmproj_reference),
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         },
         wait_for_slots_ready: true,
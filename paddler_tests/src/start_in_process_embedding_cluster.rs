@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 
 use crate::cluster_handle::ClusterHandle;
 use crate::in_process_cluster_params::InProcessClusterParams;
@@ -19,9 +22,13 @@ pub async fn start_in_process_embedding_cluster(
         slots_per_agent,
         desired_state: BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         },
         ..InProcessClusterParams::default()
@@ -20,11 +20,13 @@ pub async fn start_subprocess_cluster(
     SubprocessClusterParams {
         agent_count,
         agent_name_prefix,
+        buffered_batch_request_timeout,
         buffered_request_timeout,
         desired_state,
         inference_cors_allowed_hosts,
         inference_item_timeout,
         management_cors_allowed_hosts,
+        max_buffered_batch_requests,
         max_buffered_requests,
         slots_per_agent,
         state_database_url,
@@ -41,6 +43,8 @@ pub async fn start_subprocess_cluster(
         .arg(addresses.inference.to_string())
         .arg("--management-addr")
         .arg(addresses.management.to_string())
+        .arg("--compat-anthropic-addr")
+        .arg(addresses.compat_anthropic.to_string())
         .arg("--compat-openai-addr")
         .arg(addresses.compat_openai.to_string())
         .arg("--state-database")
@@ -49,6 +53,10 @@ pub async fn start_subprocess_cluster(
         .arg(max_buffered_requests.to_string())
         .arg("--buffered-request-timeout")
         .arg(buffered_request_timeout.as_millis().to_string())
+        .arg("--max-buffered-batch-requests")
+        .arg(max_buffered_batch_requests.to_string())
+        .arg("--buffered-batch-request-timeout")
+        .arg(buffered_batch_request_timeout.as_millis().to_string())
         .arg("--inference-item-timeout")
         .arg(inference_item_timeout.as_millis().to_string())
         .stdout(Stdio::null())
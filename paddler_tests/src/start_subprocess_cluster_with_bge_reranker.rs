@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::pooling_type::PoolingType;
+use paddler_types::rollout_strategy::RolloutStrategy;
+
+use crate::cluster_handle::ClusterHandle;
+use crate::model_card::ModelCard;
+use crate::model_card::bge_reranker_v2_m3::bge_reranker_v2_m3;
+use crate::start_subprocess_cluster::start_subprocess_cluster;
+use crate::subprocess_cluster_params::SubprocessClusterParams;
+
+pub async fn start_subprocess_cluster_with_bge_reranker(
+    slots_per_agent: i32,
+    agent_count: usize,
+) -> Result<ClusterHandle> {
+    let ModelCard { reference, .. } = bge_reranker_v2_m3();
+
+    start_subprocess_cluster(SubprocessClusterParams {
+        agent_count,
+        slots_per_agent,
+        desired_state: Some(BalancerDesiredState {
+            chat_template_override: None,
+            dispatch_strategy: None,
+            inference_parameters: InferenceParameters {
+                enable_embeddings: true,
+                pooling_type: PoolingType::Rank,
+                ..InferenceParameters::default()
+            },
+            model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
+            multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
+            use_chat_template_override: false,
+        }),
+        wait_for_slots_ready: true,
+        ..SubprocessClusterParams::default()
+    })
+    .await
+}
@@ -5,11 +5,13 @@ use paddler_types::balancer_desired_state::BalancerDesiredState;
 pub struct SubprocessClusterParams {
     pub agent_count: usize,
     pub agent_name_prefix: String,
+    pub buffered_batch_request_timeout: Duration,
     pub buffered_request_timeout: Duration,
     pub desired_state: Option<BalancerDesiredState>,
     pub inference_cors_allowed_hosts: Vec<String>,
     pub inference_item_timeout: Duration,
     pub management_cors_allowed_hosts: Vec<String>,
+    pub max_buffered_batch_requests: i32,
     pub max_buffered_requests: i32,
     pub slots_per_agent: i32,
     pub state_database_url: String,
@@ -21,11 +23,13 @@ impl Default for SubprocessClusterParams {
         Self {
             agent_count: 1,
             agent_name_prefix: "test-agent".to_owned(),
+            buffered_batch_request_timeout: Duration::from_secs(10),
             buffered_request_timeout: Duration::from_secs(10),
             desired_state: Some(BalancerDesiredState::default()),
             inference_cors_allowed_hosts: Vec::new(),
             inference_item_timeout: Duration::from_secs(30),
             management_cors_allowed_hosts: Vec::new(),
+            max_buffered_batch_requests: 10,
             max_buffered_requests: 10,
             slots_per_agent: 4,
             state_database_url: "memory://".to_owned(),
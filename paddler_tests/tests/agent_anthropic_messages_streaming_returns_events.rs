@@ -0,0 +1,53 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+use serde_json::json;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_anthropic_messages_streaming_returns_events() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_qwen3(2, 1).await?;
+
+    let anthropic_url = cluster
+        .addresses
+        .compat_anthropic_base_url()?
+        .join("v1/messages")?;
+
+    let response = reqwest::Client::new()
+        .post(anthropic_url)
+        .json(&json!({
+            "model": "test",
+            "max_tokens": 10,
+            "system": "Answer in one word.",
+            "messages": [{"role": "user", "content": "Say hello"}],
+            "stream": true,
+        }))
+        .send()
+        .await
+        .context("Anthropic compat streaming request should succeed")?;
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.context("should read response body")?;
+
+    let event_types: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+
+    assert_eq!(event_types.first(), Some(&"message_start"));
+    assert!(event_types.contains(&"content_block_delta"));
+    assert_eq!(
+        event_types[event_types.len().saturating_sub(2)..],
+        ["message_delta", "message_stop"]
+    );
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use paddler::balancer::agent_controller_update_result::AgentControllerUpdateResult;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
+
+#[test]
+fn agent_controller_applies_status_snapshot_with_newer_state_application_count() {
+    let controller = make_agent_controller_without_remote_agent("test-agent");
+
+    let snapshot = SlotAggregatedStatusSnapshot {
+        state_application_count: 1,
+        version: 1,
+        ..Default::default()
+    };
+
+    let result = controller.update_from_slot_aggregated_status_snapshot(snapshot);
+
+    assert!(matches!(result, AgentControllerUpdateResult::Updated));
+    assert_eq!(controller.state_application_count.get(), 1);
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+
+#[test]
+fn agent_controller_pool_dispatches_embeddings_only_to_agents_that_accept_them() -> Result<()> {
+    let pool = AgentControllerPool::default();
+
+    for agent_id in ["agent-a", "agent-b"] {
+        let controller = Arc::new(make_agent_controller_without_remote_agent(agent_id));
+
+        controller.slots_total.set(4);
+        controller.accepts_embeddings.set(agent_id == "agent-b");
+        pool.register_agent_controller(agent_id.to_owned(), controller)?;
+    }
+
+    let embedding_criteria = DispatchCriteria {
+        requires_embeddings: true,
+        ..DispatchCriteria::default()
+    };
+
+    let embedding_requests = (0..4)
+        .map(|_| {
+            pool.take_agent_controller(&embedding_criteria)
+                .ok_or_else(|| anyhow!("agent-b must have a free slot"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for embedding_request in &embedding_requests {
+        assert_eq!(embedding_request.agent_controller.id, "agent-b");
+    }
+
+    assert!(
+        pool.take_agent_controller(&embedding_criteria).is_none(),
+        "embeddings must not be dispatched to an agent that does not accept them"
+    );
+    assert!(
+        pool.take_agent_controller(&DispatchCriteria::default())
+            .is_some(),
+        "other requests must still be dispatched to agent-a"
+    );
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
 use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
 use tokio::sync::Barrier;
 
@@ -24,7 +25,7 @@ async fn agent_controller_pool_does_not_oversubscribe_under_concurrent_dispatch(
         handles.push(tokio::spawn(async move {
             barrier_for_task.wait().await;
 
-            pool_for_task.take_least_busy_agent_controller()
+            pool_for_task.take_agent_controller(&DispatchCriteria::default())
         }));
     }
 
@@ -38,7 +39,7 @@ async fn agent_controller_pool_does_not_oversubscribe_under_concurrent_dispatch(
 
     assert!(
         acquired.len() <= 1,
-        "take_least_busy_agent_controller oversubscribed: {} callers held an agent simultaneously with slots_total=1",
+        "take_agent_controller oversubscribed: {} callers held an agent simultaneously with slots_total=1",
         acquired.len()
     );
 
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+#[test]
+fn agent_controller_pool_least_outstanding_tokens_avoids_loaded_agent() -> Result<()> {
+    let pool = AgentControllerPool::new(DispatchStrategy::LeastOutstandingTokens);
+
+    for agent_id in ["agent-a", "agent-b"] {
+        let controller = Arc::new(make_agent_controller_without_remote_agent(agent_id));
+
+        controller.slots_total.set(4);
+        pool.register_agent_controller(agent_id.to_owned(), controller)?;
+    }
+
+    let long_request = pool
+        .take_agent_controller(&DispatchCriteria {
+            estimated_tokens: 4000,
+            ..DispatchCriteria::default()
+        })
+        .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+    let short_requests = (0..2)
+        .map(|_| {
+            pool.take_agent_controller(&DispatchCriteria {
+                estimated_tokens: 100,
+                ..DispatchCriteria::default()
+            })
+            .ok_or_else(|| anyhow!("a free slot must be available"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for short_request in &short_requests {
+        assert_ne!(
+            short_request.agent_controller.id, long_request.agent_controller.id,
+            "short requests must go to the agent that is not busy with the long one"
+        );
+    }
+
+    let long_request_agent = long_request.agent_controller.clone();
+
+    drop(long_request);
+
+    assert_eq!(
+        long_request_agent.outstanding_tokens.get(),
+        0,
+        "releasing the slot must release its outstanding tokens"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+
+#[test]
+fn agent_controller_pool_prefers_agent_that_served_affinity_key() -> Result<()> {
+    let pool = AgentControllerPool::default();
+
+    for agent_id in ["agent-a", "agent-b"] {
+        let controller = Arc::new(make_agent_controller_without_remote_agent(agent_id));
+
+        controller.slots_total.set(2);
+        pool.register_agent_controller(agent_id.to_owned(), controller)?;
+    }
+
+    let conversation = DispatchCriteria {
+        affinity_key: Some(42),
+        ..DispatchCriteria::default()
+    };
+
+    let first_turn = pool
+        .take_agent_controller(&conversation)
+        .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+    // The serving agent is now busier, so least-busy alone would pick the other one
+    let second_turn = pool
+        .take_agent_controller(&conversation)
+        .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+    assert_eq!(
+        second_turn.agent_controller.id, first_turn.agent_controller.id,
+        "requests with the same affinity key must go back to the agent that served it"
+    );
+
+    let third_turn = pool
+        .take_agent_controller(&conversation)
+        .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+    assert_ne!(
+        third_turn.agent_controller.id, first_turn.agent_controller.id,
+        "when the preferred agent is full the request must fall back to another agent"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+#[test]
+fn agent_controller_pool_round_robin_alternates_between_agents() -> Result<()> {
+    let pool = AgentControllerPool::new(DispatchStrategy::RoundRobin);
+
+    for agent_id in ["agent-a", "agent-b"] {
+        let controller = Arc::new(make_agent_controller_without_remote_agent(agent_id));
+
+        controller.slots_total.set(4);
+        pool.register_agent_controller(agent_id.to_owned(), controller)?;
+    }
+
+    let mut dispatched_ids = Vec::new();
+
+    for _ in 0..4 {
+        let dispatched = pool
+            .take_agent_controller(&DispatchCriteria::default())
+            .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+        dispatched_ids.push(dispatched.agent_controller.id.clone());
+    }
+
+    assert_eq!(
+        dispatched_ids,
+        vec!["agent-a", "agent-b", "agent-a", "agent-b"],
+        "round-robin must alternate regardless of how busy the agents are"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+
+fn take_agent_id(pool: &AgentControllerPool, model: Option<&str>) -> Result<String> {
+    let dispatched_agent = pool
+        .take_agent_controller(&DispatchCriteria {
+            model: model.map(ToOwned::to_owned),
+            ..DispatchCriteria::default()
+        })
+        .ok_or_else(|| anyhow!("a free slot must be available"))?;
+
+    Ok(dispatched_agent.agent_controller.id.clone())
+}
+
+#[test]
+fn agent_controller_pool_routes_requests_to_model_pool() -> Result<()> {
+    let pool = AgentControllerPool::default();
+
+    pool.set_model_pools(BTreeSet::from(["embedder".to_owned()]));
+
+    for agent_id in ["agent-a", "agent-b"] {
+        let controller = Arc::new(make_agent_controller_without_remote_agent(agent_id));
+
+        controller.slots_total.set(4);
+        pool.assign_model_pool(&controller);
+        pool.register_agent_controller(agent_id.to_owned(), controller)?;
+    }
+
+    let default_agent = pool
+        .get_agent_controller("agent-a")
+        .ok_or_else(|| anyhow!("agent-a must be registered"))?;
+    let embedder_agent = pool
+        .get_agent_controller("agent-b")
+        .ok_or_else(|| anyhow!("agent-b must be registered"))?;
+
+    assert_eq!(default_agent.get_model_pool(), None);
+    assert_eq!(embedder_agent.get_model_pool().as_deref(), Some("embedder"));
+
+    assert_eq!(take_agent_id(&pool, Some("embedder"))?, "agent-b");
+    assert_eq!(take_agent_id(&pool, None)?, "agent-a");
+    assert!(
+        pool.resolve_model_pool(Some("unknown-model")).is_err(),
+        "requests naming a model without a pool must be rejected"
+    );
+    assert!(
+        pool.take_agent_controller(&DispatchCriteria {
+            model: Some("unknown-model".to_owned()),
+            ..DispatchCriteria::default()
+        })
+        .is_none()
+    );
+
+    Ok(())
+}
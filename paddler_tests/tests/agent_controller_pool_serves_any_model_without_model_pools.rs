@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+
+#[test]
+fn agent_controller_pool_serves_any_model_without_model_pools() -> Result<()> {
+    let pool = AgentControllerPool::default();
+    let controller = Arc::new(make_agent_controller_without_remote_agent("agent-a"));
+
+    controller.slots_total.set(4);
+    pool.assign_model_pool(&controller);
+    pool.register_agent_controller("agent-a".to_owned(), controller)?;
+
+    assert_eq!(pool.resolve_model_pool(Some("any-model"))?, None);
+
+    let dispatched_agent = pool
+        .take_agent_controller(&DispatchCriteria {
+            model: Some("any-model".to_owned()),
+            ..DispatchCriteria::default()
+        })
+        .ok_or_else(|| anyhow!("the default model must serve requests naming any model"))?;
+
+    assert_eq!(dispatched_agent.agent_controller.id, "agent-a");
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use anyhow::Context as _;
 use anyhow::Result;
 use anyhow::anyhow;
 use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
 use paddler::subscribes_to_updates::SubscribesToUpdates as _;
 use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
 
@@ -22,7 +23,7 @@ fn agent_controller_pool_signals_update_when_slot_guard_drops() -> Result<()> {
     update_rx.borrow_and_update();
 
     let dispatched = pool
-        .take_least_busy_agent_controller()
+        .take_agent_controller(&DispatchCriteria::default())
         .ok_or_else(|| anyhow!("a free slot must be available"))?;
 
     update_rx.borrow_and_update();
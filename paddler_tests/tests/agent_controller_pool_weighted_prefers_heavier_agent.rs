@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::dispatch_criteria::DispatchCriteria;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::dispatch_strategy::DispatchStrategy;
+
+#[test]
+fn agent_controller_pool_weighted_prefers_heavier_agent() -> Result<()> {
+    let pool = AgentControllerPool::new(DispatchStrategy::Weighted);
+
+    let mut light_controller = make_agent_controller_without_remote_agent("light-agent");
+
+    light_controller.weight = 1;
+    light_controller.slots_total.set(8);
+    pool.register_agent_controller("light-agent".to_owned(), Arc::new(light_controller))?;
+
+    let mut heavy_controller = make_agent_controller_without_remote_agent("heavy-agent");
+
+    heavy_controller.weight = 3;
+    heavy_controller.slots_total.set(8);
+    pool.register_agent_controller("heavy-agent".to_owned(), Arc::new(heavy_controller))?;
+
+    let mut held = Vec::new();
+
+    for _ in 0..4 {
+        held.push(
+            pool.take_agent_controller(&DispatchCriteria::default())
+                .ok_or_else(|| anyhow!("a free slot must be available"))?,
+        );
+    }
+
+    let heavy_count = held
+        .iter()
+        .filter(|dispatched| dispatched.agent_controller.id == "heavy-agent")
+        .count();
+
+    assert_eq!(
+        heavy_count, 3,
+        "agent with weight 3 must take three out of four requests"
+    );
+
+    Ok(())
+}
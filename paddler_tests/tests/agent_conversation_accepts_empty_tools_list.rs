@@ -12,6 +12,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -28,11 +29,20 @@ async fn agent_conversation_accepts_empty_tools_list() -> Result<()> {
             conversation_history: ConversationHistory::new(vec![ConversationMessage {
                 content: ConversationMessageContent::Text("Say hello".to_owned()),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -41,7 +51,12 @@ async fn agent_conversation_accepts_empty_tools_list() -> Result<()> {
     let token_count = collected
         .token_results
         .iter()
-        .filter(|result| matches!(result, GeneratedTokenResult::Token(_)))
+        .filter(|result| {
+            matches!(
+                result,
+                GeneratedTokenResult::ReasoningToken(_) | GeneratedTokenResult::Token(_)
+            )
+        })
         .count();
 
     assert!(token_count > 0);
@@ -16,6 +16,7 @@ use paddler_types::request_params::continue_from_conversation_history_params::to
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 use serde_json::Map;
 use serde_json::Value;
@@ -41,10 +42,18 @@ async fn agent_conversation_with_function_tool_succeeds() -> Result<()> {
             conversation_history: ConversationHistory::new(vec![ConversationMessage {
                 content: ConversationMessageContent::Text("Say hello".to_owned()),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 50,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![Tool::Function(FunctionCall {
                 function: Function {
                     name: "get_weather".to_owned(),
@@ -57,6 +66,7 @@ async fn agent_conversation_with_function_tool_succeeds() -> Result<()> {
                     }),
                 },
             })],
+            truncation: None,
         })
         .await?;
 
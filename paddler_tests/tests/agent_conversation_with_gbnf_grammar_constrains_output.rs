@@ -12,6 +12,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::grammar_constraint::GrammarConstraint;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -30,14 +31,23 @@ async fn agent_conversation_with_gbnf_grammar_constrains_output() -> Result<()>
                     "Is the sky blue? Answer yes or no.".to_owned(),
                 ),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: false,
             grammar: Some(GrammarConstraint::Gbnf {
                 grammar: r"root ::= [Yy][Ee][Ss] | [Nn][Oo]".to_owned(),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
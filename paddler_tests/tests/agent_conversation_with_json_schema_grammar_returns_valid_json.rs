@@ -12,6 +12,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::grammar_constraint::GrammarConstraint;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -28,13 +29,22 @@ async fn agent_conversation_with_json_schema_grammar_returns_valid_json() -> Res
             conversation_history: ConversationHistory::new(vec![ConversationMessage {
                 content: ConversationMessageContent::Text("What is 2+2?".to_owned()),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: false,
             grammar: Some(GrammarConstraint::JsonSchema {
                 schema: r#"{"type": "object", "properties": {"answer": {"type": "string"}}, "required": ["answer"]}"#.to_owned(),
             }),
+            logprobs: None,
             max_tokens: 50,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -10,6 +10,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -35,21 +36,28 @@ async fn agent_embedding_batch_distribution_independent_of_context_size() -> Res
                 EmbeddingInputDocument {
                     content: "This is the first document with enough content to contribute meaningfully to the batch size calculation".to_owned(),
                     id: "doc-chunk-1".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the second document that should be processed in a potentially different batch from the first".to_owned(),
                     id: "doc-chunk-2".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the third document adding more content to ensure the total exceeds the configured chunk limit".to_owned(),
                     id: "doc-chunk-3".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the fourth document which should demonstrate that batching distributes across agent requests".to_owned(),
                     id: "doc-chunk-4".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await?;
 
@@ -10,6 +10,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -33,13 +34,18 @@ async fn agent_embedding_batch_returns_one_embedding_per_input_document() -> Res
                 EmbeddingInputDocument {
                     content: "The quick brown fox jumps over the lazy dog".to_owned(),
                     id: "doc-alpha".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "Machine learning is a subset of artificial intelligence".to_owned(),
                     id: "doc-beta".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await?;
 
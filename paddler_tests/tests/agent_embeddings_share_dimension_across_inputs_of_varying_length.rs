@@ -8,6 +8,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -31,17 +32,23 @@ async fn agent_embeddings_share_dimension_across_inputs_of_varying_length() -> R
                 EmbeddingInputDocument {
                     content: "Hello".to_owned(),
                     id: "doc-short".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "The quick brown fox jumped over the lazy dog.".to_owned(),
                     id: "doc-medium".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "Rust is a systems programming language focused on safety, speed, and concurrency. It achieves memory safety without garbage collection.".to_owned(),
                     id: "doc-long".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await?;
 
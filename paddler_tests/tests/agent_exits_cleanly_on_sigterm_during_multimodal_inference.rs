@@ -3,6 +3,8 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use futures_util::StreamExt as _;
@@ -27,6 +29,8 @@ use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::conversation_message_content_part::ConversationMessageContentPart;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -50,9 +54,13 @@ async fn agent_exits_cleanly_on_sigterm_during_multimodal_inference() -> Result<
         wait_for_slots_ready: false,
         desired_state: Some(BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
             model: AgentDesiredModel::HuggingFace(primary_reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::HuggingFace(mmproj_reference),
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         }),
         ..SubprocessClusterParams::default()
@@ -100,11 +108,20 @@ async fn agent_exits_cleanly_on_sigterm_during_multimodal_inference() -> Result<
                     },
                 ]),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -3,6 +3,8 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use futures_util::StreamExt as _;
@@ -20,6 +22,8 @@ use paddler_tests::terminate_child::terminate_child;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -39,9 +43,13 @@ async fn agent_exits_cleanly_when_killed_during_generation() -> Result<()> {
         wait_for_slots_ready: false,
         desired_state: Some(BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         }),
         ..SubprocessClusterParams::default()
@@ -75,8 +83,14 @@ async fn agent_exits_cleanly_when_killed_during_generation() -> Result<()> {
     let mut stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 1000,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Write a long story".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -10,6 +10,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -41,13 +42,17 @@ async fn agent_isolates_concurrent_embedding_requests_per_client() -> Result<()>
                         "Content from client {client_index} document {document_index}."
                     ),
                     id: format!("client-{client_index}-doc-{document_index}"),
+                    tokens: None,
                 })
                 .collect();
 
             let stream = inference_client
                 .post_generate_embedding_batch(&GenerateEmbeddingBatchParams {
                     input_batch,
+                    model: None,
                     normalization_method: EmbeddingNormalizationMethod::None,
+                    priority: RequestPriority::Interactive,
+                    rerank_query: None,
                 })
                 .await?;
 
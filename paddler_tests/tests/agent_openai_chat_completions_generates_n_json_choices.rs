@@ -0,0 +1,59 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+use serde_json::json;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_openai_chat_completions_generates_n_json_choices() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_qwen3(2, 1).await?;
+
+    let openai_url = cluster
+        .addresses
+        .compat_openai_base_url()?
+        .join("v1/chat/completions")?;
+
+    let response = reqwest::Client::new()
+        .post(openai_url)
+        .json(&json!({
+            "model": "test",
+            "messages": [{"role": "user", "content": "Describe a cat as JSON"}],
+            "max_completion_tokens": 30,
+            "n": 2,
+            "seed": 42,
+            "temperature": 0.7,
+            "response_format": {"type": "json_object"},
+            "stream": false,
+        }))
+        .send()
+        .await
+        .context("OpenAI compat request should succeed")?;
+
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.context("response should be JSON")?;
+    let choices = body["choices"]
+        .as_array()
+        .context("choices should be an array")?;
+
+    assert_eq!(choices.len(), 2);
+    assert_eq!(choices[1]["index"], 1);
+
+    for choice in choices {
+        let content = choice["message"]["content"].as_str().unwrap_or("");
+
+        assert!(
+            content.trim_start().starts_with('{'),
+            "content should be a JSON object: {content}"
+        );
+    }
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
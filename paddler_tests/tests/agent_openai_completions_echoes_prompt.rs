@@ -0,0 +1,48 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+use serde_json::json;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_openai_completions_echoes_prompt() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_qwen3(2, 1).await?;
+
+    let openai_url = cluster
+        .addresses
+        .compat_openai_base_url()?
+        .join("v1/completions")?;
+
+    let response = reqwest::Client::new()
+        .post(openai_url)
+        .json(&json!({
+            "model": "test",
+            "prompt": "The capital of France is",
+            "max_tokens": 5,
+            "echo": true,
+        }))
+        .send()
+        .await
+        .context("OpenAI compat request should succeed")?;
+
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.context("response should be JSON")?;
+    let text = body["choices"][0]["text"].as_str().unwrap_or("");
+
+    assert_eq!(body["object"], "text_completion");
+    assert!(
+        text.starts_with("The capital of France is") && text.len() > 24,
+        "text should be the prompt followed by the completion: {text}"
+    );
+    assert!(body["usage"]["completion_tokens"].as_u64().unwrap_or(0) > 0);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
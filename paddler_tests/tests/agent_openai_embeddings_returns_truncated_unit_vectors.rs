@@ -0,0 +1,70 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler_tests::start_in_process_embedding_cluster::start_in_process_embedding_cluster;
+use paddler_types::inference_parameters::InferenceParameters;
+use serde_json::json;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_openai_embeddings_returns_truncated_unit_vectors() -> Result<()> {
+    let cluster = start_in_process_embedding_cluster(
+        InferenceParameters {
+            enable_embeddings: true,
+            ..InferenceParameters::default()
+        },
+        2,
+    )
+    .await?;
+
+    let openai_url = cluster
+        .addresses
+        .compat_openai_base_url()?
+        .join("v1/embeddings")?;
+
+    let response = reqwest::Client::new()
+        .post(openai_url)
+        .json(&json!({
+            "model": "test",
+            "input": ["The food was delicious", "The waiter was friendly"],
+            "dimensions": 64,
+        }))
+        .send()
+        .await
+        .context("OpenAI compat request should succeed")?;
+
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.context("response should be JSON")?;
+
+    assert_eq!(body["object"], "list");
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(2));
+    assert!(body["usage"]["prompt_tokens"].as_u64().unwrap_or(0) > 0);
+
+    for (index, data) in body["data"]
+        .as_array()
+        .context("data should be an array")?
+        .iter()
+        .enumerate()
+    {
+        assert_eq!(data["index"], index);
+
+        let embedding = data["embedding"]
+            .as_array()
+            .context("embedding should be an array")?;
+        let norm = embedding
+            .iter()
+            .filter_map(serde_json::Value::as_f64)
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt();
+
+        assert_eq!(embedding.len(), 64);
+        assert!((norm - 1.0).abs() < 1e-3, "norm should be 1, got {norm}");
+    }
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
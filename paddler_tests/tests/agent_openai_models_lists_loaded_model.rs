@@ -0,0 +1,39 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_openai_models_lists_loaded_model() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_qwen3(2, 1).await?;
+
+    let openai_url = cluster
+        .addresses
+        .compat_openai_base_url()?
+        .join("v1/models")?;
+
+    let response = reqwest::Client::new()
+        .get(openai_url)
+        .send()
+        .await
+        .context("OpenAI compat request should succeed")?;
+
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.context("response should be JSON")?;
+    let models = body["data"].as_array().context("data should be an array")?;
+
+    assert_eq!(body["object"], "list");
+    assert_eq!(models.len(), 1, "the agent serves a single model: {body}");
+    assert_eq!(models[0]["object"], "model");
+    assert!(!models[0]["id"].as_str().unwrap_or("").is_empty());
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
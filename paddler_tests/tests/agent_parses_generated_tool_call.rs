@@ -0,0 +1,98 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::Tool;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::FunctionCall;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::request_priority::RequestPriority;
+use reqwest::Client;
+use serde_json::Map;
+use serde_json::Value;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_parses_generated_tool_call() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_qwen3(2, 1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let mut location_properties = Map::new();
+
+    location_properties.insert(
+        "location".to_owned(),
+        serde_json::json!({"type": "string", "description": "The city name"}),
+    );
+
+    let stream = inference_client
+        .post_continue_from_conversation_history(&ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(vec![ConversationMessage {
+                content: ConversationMessageContent::Text(
+                    "What is the weather in Paris right now? Use the tool.".to_owned(),
+                ),
+                role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
+            }]),
+            enable_thinking: false,
+            grammar: None,
+            logprobs: None,
+            max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
+            tools: vec![Tool::Function(FunctionCall {
+                function: Function {
+                    name: "get_weather".to_owned(),
+                    description: "Get the current weather for a location".to_owned(),
+                    parameters: Parameters::Schema(ValidatedParametersSchema {
+                        schema_type: "object".to_owned(),
+                        properties: Some(location_properties),
+                        required: Some(vec!["location".to_owned()]),
+                        additional_properties: Some(Value::Bool(false)),
+                    }),
+                },
+            })],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    let tool_call = collected
+        .token_results
+        .iter()
+        .find_map(|result| match result {
+            GeneratedTokenResult::ToolCall(tool_call) => Some(tool_call),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("expected a tool call, got: {}", collected.text))?;
+
+    assert_eq!(tool_call.name, "get_weather");
+    assert!(tool_call.arguments["location"].is_string());
+    assert!(
+        !collected.text.contains("<tool_call>"),
+        "tool call markup should not reach the client"
+    );
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
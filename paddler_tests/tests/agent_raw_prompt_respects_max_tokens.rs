@@ -9,6 +9,7 @@ use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -22,8 +23,14 @@ async fn agent_raw_prompt_respects_max_tokens() -> Result<()> {
     let stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "The capital of France is".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
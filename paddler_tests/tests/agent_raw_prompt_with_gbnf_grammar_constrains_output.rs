@@ -9,6 +9,7 @@ use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
 use paddler_types::grammar_constraint::GrammarConstraint;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -25,10 +26,16 @@ async fn agent_raw_prompt_with_gbnf_grammar_constrains_output() -> Result<()> {
                 grammar: r#"root ::= "yes" | "no""#.to_owned(),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt:
                 "<|im_start|>user\nIs the sky blue? Answer yes or no.<|im_end|>\n<|im_start|>assistant\n<think>\n\n</think>\n\n"
                     .to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
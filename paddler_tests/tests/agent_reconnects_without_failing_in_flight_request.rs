@@ -0,0 +1,171 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use futures_util::StreamExt as _;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::current_test_device::current_test_device;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::model_card::ModelCard;
+use paddler_tests::model_card::qwen3_0_6b::qwen3_0_6b;
+use paddler_tests::severable_tcp_proxy::SeverableTcpProxy;
+use paddler_tests::spawn_agent_subprocess::spawn_agent_subprocess;
+use paddler_tests::spawn_agent_subprocess_params::SpawnAgentSubprocessParams;
+use paddler_tests::start_subprocess_cluster::start_subprocess_cluster;
+use paddler_tests::subprocess_cluster_params::SubprocessClusterParams;
+use paddler_tests::terminate_child::terminate_child;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_client::Message as InferenceMessage;
+use paddler_types::inference_client::Response as InferenceResponse;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
+use paddler_types::sampling_parameters::SamplingParameters;
+use paddler_types::streamable_result::StreamableResult as _;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn agent_reconnects_without_failing_in_flight_request() -> Result<()> {
+    let device = current_test_device()?;
+
+    device.require_available()?;
+
+    let ModelCard {
+        gpu_layer_count,
+        reference,
+    } = qwen3_0_6b();
+
+    let mut cluster = start_subprocess_cluster(SubprocessClusterParams {
+        agent_count: 0,
+        wait_for_slots_ready: false,
+        desired_state: Some(BalancerDesiredState {
+            chat_template_override: None,
+            dispatch_strategy: None,
+            inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
+            model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
+            multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
+            use_chat_template_override: false,
+        }),
+        ..SubprocessClusterParams::default()
+    })
+    .await?;
+
+    let proxy = SeverableTcpProxy::start(cluster.addresses.management).await?;
+
+    let mut agent = spawn_agent_subprocess(SpawnAgentSubprocessParams {
+        management_addr: proxy.addr,
+        name: Some("reconnecting-agent".to_owned()),
+        slots: 1,
+    })?;
+
+    cluster.agents.wait_for_slots_ready(1, 1).await?;
+    proxy.until_connections_accepted(1).await?;
+
+    let registered_snapshot = cluster
+        .agents
+        .until(|snapshot| snapshot.agents.len() == 1)
+        .await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let params = ContinueFromRawPromptParams {
+        grammar: None,
+        logprobs: None,
+        max_tokens: 100,
+        model: None,
+        priority: RequestPriority::Interactive,
+        raw_prompt: "Count from one to fifty:".to_owned(),
+        sampling: Some(SamplingParameters {
+            temperature: Some(0.0),
+            top_k: Some(1),
+            ..SamplingParameters::default()
+        }),
+        stop: vec![],
+        truncation: None,
+    };
+
+    let mut stream = inference_client
+        .post_continue_from_raw_prompt(&params)
+        .await?;
+
+    let first_text = match stream
+        .next()
+        .await
+        .context("stream should yield the first token")??
+    {
+        InferenceMessage::Response(ResponseEnvelope {
+            response: InferenceResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+            ..
+        }) => token,
+        _ => String::new(),
+    };
+
+    proxy.sever_connections();
+
+    let collected = collect_generated_tokens(stream)
+        .await
+        .context("in-flight request should survive the agent reconnecting")?;
+
+    assert!(
+        collected
+            .token_results
+            .last()
+            .is_some_and(|token_result| token_result.is_done())
+    );
+
+    // Only an agent that connected again opens a second connection through the proxy
+    proxy.until_connections_accepted(2).await?;
+
+    let reconnected_snapshot = cluster
+        .agents
+        .until(|snapshot| snapshot.agents.len() == 1)
+        .await?;
+
+    assert_eq!(
+        reconnected_snapshot.agents[0].id, registered_snapshot.agents[0].id,
+        "the agent should keep its identity across reconnects"
+    );
+
+    // Agents that are still reconnecting do not get new requests
+    let collected_after_reconnect = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&params)
+            .await?,
+    )
+    .await
+    .context("the reconnected agent should take new requests")?;
+
+    assert!(
+        collected_after_reconnect
+            .token_results
+            .last()
+            .is_some_and(|token_result| token_result.is_done())
+    );
+
+    // Greedy sampling repeats the same text, so any token lost to the severed connection shows up
+    assert_eq!(
+        format!("{first_text}{}", collected.text),
+        collected_after_reconnect.text,
+        "tokens queued while the agent was disconnected should be delivered after it reconnects"
+    );
+
+    terminate_child(&mut agent)?;
+    agent.wait().await?;
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
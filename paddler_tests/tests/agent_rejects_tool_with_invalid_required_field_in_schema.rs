@@ -15,6 +15,7 @@ use paddler_types::request_params::continue_from_conversation_history_params::to
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
 use paddler_types::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 use serde_json::Map;
 
@@ -36,10 +37,18 @@ async fn agent_rejects_tool_with_invalid_required_field_in_schema() -> Result<()
             conversation_history: ConversationHistory::new(vec![ConversationMessage {
                 content: ConversationMessageContent::Text("Say hello".to_owned()),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![Tool::Function(FunctionCall {
                 function: Function {
                     name: "test_fn".to_owned(),
@@ -52,6 +61,7 @@ async fn agent_rejects_tool_with_invalid_required_field_in_schema() -> Result<()
                     }),
                 },
             })],
+            truncation: None,
         })
         .await;
 
@@ -10,6 +10,7 @@ use paddler_tests::agents_status::assert_slots_processing::assert_slots_processi
 use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -29,8 +30,14 @@ async fn agent_releases_slot_when_websocket_client_disconnects() -> Result<()> {
     let mut stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Write a long story about an explorer".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_tests::collect_embedding_results::collect_embedding_results;
 use paddler_tests::in_process_cluster_params::InProcessClusterParams;
@@ -13,6 +15,8 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -25,9 +29,13 @@ async fn agent_returns_error_when_embeddings_disabled_in_parameters() -> Result<
         slots_per_agent: 1,
         desired_state: BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         },
         wait_for_slots_ready: true,
@@ -43,8 +51,12 @@ async fn agent_returns_error_when_embeddings_disabled_in_parameters() -> Result<
             input_batch: vec![EmbeddingInputDocument {
                 content: "Hello world".to_owned(),
                 id: "doc-1".to_owned(),
+                tokens: None,
             }],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await;
 
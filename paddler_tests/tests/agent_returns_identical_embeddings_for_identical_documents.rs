@@ -9,6 +9,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -34,13 +35,18 @@ async fn agent_returns_identical_embeddings_for_identical_documents() -> Result<
                 EmbeddingInputDocument {
                     content: repeated_content.to_owned(),
                     id: "doc-first".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: repeated_content.to_owned(),
                     id: "doc-second".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await?;
 
@@ -14,6 +14,7 @@ use paddler_types::conversation_message_content_part::ConversationMessageContent
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -39,11 +40,20 @@ async fn agent_returns_image_decoding_error_for_malformed_data_uri() -> Result<(
                     },
                 ]),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await;
 
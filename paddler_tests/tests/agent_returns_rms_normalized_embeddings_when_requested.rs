@@ -8,6 +8,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -30,8 +31,12 @@ async fn agent_returns_rms_normalized_embeddings_when_requested() -> Result<()>
             input_batch: vec![EmbeddingInputDocument {
                 content: "Testing RMS normalization on embeddings".to_owned(),
                 id: "doc-rms".to_owned(),
+                tokens: None,
             }],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::RmsNorm { epsilon: 1e-6 },
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
         .await?;
 
@@ -15,6 +15,7 @@ use paddler_types::conversation_message_content_part::ConversationMessageContent
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -42,20 +43,31 @@ async fn agent_streams_tokens_from_image_data_uri() -> Result<()> {
                     },
                 ]),
                 role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
             }]),
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 100,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
     let collected = collect_generated_tokens(stream).await?;
 
-    let received_tokens = collected
-        .token_results
-        .iter()
-        .any(|result| matches!(result, GeneratedTokenResult::Token(_)));
+    let received_tokens = collected.token_results.iter().any(|result| {
+        matches!(
+            result,
+            GeneratedTokenResult::ReasoningToken(_) | GeneratedTokenResult::Token(_)
+        )
+    });
 
     assert!(received_tokens);
 
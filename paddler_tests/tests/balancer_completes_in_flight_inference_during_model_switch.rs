@@ -3,6 +3,8 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_tests::collect_generated_tokens::collect_generated_tokens;
 use paddler_tests::inference_http_client::InferenceHttpClient;
@@ -12,6 +14,8 @@ use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::grammar_constraint::GrammarConstraint;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -30,17 +34,27 @@ async fn balancer_completes_in_flight_inference_during_model_switch() -> Result<
                 grammar: format!("root ::= \"{expected_output}\""),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Say the following: the quick brown fox jumps over the lazy dog".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
     // Trigger model switch to a nonexistent path while the request is in flight
     let switch_state = BalancerDesiredState {
         chat_template_override: None,
+        dispatch_strategy: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::LocalToAgent("/nonexistent/model.gguf".to_owned()),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         use_chat_template_override: false,
     };
 
@@ -3,6 +3,7 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -17,6 +18,8 @@ use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::inference_client::Message;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -40,9 +43,13 @@ async fn balancer_distributes_buffered_requests_across_two_agents() -> Result<()
         agent_name_prefix: "distributed-agent".to_owned(),
         desired_state: Some(BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         }),
         ..SubprocessClusterParams::default()
@@ -58,8 +65,14 @@ async fn balancer_distributes_buffered_requests_across_two_agents() -> Result<()
         let stream = inference_client
             .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
+                priority: RequestPriority::Interactive,
                 raw_prompt: "Hello".to_owned(),
+                sampling: None,
+                stop: vec![],
+                truncation: None,
             })
             .await?;
 
@@ -13,6 +13,7 @@ use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -38,11 +39,15 @@ async fn balancer_fans_out_embedding_batch_to_all_agents() -> Result<()> {
         .map(|index| EmbeddingInputDocument {
             content: format!("Document number {index:02}: {filler}"),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
     let params = GenerateEmbeddingBatchParams {
         input_batch,
+        model: None,
         normalization_method: EmbeddingNormalizationMethod::None,
+        priority: RequestPriority::Interactive,
+        rerank_query: None,
     };
 
     let mut seen_busy_agents: BTreeSet<String> = BTreeSet::new();
@@ -0,0 +1,84 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use paddler_tests::current_test_device::current_test_device;
+use paddler_tests::model_card::ModelCard;
+use paddler_tests::model_card::qwen3_0_6b::qwen3_0_6b;
+use paddler_tests::start_subprocess_cluster_with_qwen3::start_subprocess_cluster_with_qwen3;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn balancer_keeps_an_agent_serving_during_agent_by_agent_rollout() -> Result<()> {
+    let mut cluster = start_subprocess_cluster_with_qwen3(1, 2).await?;
+
+    let device = current_test_device()?;
+    let ModelCard {
+        gpu_layer_count,
+        reference,
+    } = qwen3_0_6b();
+    let inference_parameters = device.inference_parameters_for_full_offload(gpu_layer_count);
+
+    // A different context size makes every agent reload the model
+    let rollout_state = BalancerDesiredState {
+        chat_template_override: None,
+        dispatch_strategy: None,
+        inference_parameters: InferenceParameters {
+            context_size: inference_parameters.context_size / 2,
+            ..inference_parameters
+        },
+        model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
+        multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AgentByAgent,
+        use_chat_template_override: false,
+    };
+
+    cluster
+        .paddler_client
+        .management()
+        .put_balancer_desired_state(&rollout_state)
+        .await
+        .map_err(anyhow::Error::new)?;
+
+    let mut agents_reloading = BTreeSet::new();
+    let mut agents_reloaded = BTreeSet::new();
+    let mut is_serving_throughout = true;
+
+    cluster
+        .agents
+        .until(|snapshot| {
+            is_serving_throughout =
+                is_serving_throughout && snapshot.agents.iter().any(|agent| agent.slots_total > 0);
+
+            for agent in &snapshot.agents {
+                if agent.slots_total == 0 {
+                    agents_reloading.insert(agent.id.clone());
+                } else if agents_reloading.contains(&agent.id) {
+                    agents_reloaded.insert(agent.id.clone());
+                }
+            }
+
+            agents_reloaded.len() == 2
+        })
+        .await?;
+
+    assert!(
+        is_serving_throughout,
+        "at least one agent should have slots while the others reload"
+    );
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
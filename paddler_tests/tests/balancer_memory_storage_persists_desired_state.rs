@@ -3,6 +3,8 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_tests::model_card::ModelCard;
@@ -12,6 +14,7 @@ use paddler_tests::subprocess_cluster_params::SubprocessClusterParams;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
 #[tokio::test(flavor = "multi_thread")]
@@ -20,9 +23,13 @@ async fn balancer_memory_storage_persists_desired_state() -> Result<()> {
 
     let desired_state = BalancerDesiredState {
         chat_template_override: None,
+        dispatch_strategy: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         use_chat_template_override: false,
     };
 
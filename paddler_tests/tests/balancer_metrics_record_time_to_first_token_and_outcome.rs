@@ -0,0 +1,46 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn balancer_metrics_record_time_to_first_token_and_outcome() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 5,
+            model: None,
+            priority: RequestPriority::Interactive,
+            raw_prompt: "Count from one to one hundred:".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
+        })
+        .await?;
+
+    collect_generated_tokens(stream).await?;
+
+    let metrics = cluster.paddler_client.management().get_metrics().await?;
+
+    assert!(metrics.contains("requests_finished_total{outcome=\"done\"} 1\n"));
+    assert!(metrics.contains("time_to_first_token_seconds_count 1\n"));
+    assert!(metrics.contains("request_duration_seconds_count 1\n"));
+    assert!(metrics.contains("buffer_wait_seconds_count 1\n"));
+    assert!(metrics.contains("agent_slots_total{agent_id="));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
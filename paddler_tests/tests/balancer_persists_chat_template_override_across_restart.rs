@@ -3,6 +3,8 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_tests::model_card::ModelCard;
@@ -14,6 +16,7 @@ use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::chat_template::ChatTemplate;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
 #[tokio::test(flavor = "multi_thread")]
@@ -28,9 +31,13 @@ async fn balancer_persists_chat_template_override_across_restart() -> Result<()>
         chat_template_override: Some(ChatTemplate {
             content: template_content.clone(),
         }),
+        dispatch_strategy: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         use_chat_template_override: true,
     };
 
@@ -0,0 +1,51 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use anyhow::Result;
+use paddler_tests::collect_rerank_results::collect_rerank_results;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_subprocess_cluster_with_bge_reranker::start_subprocess_cluster_with_bge_reranker;
+use paddler_types::request_params::RerankParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rerank_document::RerankDocument;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn balancer_ranks_the_relevant_document_first_when_reranking() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_bge_reranker(4, 1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let document = |id: &str, content: &str| RerankDocument {
+        content: content.to_owned(),
+        id: id.to_owned(),
+    };
+    let params = RerankParams {
+        documents: vec![
+            document("giraffe", "Giraffes are the tallest living land animals."),
+            document("paris", "Paris is the capital and largest city of France."),
+            document("rust", "Rust is a systems programming language."),
+        ],
+        model: None,
+        priority: RequestPriority::Interactive,
+        query: "What is the capital of France?".to_owned(),
+        sort_by_score: false,
+        top_n: Some(2),
+    };
+
+    let collected = collect_rerank_results(inference_client.post_rerank(&params).await?).await?;
+
+    assert!(collected.saw_done);
+    assert!(collected.errors.is_empty());
+    assert_eq!(collected.scores.len(), 2);
+    assert_eq!(collected.scores[0].document_id, "paris");
+    assert!(collected.scores[0].score > collected.scores[1].score);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
     feature = "tests_that_use_llms"
 ))]
 
+use std::collections::BTreeMap;
 use std::io::Write as _;
 
 use anyhow::Context as _;
@@ -13,6 +14,7 @@ use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::agent_issue::AgentIssue;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use tempfile::NamedTempFile;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -34,9 +36,13 @@ async fn balancer_reports_model_cannot_be_loaded_for_corrupt_file() -> Result<()
         wait_for_slots_ready: false,
         desired_state: Some(BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent(corrupt_model_path.clone()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         }),
         ..SubprocessClusterParams::default()
@@ -0,0 +1,61 @@
+#![cfg(all(
+    feature = "tests_that_use_compiled_paddler",
+    feature = "tests_that_use_llms"
+))]
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use paddler_tests::collect_rerank_results::collect_rerank_results;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_subprocess_cluster_with_bge_reranker::start_subprocess_cluster_with_bge_reranker;
+use paddler_types::request_params::RerankParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rerank_document::RerankDocument;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn balancer_streams_a_rerank_score_for_every_document() -> Result<()> {
+    let cluster = start_subprocess_cluster_with_bge_reranker(4, 2).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let filler = "x".repeat(380);
+    let params = RerankParams {
+        documents: (0..12)
+            .map(|index| RerankDocument {
+                content: format!("Document number {index:02}: {filler}"),
+                id: format!("doc-{index}"),
+            })
+            .collect(),
+        model: None,
+        priority: RequestPriority::Interactive,
+        query: "Which document is number 07?".to_owned(),
+        sort_by_score: false,
+        top_n: None,
+    };
+
+    let collected = collect_rerank_results(inference_client.post_rerank(&params).await?).await?;
+
+    let scored_document_ids: BTreeSet<String> = collected
+        .scores
+        .iter()
+        .map(|score| score.document_id.clone())
+        .collect();
+    let expected_document_ids: BTreeSet<String> = params
+        .documents
+        .iter()
+        .map(|document| document.id.clone())
+        .collect();
+
+    assert!(collected.saw_done);
+    assert!(collected.errors.is_empty());
+    assert_eq!(collected.scores.len(), 12);
+    assert_eq!(scored_document_ids, expected_document_ids);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -9,12 +9,15 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 fn user_message(text: &str) -> ConversationMessage {
     ConversationMessage {
         content: ConversationMessageContent::Text(text.to_owned()),
         role: "user".to_owned(),
+        tool_call_id: None,
+        tool_calls: vec![],
     }
 }
 
@@ -32,8 +35,15 @@ async fn continuous_batch_concurrent_conversation_history_requests_complete() ->
             conversation_history: ConversationHistory::new(vec![user_message("What is 2+2?")]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -43,8 +53,15 @@ async fn continuous_batch_concurrent_conversation_history_requests_complete() ->
             conversation_history: ConversationHistory::new(vec![user_message("Name a color")]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -71,11 +88,11 @@ async fn continuous_batch_concurrent_conversation_history_requests_complete() ->
     assert!(tokens_b > 0);
     assert!(matches!(
         collected_a.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
     assert!(matches!(
         collected_b.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_tests::collect_generated_tokens::collect_generated_tokens;
 use paddler_tests::current_test_device::current_test_device;
@@ -10,8 +12,11 @@ use paddler_tests::model_card::qwen3_0_6b::qwen3_0_6b;
 use paddler_tests::start_in_process_cluster::start_in_process_cluster;
 use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::finish_reason::FinishReason;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -37,9 +42,13 @@ async fn continuous_batch_evicts_long_sequence_under_kv_pressure() -> Result<()>
         slots_per_agent: 2,
         desired_state: BalancerDesiredState {
             chat_template_override: None,
+            dispatch_strategy: None,
             inference_parameters,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             use_chat_template_override: false,
         },
         wait_for_slots_ready: true,
@@ -55,16 +64,28 @@ async fn continuous_batch_evicts_long_sequence_under_kv_pressure() -> Result<()>
     let long_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: long_prompt.to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
     let short_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Hi".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -76,17 +97,16 @@ async fn continuous_batch_evicts_long_sequence_under_kv_pressure() -> Result<()>
     let long_collected = long_collected?;
     let short_collected = short_collected?;
 
-    let long_was_evicted = long_collected.token_results.iter().any(|result| {
-        matches!(result, GeneratedTokenResult::SamplerError(message) if message.contains("evicted"))
-    });
-
     assert!(
-        long_was_evicted,
+        matches!(
+            long_collected.token_results.last(),
+            Some(GeneratedTokenResult::Done(FinishReason::Evicted))
+        ),
         "long prompt must be evicted under KV pressure"
     );
     assert!(matches!(
         short_collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
@@ -0,0 +1,113 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Context as _;
+use anyhow::Result;
+use futures_util::StreamExt as _;
+use paddler_tests::collect_embedding_results::collect_embedding_results;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_embedding_cluster::start_in_process_embedding_cluster;
+use paddler_types::embedding_input_document::EmbeddingInputDocument;
+use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_client::Message as InferenceMessage;
+use paddler_types::inference_client::Response as InferenceResponse;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::jsonrpc::ResponseEnvelope;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn continuous_batch_generation_is_unaffected_by_concurrent_embedding() -> Result<()> {
+    let cluster = start_in_process_embedding_cluster(
+        InferenceParameters {
+            enable_embeddings: true,
+            ..InferenceParameters::default()
+        },
+        4,
+    )
+    .await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let greedy_params = ContinueFromRawPromptParams {
+        grammar: None,
+        logprobs: None,
+        max_tokens: 40,
+        model: None,
+        priority: RequestPriority::Interactive,
+        raw_prompt: "Tell me a long story about a cat".to_owned(),
+        sampling: Some(SamplingParameters {
+            temperature: Some(0.0),
+            top_k: Some(1),
+            ..SamplingParameters::default()
+        }),
+        stop: vec![],
+        truncation: None,
+    };
+
+    let generated_alone = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&greedy_params)
+            .await?,
+    )
+    .await?;
+
+    let mut generation_stream = inference_client
+        .post_continue_from_raw_prompt(&greedy_params)
+        .await?;
+
+    // Waits until the generation is under way, so the embedding batches land in between its steps
+    let first_text = match generation_stream
+        .next()
+        .await
+        .context("generation stream must yield at least one message")??
+    {
+        InferenceMessage::Response(ResponseEnvelope {
+            response: InferenceResponse::GeneratedToken(GeneratedTokenResult::Token(token)),
+            ..
+        }) => token,
+        _ => String::new(),
+    };
+
+    let embedding_stream = inference_client
+        .post_generate_embedding_batch(&GenerateEmbeddingBatchParams {
+            input_batch: (0..8)
+                .map(|index| EmbeddingInputDocument {
+                    content: format!("Document number {index} about the weather"),
+                    id: format!("doc{index}"),
+                    tokens: None,
+                })
+                .collect(),
+            model: None,
+            normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
+        })
+        .await?;
+
+    let (generated_alongside, collected) = tokio::join!(
+        collect_generated_tokens(generation_stream),
+        collect_embedding_results(embedding_stream)
+    );
+
+    let generated_alongside = generated_alongside?;
+    let collected = collected?;
+
+    assert!(collected.errors.is_empty());
+    assert_eq!(collected.embeddings.len(), 8);
+    assert!(!generated_alone.text.is_empty());
+    assert_eq!(
+        format!("{first_text}{}", generated_alongside.text),
+        generated_alone.text
+    );
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
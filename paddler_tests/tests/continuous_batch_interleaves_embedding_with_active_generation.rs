@@ -5,17 +5,26 @@ use futures_util::StreamExt as _;
 use paddler_tests::collect_embedding_results::collect_embedding_results;
 use paddler_tests::collect_generated_tokens::collect_generated_tokens;
 use paddler_tests::inference_http_client::InferenceHttpClient;
-use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_tests::start_in_process_embedding_cluster::start_in_process_embedding_cluster;
 use paddler_types::embedding_input_document::EmbeddingInputDocument;
 use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_types::inference_parameters::InferenceParameters;
 use paddler_types::request_params::ContinueFromRawPromptParams;
 use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
 #[tokio::test(flavor = "multi_thread")]
-async fn continuous_batch_rejects_embedding_during_active_generation() -> Result<()> {
-    let cluster = start_in_process_cluster_with_qwen3(2).await?;
+async fn continuous_batch_interleaves_embedding_with_active_generation() -> Result<()> {
+    let cluster = start_in_process_embedding_cluster(
+        InferenceParameters {
+            enable_embeddings: true,
+            ..InferenceParameters::default()
+        },
+        2,
+    )
+    .await?;
 
     let inference_client =
         InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
@@ -23,8 +32,14 @@ async fn continuous_batch_rejects_embedding_during_active_generation() -> Result
     let mut generation_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 50,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Tell me a long story about a cat".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -33,28 +48,32 @@ async fn continuous_batch_rejects_embedding_during_active_generation() -> Result
         .await
         .ok_or_else(|| anyhow::anyhow!("generation stream must yield at least one message"))?;
 
-    let embedding_outcome = inference_client
+    let embedding_stream = inference_client
         .post_generate_embedding_batch(&GenerateEmbeddingBatchParams {
             input_batch: vec![EmbeddingInputDocument {
                 content: "test".to_owned(),
                 id: "doc1".to_owned(),
+                tokens: None,
             }],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         })
-        .await;
+        .await?;
 
-    if let Ok(embedding_stream) = embedding_outcome {
-        let collected = collect_embedding_results(embedding_stream).await;
+    let (generated, collected) = tokio::join!(
+        collect_generated_tokens(generation_stream),
+        collect_embedding_results(embedding_stream)
+    );
 
-        if let Ok(collected) = collected {
-            assert!(
-                !collected.errors.is_empty() || collected.embeddings.is_empty(),
-                "embedding request must fail when text-only model is busy generating"
-            );
-        }
-    }
+    let generated = generated?;
+    let collected = collected?;
 
-    let _drained = collect_generated_tokens(generation_stream).await;
+    assert!(!generated.token_results.is_empty());
+    assert!(collected.errors.is_empty());
+    assert_eq!(collected.embeddings.len(), 1);
+    assert!(collected.saw_done);
 
     cluster.shutdown().await?;
 
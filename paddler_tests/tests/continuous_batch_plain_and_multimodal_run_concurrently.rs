@@ -13,6 +13,7 @@ use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::ContinueFromRawPromptParams;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -26,8 +27,14 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
     let plain_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 64,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Write a long poem about the sea.".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -39,12 +46,16 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
                 "You are a helpful assistant. Give engaging, short, precise answers.".to_owned(),
             ),
             role: "system".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text(
                 "Hello! How can I help you today?".to_owned(),
             ),
             role: "assistant".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Parts(vec![
@@ -58,6 +69,8 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
                 },
             ]),
             role: "user".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
     ]);
 
@@ -67,8 +80,15 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
             conversation_history: multimodal_conversation,
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 32,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -103,7 +123,7 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
         );
         assert!(matches!(
             collected.token_results.last(),
-            Some(GeneratedTokenResult::Done)
+            Some(GeneratedTokenResult::Done(_))
         ));
     }
 
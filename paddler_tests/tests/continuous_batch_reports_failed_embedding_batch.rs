@@ -0,0 +1,55 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_embedding_results::collect_embedding_results;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_embedding_cluster::start_in_process_embedding_cluster;
+use paddler_types::embedding_input_document::EmbeddingInputDocument;
+use paddler_types::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::request_params::GenerateEmbeddingBatchParams;
+use paddler_types::request_priority::RequestPriority;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn continuous_batch_reports_failed_embedding_batch() -> Result<()> {
+    let cluster = start_in_process_embedding_cluster(
+        InferenceParameters {
+            batch_n_tokens: 16,
+            enable_embeddings: true,
+            ..InferenceParameters::default()
+        },
+        1,
+    )
+    .await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    // A single input that does not fit in the batch cannot be embedded at all
+    let collected = collect_embedding_results(
+        inference_client
+            .post_generate_embedding_batch(&GenerateEmbeddingBatchParams {
+                input_batch: vec![EmbeddingInputDocument {
+                    content: "This document is much longer than the sixteen tokens a single batch can hold, so it cannot be decoded".to_owned(),
+                    id: "doc1".to_owned(),
+                    tokens: None,
+                }],
+                model: None,
+                normalization_method: EmbeddingNormalizationMethod::None,
+                priority: RequestPriority::Interactive,
+                rerank_query: None,
+            })
+            .await?,
+    )
+    .await?;
+
+    assert_eq!(collected.errors.len(), 1);
+    assert!(collected.embeddings.is_empty());
+    assert!(!collected.saw_done);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
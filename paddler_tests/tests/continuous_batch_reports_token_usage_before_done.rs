@@ -0,0 +1,55 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn continuous_batch_reports_token_usage_before_done() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 5,
+            model: None,
+            priority: RequestPriority::Interactive,
+            raw_prompt: "Count from one to one hundred:".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+    let results_count = collected.token_results.len();
+
+    assert!(results_count >= 2);
+
+    let Some(GeneratedTokenResult::Usage(token_usage)) =
+        collected.token_results.get(results_count - 2)
+    else {
+        anyhow::bail!("expected token usage right before Done");
+    };
+
+    assert_eq!(token_usage.completion_tokens, 5);
+    assert!(token_usage.prompt_tokens > 0);
+
+    let metrics = cluster.paddler_client.management().get_metrics().await?;
+
+    assert!(metrics.contains("completion_tokens_total 5"));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -25,8 +26,14 @@ async fn continuous_batch_serves_four_concurrent_requests() -> Result<()> {
             inference_client
                 .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 8,
+                    model: None,
+                    priority: RequestPriority::Interactive,
                     raw_prompt: prompt.to_owned(),
+                    sampling: None,
+                    stop: vec![],
+                    truncation: None,
                 })
                 .await
         }
@@ -52,7 +59,7 @@ async fn continuous_batch_serves_four_concurrent_requests() -> Result<()> {
         );
         assert!(matches!(
             collected.token_results.last(),
-            Some(GeneratedTokenResult::Done)
+            Some(GeneratedTokenResult::Done(_))
         ));
     }
 
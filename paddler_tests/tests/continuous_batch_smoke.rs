@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_tests::collect_generated_tokens::collect_generated_tokens;
@@ -13,6 +15,8 @@ use paddler_types::agent_desired_model::AgentDesiredModel;
 use paddler_types::balancer_desired_state::BalancerDesiredState;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -31,9 +35,13 @@ async fn continuous_batch_smoke_generates_tokens() -> Result<()> {
 
     let desired_state = BalancerDesiredState {
         chat_template_override: None,
+        dispatch_strategy: None,
         inference_parameters: device.inference_parameters_for_full_offload(gpu_layer_count),
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
+        prefetch_model: AgentDesiredModel::None,
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         use_chat_template_override: false,
     };
 
@@ -53,8 +61,14 @@ async fn continuous_batch_smoke_generates_tokens() -> Result<()> {
     let stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 16,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Count from 1 to 5:".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await
         .context("failed to POST /api/v1/continue_from_raw_prompt")?;
@@ -76,7 +90,7 @@ async fn continuous_batch_smoke_generates_tokens() -> Result<()> {
     assert!(
         matches!(
             collected.token_results.last(),
-            Some(GeneratedTokenResult::Done)
+            Some(GeneratedTokenResult::Done(_))
         ),
         "smoke test stream did not terminate with Done"
     );
@@ -7,6 +7,7 @@ use paddler_tests::agents_status::assert_slots_processing::assert_slots_processi
 use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -26,8 +27,14 @@ async fn continuous_batch_stop_signal_terminates_generation_before_max_tokens()
     let mut stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 500,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Write a very long story about a dragon".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
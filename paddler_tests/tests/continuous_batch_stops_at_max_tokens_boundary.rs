@@ -4,8 +4,10 @@ use anyhow::Result;
 use paddler_tests::collect_generated_tokens::collect_generated_tokens;
 use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::finish_reason::FinishReason;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -19,8 +21,14 @@ async fn continuous_batch_stops_at_max_tokens_boundary() -> Result<()> {
     let stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 5,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Count from one to one hundred:".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -38,7 +46,7 @@ async fn continuous_batch_stops_at_max_tokens_boundary() -> Result<()> {
     );
     assert!(matches!(
         collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(FinishReason::MaxTokens))
     ));
 
     cluster.shutdown().await?;
@@ -7,6 +7,7 @@ use paddler_tests::inference_http_client::InferenceHttpClient;
 use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -20,8 +21,14 @@ async fn continuous_batch_stops_generation_when_stop_sender_dropped() -> Result<
     let mut first_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 500,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Write a long essay about photosynthesis".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -35,8 +42,14 @@ async fn continuous_batch_stops_generation_when_stop_sender_dropped() -> Result<
     let second_stream = inference_client
         .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
             raw_prompt: "Hello".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -44,7 +57,7 @@ async fn continuous_batch_stops_generation_when_stop_sender_dropped() -> Result<
 
     assert!(matches!(
         second_collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     let second_token_count = second_collected
@@ -12,6 +12,7 @@ use paddler_types::conversation_message_content_part::ConversationMessageContent
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 fn build_multimodal_conversation(image_data_uri: &str) -> ConversationHistory {
@@ -21,12 +22,16 @@ fn build_multimodal_conversation(image_data_uri: &str) -> ConversationHistory {
                 "You are a helpful assistant. Give engaging, short, precise answers.".to_owned(),
             ),
             role: "system".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text(
                 "Hello! How can I help you today?".to_owned(),
             ),
             role: "assistant".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Parts(vec![
@@ -40,6 +45,8 @@ fn build_multimodal_conversation(image_data_uri: &str) -> ConversationHistory {
                 },
             ]),
             role: "user".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
     ])
 }
@@ -60,8 +67,15 @@ async fn continuous_batch_two_concurrent_multimodal_requests_produce_tokens() ->
             conversation_history: build_multimodal_conversation(&image_data_uri),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 32,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -71,8 +85,15 @@ async fn continuous_batch_two_concurrent_multimodal_requests_produce_tokens() ->
             conversation_history: build_multimodal_conversation(&image_data_uri),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 32,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -104,7 +125,7 @@ async fn continuous_batch_two_concurrent_multimodal_requests_produce_tokens() ->
         );
         assert!(matches!(
             collected.token_results.last(),
-            Some(GeneratedTokenResult::Done)
+            Some(GeneratedTokenResult::Done(_))
         ));
     }
 
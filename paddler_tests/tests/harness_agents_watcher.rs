@@ -14,6 +14,7 @@ use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
 fn make_snapshot(agent_id: &str, slots_total: i32) -> AgentControllerPoolSnapshot {
     AgentControllerPoolSnapshot {
         agents: vec![AgentControllerSnapshot {
+            accepts_embeddings: false,
             desired_slots_total: slots_total,
             download_current: 0,
             download_filename: None,
@@ -21,7 +22,10 @@ fn make_snapshot(agent_id: &str, slots_total: i32) -> AgentControllerPoolSnapsho
             id: agent_id.to_owned(),
             issues: BTreeSet::new(),
             model_path: None,
+            model_pool: None,
             name: None,
+            prefix_cache_hits: 0,
+            prefix_cache_misses: 0,
             slots_processing: 0,
             slots_total,
             state_application_status: AgentStateApplicationStatus::Applied,
@@ -4,7 +4,7 @@ use anyhow::Result;
 use paddler_tests::balancer_addresses::BalancerAddresses;
 
 #[tokio::test(flavor = "multi_thread")]
-async fn picks_three_distinct_ports_per_invocation() -> Result<()> {
+async fn picks_four_distinct_ports_per_invocation() -> Result<()> {
     let addresses = BalancerAddresses::pick()?;
 
     let mut ports = HashSet::new();
@@ -15,8 +15,8 @@ async fn picks_three_distinct_ports_per_invocation() -> Result<()> {
 
     assert_eq!(
         ports.len(),
-        3,
-        "expected 3 distinct ports inside a single BalancerAddresses, got {ports:?}"
+        4,
+        "expected 4 distinct ports inside a single BalancerAddresses, got {ports:?}"
     );
 
     Ok(())
@@ -37,14 +37,15 @@ async fn parallel_invocations_never_produce_internal_collisions() -> Result<()>
 
         let mut ports = HashSet::new();
 
+        ports.insert(addresses.compat_anthropic.port());
         ports.insert(addresses.compat_openai.port());
         ports.insert(addresses.inference.port());
         ports.insert(addresses.management.port());
 
         assert_eq!(
             ports.len(),
-            3,
-            "BalancerAddresses::pick returned a collision inside the triple: {ports:?}"
+            4,
+            "BalancerAddresses::pick returned a collision inside the quadruple: {ports:?}"
         );
     }
 
@@ -30,6 +30,22 @@ async fn management_metrics_endpoint_exposes_prometheus_gauges() -> Result<()> {
         metrics.contains("slots_total"),
         "metrics must contain slots_total gauge"
     );
+    assert!(
+        metrics.contains("prompt_tokens_total 0"),
+        "metrics must contain prompt_tokens_total counter"
+    );
+    assert!(
+        metrics.contains("completion_tokens_total 0"),
+        "metrics must contain completion_tokens_total counter"
+    );
+    assert!(
+        metrics.contains("requests_finished_total{outcome=\"done\"} 0"),
+        "metrics must contain requests_finished_total counter"
+    );
+    assert!(
+        metrics.contains("time_to_first_token_seconds_count 0"),
+        "metrics must contain time_to_first_token_seconds histogram"
+    );
 
     cluster.shutdown().await?;
 
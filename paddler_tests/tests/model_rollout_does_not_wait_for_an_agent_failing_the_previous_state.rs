@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::model_rollout::ModelRollout;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::agent_desired_state::AgentDesiredState;
+use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn model_rollout_does_not_wait_for_an_agent_failing_the_previous_state() -> Result<()> {
+    let pool = Arc::new(AgentControllerPool::default());
+    let failing_agent = Arc::new(make_agent_controller_without_remote_agent("failing-agent"));
+    let next_agent = Arc::new(make_agent_controller_without_remote_agent("next-agent"));
+
+    failing_agent
+        .state_application_status_code
+        .set(AgentStateApplicationStatus::AttemptedAndNotAppliable as i32);
+    next_agent
+        .state_application_status_code
+        .set(AgentStateApplicationStatus::Applied as i32);
+
+    for agent_controller in [&failing_agent, &next_agent] {
+        pool.register_agent_controller(agent_controller.id.clone(), agent_controller.clone())
+            .context("agent registration must succeed")?;
+    }
+
+    let _model_rollout = ModelRollout::spawn(
+        pool.clone(),
+        vec![
+            (failing_agent.clone(), AgentDesiredState::default()),
+            (next_agent.clone(), AgentDesiredState::default()),
+        ],
+    );
+
+    timeout(
+        Duration::from_secs(5),
+        next_agent.agent_message_rx.lock().await.recv(),
+    )
+    .await
+    .context("the rollout must not wait for an agent that was not serving")?
+    .context("the next agent must get the new state")?;
+
+    assert!(failing_agent.get_desired_state().is_some());
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use paddler::balancer::agent_controller_pool::AgentControllerPool;
+use paddler::balancer::model_rollout::ModelRollout;
+use paddler_tests::make_agent_controller_without_remote_agent::make_agent_controller_without_remote_agent;
+use paddler_types::agent_desired_state::AgentDesiredState;
+use paddler_types::agent_state_application_status::AgentStateApplicationStatus;
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn model_rollout_stops_when_an_agent_fails_to_apply_the_state() -> Result<()> {
+    let pool = Arc::new(AgentControllerPool::default());
+    let failing_agent = Arc::new(make_agent_controller_without_remote_agent("failing-agent"));
+    let next_agent = Arc::new(make_agent_controller_without_remote_agent("next-agent"));
+
+    for agent_controller in [&failing_agent, &next_agent] {
+        agent_controller
+            .state_application_status_code
+            .set(AgentStateApplicationStatus::Applied as i32);
+
+        pool.register_agent_controller(agent_controller.id.clone(), agent_controller.clone())
+            .context("agent registration must succeed")?;
+    }
+
+    let _model_rollout = ModelRollout::spawn(
+        pool.clone(),
+        vec![
+            (failing_agent.clone(), AgentDesiredState::default()),
+            (next_agent.clone(), AgentDesiredState::default()),
+        ],
+    );
+
+    failing_agent
+        .agent_message_rx
+        .lock()
+        .await
+        .recv()
+        .await
+        .context("the first agent must get the new state")?;
+
+    failing_agent
+        .state_application_status_code
+        .set(AgentStateApplicationStatus::AttemptedAndNotAppliable as i32);
+    pool.signal_update();
+
+    assert!(
+        timeout(
+            Duration::from_secs(1),
+            next_agent.agent_message_rx.lock().await.recv()
+        )
+        .await
+        .is_err(),
+        "the rollout must not go past the agent that failed to apply the new state"
+    );
+
+    Ok(())
+}
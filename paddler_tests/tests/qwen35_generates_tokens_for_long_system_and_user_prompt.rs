@@ -9,6 +9,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 fn build_long_link_list() -> String {
@@ -67,10 +68,14 @@ async fn qwen35_generates_tokens_for_long_system_and_user_prompt() -> Result<()>
         ConversationMessage {
             content: ConversationMessageContent::Text(system_prompt.to_owned()),
             role: "system".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text(user_prompt),
             role: "user".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
     ]);
 
@@ -80,8 +85,15 @@ async fn qwen35_generates_tokens_for_long_system_and_user_prompt() -> Result<()>
             conversation_history,
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 512,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -96,7 +108,7 @@ async fn qwen35_generates_tokens_for_long_system_and_user_prompt() -> Result<()>
     assert!(token_count > 0);
     assert!(matches!(
         collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
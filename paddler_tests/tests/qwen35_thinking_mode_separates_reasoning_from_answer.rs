@@ -0,0 +1,70 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3_5::start_in_process_cluster_with_qwen3_5;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen35_thinking_mode_separates_reasoning_from_answer() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3_5(1, false).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_conversation_history(&ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(vec![ConversationMessage {
+                content: ConversationMessageContent::Text("What is 2+2?".to_owned()),
+                role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
+            }]),
+            enable_thinking: true,
+            grammar: None,
+            logprobs: None,
+            max_tokens: 2000,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
+            tools: vec![],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    let reasoning: String = collected
+        .token_results
+        .iter()
+        .filter_map(|result| match result {
+            GeneratedTokenResult::ReasoningToken(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    assert!(matches!(
+        collected.token_results.last(),
+        Some(GeneratedTokenResult::Done(FinishReason::Eos))
+    ));
+    assert!(!reasoning.is_empty());
+    assert!(!reasoning.contains("<think>") && !reasoning.contains("</think>"));
+    assert!(!collected.text.is_empty());
+    assert!(!collected.text.contains("<think>") && !collected.text.contains("</think>"));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -25,16 +26,22 @@ async fn qwen35_thinking_multi_turn_conversation_stops_cleanly() -> Result<()> {
                 "You are a helpful assistant. Give engaging, short, precise answers. Be friendly, supportive, use emojis.".to_owned(),
             ),
             role: "system".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text(
                 "Hello! How can I help you today?".to_owned(),
             ),
             role: "assistant".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text("hi".to_owned()),
             role: "user".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
     ]);
 
@@ -44,8 +51,15 @@ async fn qwen35_thinking_multi_turn_conversation_stops_cleanly() -> Result<()> {
             conversation_history,
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 1000,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -54,14 +68,19 @@ async fn qwen35_thinking_multi_turn_conversation_stops_cleanly() -> Result<()> {
     let token_count = collected
         .token_results
         .iter()
-        .filter(|result| matches!(result, GeneratedTokenResult::Token(_)))
+        .filter(|result| {
+            matches!(
+                result,
+                GeneratedTokenResult::ReasoningToken(_) | GeneratedTokenResult::Token(_)
+            )
+        })
         .count();
 
     assert!(token_count > 0);
     assert!(token_count <= 1000);
     assert!(matches!(
         collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
@@ -9,6 +9,7 @@ use paddler_types::conversation_message::ConversationMessage;
 use paddler_types::conversation_message_content::ConversationMessageContent;
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -25,12 +26,16 @@ async fn qwen35_with_system_message_completes_with_thinking() -> Result<()> {
                 "You are a focused web crawler assistant. Your only job is to decide which links to follow to discover more relevant pages. Respond with JSON only.".to_owned(),
             ),
             role: "system".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
         ConversationMessage {
             content: ConversationMessageContent::Text(
                 "Goal: \"find all PDF reports\"\n\nPage: https://example.com/reports\n\nFollowable links:\n[0] [Navigation] \"Home\" → /home\n[1] [PrimaryListing] \"Annual Report 2024\" → /reports/annual-2024.pdf\n[2] [PrimaryListing] \"Q3 Financial Summary\" → /reports/q3-summary.pdf\n[3] [Navigation] \"Next Page\" → /reports?page=2".to_owned(),
             ),
             role: "user".to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         },
     ]);
 
@@ -40,8 +45,15 @@ async fn qwen35_with_system_message_completes_with_thinking() -> Result<()> {
             conversation_history,
             enable_thinking: true,
             grammar: None,
+            logprobs: None,
             max_tokens: 2000,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -50,13 +62,18 @@ async fn qwen35_with_system_message_completes_with_thinking() -> Result<()> {
     let token_count = collected
         .token_results
         .iter()
-        .filter(|result| matches!(result, GeneratedTokenResult::Token(_)))
+        .filter(|result| {
+            matches!(
+                result,
+                GeneratedTokenResult::ReasoningToken(_) | GeneratedTokenResult::Token(_)
+            )
+        })
         .count();
 
     assert!(token_count > 0);
     assert!(matches!(
         collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
@@ -0,0 +1,98 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::current_test_device::current_test_device;
+use paddler_tests::in_process_cluster_params::InProcessClusterParams;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::model_card::ModelCard;
+use paddler_tests::model_card::qwen3_0_6b::qwen3_0_6b;
+use paddler_tests::start_in_process_cluster::start_in_process_cluster;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::dry_parameters::DryParameters;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
+use paddler_types::xtc_parameters::XtcParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_generates_with_every_ordered_sampler_enabled() -> Result<()> {
+    let device = current_test_device()?;
+
+    device.require_available()?;
+
+    let ModelCard {
+        gpu_layer_count,
+        reference,
+    } = qwen3_0_6b();
+
+    let cluster = start_in_process_cluster(InProcessClusterParams {
+        slots_per_agent: 1,
+        desired_state: BalancerDesiredState {
+            chat_template_override: None,
+            dispatch_strategy: None,
+            inference_parameters: InferenceParameters {
+                dry: Some(DryParameters {
+                    allowed_length: 2,
+                    base: 1.75,
+                    multiplier: 0.8,
+                    penalty_last_n: -1,
+                    sequence_breakers: vec!["\n".to_owned(), ":".to_owned()],
+                }),
+                top_n_sigma: Some(1.5),
+                typical_p: Some(0.9),
+                xtc: Some(XtcParameters {
+                    probability: 0.5,
+                    threshold: 0.1,
+                }),
+                ..device.inference_parameters_for_full_offload(gpu_layer_count)
+            },
+            model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
+            multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
+            use_chat_template_override: false,
+        },
+        wait_for_slots_ready: true,
+        ..InProcessClusterParams::default()
+    })
+    .await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let collected = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+                grammar: None,
+                logprobs: None,
+                max_tokens: 20,
+                model: None,
+                priority: RequestPriority::Interactive,
+                raw_prompt: "Write a short poem about the sea.".to_owned(),
+                sampling: None,
+                stop: vec![],
+                truncation: None,
+            })
+            .await?,
+    )
+    .await?;
+
+    assert!(!collected.text.is_empty());
+    assert!(matches!(
+        collected.token_results.last(),
+        Some(GeneratedTokenResult::Done(_))
+    ));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::finish_reason::FinishReason;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_generation_ends_at_stop_sequence() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 100,
+            model: None,
+            priority: RequestPriority::Interactive,
+            raw_prompt: "1, 2, 3, 4,".to_owned(),
+            sampling: Some(SamplingParameters {
+                temperature: Some(0.0),
+                top_k: Some(1),
+                ..SamplingParameters::default()
+            }),
+            stop: vec![", 8".to_owned()],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    assert!(
+        collected.text.contains('7'),
+        "text before the stop sequence must be returned: {}",
+        collected.text
+    );
+    assert!(
+        !collected.text.contains('8'),
+        "stop sequence must not be returned: {}",
+        collected.text
+    );
+    assert!(matches!(
+        collected.token_results.last(),
+        Some(GeneratedTokenResult::Done(FinishReason::StopSequence(stop_sequence)))
+            if stop_sequence == ", 8"
+    ));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_greedy_sampling_override_is_deterministic() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let greedy_params = ContinueFromRawPromptParams {
+        grammar: None,
+        logprobs: None,
+        max_tokens: 20,
+        model: None,
+        priority: RequestPriority::Interactive,
+        raw_prompt: "Write a short poem about the sea.".to_owned(),
+        sampling: Some(SamplingParameters {
+            temperature: Some(0.0),
+            top_k: Some(1),
+            ..SamplingParameters::default()
+        }),
+        stop: vec![],
+        truncation: None,
+    };
+
+    let first = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&greedy_params)
+            .await?,
+    )
+    .await?;
+    let second = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&greedy_params)
+            .await?,
+    )
+    .await?;
+
+    assert!(!first.text.is_empty());
+    assert_eq!(first.text, second.text);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::truncation_policy::TruncationPolicy;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_rejects_conversation_exceeding_context() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_conversation_history(&ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(vec![ConversationMessage {
+                content: ConversationMessageContent::Text("hi".to_owned()),
+                role: "user".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![],
+            }]),
+            enable_thinking: false,
+            grammar: None,
+            logprobs: None,
+            max_tokens: 1_000_000,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
+            tools: vec![],
+            truncation: Some(TruncationPolicy::Reject),
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    assert_eq!(collected.token_results.len(), 1);
+
+    let Some(GeneratedTokenResult::ContextOverflow(context_overflow)) =
+        collected.token_results.last()
+    else {
+        anyhow::bail!("expected the request to be rejected with a context overflow");
+    };
+
+    assert_eq!(context_overflow.max_tokens, 1_000_000);
+    assert!(context_overflow.prompt_tokens > 0);
+    assert!(context_overflow.context_size < context_overflow.max_tokens);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
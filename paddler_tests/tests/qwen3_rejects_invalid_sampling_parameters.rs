@@ -0,0 +1,52 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_rejects_invalid_sampling_parameters() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
+            raw_prompt: "The capital of France is".to_owned(),
+            sampling: Some(SamplingParameters {
+                top_p: Some(1.5),
+                ..SamplingParameters::default()
+            }),
+            stop: vec![],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    assert!(
+        matches!(
+            collected.token_results.as_slice(),
+            [GeneratedTokenResult::InvalidSamplingParameters(_)]
+        ),
+        "expected InvalidSamplingParameters, got: {:?}",
+        collected.token_results
+    );
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
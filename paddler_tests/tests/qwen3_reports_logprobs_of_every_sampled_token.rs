@@ -0,0 +1,73 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::token_usage::TokenUsage;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_reports_logprobs_of_every_sampled_token() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let stream = inference_client
+        .post_continue_from_raw_prompt(&ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: Some(3),
+            max_tokens: 10,
+            model: None,
+            priority: RequestPriority::Interactive,
+            raw_prompt:
+                "<|im_start|>user\nHow can I make a cat happy?<|im_end|>\n<|im_start|>assistant\n"
+                    .to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
+        })
+        .await?;
+
+    let collected = collect_generated_tokens(stream).await?;
+
+    let token_logprobs_list: Vec<_> = collected
+        .token_results
+        .iter()
+        .filter_map(|result| match result {
+            GeneratedTokenResult::TokenLogprobs(token_logprobs) => Some(token_logprobs),
+            _ => None,
+        })
+        .collect();
+    let completion_tokens = collected
+        .token_results
+        .iter()
+        .find_map(|result| match result {
+            GeneratedTokenResult::Usage(TokenUsage {
+                completion_tokens, ..
+            }) => Some(*completion_tokens),
+            _ => None,
+        });
+
+    assert_eq!(Some(token_logprobs_list.len()), completion_tokens);
+
+    for token_logprobs in token_logprobs_list {
+        assert!(token_logprobs.sampled.logprob <= 0.0);
+        assert_eq!(token_logprobs.top_logprobs.len(), 3);
+        assert!(
+            token_logprobs
+                .top_logprobs
+                .windows(2)
+                .all(|pair| pair[0].logprob >= pair[1].logprob)
+        );
+    }
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
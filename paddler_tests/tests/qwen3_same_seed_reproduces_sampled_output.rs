@@ -0,0 +1,65 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::start_in_process_cluster_with_qwen3::start_in_process_cluster_with_qwen3;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::request_params::ContinueFromRawPromptParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::sampling_parameters::SamplingParameters;
+use reqwest::Client;
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_same_seed_reproduces_sampled_output() -> Result<()> {
+    let cluster = start_in_process_cluster_with_qwen3(1).await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let seeded_params = ContinueFromRawPromptParams {
+        grammar: None,
+        logprobs: None,
+        max_tokens: 30,
+        model: None,
+        priority: RequestPriority::Interactive,
+        raw_prompt: "Write a short poem about the sea.".to_owned(),
+        sampling: Some(SamplingParameters {
+            seed: Some(1234),
+            temperature: Some(1.0),
+            ..SamplingParameters::default()
+        }),
+        stop: vec![],
+        truncation: None,
+    };
+
+    let first = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&seeded_params)
+            .await?,
+    )
+    .await?;
+    let second = collect_generated_tokens(
+        inference_client
+            .post_continue_from_raw_prompt(&seeded_params)
+            .await?,
+    )
+    .await?;
+
+    assert!(!first.text.is_empty());
+    assert_eq!(first.text, second.text);
+
+    for collected in [&first, &second] {
+        assert!(
+            collected
+                .token_results
+                .iter()
+                .any(|result| matches!(result, GeneratedTokenResult::Seed(1234)))
+        );
+    }
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
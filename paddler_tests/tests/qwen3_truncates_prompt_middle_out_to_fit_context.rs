@@ -0,0 +1,123 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use paddler_tests::collect_generated_tokens::collect_generated_tokens;
+use paddler_tests::current_test_device::current_test_device;
+use paddler_tests::in_process_cluster_params::InProcessClusterParams;
+use paddler_tests::inference_http_client::InferenceHttpClient;
+use paddler_tests::model_card::ModelCard;
+use paddler_tests::model_card::qwen3_0_6b::qwen3_0_6b;
+use paddler_tests::start_in_process_cluster::start_in_process_cluster;
+use paddler_types::agent_desired_model::AgentDesiredModel;
+use paddler_types::balancer_desired_state::BalancerDesiredState;
+use paddler_types::conversation_history::ConversationHistory;
+use paddler_types::conversation_message::ConversationMessage;
+use paddler_types::conversation_message_content::ConversationMessageContent;
+use paddler_types::generated_token_result::GeneratedTokenResult;
+use paddler_types::inference_parameters::InferenceParameters;
+use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
+use paddler_types::rollout_strategy::RolloutStrategy;
+use paddler_types::token_usage::TokenUsage;
+use paddler_types::truncation_policy::TruncationPolicy;
+use reqwest::Client;
+
+const CONTEXT_SIZE: usize = 1024;
+const MAX_TOKENS: usize = 16;
+
+fn text_message(role: &str, text: String) -> ConversationMessage {
+    ConversationMessage {
+        content: ConversationMessageContent::Text(text),
+        role: role.to_owned(),
+        tool_call_id: None,
+        tool_calls: vec![],
+    }
+}
+
+#[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_truncates_prompt_middle_out_to_fit_context() -> Result<()> {
+    let device = current_test_device()?;
+
+    device.require_available()?;
+
+    let ModelCard {
+        gpu_layer_count,
+        reference,
+    } = qwen3_0_6b();
+
+    let cluster = start_in_process_cluster(InProcessClusterParams {
+        slots_per_agent: 1,
+        desired_state: BalancerDesiredState {
+            chat_template_override: None,
+            dispatch_strategy: None,
+            inference_parameters: InferenceParameters {
+                context_size: u32::try_from(CONTEXT_SIZE)?,
+                ..device.inference_parameters_for_full_offload(gpu_layer_count)
+            },
+            model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
+            multimodal_projection: AgentDesiredModel::None,
+            prefetch_model: AgentDesiredModel::None,
+            rollout_strategy: RolloutStrategy::AllAtOnce,
+            use_chat_template_override: false,
+        },
+        wait_for_slots_ready: true,
+        ..InProcessClusterParams::default()
+    })
+    .await?;
+
+    let inference_client =
+        InferenceHttpClient::new(Client::new(), cluster.addresses.inference_base_url()?);
+
+    let messages = vec![text_message(
+        "user",
+        format!(
+            "{}Summarize the text above in one word.",
+            "The quick brown fox jumps over the lazy dog. ".repeat(200)
+        ),
+    )];
+
+    let collected = collect_generated_tokens(
+        inference_client
+            .post_continue_from_conversation_history(&ContinueFromConversationHistoryParams {
+                add_generation_prompt: true,
+                conversation_history: ConversationHistory::new(messages),
+                enable_thinking: false,
+                grammar: None,
+                logprobs: None,
+                max_tokens: i32::try_from(MAX_TOKENS)?,
+                model: None,
+                priority: RequestPriority::Interactive,
+                sampling: None,
+                session_id: None,
+                stop: vec![],
+                tools: vec![],
+                truncation: Some(TruncationPolicy::MiddleOut),
+            })
+            .await?,
+    )
+    .await?;
+
+    let prompt_tokens = collected
+        .token_results
+        .iter()
+        .find_map(|result| match result {
+            GeneratedTokenResult::Usage(TokenUsage { prompt_tokens, .. }) => Some(*prompt_tokens),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("expected token usage to be reported"))?;
+
+    assert!(prompt_tokens > 0);
+    assert!(prompt_tokens <= CONTEXT_SIZE - MAX_TOKENS);
+    assert!(matches!(
+        collected.token_results.last(),
+        Some(GeneratedTokenResult::Done(_))
+    ));
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -12,6 +12,7 @@ use paddler_types::conversation_message_content_part::ConversationMessageContent
 use paddler_types::generated_token_result::GeneratedTokenResult;
 use paddler_types::image_url::ImageUrl;
 use paddler_types::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_types::request_priority::RequestPriority;
 use reqwest::Client;
 
 #[serial_test::file_serial(model_load, path => "../target/model_load.lock")]
@@ -36,6 +37,8 @@ async fn smolvlm2_generates_tokens_from_image_input() -> Result<()> {
             },
         ]),
         role: "user".to_owned(),
+        tool_call_id: None,
+        tool_calls: vec![],
     }]);
 
     let stream = inference_client
@@ -44,8 +47,15 @@ async fn smolvlm2_generates_tokens_from_image_input() -> Result<()> {
             conversation_history,
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 200,
+            model: None,
+            priority: RequestPriority::Interactive,
+            sampling: None,
+            session_id: None,
+            stop: vec![],
             tools: vec![],
+            truncation: None,
         })
         .await?;
 
@@ -60,7 +70,7 @@ async fn smolvlm2_generates_tokens_from_image_input() -> Result<()> {
     assert!(token_count > 0);
     assert!(matches!(
         collected.token_results.last(),
-        Some(GeneratedTokenResult::Done)
+        Some(GeneratedTokenResult::Done(_))
     ));
 
     cluster.shutdown().await?;
@@ -9,6 +9,7 @@ use crate::agent_state_application_status::AgentStateApplicationStatus;
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct AgentControllerSnapshot {
+    pub accepts_embeddings: bool,
     pub desired_slots_total: i32,
     pub download_current: usize,
     pub download_filename: Option<String>,
@@ -16,7 +17,11 @@ pub struct AgentControllerSnapshot {
     pub id: String,
     pub issues: BTreeSet<AgentIssue>,
     pub model_path: Option<String>,
+    /// Named model pool the agent serves, or `None` when it serves the default model
+    pub model_pool: Option<String>,
     pub name: Option<String>,
+    pub prefix_cache_hits: usize,
+    pub prefix_cache_misses: usize,
     pub slots_processing: i32,
     pub slots_total: i32,
     pub state_application_status: AgentStateApplicationStatus,
@@ -12,4 +12,73 @@ pub struct AgentDesiredState {
     pub inference_parameters: InferenceParameters,
     pub model: AgentDesiredModel,
     pub multimodal_projection: AgentDesiredModel,
+    /// Downloaded in the background while the agent keeps serving `model`, so that switching to
+    /// it later does not have to wait for the download
+    #[serde(default)]
+    pub prefetch_model: AgentDesiredModel,
+}
+
+impl AgentDesiredState {
+    /// Changing only the model to prefetch does not disturb the model that is loaded
+    #[must_use]
+    pub fn requires_reload_to_switch_to(&self, next: &Self) -> bool {
+        self.chat_template_override != next.chat_template_override
+            || self.inference_parameters != next.inference_parameters
+            || self.model != next.model
+            || self.multimodal_projection != next.multimodal_projection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    fn local_model(path: &str) -> AgentDesiredModel {
+        AgentDesiredModel::LocalToAgent(path.to_owned())
+    }
+
+    #[test]
+    fn switching_prefetch_model_does_not_require_reload() {
+        let current = AgentDesiredState {
+            model: local_model("current.gguf"),
+            ..AgentDesiredState::default()
+        };
+        let next = AgentDesiredState {
+            prefetch_model: local_model("next.gguf"),
+            ..current.clone()
+        };
+
+        assert!(!current.requires_reload_to_switch_to(&next));
+    }
+
+    #[test]
+    fn switching_model_requires_reload() {
+        let current = AgentDesiredState {
+            model: local_model("current.gguf"),
+            prefetch_model: local_model("next.gguf"),
+            ..AgentDesiredState::default()
+        };
+        let next = AgentDesiredState {
+            model: local_model("next.gguf"),
+            ..current.clone()
+        };
+
+        assert!(current.requires_reload_to_switch_to(&next));
+    }
+
+    #[test]
+    fn prefetch_model_defaults_to_none() -> Result<()> {
+        let agent_desired_state: AgentDesiredState = serde_json::from_value(serde_json::json!({
+            "chat_template_override": null,
+            "inference_parameters": InferenceParameters::default(),
+            "model": "None",
+            "multimodal_projection": "None",
+        }))?;
+
+        assert_eq!(agent_desired_state.prefetch_model, AgentDesiredModel::None);
+
+        Ok(())
+    }
 }
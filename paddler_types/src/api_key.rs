@@ -0,0 +1,127 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::api_key_rate_limit::ApiKeyRateLimit;
+use crate::api_key_scope::ApiKeyScope;
+use crate::validates::Validates;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+    /// Buffered requests of keys with a weight get their own share of the queue, proportional
+    /// to the weight. Requests of the other keys share a single first-come, first-served queue
+    #[serde(default)]
+    pub fair_share_weight: Option<u32>,
+    /// Identifies the key in logs, so that the secret itself never has to be printed
+    pub name: String,
+    #[serde(default)]
+    pub rate_limit: ApiKeyRateLimit,
+    pub scopes: BTreeSet<ApiKeyScope>,
+    /// Bearer token clients send in the `Authorization` header
+    pub secret: String,
+}
+
+impl Validates<Self> for ApiKey {
+    fn validate(self) -> Result<Self> {
+        if self.name.trim().is_empty() {
+            bail!("API key name must not be empty");
+        }
+
+        if self.secret.trim().is_empty() {
+            bail!("API key '{}' must have a secret", self.name);
+        }
+
+        if self.scopes.is_empty() {
+            bail!("API key '{}' must have at least one scope", self.name);
+        }
+
+        if self.fair_share_weight == Some(0) {
+            bail!(
+                "API key '{}' must have a positive fair share weight",
+                self.name
+            );
+        }
+
+        Ok(self)
+    }
+}
+
+impl Validates<Self> for Vec<ApiKey> {
+    fn validate(self) -> Result<Self> {
+        let mut names = BTreeSet::new();
+        let mut secrets = BTreeSet::new();
+
+        for api_key in &self {
+            if !names.insert(api_key.name.as_str()) {
+                bail!("API key name '{}' is used more than once", api_key.name);
+            }
+
+            if !secrets.insert(api_key.secret.as_str()) {
+                bail!(
+                    "API key '{}' reuses the secret of another key",
+                    api_key.name
+                );
+            }
+        }
+
+        self.into_iter().map(Validates::validate).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key(name: &str, secret: &str) -> ApiKey {
+        ApiKey {
+            fair_share_weight: None,
+            name: name.to_owned(),
+            rate_limit: ApiKeyRateLimit::default(),
+            scopes: BTreeSet::from([ApiKeyScope::Inference]),
+            secret: secret.to_owned(),
+        }
+    }
+
+    #[test]
+    fn validate_succeeds_for_distinct_keys() {
+        let api_keys = vec![api_key("first", "secret-1"), api_key("second", "secret-2")];
+
+        assert!(api_keys.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_for_empty_secret() {
+        assert!(api_key("first", " ").validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_key_without_scopes() {
+        let api_key = ApiKey {
+            scopes: BTreeSet::new(),
+            ..api_key("first", "secret-1")
+        };
+
+        assert!(api_key.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_zero_fair_share_weight() {
+        let api_key = ApiKey {
+            fair_share_weight: Some(0),
+            ..api_key("first", "secret-1")
+        };
+
+        assert!(api_key.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_shared_secret() {
+        let api_keys = vec![api_key("first", "secret-1"), api_key("second", "secret-1")];
+
+        assert!(api_keys.validate().is_err());
+    }
+}
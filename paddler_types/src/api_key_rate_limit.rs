@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits are counted in one-minute windows; a limit that is not set is not enforced.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyRateLimit {
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    /// Requests are charged their estimated size (prompt and completion budget) when admitted
+    #[serde(default)]
+    pub tokens_per_minute: Option<u32>,
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum ApiKeyScope {
+    /// Inference service and the `OpenAI`-compatible service
+    Inference,
+    /// Management service: desired state, API keys, agents and metrics
+    Management,
+}
@@ -1,18 +1,39 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use anyhow::anyhow;
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::agent_desired_model::AgentDesiredModel;
 use crate::agent_desired_state::AgentDesiredState;
 use crate::chat_template::ChatTemplate;
+use crate::dispatch_strategy::DispatchStrategy;
 use crate::inference_parameters::InferenceParameters;
+use crate::model_pool_desired_state::ModelPoolDesiredState;
+use crate::rollout_strategy::RolloutStrategy;
+use crate::validates::Validates;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct BalancerDesiredState {
     pub chat_template_override: Option<ChatTemplate>,
+    /// Overrides the strategy the balancer was started with when set
+    #[serde(default)]
+    pub dispatch_strategy: Option<DispatchStrategy>,
     pub inference_parameters: InferenceParameters,
+    /// Model served to requests that do not name any of the `model_pools`
     pub model: AgentDesiredModel,
+    /// Additional models served by the same cluster, keyed by the name requests use to pick them
+    #[serde(default)]
+    pub model_pools: BTreeMap<String, ModelPoolDesiredState>,
     pub multimodal_projection: AgentDesiredModel,
+    /// Model agents download in the background, ahead of `model` being switched to it
+    #[serde(default)]
+    pub prefetch_model: AgentDesiredModel,
+    /// How agents switch over when a change makes them reload their model
+    #[serde(default)]
+    pub rollout_strategy: RolloutStrategy,
     pub use_chat_template_override: bool,
 }
 
@@ -28,6 +49,97 @@ impl BalancerDesiredState {
             inference_parameters: self.inference_parameters.clone(),
             model: self.model.clone(),
             multimodal_projection: self.multimodal_projection.clone(),
+            prefetch_model: self.prefetch_model.clone(),
         }
     }
 }
+
+impl Validates<Self> for BalancerDesiredState {
+    fn validate(self) -> Result<Self> {
+        Ok(Self {
+            inference_parameters: self.inference_parameters.validate()?,
+            model_pools: self
+                .model_pools
+                .into_iter()
+                .map(|(name, model_pool)| {
+                    let model_pool = model_pool
+                        .validate()
+                        .map_err(|err| anyhow!("model pool '{name}': {err}"))?;
+
+                    Ok((name, model_pool))
+                })
+                .collect::<Result<_>>()?,
+            ..self
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserializes_without_prefetch_model_and_rollout_strategy() -> Result<()> {
+        let balancer_desired_state: BalancerDesiredState =
+            serde_json::from_value(serde_json::json!({
+                "chat_template_override": null,
+                "inference_parameters": InferenceParameters::default(),
+                "model": "None",
+                "multimodal_projection": "None",
+                "use_chat_template_override": false,
+            }))?;
+
+        assert_eq!(
+            balancer_desired_state.prefetch_model,
+            AgentDesiredModel::None
+        );
+        assert_eq!(
+            balancer_desired_state.rollout_strategy,
+            RolloutStrategy::AllAtOnce
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn agents_get_the_model_to_prefetch() {
+        let prefetch_model = AgentDesiredModel::LocalToAgent("next.gguf".to_owned());
+        let balancer_desired_state = BalancerDesiredState {
+            prefetch_model: prefetch_model.clone(),
+            ..BalancerDesiredState::default()
+        };
+
+        assert_eq!(
+            balancer_desired_state
+                .to_agent_desired_state()
+                .prefetch_model,
+            prefetch_model
+        );
+    }
+
+    #[test]
+    fn validation_rejects_invalid_model_pool_inference_parameters() {
+        let balancer_desired_state = BalancerDesiredState {
+            model_pools: BTreeMap::from([(
+                "embeddings".to_owned(),
+                ModelPoolDesiredState {
+                    inference_parameters: InferenceParameters {
+                        image_resize_to_fit: 0,
+                        ..InferenceParameters::default()
+                    },
+                    ..ModelPoolDesiredState::default()
+                },
+            )]),
+            ..BalancerDesiredState::default()
+        };
+
+        assert!(balancer_desired_state.validate().is_err());
+    }
+
+    #[test]
+    fn validation_accepts_default_state() {
+        assert!(BalancerDesiredState::default().validate().is_ok());
+    }
+}
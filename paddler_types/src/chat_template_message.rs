@@ -1,9 +1,14 @@
 use serde::Serialize;
 
 use crate::chat_template_message_content::ChatTemplateMessageContent;
+use crate::chat_template_tool_call::ChatTemplateToolCall;
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct ChatTemplateMessage {
     pub content: ChatTemplateMessageContent,
     pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ChatTemplateToolCall>,
 }
@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+use crate::chat_template_tool_call_function::ChatTemplateToolCallFunction;
+use crate::tool_call::ToolCall;
+
+/// Tool call in the shape chat templates expect, which follows the `OpenAI` API
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ChatTemplateToolCall {
+    pub function: ChatTemplateToolCallFunction,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub tool_call_type: String,
+}
+
+impl From<&ToolCall> for ChatTemplateToolCall {
+    fn from(tool_call: &ToolCall) -> Self {
+        Self {
+            function: ChatTemplateToolCallFunction {
+                arguments: tool_call.arguments.clone(),
+                name: tool_call.name.clone(),
+            },
+            id: tool_call.id.clone(),
+            tool_call_type: "function".to_owned(),
+        }
+    }
+}
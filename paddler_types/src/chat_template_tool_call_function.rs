@@ -0,0 +1,8 @@
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ChatTemplateToolCallFunction {
+    pub arguments: Value,
+    pub name: String,
+}
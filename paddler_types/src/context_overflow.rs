@@ -0,0 +1,23 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContextOverflow {
+    /// Tokens a single sequence can hold on the agent
+    pub context_size: usize,
+    pub max_tokens: usize,
+    pub prompt_tokens: usize,
+}
+
+impl fmt::Display for ContextOverflow {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "prompt of {} tokens and up to {} generated tokens do not fit in the context of {} tokens",
+            self.prompt_tokens, self.max_tokens, self.context_size
+        )
+    }
+}
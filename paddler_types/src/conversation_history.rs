@@ -5,6 +5,7 @@ use crate::chat_template_message::ChatTemplateMessage;
 use crate::chat_template_message_content::ChatTemplateMessageContent;
 use crate::chat_template_message_content_part::ChatTemplateMessageContentPart;
 use crate::chat_template_messages::ChatTemplateMessages;
+use crate::chat_template_tool_call::ChatTemplateToolCall;
 use crate::conversation_message::ConversationMessage;
 use crate::conversation_message_content::ConversationMessageContent;
 use crate::conversation_message_content_part::ConversationMessageContentPart;
@@ -23,6 +24,51 @@ impl ConversationHistory {
         Self { messages }
     }
 
+    /// Messages that can be dropped to make the conversation shorter, which are all but the
+    /// system messages and the last message
+    #[must_use]
+    pub fn droppable_messages_count(&self) -> usize {
+        self.messages
+            .iter()
+            .take(self.messages.len().saturating_sub(1))
+            .filter(|message| message.role != "system")
+            .count()
+    }
+
+    /// Drops `count` of the oldest droppable messages, along with the tool results left behind
+    /// without the calls they answer
+    #[must_use]
+    pub fn without_oldest_messages(&self, count: usize) -> Self {
+        let last_index = self.messages.len().saturating_sub(1);
+        let mut dropped_count = 0;
+        let mut is_dropping_orphaned_tool_results = false;
+        let mut messages = Vec::with_capacity(self.messages.len());
+
+        for (index, message) in self.messages.iter().enumerate() {
+            let is_system = message.role == "system";
+            let is_droppable = !is_system && index != last_index;
+
+            if is_droppable && dropped_count < count {
+                dropped_count += 1;
+                is_dropping_orphaned_tool_results = true;
+
+                continue;
+            }
+
+            if is_droppable && is_dropping_orphaned_tool_results && message.role == "tool" {
+                continue;
+            }
+
+            if !is_system {
+                is_dropping_orphaned_tool_results = false;
+            }
+
+            messages.push(message.clone());
+        }
+
+        Self::new(messages)
+    }
+
     #[must_use]
     pub fn extract_image_urls(&self) -> Vec<&ImageUrl> {
         self.messages
@@ -67,6 +113,12 @@ impl ConversationHistory {
                         }
                     },
                     role: message.role.clone(),
+                    tool_call_id: message.tool_call_id.clone(),
+                    tool_calls: message
+                        .tool_calls
+                        .iter()
+                        .map(ChatTemplateToolCall::from)
+                        .collect(),
                 })
                 .collect(),
         }
@@ -76,11 +128,14 @@ impl ConversationHistory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tool_call::ToolCall;
 
     fn make_text_message(role: &str, text: &str) -> ConversationMessage {
         ConversationMessage {
             content: ConversationMessageContent::Text(text.to_owned()),
             role: role.to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         }
     }
 
@@ -91,9 +146,60 @@ mod tests {
         ConversationMessage {
             content: ConversationMessageContent::Parts(parts),
             role: role.to_owned(),
+            tool_call_id: None,
+            tool_calls: vec![],
         }
     }
 
+    #[test]
+    fn droppable_messages_exclude_system_and_last_message() {
+        let history = ConversationHistory::new(vec![
+            make_text_message("system", "be brief"),
+            make_text_message("user", "hi"),
+            make_text_message("assistant", "hello"),
+            make_text_message("user", "how are you?"),
+        ]);
+
+        assert_eq!(history.droppable_messages_count(), 2);
+    }
+
+    #[test]
+    fn without_oldest_messages_keeps_system_and_last_message() {
+        let history = ConversationHistory::new(vec![
+            make_text_message("system", "be brief"),
+            make_text_message("user", "hi"),
+            make_text_message("assistant", "hello"),
+            make_text_message("user", "how are you?"),
+        ]);
+
+        assert_eq!(
+            history.without_oldest_messages(2),
+            ConversationHistory::new(vec![
+                make_text_message("system", "be brief"),
+                make_text_message("user", "how are you?"),
+            ])
+        );
+    }
+
+    #[test]
+    fn without_oldest_messages_drops_orphaned_tool_results() {
+        let history = ConversationHistory::new(vec![
+            make_text_message("user", "weather in Paris?"),
+            make_text_message("assistant", ""),
+            make_text_message("tool", "sunny"),
+            make_text_message("assistant", "it is sunny"),
+            make_text_message("user", "thanks"),
+        ]);
+
+        assert_eq!(
+            history.without_oldest_messages(2),
+            ConversationHistory::new(vec![
+                make_text_message("assistant", "it is sunny"),
+                make_text_message("user", "thanks"),
+            ])
+        );
+    }
+
     #[test]
     fn extract_image_urls_from_mixed_content() {
         let history = ConversationHistory::new(vec![
@@ -164,4 +270,38 @@ mod tests {
         );
         assert_eq!(result.messages[0].role, "assistant");
     }
+
+    #[test]
+    fn replace_images_with_marker_carries_tool_calls_and_results() {
+        let history = ConversationHistory::new(vec![
+            ConversationMessage {
+                content: ConversationMessageContent::Text(String::new()),
+                role: "assistant".to_owned(),
+                tool_call_id: None,
+                tool_calls: vec![ToolCall {
+                    arguments: serde_json::json!({"city": "Paris"}),
+                    id: "call_1".to_owned(),
+                    name: "get_weather".to_owned(),
+                }],
+            },
+            ConversationMessage {
+                content: ConversationMessageContent::Text("sunny".to_owned()),
+                role: "tool".to_owned(),
+                tool_call_id: Some("call_1".to_owned()),
+                tool_calls: vec![],
+            },
+        ]);
+
+        let result = history.replace_images_with_marker(&MediaMarker::new("[IMAGE]".to_owned()));
+
+        assert_eq!(
+            serde_json::to_value(&result.messages[0].tool_calls[0]).ok(),
+            Some(serde_json::json!({
+                "function": {"arguments": {"city": "Paris"}, "name": "get_weather"},
+                "id": "call_1",
+                "type": "function"
+            }))
+        );
+        assert_eq!(result.messages[1].tool_call_id.as_deref(), Some("call_1"));
+    }
 }
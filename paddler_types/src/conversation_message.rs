@@ -2,10 +2,17 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::conversation_message_content::ConversationMessageContent;
+use crate::tool_call::ToolCall;
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ConversationMessage {
     pub content: ConversationMessageContent,
     pub role: String,
+    /// Set on `tool` role messages, to tell which call the message is the result of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// Calls the assistant made in this turn
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
 }
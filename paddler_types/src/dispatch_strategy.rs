@@ -0,0 +1,92 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DispatchStrategy {
+    /// Agent with the fewest slots currently processing requests
+    #[default]
+    LeastBusy,
+    /// Agent with the fewest tokens (prompt and completion budget) still in flight
+    LeastOutstandingTokens,
+    /// Less busy out of two randomly picked agents
+    PowerOfTwoChoices,
+    /// Agents take turns in a fixed order
+    RoundRobin,
+    /// Agent with the fewest processing slots relative to the weight it registered with
+    Weighted,
+}
+
+impl DispatchStrategy {
+    #[must_use]
+    pub const fn as_cli_value(&self) -> &'static str {
+        match self {
+            Self::LeastBusy => "least-busy",
+            Self::LeastOutstandingTokens => "least-outstanding-tokens",
+            Self::PowerOfTwoChoices => "power-of-two-choices",
+            Self::RoundRobin => "round-robin",
+            Self::Weighted => "weighted",
+        }
+    }
+}
+
+impl fmt::Display for DispatchStrategy {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_cli_value())
+    }
+}
+
+impl FromStr for DispatchStrategy {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "least-busy" => Ok(Self::LeastBusy),
+            "least-outstanding-tokens" => Ok(Self::LeastOutstandingTokens),
+            "power-of-two-choices" => Ok(Self::PowerOfTwoChoices),
+            "round-robin" => Ok(Self::RoundRobin),
+            "weighted" => Ok(Self::Weighted),
+            unknown => Err(anyhow!(
+                "Unsupported dispatch strategy '{unknown}'. Supported: least-busy, least-outstanding-tokens, power-of-two-choices, round-robin, weighted"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_cli_value_back_into_the_same_strategy() -> Result<()> {
+        for strategy in [
+            DispatchStrategy::LeastBusy,
+            DispatchStrategy::LeastOutstandingTokens,
+            DispatchStrategy::PowerOfTwoChoices,
+            DispatchStrategy::RoundRobin,
+            DispatchStrategy::Weighted,
+        ] {
+            assert_eq!(
+                DispatchStrategy::from_str(strategy.as_cli_value())?,
+                strategy
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_strategy() {
+        assert!(DispatchStrategy::from_str("random").is_err());
+    }
+
+    #[test]
+    fn defaults_to_least_busy() {
+        assert_eq!(DispatchStrategy::default(), DispatchStrategy::LeastBusy);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validates::Validates;
+
+/// "Don't Repeat Yourself" sampler, penalizing tokens that would extend a sequence already
+/// repeated in the context
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DryParameters {
+    /// Repeated sequences up to this length are not penalized
+    pub allowed_length: i32,
+    /// Base of the penalty, which grows exponentially with the length of the repetition
+    pub base: f32,
+    /// Strength of the penalty (0.0 = disabled)
+    pub multiplier: f32,
+    /// How many tokens to scan for repetitions (-1 = context size, 0 = disabled)
+    pub penalty_last_n: i32,
+    /// Strings that end a sequence, so that repetitions are not matched across them
+    pub sequence_breakers: Vec<String>,
+}
+
+impl Validates<Self> for DryParameters {
+    fn validate(self) -> Result<Self> {
+        if self.allowed_length < 0 {
+            bail!(
+                "dry allowed_length must not be negative, got {}",
+                self.allowed_length
+            );
+        }
+
+        if !(self.base.is_finite() && self.base >= 1.0) {
+            bail!(
+                "dry base must be greater than or equal to 1.0, got {}",
+                self.base
+            );
+        }
+
+        if !(self.multiplier.is_finite() && self.multiplier >= 0.0) {
+            bail!(
+                "dry multiplier must be greater than or equal to 0.0, got {}",
+                self.multiplier
+            );
+        }
+
+        if self.penalty_last_n < -1 {
+            bail!(
+                "dry penalty_last_n must be -1 (context size), 0 (disabled) or positive, got {}",
+                self.penalty_last_n
+            );
+        }
+
+        if self
+            .sequence_breakers
+            .iter()
+            .any(|sequence_breaker| sequence_breaker.is_empty() || sequence_breaker.contains('\0'))
+        {
+            bail!("dry sequence_breakers must be non-empty and must not contain null bytes");
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dry_parameters() -> DryParameters {
+        DryParameters {
+            allowed_length: 2,
+            base: 1.75,
+            multiplier: 0.8,
+            penalty_last_n: -1,
+            sequence_breakers: vec!["\n".to_owned(), ":".to_owned()],
+        }
+    }
+
+    #[test]
+    fn validate_succeeds_with_llama_cpp_defaults() {
+        assert!(dry_parameters().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_for_base_below_one() {
+        let params = DryParameters {
+            base: 0.5,
+            ..dry_parameters()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_empty_sequence_breaker() {
+        let params = DryParameters {
+            sequence_breakers: vec![String::new()],
+            ..dry_parameters()
+        };
+
+        assert!(params.validate().is_err());
+    }
+}
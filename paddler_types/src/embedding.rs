@@ -12,6 +12,8 @@ use crate::pooling_type::PoolingType;
 #[serde(deny_unknown_fields)]
 pub struct Embedding {
     pub embedding: Vec<f32>,
+    /// Number of tokens the source document was made of
+    pub input_tokens: usize,
     pub normalization_method: EmbeddingNormalizationMethod,
     pub pooling_type: PoolingType,
     pub source_document_id: String,
@@ -44,6 +46,7 @@ impl Embedding {
                     rms_norm(&self.embedding, *epsilon)
                 }
             },
+            input_tokens: self.input_tokens,
             normalization_method: normalization_method.clone(),
             pooling_type: self.pooling_type.clone(),
             source_document_id: self.source_document_id,
@@ -58,6 +61,7 @@ mod tests {
     fn make_embedding(values: Vec<f32>, method: EmbeddingNormalizationMethod) -> Embedding {
         Embedding {
             embedding: values,
+            input_tokens: 2,
             normalization_method: method,
             pooling_type: PoolingType::Mean,
             source_document_id: "test".to_owned(),
@@ -133,12 +137,14 @@ mod tests {
     fn test_normalize_preserves_metadata() -> Result<()> {
         let embedding = Embedding {
             embedding: vec![3.0, 4.0],
+            input_tokens: 7,
             normalization_method: EmbeddingNormalizationMethod::None,
             pooling_type: PoolingType::Cls,
             source_document_id: "doc-42".to_owned(),
         };
         let result = embedding.normalize(&EmbeddingNormalizationMethod::L2)?;
 
+        assert_eq!(result.input_tokens, 7);
         assert!(matches!(result.pooling_type, PoolingType::Cls));
         assert_eq!(result.source_document_id, "doc-42");
 
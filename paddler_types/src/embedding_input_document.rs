@@ -6,4 +6,7 @@ use serde::Serialize;
 pub struct EmbeddingInputDocument {
     pub content: String,
     pub id: String,
+    /// Already tokenized input; the content is ignored when it is set
+    #[serde(default)]
+    pub tokens: Option<Vec<i32>>,
 }
@@ -38,6 +38,7 @@ mod tests {
     fn embedding_is_not_done() {
         let result = EmbeddingResult::Embedding(Embedding {
             embedding: vec![1.0],
+            input_tokens: 1,
             normalization_method: EmbeddingNormalizationMethod::None,
             pooling_type: PoolingType::Mean,
             source_document_id: "doc".to_owned(),
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FinishReason {
+    /// Client asked to stop generating
+    Cancelled,
+    /// Model produced an end-of-generation token
+    Eos,
+    /// Agent ran out of KV cache space and dropped the request
+    Evicted,
+    /// Request reached its `max_tokens` limit
+    MaxTokens,
+    /// Generated text contained one of the requested stop sequences
+    StopSequence(String),
+}
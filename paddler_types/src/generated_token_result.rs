@@ -1,21 +1,41 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::context_overflow::ContextOverflow;
+use crate::finish_reason::FinishReason;
 use crate::streamable_result::StreamableResult;
+use crate::token_logprobs::TokenLogprobs;
+use crate::token_usage::TokenUsage;
+use crate::tool_call::ToolCall;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum GeneratedTokenResult {
     ChatTemplateError(String),
-    Done,
+    /// The prompt, along with `max_tokens`, does not fit in the context
+    ContextOverflow(ContextOverflow),
+    Done(FinishReason),
     GrammarIncompatibleWithThinking(String),
     GrammarInitializationFailed(String),
     GrammarRejectedModelOutput(String),
     GrammarSyntaxError(String),
     ImageDecodingFailed(String),
+    InvalidSamplingParameters(String),
     MultimodalNotSupported(String),
+    /// Text generated between the model's reasoning markers, without the markers
+    ReasoningToken(String),
     SamplerError(String),
+    /// Seed the request was sampled with, sent right before `Usage`, so that the same request
+    /// can be reproduced on the same agent
+    Seed(u32),
     Token(String),
+    /// Sent for every sampled token of a request that asked for logprobs, ahead of the text the
+    /// token produced, which can still be held back or dropped as a part of a stop sequence
+    TokenLogprobs(TokenLogprobs),
+    /// Replaces the tokens the tool call was generated as
+    ToolCall(ToolCall),
+    /// Sent right before `Done`
+    Usage(TokenUsage),
 }
 
 impl StreamableResult for GeneratedTokenResult {
@@ -23,12 +43,14 @@ impl StreamableResult for GeneratedTokenResult {
         matches!(
             self,
             Self::ChatTemplateError(_)
-                | Self::Done
+                | Self::ContextOverflow(_)
+                | Self::Done(_)
                 | Self::GrammarIncompatibleWithThinking(_)
                 | Self::GrammarInitializationFailed(_)
                 | Self::GrammarRejectedModelOutput(_)
                 | Self::GrammarSyntaxError(_)
                 | Self::ImageDecodingFailed(_)
+                | Self::InvalidSamplingParameters(_)
                 | Self::MultimodalNotSupported(_)
                 | Self::SamplerError(_)
         )
@@ -38,10 +60,11 @@ impl StreamableResult for GeneratedTokenResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::token_logprob::TokenLogprob;
 
     #[test]
     fn done_is_done() {
-        assert!(GeneratedTokenResult::Done.is_done());
+        assert!(GeneratedTokenResult::Done(FinishReason::Eos).is_done());
     }
 
     #[test]
@@ -49,6 +72,18 @@ mod tests {
         assert!(GeneratedTokenResult::ChatTemplateError("err".to_owned()).is_done());
     }
 
+    #[test]
+    fn context_overflow_is_done() {
+        assert!(
+            GeneratedTokenResult::ContextOverflow(ContextOverflow {
+                context_size: 4096,
+                max_tokens: 1000,
+                prompt_tokens: 3500,
+            })
+            .is_done()
+        );
+    }
+
     #[test]
     fn grammar_incompatible_with_thinking_is_done() {
         assert!(GeneratedTokenResult::GrammarIncompatibleWithThinking("err".to_owned()).is_done());
@@ -74,6 +109,11 @@ mod tests {
         assert!(GeneratedTokenResult::ImageDecodingFailed("err".to_owned()).is_done());
     }
 
+    #[test]
+    fn invalid_sampling_parameters_is_done() {
+        assert!(GeneratedTokenResult::InvalidSamplingParameters("err".to_owned()).is_done());
+    }
+
     #[test]
     fn multimodal_not_supported_is_done() {
         assert!(GeneratedTokenResult::MultimodalNotSupported("err".to_owned()).is_done());
@@ -84,8 +124,53 @@ mod tests {
         assert!(GeneratedTokenResult::SamplerError("err".to_owned()).is_done());
     }
 
+    #[test]
+    fn reasoning_token_is_not_done() {
+        assert!(!GeneratedTokenResult::ReasoningToken("hmm".to_owned()).is_done());
+    }
+
+    #[test]
+    fn seed_is_not_done() {
+        assert!(!GeneratedTokenResult::Seed(42).is_done());
+    }
+
     #[test]
     fn token_is_not_done() {
         assert!(!GeneratedTokenResult::Token("hello".to_owned()).is_done());
     }
+
+    #[test]
+    fn token_logprobs_is_not_done() {
+        let token_logprob = TokenLogprob {
+            bytes: b"hello".to_vec(),
+            id: 42,
+            logprob: -0.5,
+            token: "hello".to_owned(),
+        };
+
+        assert!(
+            !GeneratedTokenResult::TokenLogprobs(TokenLogprobs {
+                sampled: token_logprob.clone(),
+                top_logprobs: vec![token_logprob],
+            })
+            .is_done()
+        );
+    }
+
+    #[test]
+    fn tool_call_is_not_done() {
+        assert!(
+            !GeneratedTokenResult::ToolCall(ToolCall {
+                arguments: serde_json::json!({"city": "Paris"}),
+                id: "call_1".to_owned(),
+                name: "get_weather".to_owned(),
+            })
+            .is_done()
+        );
+    }
+
+    #[test]
+    fn usage_is_not_done() {
+        assert!(!GeneratedTokenResult::Usage(TokenUsage::default()).is_done());
+    }
 }
@@ -3,12 +3,14 @@ use serde::Serialize;
 
 use crate::embedding_result::EmbeddingResult;
 use crate::generated_token_result::GeneratedTokenResult;
+use crate::rerank_result::RerankResult;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum Response {
     Embedding(EmbeddingResult),
     GeneratedToken(GeneratedTokenResult),
+    Rerank(RerankResult),
     Timeout,
     TooManyBufferedRequests,
 }
@@ -24,3 +26,9 @@ impl From<GeneratedTokenResult> for Response {
         Self::GeneratedToken(result)
     }
 }
+
+impl From<RerankResult> for Response {
+    fn from(result: RerankResult) -> Self {
+        Self::Rerank(result)
+    }
+}
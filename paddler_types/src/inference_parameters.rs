@@ -1,23 +1,38 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
 use anyhow::bail;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::dry_parameters::DryParameters;
 use crate::kv_cache_dtype::KvCacheDtype;
+use crate::logit_bias::LogitBias;
+use crate::mirostat_parameters::MirostatParameters;
 use crate::pooling_type::PoolingType;
+use crate::reasoning_markers::ReasoningMarkers;
+use crate::sampler_type::SamplerType;
 use crate::validates::Validates;
+use crate::xtc_parameters::XtcParameters;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct InferenceParameters {
     pub batch_n_tokens: usize,
     pub context_size: u32,
+    #[serde(default)]
+    pub dry: Option<DryParameters>,
     pub enable_embeddings: bool,
     pub image_resize_to_fit: u32,
     pub k_cache_dtype: KvCacheDtype,
     pub v_cache_dtype: KvCacheDtype,
+    /// Biases applied to the logits before any other sampler
+    #[serde(default)]
+    pub logit_bias: Vec<LogitBias>,
     /// The minimum probability for a token to be considered, relative to the probability of the most likely token
     pub min_p: f32,
+    #[serde(default)]
+    pub mirostat: Option<MirostatParameters>,
     /// Number of model layers to offload to GPU. 0 = CPU-only.
     /// Set to a value >= the model's transformer block count for full GPU offload.
     pub n_gpu_layers: u32,
@@ -28,12 +43,30 @@ pub struct InferenceParameters {
     /// Penalty for repeating tokens (1.0 = disabled)
     pub penalty_repeat: f32,
     pub pooling_type: PoolingType,
+    /// Overrides the reasoning delimiters detected from the chat template
+    #[serde(default)]
+    pub reasoning_markers: Option<ReasoningMarkers>,
+    /// Order the samplers are applied in, samplers that are not listed are skipped
+    #[serde(default = "SamplerType::default_order")]
+    pub sampler_order: Vec<SamplerType>,
+    /// Seeds the final sampler of requests that do not set their own seed. Leave unset to pick
+    /// a random seed for every request.
+    #[serde(default)]
+    pub seed: Option<u32>,
     /// Adjust the randomness of the generated text (0.0 = greedy/deterministic)
     pub temperature: f32,
     /// Limit the next token selection to the K most probable tokens
     pub top_k: i32,
+    /// Keep only the tokens within N standard deviations of the most probable one
+    #[serde(default)]
+    pub top_n_sigma: Option<f32>,
     /// Limit the next token selection to a subset of tokens with a cumulative probability above a threshold P
     pub top_p: f32,
+    /// Keep the tokens whose surprise is closest to the expected one, up to a cumulative probability P
+    #[serde(default)]
+    pub typical_p: Option<f32>,
+    #[serde(default)]
+    pub xtc: Option<XtcParameters>,
 }
 
 impl Validates<Self> for InferenceParameters {
@@ -42,7 +75,45 @@ impl Validates<Self> for InferenceParameters {
             bail!("image_resize_to_fit must be greater than zero");
         }
 
-        Ok(self)
+        let mut listed_samplers = HashSet::new();
+
+        if let Some(duplicated_sampler) = self
+            .sampler_order
+            .iter()
+            .find(|sampler_type| !listed_samplers.insert(**sampler_type))
+        {
+            bail!("sampler_order lists {duplicated_sampler:?} more than once");
+        }
+
+        if let Some(reasoning_markers) = &self.reasoning_markers
+            && (reasoning_markers.start.is_empty() || reasoning_markers.end.is_empty())
+        {
+            bail!("reasoning_markers start and end must not be empty");
+        }
+
+        if let Some(top_n_sigma) = self.top_n_sigma
+            && !(top_n_sigma.is_finite() && top_n_sigma > 0.0)
+        {
+            bail!("top_n_sigma must be greater than 0.0, got {top_n_sigma}");
+        }
+
+        if let Some(typical_p) = self.typical_p
+            && !(typical_p > 0.0 && typical_p <= 1.0)
+        {
+            bail!("typical_p must be greater than 0.0 and at most 1.0, got {typical_p}");
+        }
+
+        Ok(Self {
+            dry: self.dry.map(Validates::validate).transpose()?,
+            logit_bias: self
+                .logit_bias
+                .into_iter()
+                .map(Validates::validate)
+                .collect::<Result<Vec<_>>>()?,
+            mirostat: self.mirostat.map(Validates::validate).transpose()?,
+            xtc: self.xtc.map(Validates::validate).transpose()?,
+            ..self
+        })
     }
 }
 
@@ -51,20 +122,29 @@ impl Default for InferenceParameters {
         Self {
             batch_n_tokens: 512,
             context_size: 8192,
+            dry: None,
             enable_embeddings: false,
             image_resize_to_fit: 1024,
             k_cache_dtype: KvCacheDtype::Q8_0,
             v_cache_dtype: KvCacheDtype::Q8_0,
+            logit_bias: vec![],
             min_p: 0.05,
+            mirostat: None,
             n_gpu_layers: 0,
             penalty_frequency: 0.0,
             penalty_last_n: -1,
             penalty_presence: 0.8,
             penalty_repeat: 1.1,
             pooling_type: PoolingType::Last,
+            reasoning_markers: None,
+            sampler_order: SamplerType::default_order(),
+            seed: None,
             temperature: 0.8,
             top_k: 80,
+            top_n_sigma: None,
             top_p: 0.8,
+            typical_p: None,
+            xtc: None,
         }
     }
 }
@@ -89,4 +169,96 @@ mod tests {
 
         assert!(params.validate().is_err());
     }
+
+    #[test]
+    fn validate_fails_when_reasoning_marker_is_empty() {
+        for (start, end) in [("", "</think>"), ("<think>", ""), ("", "")] {
+            let params = InferenceParameters {
+                reasoning_markers: Some(ReasoningMarkers {
+                    end: end.to_owned(),
+                    start: start.to_owned(),
+                }),
+                ..InferenceParameters::default()
+            };
+
+            assert!(params.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn validate_fails_for_duplicated_sampler() {
+        let params = InferenceParameters {
+            sampler_order: vec![
+                SamplerType::TopK,
+                SamplerType::Temperature,
+                SamplerType::TopK,
+            ],
+            ..InferenceParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_typical_p_of_zero() {
+        let params = InferenceParameters {
+            typical_p: Some(0.0),
+            ..InferenceParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_checks_nested_sampler_parameters() {
+        let params = InferenceParameters {
+            xtc: Some(XtcParameters {
+                probability: 2.0,
+                threshold: 0.1,
+            }),
+            ..InferenceParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn deserializes_without_sampler_fields() -> Result<()> {
+        let mut serialized = serde_json::to_value(InferenceParameters::default())?;
+
+        if let Some(fields) = serialized.as_object_mut() {
+            for field in [
+                "dry",
+                "logit_bias",
+                "mirostat",
+                "sampler_order",
+                "top_n_sigma",
+                "typical_p",
+                "xtc",
+            ] {
+                fields.remove(field);
+            }
+        }
+
+        let params: InferenceParameters = serde_json::from_value(serialized)?;
+
+        assert_eq!(params, InferenceParameters::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserializes_without_seed() -> Result<()> {
+        let mut serialized = serde_json::to_value(InferenceParameters::default())?;
+
+        if let Some(fields) = serialized.as_object_mut() {
+            fields.remove("seed");
+        }
+
+        let params: InferenceParameters = serde_json::from_value(serialized)?;
+
+        assert_eq!(params.seed, None);
+
+        Ok(())
+    }
 }
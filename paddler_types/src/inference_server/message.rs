@@ -7,6 +7,10 @@ use crate::jsonrpc::ErrorEnvelope;
 use crate::jsonrpc::RequestEnvelope;
 use crate::rpc_message::RpcMessage;
 
+#[expect(
+    clippy::large_enum_variant,
+    reason = "deserialized once per request and immediately dispatched"
+)]
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum Message<TParametersSchema> {
@@ -5,6 +5,9 @@ pub mod agent_desired_state;
 pub mod agent_issue;
 pub mod agent_issue_params;
 pub mod agent_state_application_status;
+pub mod api_key;
+pub mod api_key_rate_limit;
+pub mod api_key_scope;
 pub mod balancer_desired_state;
 pub mod buffered_request_manager_snapshot;
 pub mod chat_template;
@@ -12,14 +15,20 @@ pub mod chat_template_message;
 pub mod chat_template_message_content;
 pub mod chat_template_message_content_part;
 pub mod chat_template_messages;
+pub mod chat_template_tool_call;
+pub mod chat_template_tool_call_function;
+pub mod context_overflow;
 pub mod conversation_history;
 pub mod conversation_message;
 pub mod conversation_message_content;
 pub mod conversation_message_content_part;
+pub mod dispatch_strategy;
+pub mod dry_parameters;
 pub mod embedding;
 pub mod embedding_input_document;
 pub mod embedding_normalization_method;
 pub mod embedding_result;
+pub mod finish_reason;
 pub mod generated_token_result;
 pub mod grammar_constraint;
 pub mod huggingface_model_reference;
@@ -29,12 +38,32 @@ pub mod inference_parameters;
 pub mod inference_server;
 pub mod jsonrpc;
 pub mod kv_cache_dtype;
+pub mod logit_bias;
+pub mod logit_bias_token;
 pub mod media_marker;
+pub mod mirostat_parameters;
+pub mod mirostat_version;
 pub mod model_metadata;
+pub mod model_pool_desired_state;
 pub mod normalization;
 pub mod pooling_type;
+pub mod reasoning_markers;
+pub mod redacted_api_key;
+pub mod rerank_document;
+pub mod rerank_result;
+pub mod rerank_score;
 pub mod request_params;
+pub mod request_priority;
+pub mod rollout_strategy;
 pub mod rpc_message;
+pub mod sampler_type;
+pub mod sampling_parameters;
 pub mod slot_aggregated_status_snapshot;
 pub mod streamable_result;
+pub mod token_logprob;
+pub mod token_logprobs;
+pub mod token_usage;
+pub mod tool_call;
+pub mod truncation_policy;
 pub mod validates;
+pub mod xtc_parameters;
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::logit_bias_token::LogitBiasToken;
+use crate::validates::Validates;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogitBias {
+    /// Added to the logit of the token, negative values make it less likely
+    pub bias: f32,
+    pub token: LogitBiasToken,
+}
+
+impl Validates<Self> for LogitBias {
+    fn validate(self) -> Result<Self> {
+        if !self.bias.is_finite() {
+            bail!("logit bias must be a finite number");
+        }
+
+        match &self.token {
+            LogitBiasToken::Id(id) if *id < 0 => {
+                bail!("logit bias token id must not be negative, got {id}")
+            }
+            LogitBiasToken::Text(text) if text.is_empty() => {
+                bail!("logit bias token text must not be empty")
+            }
+            LogitBiasToken::Id(_) | LogitBiasToken::Text(_) => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_token_id_and_text() -> Result<()> {
+        let biases: Vec<LogitBias> = serde_json::from_str(
+            r#"[{"bias": -100.0, "token": 42}, {"bias": 2.5, "token": " hello"}]"#,
+        )?;
+
+        assert_eq!(biases[0].token, LogitBiasToken::Id(42));
+        assert_eq!(biases[1].token, LogitBiasToken::Text(" hello".to_owned()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_fails_for_negative_token_id() {
+        let logit_bias = LogitBias {
+            bias: 1.0,
+            token: LogitBiasToken::Id(-1),
+        };
+
+        assert!(logit_bias.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_empty_token_text() {
+        let logit_bias = LogitBias {
+            bias: 1.0,
+            token: LogitBiasToken::Text(String::new()),
+        };
+
+        assert!(logit_bias.validate().is_err());
+    }
+}
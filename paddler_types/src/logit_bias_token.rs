@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum LogitBiasToken {
+    Id(i32),
+    /// Tokenized with the model's vocabulary, the bias applies to every resulting token
+    Text(String),
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::mirostat_version::MirostatVersion;
+use crate::validates::Validates;
+
+/// Mirostat keeps the surprise of the generated text close to a target. When enabled, it
+/// replaces the ordered samplers, and only the temperature is applied before it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MirostatParameters {
+    /// Learning rate of the surprise estimate
+    pub eta: f32,
+    /// Target surprise (cross-entropy) of the generated text
+    pub tau: f32,
+    pub version: MirostatVersion,
+}
+
+impl Validates<Self> for MirostatParameters {
+    fn validate(self) -> Result<Self> {
+        if !(self.eta.is_finite() && self.eta > 0.0) {
+            bail!("mirostat eta must be greater than 0.0, got {}", self.eta);
+        }
+
+        if !(self.tau.is_finite() && self.tau >= 0.0) {
+            bail!(
+                "mirostat tau must be greater than or equal to 0.0, got {}",
+                self.tau
+            );
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_succeeds_with_llama_cpp_defaults() {
+        let params = MirostatParameters {
+            eta: 0.1,
+            tau: 5.0,
+            version: MirostatVersion::V2,
+        };
+
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_for_zero_eta() {
+        let params = MirostatParameters {
+            eta: 0.0,
+            tau: 5.0,
+            version: MirostatVersion::V1,
+        };
+
+        assert!(params.validate().is_err());
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MirostatVersion {
+    /// Estimates how many of the most probable tokens to keep from the Zipf exponent of the
+    /// distribution
+    V1,
+    /// Keeps the tokens whose surprise does not exceed the running estimate
+    V2,
+}
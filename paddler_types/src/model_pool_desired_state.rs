@@ -0,0 +1,48 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::agent_desired_model::AgentDesiredModel;
+use crate::agent_desired_state::AgentDesiredState;
+use crate::chat_template::ChatTemplate;
+use crate::inference_parameters::InferenceParameters;
+use crate::validates::Validates;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPoolDesiredState {
+    pub chat_template_override: Option<ChatTemplate>,
+    pub inference_parameters: InferenceParameters,
+    pub model: AgentDesiredModel,
+    pub multimodal_projection: AgentDesiredModel,
+    /// Model agents of the pool download in the background, ahead of `model` being switched to it
+    #[serde(default)]
+    pub prefetch_model: AgentDesiredModel,
+    pub use_chat_template_override: bool,
+}
+
+impl ModelPoolDesiredState {
+    #[must_use]
+    pub fn to_agent_desired_state(&self) -> AgentDesiredState {
+        AgentDesiredState {
+            chat_template_override: if self.use_chat_template_override {
+                self.chat_template_override.clone()
+            } else {
+                None
+            },
+            inference_parameters: self.inference_parameters.clone(),
+            model: self.model.clone(),
+            multimodal_projection: self.multimodal_projection.clone(),
+            prefetch_model: self.prefetch_model.clone(),
+        }
+    }
+}
+
+impl Validates<Self> for ModelPoolDesiredState {
+    fn validate(self) -> Result<Self> {
+        Ok(Self {
+            inference_parameters: self.inference_parameters.validate()?,
+            ..self
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Delimiters a model wraps its reasoning in, for example `<think>` and `</think>`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReasoningMarkers {
+    pub end: String,
+    pub start: String,
+}
+
+impl ReasoningMarkers {
+    /// Recognizes the `<think>` markers used by Qwen and `DeepSeek` models
+    #[must_use]
+    pub fn detect(chat_template: &str) -> Option<Self> {
+        chat_template.contains("<think>").then(|| Self {
+            end: "</think>".to_owned(),
+            start: "<think>".to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_think_markers_from_chat_template() {
+        assert_eq!(
+            ReasoningMarkers::detect("{{- '<think>\\n\\n</think>\\n\\n' }}"),
+            Some(ReasoningMarkers {
+                end: "</think>".to_owned(),
+                start: "<think>".to_owned(),
+            })
+        );
+        assert_eq!(ReasoningMarkers::detect("{{ message.content }}"), None);
+    }
+}
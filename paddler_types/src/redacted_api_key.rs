@@ -0,0 +1,62 @@
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::api_key::ApiKey;
+use crate::api_key_rate_limit::ApiKeyRateLimit;
+use crate::api_key_scope::ApiKeyScope;
+
+/// API key as the management API lists it, without its secret
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactedApiKey {
+    pub fair_share_weight: Option<u32>,
+    pub name: String,
+    pub rate_limit: ApiKeyRateLimit,
+    pub scopes: BTreeSet<ApiKeyScope>,
+}
+
+impl From<ApiKey> for RedactedApiKey {
+    fn from(
+        ApiKey {
+            fair_share_weight,
+            name,
+            rate_limit,
+            scopes,
+            secret: _,
+        }: ApiKey,
+    ) -> Self {
+        Self {
+            fair_share_weight,
+            name,
+            rate_limit,
+            scopes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn serializes_without_secret() -> Result<()> {
+        let redacted_api_key = RedactedApiKey::from(ApiKey {
+            fair_share_weight: None,
+            name: "ci".to_owned(),
+            rate_limit: ApiKeyRateLimit::default(),
+            scopes: BTreeSet::from([ApiKeyScope::Inference]),
+            secret: "ci-secret".to_owned(),
+        });
+
+        let serialized = serde_json::to_string(&redacted_api_key)?;
+
+        assert!(!serialized.contains("ci-secret"));
+        assert_eq!(redacted_api_key.name, "ci");
+
+        Ok(())
+    }
+}
@@ -1,17 +1,22 @@
 pub mod tool;
 
 use anyhow::Result;
+use anyhow::bail;
 use serde::Deserialize;
 use serde::Serialize;
 
 use self::tool::Tool;
+use super::MAX_LOGPROBS;
 use crate::conversation_history::ConversationHistory;
 use crate::grammar_constraint::GrammarConstraint;
+use crate::request_priority::RequestPriority;
+use crate::sampling_parameters::SamplingParameters;
+use crate::truncation_policy::TruncationPolicy;
 use crate::validates::Validates;
 use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
 use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(bound(deserialize = "TParametersSchema: serde::Deserialize<'de>"))]
 pub struct ContinueFromConversationHistoryParams<TParametersSchema> {
@@ -20,26 +25,120 @@ pub struct ContinueFromConversationHistoryParams<TParametersSchema> {
     pub enable_thinking: bool,
     #[serde(default)]
     pub grammar: Option<GrammarConstraint>,
+    /// Reports the logprob of every sampled token along with this many most likely alternatives;
+    /// nothing is reported when unset
+    #[serde(default)]
+    pub logprobs: Option<u32>,
     pub max_tokens: i32,
+    /// Routes the request to the model pool of that name; the default model serves requests
+    /// without one
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    #[serde(default)]
+    pub sampling: Option<SamplingParameters>,
+    /// Requests sharing a session are routed to the same agent when possible,
+    /// so that it can reuse the conversation it already has in its KV cache
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Generation ends as soon as the output contains any of these; the sequence itself is not returned
+    #[serde(default)]
+    pub stop: Vec<String>,
     #[serde(default)]
     pub tools: Vec<Tool<TParametersSchema>>,
+    /// Fits the conversation in the context before it is ingested; the prompt is left as it is
+    /// when unset
+    #[serde(default)]
+    pub truncation: Option<TruncationPolicy>,
 }
 
 impl Validates<ContinueFromConversationHistoryParams<ValidatedParametersSchema>>
     for ContinueFromConversationHistoryParams<RawParametersSchema>
 {
     fn validate(self) -> Result<ContinueFromConversationHistoryParams<ValidatedParametersSchema>> {
+        if let Some(logprobs) = self.logprobs
+            && logprobs > MAX_LOGPROBS
+        {
+            bail!("logprobs must be at most {MAX_LOGPROBS}, got {logprobs}");
+        }
+
         Ok(ContinueFromConversationHistoryParams {
             add_generation_prompt: self.add_generation_prompt,
             conversation_history: self.conversation_history,
             enable_thinking: self.enable_thinking,
             grammar: self.grammar,
+            logprobs: self.logprobs,
             max_tokens: self.max_tokens,
+            model: self.model,
+            priority: self.priority,
+            sampling: self.sampling,
+            session_id: self.session_id,
+            stop: self.stop,
             tools: self
                 .tools
                 .into_iter()
                 .map(Validates::validate)
                 .collect::<Result<Vec<_>>>()?,
+            truncation: self.truncation,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_params(
+        truncation: Option<&str>,
+    ) -> Result<ContinueFromConversationHistoryParams<RawParametersSchema>> {
+        let mut params = serde_json::json!({
+            "add_generation_prompt": true,
+            "conversation_history": [{"content": "hi", "role": "user"}],
+            "enable_thinking": false,
+            "max_tokens": 50,
+        });
+
+        if let Some(truncation) = truncation {
+            params["truncation"] = serde_json::json!(truncation);
+        }
+
+        Ok(serde_json::from_value(params)?)
+    }
+
+    #[test]
+    fn truncation_is_optional() -> Result<()> {
+        assert_eq!(make_params(None)?.truncation, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn truncation_is_kept_through_validation() -> Result<()> {
+        let params = make_params(Some("DropOldestMessages"))?.validate()?;
+
+        assert_eq!(
+            params.truncation,
+            Some(TruncationPolicy::DropOldestMessages)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn logprobs_over_the_limit_are_rejected() -> Result<()> {
+        let params = ContinueFromConversationHistoryParams {
+            logprobs: Some(MAX_LOGPROBS + 1),
+            ..make_params(None)?
+        };
+
+        assert!(params.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_truncation_is_rejected() {
+        assert!(make_params(Some("Summarize")).is_err());
+    }
+}
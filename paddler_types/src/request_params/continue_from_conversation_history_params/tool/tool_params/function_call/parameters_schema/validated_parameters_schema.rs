@@ -1,3 +1,6 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use jsonschema::validator_for;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Map;
@@ -16,3 +19,45 @@ pub struct ValidatedParametersSchema {
     #[serde(rename = "additionalProperties")]
     pub additional_properties: Option<Value>,
 }
+
+impl ValidatedParametersSchema {
+    /// Checks the arguments the model generated for a tool call against this schema
+    pub fn validate_arguments(&self, arguments: &Value) -> Result<()> {
+        let schema = serde_json::to_value(self)?;
+        let validator = validator_for(&schema).map_err(|err| anyhow!("{err}"))?;
+
+        validator
+            .validate(arguments)
+            .map_err(|err| anyhow!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn weather_schema() -> ValidatedParametersSchema {
+        ValidatedParametersSchema {
+            schema_type: "object".to_owned(),
+            properties: json!({"city": {"type": "string"}}).as_object().cloned(),
+            required: Some(vec!["city".to_owned()]),
+            additional_properties: Some(json!(false)),
+        }
+    }
+
+    #[test]
+    fn test_accepts_matching_arguments() -> Result<()> {
+        weather_schema().validate_arguments(&json!({"city": "Paris"}))
+    }
+
+    #[test]
+    fn test_rejects_missing_required_argument() {
+        assert!(
+            weather_schema()
+                .validate_arguments(&json!({"country": "France"}))
+                .is_err()
+        );
+    }
+}
@@ -1,13 +1,105 @@
+use anyhow::Result;
+use anyhow::bail;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::MAX_LOGPROBS;
 use crate::grammar_constraint::GrammarConstraint;
+use crate::request_priority::RequestPriority;
+use crate::sampling_parameters::SamplingParameters;
+use crate::truncation_policy::TruncationPolicy;
+use crate::validates::Validates;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ContinueFromRawPromptParams {
     #[serde(default)]
     pub grammar: Option<GrammarConstraint>,
+    /// Reports the logprob of every sampled token along with this many most likely alternatives;
+    /// nothing is reported when unset
+    #[serde(default)]
+    pub logprobs: Option<u32>,
     pub max_tokens: i32,
+    /// Routes the request to the model pool of that name; the default model serves requests
+    /// without one
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub priority: RequestPriority,
     pub raw_prompt: String,
+    #[serde(default)]
+    pub sampling: Option<SamplingParameters>,
+    /// Generation ends as soon as the output contains any of these; the sequence itself is not returned
+    #[serde(default)]
+    pub stop: Vec<String>,
+    /// Fits the prompt in the context before it is ingested; the prompt is left as it is when
+    /// unset
+    #[serde(default)]
+    pub truncation: Option<TruncationPolicy>,
+}
+
+impl Validates<Self> for ContinueFromRawPromptParams {
+    fn validate(self) -> Result<Self> {
+        if let Some(logprobs) = self.logprobs
+            && logprobs > MAX_LOGPROBS
+        {
+            bail!("logprobs must be at most {MAX_LOGPROBS}, got {logprobs}");
+        }
+
+        if self.truncation == Some(TruncationPolicy::DropOldestMessages) {
+            bail!("DropOldestMessages truncation applies only to conversation histories");
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_params(logprobs: Option<u32>) -> ContinueFromRawPromptParams {
+        ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs,
+            max_tokens: 50,
+            model: None,
+            priority: RequestPriority::default(),
+            raw_prompt: "Hello".to_owned(),
+            sampling: None,
+            stop: vec![],
+            truncation: None,
+        }
+    }
+
+    #[test]
+    fn accepts_logprobs_up_to_the_limit() {
+        assert!(make_params(None).validate().is_ok());
+        assert!(make_params(Some(MAX_LOGPROBS)).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_logprobs_over_the_limit() {
+        assert!(make_params(Some(MAX_LOGPROBS + 1)).validate().is_err());
+    }
+
+    #[test]
+    fn rejects_dropping_messages_from_raw_prompt() {
+        let params = ContinueFromRawPromptParams {
+            truncation: Some(TruncationPolicy::DropOldestMessages),
+            ..make_params(None)
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn accepts_middle_out_truncation() {
+        let params = ContinueFromRawPromptParams {
+            truncation: Some(TruncationPolicy::MiddleOut),
+            ..make_params(None)
+        };
+
+        assert!(params.validate().is_ok());
+    }
 }
@@ -1,12 +1,16 @@
 use super::GenerateEmbeddingBatchParams;
 use crate::embedding_input_document::EmbeddingInputDocument;
 use crate::embedding_normalization_method::EmbeddingNormalizationMethod;
+use crate::request_priority::RequestPriority;
 
 pub struct ChunkByInputSizeIter<'embedding_batch> {
     pub chunk_size: usize,
     pub current_index: usize,
     pub input_batch: &'embedding_batch [EmbeddingInputDocument],
+    pub model: &'embedding_batch Option<String>,
     pub normalization_method: &'embedding_batch EmbeddingNormalizationMethod,
+    pub priority: RequestPriority,
+    pub rerank_query: &'embedding_batch Option<String>,
 }
 
 impl Iterator for ChunkByInputSizeIter<'_> {
@@ -19,10 +23,18 @@ impl Iterator for ChunkByInputSizeIter<'_> {
 
         let mut current_batch = Vec::new();
         let mut current_size = 0;
+        let rerank_query_size = self
+            .rerank_query
+            .as_ref()
+            .map_or(0, |rerank_query| rerank_query.chars().count());
 
         while self.current_index < self.input_batch.len() {
             let input = &self.input_batch[self.current_index];
-            let input_size = input.content.chars().count();
+            let input_size = input
+                .tokens
+                .as_ref()
+                .map_or_else(|| input.content.chars().count(), Vec::len)
+                + rerank_query_size;
 
             if current_size + input_size > self.chunk_size && !current_batch.is_empty() {
                 break;
@@ -38,7 +50,10 @@ impl Iterator for ChunkByInputSizeIter<'_> {
         } else {
             Some(GenerateEmbeddingBatchParams {
                 input_batch: current_batch,
+                model: self.model.clone(),
                 normalization_method: self.normalization_method.clone(),
+                priority: self.priority,
+                rerank_query: self.rerank_query.clone(),
             })
         }
     }
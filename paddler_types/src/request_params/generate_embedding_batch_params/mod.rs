@@ -6,21 +6,37 @@ use serde::Serialize;
 use self::chunk_by_input_size_iter::ChunkByInputSizeIter;
 use crate::embedding_input_document::EmbeddingInputDocument;
 use crate::embedding_normalization_method::EmbeddingNormalizationMethod;
+use crate::request_priority::RequestPriority;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenerateEmbeddingBatchParams {
     pub input_batch: Vec<EmbeddingInputDocument>,
+    /// Routes the request to the model pool of that name; the default model serves requests
+    /// without one
+    #[serde(default)]
+    pub model: Option<String>,
     pub normalization_method: EmbeddingNormalizationMethod,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    /// Pairs every document with that query, so a reranking model scores how relevant the
+    /// document is to it instead of embedding the document alone
+    #[serde(default)]
+    pub rerank_query: Option<String>,
 }
 
 impl GenerateEmbeddingBatchParams {
-    /// Input size is the total number of characters in the resulting batches.
+    /// Input size is the total number of characters in the resulting batches. Tokenized
+    /// documents count each of their tokens as a character, and every document also counts the
+    /// rerank query it is paired with.
     #[must_use]
     pub fn chunk_by_input_size(&self, chunk_size: usize) -> ChunkByInputSizeIter<'_> {
         ChunkByInputSizeIter {
             input_batch: &self.input_batch,
+            model: &self.model,
             normalization_method: &self.normalization_method,
+            priority: self.priority,
+            rerank_query: &self.rerank_query,
             chunk_size,
             current_index: 0,
         }
@@ -35,13 +51,17 @@ mod tests {
         EmbeddingInputDocument {
             content: content.to_owned(),
             id: id.to_owned(),
+            tokens: None,
         }
     }
 
     fn make_params(docs: Vec<EmbeddingInputDocument>) -> GenerateEmbeddingBatchParams {
         GenerateEmbeddingBatchParams {
             input_batch: docs,
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         }
     }
 
@@ -122,7 +142,10 @@ mod tests {
     fn test_chunk_preserves_normalization_method() {
         let params = GenerateEmbeddingBatchParams {
             input_batch: vec![make_doc("1", "test")],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::L2,
+            priority: RequestPriority::Interactive,
+            rerank_query: None,
         };
 
         let batches = params.chunk_by_input_size(100).collect::<Vec<_>>();
@@ -144,4 +167,36 @@ mod tests {
         assert_eq!(batches.len(), 1);
         assert_eq!(batches[0].input_batch.len(), 2);
     }
+
+    #[test]
+    fn test_chunk_counts_tokens_of_tokenized_documents() {
+        let params = make_params(vec![
+            EmbeddingInputDocument {
+                content: String::new(),
+                id: "1".to_owned(),
+                tokens: Some(vec![1, 2, 3, 4, 5, 6]),
+            },
+            make_doc("2", "12345"),
+        ]);
+
+        let batches = params.chunk_by_input_size(10).collect::<Vec<_>>();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].input_batch[0].id, "1");
+        assert_eq!(batches[1].input_batch[0].id, "2");
+    }
+
+    #[test]
+    fn test_chunk_counts_rerank_query_for_every_document() {
+        let params = GenerateEmbeddingBatchParams {
+            rerank_query: Some("query".to_owned()),
+            ..make_params(vec![make_doc("1", "12345"), make_doc("2", "12345")])
+        };
+
+        // (5 + 5) + (5 + 5) = 20, exceeds chunk_size of 15
+        let batches = params.chunk_by_input_size(15).collect::<Vec<_>>();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].rerank_query.as_deref(), Some("query"));
+    }
 }
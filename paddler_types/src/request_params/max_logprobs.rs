@@ -0,0 +1,3 @@
+/// Most likely alternatives a request can ask to have reported for every sampled token; the same
+/// limit as the `OpenAI` API
+pub const MAX_LOGPROBS: u32 = 20;
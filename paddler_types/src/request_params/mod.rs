@@ -1,7 +1,11 @@
 pub mod continue_from_conversation_history_params;
 mod continue_from_raw_prompt_params;
 mod generate_embedding_batch_params;
+mod max_logprobs;
+mod rerank_params;
 
 pub use continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
 pub use continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 pub use generate_embedding_batch_params::GenerateEmbeddingBatchParams;
+pub use max_logprobs::MAX_LOGPROBS;
+pub use rerank_params::RerankParams;
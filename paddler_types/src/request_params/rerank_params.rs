@@ -0,0 +1,194 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::GenerateEmbeddingBatchParams;
+use crate::embedding_input_document::EmbeddingInputDocument;
+use crate::embedding_normalization_method::EmbeddingNormalizationMethod;
+use crate::request_priority::RequestPriority;
+use crate::rerank_document::RerankDocument;
+use crate::rerank_score::RerankScore;
+use crate::validates::Validates;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RerankParams {
+    pub documents: Vec<RerankDocument>,
+    /// Routes the request to the model pool of that name; the default model serves requests
+    /// without one
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    pub query: String,
+    /// Scores are held back until every document is scored, and then returned from the most to
+    /// the least relevant document. Otherwise they are streamed as soon as agents produce them.
+    #[serde(default)]
+    pub sort_by_score: bool,
+    /// Returns only that many of the most relevant documents; implies sorting by score
+    #[serde(default)]
+    pub top_n: Option<usize>,
+}
+
+impl RerankParams {
+    #[must_use]
+    pub const fn is_sorted(&self) -> bool {
+        self.sort_by_score || self.top_n.is_some()
+    }
+
+    /// Orders the scores from the most to the least relevant document and keeps the `top_n` of
+    /// them
+    #[must_use]
+    pub fn rank(&self, mut scores: Vec<RerankScore>) -> Vec<RerankScore> {
+        scores.sort_by(|left, right| right.score.total_cmp(&left.score));
+
+        if let Some(top_n) = self.top_n {
+            scores.truncate(top_n);
+        }
+
+        scores
+    }
+
+    /// Reranking reuses the embedding pipeline: a model with rank pooling produces a single
+    /// relevance score as the embedding of each query and document pair.
+    #[must_use]
+    pub fn to_embedding_batch_params(&self) -> GenerateEmbeddingBatchParams {
+        GenerateEmbeddingBatchParams {
+            input_batch: self
+                .documents
+                .iter()
+                .map(|document| EmbeddingInputDocument {
+                    content: document.content.clone(),
+                    id: document.id.clone(),
+                    tokens: None,
+                })
+                .collect(),
+            model: self.model.clone(),
+            normalization_method: EmbeddingNormalizationMethod::None,
+            priority: self.priority,
+            rerank_query: Some(self.query.clone()),
+        }
+    }
+}
+
+impl Validates<Self> for RerankParams {
+    fn validate(self) -> Result<Self> {
+        if self.documents.is_empty() {
+            bail!("documents must not be empty");
+        }
+
+        if self.query.is_empty() {
+            bail!("query must not be empty");
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(document_id: &str, score: f32) -> RerankScore {
+        RerankScore {
+            document_id: document_id.to_owned(),
+            input_tokens: 1,
+            score,
+        }
+    }
+
+    fn make_params(top_n: Option<usize>) -> RerankParams {
+        RerankParams {
+            documents: vec![RerankDocument {
+                content: "Paris is the capital of France".to_owned(),
+                id: "paris".to_owned(),
+            }],
+            model: Some("reranker".to_owned()),
+            priority: RequestPriority::Batch,
+            query: "What is the capital of France?".to_owned(),
+            sort_by_score: false,
+            top_n,
+        }
+    }
+
+    #[test]
+    fn ranks_from_most_to_least_relevant() {
+        let ranked = make_params(None).rank(vec![
+            score("a", -1.5),
+            score("b", 3.0),
+            score("c", 0.25),
+        ]);
+
+        let document_ids: Vec<&str> = ranked
+            .iter()
+            .map(|score| score.document_id.as_str())
+            .collect();
+
+        assert_eq!(document_ids, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn keeps_only_top_n_scores() {
+        let ranked = make_params(Some(1)).rank(vec![score("a", 0.1), score("b", 0.9)]);
+
+        assert_eq!(ranked, vec![score("b", 0.9)]);
+    }
+
+    #[test]
+    fn top_n_implies_sorting() {
+        assert!(!make_params(None).is_sorted());
+        assert!(make_params(Some(3)).is_sorted());
+    }
+
+    #[test]
+    fn pairs_every_document_with_the_query() {
+        let embedding_batch_params = make_params(None).to_embedding_batch_params();
+
+        assert_eq!(
+            embedding_batch_params.rerank_query.as_deref(),
+            Some("What is the capital of France?")
+        );
+        assert_eq!(embedding_batch_params.input_batch[0].id, "paris");
+        assert_eq!(embedding_batch_params.model.as_deref(), Some("reranker"));
+        assert_eq!(embedding_batch_params.priority, RequestPriority::Batch);
+    }
+
+    #[test]
+    fn validation_rejects_empty_documents() {
+        let params = RerankParams {
+            documents: vec![],
+            ..make_params(None)
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validation_rejects_empty_query() {
+        let params = RerankParams {
+            query: String::new(),
+            ..make_params(None)
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validation_accepts_query_with_documents() {
+        assert!(make_params(None).validate().is_ok());
+    }
+
+    #[test]
+    fn sorting_is_optional() -> Result<()> {
+        let params: RerankParams = serde_json::from_value(serde_json::json!({
+            "documents": [],
+            "query": "query",
+        }))?;
+
+        assert!(!params.is_sorted());
+        assert_eq!(params.top_n, None);
+
+        Ok(())
+    }
+}
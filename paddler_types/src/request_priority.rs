@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Buffered requests of a higher priority are dispatched first, and each priority has its own
+/// buffer limit and timeout
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum RequestPriority {
+    /// Throughput-oriented work that can wait, like scheduled jobs
+    Batch,
+    /// Someone is waiting for the response
+    #[default]
+    Interactive,
+}
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::rerank_score::RerankScore;
+use crate::streamable_result::StreamableResult;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub enum RerankResult {
+    Done,
+    Error(String),
+    Score(RerankScore),
+}
+
+impl StreamableResult for RerankResult {
+    fn is_done(&self) -> bool {
+        matches!(self, Self::Done | Self::Error(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_is_done() {
+        assert!(RerankResult::Error("fail".to_owned()).is_done());
+    }
+
+    #[test]
+    fn score_is_not_done() {
+        let result = RerankResult::Score(RerankScore {
+            document_id: "doc".to_owned(),
+            input_tokens: 1,
+            score: 0.5,
+        });
+
+        assert!(!result.is_done());
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::embedding::Embedding;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RerankScore {
+    pub document_id: String,
+    /// Number of tokens the query and the document were made of together
+    pub input_tokens: usize,
+    /// Relevance of the document to the query as reported by the model; higher is more relevant
+    pub score: f32,
+}
+
+/// Models with rank pooling produce the relevance score as the first value of the embedding
+impl TryFrom<Embedding> for RerankScore {
+    type Error = anyhow::Error;
+
+    fn try_from(embedding: Embedding) -> Result<Self> {
+        let Some(score) = embedding.embedding.first() else {
+            return Err(anyhow!(
+                "Embedding of document {} has no relevance score",
+                embedding.source_document_id
+            ));
+        };
+
+        Ok(Self {
+            document_id: embedding.source_document_id,
+            input_tokens: embedding.input_tokens,
+            score: *score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding_normalization_method::EmbeddingNormalizationMethod;
+    use crate::pooling_type::PoolingType;
+
+    fn make_embedding(embedding: Vec<f32>) -> Embedding {
+        Embedding {
+            embedding,
+            input_tokens: 12,
+            normalization_method: EmbeddingNormalizationMethod::None,
+            pooling_type: PoolingType::Rank,
+            source_document_id: "doc".to_owned(),
+        }
+    }
+
+    #[test]
+    fn score_is_the_first_embedding_value() -> Result<()> {
+        let score = RerankScore::try_from(make_embedding(vec![2.5]))?;
+
+        assert_eq!(
+            score,
+            RerankScore {
+                document_id: "doc".to_owned(),
+                input_tokens: 12,
+                score: 2.5,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_embedding_has_no_score() {
+        assert!(RerankScore::try_from(make_embedding(vec![])).is_err());
+    }
+}
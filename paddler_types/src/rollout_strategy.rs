@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RolloutStrategy {
+    /// Every agent switches to the new state at the same time
+    #[default]
+    AllAtOnce,
+    /// Agents switch one at a time, each waiting for the previous one to apply the new state, so
+    /// that the rest keep serving in the meantime
+    AgentByAgent,
+}
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Samplers that can be reordered in the sampler chain. Logit biases are always applied first,
+/// and a token is always picked from the resulting distribution last.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum SamplerType {
+    Dry,
+    MinP,
+    Penalties,
+    Temperature,
+    TopK,
+    TopNSigma,
+    TopP,
+    TypicalP,
+    Xtc,
+}
+
+impl SamplerType {
+    /// Same order llama.cpp uses by default
+    #[must_use]
+    pub fn default_order() -> Vec<Self> {
+        vec![
+            Self::Penalties,
+            Self::Dry,
+            Self::TopNSigma,
+            Self::TopK,
+            Self::TypicalP,
+            Self::TopP,
+            Self::MinP,
+            Self::Xtc,
+            Self::Temperature,
+        ]
+    }
+}
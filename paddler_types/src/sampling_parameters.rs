@@ -0,0 +1,149 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validates::Validates;
+
+/// Overrides the sampling part of the cluster-wide `InferenceParameters` for a single request.
+/// Fields that are not set fall back to the values the agent was configured with.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SamplingParameters {
+    #[serde(default)]
+    pub min_p: Option<f32>,
+    #[serde(default)]
+    pub penalty_frequency: Option<f32>,
+    #[serde(default)]
+    pub penalty_last_n: Option<i32>,
+    #[serde(default)]
+    pub penalty_presence: Option<f32>,
+    #[serde(default)]
+    pub penalty_repeat: Option<f32>,
+    /// Seeds the final sampler, so that the same request on the same agent samples the same tokens
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_k: Option<i32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+impl Validates<Self> for SamplingParameters {
+    fn validate(self) -> Result<Self> {
+        if let Some(min_p) = self.min_p
+            && !(0.0..=1.0).contains(&min_p)
+        {
+            bail!("min_p must be between 0.0 and 1.0, got {min_p}");
+        }
+
+        if let Some(penalty_frequency) = self.penalty_frequency
+            && !penalty_frequency.is_finite()
+        {
+            bail!("penalty_frequency must be a finite number");
+        }
+
+        if let Some(penalty_last_n) = self.penalty_last_n
+            && penalty_last_n < -1
+        {
+            bail!(
+                "penalty_last_n must be -1 (context size), 0 (disabled) or positive, got {penalty_last_n}"
+            );
+        }
+
+        if let Some(penalty_presence) = self.penalty_presence
+            && !penalty_presence.is_finite()
+        {
+            bail!("penalty_presence must be a finite number");
+        }
+
+        if let Some(penalty_repeat) = self.penalty_repeat
+            && !(penalty_repeat.is_finite() && penalty_repeat > 0.0)
+        {
+            bail!("penalty_repeat must be greater than 0.0, got {penalty_repeat}");
+        }
+
+        if let Some(temperature) = self.temperature
+            && !(temperature.is_finite() && temperature >= 0.0)
+        {
+            bail!("temperature must be greater than or equal to 0.0, got {temperature}");
+        }
+
+        if let Some(top_k) = self.top_k
+            && top_k < 0
+        {
+            bail!("top_k must not be negative, got {top_k}");
+        }
+
+        if let Some(top_p) = self.top_p
+            && !(0.0..=1.0).contains(&top_p)
+        {
+            bail!("top_p must be between 0.0 and 1.0, got {top_p}");
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_succeeds_without_overrides() {
+        assert!(SamplingParameters::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_succeeds_for_greedy_sampling() {
+        let params = SamplingParameters {
+            temperature: Some(0.0),
+            top_k: Some(1),
+            ..SamplingParameters::default()
+        };
+
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_for_negative_temperature() {
+        let params = SamplingParameters {
+            temperature: Some(-0.5),
+            ..SamplingParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_top_p_above_one() {
+        let params = SamplingParameters {
+            top_p: Some(1.5),
+            ..SamplingParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_for_nan_penalty() {
+        let params = SamplingParameters {
+            penalty_presence: Some(f32::NAN),
+            ..SamplingParameters::default()
+        };
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn deserializes_partial_overrides() -> Result<()> {
+        let params: SamplingParameters = serde_json::from_str(r#"{"temperature": 0.0}"#)?;
+
+        assert_eq!(params.temperature, Some(0.0));
+        assert_eq!(params.top_k, None);
+
+        Ok(())
+    }
+}
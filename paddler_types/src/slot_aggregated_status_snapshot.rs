@@ -9,14 +9,23 @@ use crate::agent_state_application_status::AgentStateApplicationStatus;
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct SlotAggregatedStatusSnapshot {
+    /// Whether the loaded model was set up to generate embeddings
+    pub accepts_embeddings: bool,
     pub desired_slots_total: i32,
     pub download_current: usize,
     pub download_filename: Option<String>,
     pub download_total: usize,
     pub issues: BTreeSet<AgentIssue>,
     pub model_path: Option<String>,
+    /// Requests that reused KV cache cells of an earlier request sharing their prompt prefix
+    pub prefix_cache_hits: usize,
+    /// Requests that had to ingest their whole prompt
+    pub prefix_cache_misses: usize,
     pub slots_processing: i32,
     pub slots_total: i32,
+    /// Grows every time the agent applies a desired state, so the balancer can tell when the
+    /// state it sent last took effect
+    pub state_application_count: usize,
     pub state_application_status: AgentStateApplicationStatus,
     pub uses_chat_template_override: bool,
     pub version: i32,
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Log probability of a single token under the distribution the model produced, before the
+/// samplers reshaped it
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenLogprob {
+    /// A token can hold just a part of a multi-byte character, so its text can be lossy
+    pub bytes: Vec<u8>,
+    pub id: i32,
+    pub logprob: f32,
+    pub token: String,
+}
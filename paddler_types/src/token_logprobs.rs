@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::token_logprob::TokenLogprob;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenLogprobs {
+    pub sampled: TokenLogprob,
+    /// The most likely alternatives, starting with the most likely one
+    pub top_logprobs: Vec<TokenLogprob>,
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Tokens a single generation request consumed, reported by the agent once it finishes.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenUsage {
+    pub completion_tokens: usize,
+    pub prompt_tokens: usize,
+}
+
+impl TokenUsage {
+    #[must_use]
+    pub const fn total_tokens(&self) -> usize {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+}
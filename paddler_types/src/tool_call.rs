@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Call of one of the request's tools, recognized in the generated output
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolCall {
+    /// Already validated against the parameters schema of the tool
+    pub arguments: Value,
+    pub id: String,
+    pub name: String,
+}
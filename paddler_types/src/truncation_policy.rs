@@ -0,0 +1,14 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// What to do with a conversation that, along with `max_tokens`, does not fit in the context
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TruncationPolicy {
+    /// Drops the oldest messages, except for the system messages and the last message, until
+    /// the prompt fits
+    DropOldestMessages,
+    /// Cuts tokens out of the middle of the rendered prompt, keeping its beginning and its end
+    MiddleOut,
+    /// Fails the request before any of it is ingested
+    Reject,
+}
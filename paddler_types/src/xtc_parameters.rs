@@ -0,0 +1,63 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validates::Validates;
+
+/// "Exclude Top Choices" sampler, which sometimes removes the most probable tokens to make the
+/// output less predictable
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct XtcParameters {
+    /// Chance of removing the top choices when sampling a token
+    pub probability: f32,
+    /// Only tokens with at least this probability are removed, except for the least probable of
+    /// them (above 0.5 = disabled)
+    pub threshold: f32,
+}
+
+impl Validates<Self> for XtcParameters {
+    fn validate(self) -> Result<Self> {
+        if !(0.0..=1.0).contains(&self.probability) {
+            bail!(
+                "xtc probability must be between 0.0 and 1.0, got {}",
+                self.probability
+            );
+        }
+
+        if !(0.0..=1.0).contains(&self.threshold) {
+            bail!(
+                "xtc threshold must be between 0.0 and 1.0, got {}",
+                self.threshold
+            );
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_succeeds_for_probabilities() {
+        let params = XtcParameters {
+            probability: 0.5,
+            threshold: 0.1,
+        };
+
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_for_probability_above_one() {
+        let params = XtcParameters {
+            probability: 1.5,
+            threshold: 0.1,
+        };
+
+        assert!(params.validate().is_err());
+    }
+}
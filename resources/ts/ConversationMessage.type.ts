@@ -1,6 +1,9 @@
 import { type ConversationMessageContentPart } from "./ConversationMessageContentPart.type";
+import { type ToolCall } from "./ToolCall.type";
 
 export type ConversationMessage = {
   role: string;
   content: string | ConversationMessageContentPart[];
+  tool_call_id?: string;
+  tool_calls?: ToolCall[];
 };
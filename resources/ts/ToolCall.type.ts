@@ -0,0 +1,5 @@
+export type ToolCall = {
+  arguments: unknown;
+  id: string;
+  name: string;
+};
@@ -11,7 +11,11 @@ import { ChatTemplateContext } from "../contexts/ChatTemplateContext";
 import { InferenceParametersContext } from "../contexts/InferenceParametersContext";
 import { PaddlerConfigurationContext } from "../contexts/PaddlerConfigurationContext";
 import { useAgentDesiredModelUrl } from "../hooks/useAgentDesiredModelUrl";
+import { type AgentDesiredModel } from "../schemas/AgentDesiredModel";
 import { type BalancerDesiredState } from "../schemas/BalancerDesiredState";
+import { type DispatchStrategy } from "../schemas/DispatchStrategy";
+import { type ModelPoolDesiredState } from "../schemas/ModelPoolDesiredState";
+import { type RolloutStrategy } from "../schemas/RolloutStrategy";
 import { ChatTemplateBehavior } from "./ChatTemplateBehavior";
 import { InferenceParameterCacheDtype } from "./InferenceParameterCacheDtype";
 import { InferenceParameterCheckbox } from "./InferenceParameterCheckbox";
@@ -36,9 +40,17 @@ import {
 export function ChangeModelForm({
   defaultBaseModelUri,
   defaultMultimodalProjectionUri,
+  dispatchStrategy,
+  modelPools,
+  prefetchModel,
+  rolloutStrategy,
 }: {
   defaultBaseModelUri: null | string;
   defaultMultimodalProjectionUri: null | string;
+  dispatchStrategy: null | DispatchStrategy;
+  modelPools: Record<string, ModelPoolDesiredState>;
+  prefetchModel: AgentDesiredModel;
+  rolloutStrategy: RolloutStrategy;
 }) {
   const [, navigate] = useLocation();
   const { chatTemplateOverride, useChatTemplateOverride } =
@@ -85,10 +97,14 @@ export function ChangeModelForm({
 
       const desiredState: BalancerDesiredState = Object.freeze({
         chat_template_override: chatTemplateOverride,
+        dispatch_strategy: dispatchStrategy,
         inference_parameters: parameters,
         model: baseModelAgentDesiredModelState.agentDesiredModel,
+        model_pools: modelPools,
         multimodal_projection:
           multimodalProjecttionAgentDesiredModelState.agentDesiredModel,
+        prefetch_model: prefetchModel,
+        rollout_strategy: rolloutStrategy,
         use_chat_template_override: useChatTemplateOverride,
       });
 
@@ -97,8 +113,12 @@ export function ChangeModelForm({
     [
       baseModelAgentDesiredModelState,
       chatTemplateOverride,
+      dispatchStrategy,
+      modelPools,
       multimodalProjecttionAgentDesiredModelState,
       parameters,
+      prefetchModel,
+      rolloutStrategy,
       useChatTemplateOverride,
     ],
   );
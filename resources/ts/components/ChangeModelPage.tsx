@@ -48,9 +48,13 @@ export function ChangeModelPage() {
     ok({
       response: {
         chat_template_override,
+        dispatch_strategy,
         inference_parameters,
         model,
+        model_pools,
         multimodal_projection,
+        prefetch_model,
+        rollout_strategy,
         use_chat_template_override,
       },
     }) {
@@ -67,6 +71,10 @@ export function ChangeModelPage() {
               defaultMultimodalProjectionUri={modelSchemaToUrl(
                 multimodal_projection,
               )}
+              dispatchStrategy={dispatch_strategy}
+              modelPools={model_pools}
+              prefetchModel={prefetch_model}
+              rolloutStrategy={rollout_strategy}
             />
           </InferenceParametersContextProvider>
         </ChatTemplateContextProvider>
@@ -39,7 +39,12 @@ export function ConversationMessage({
         })}
       </div>
       <div className={conversationMessage__thoughts}>
-        <Markdown>{thoughts}</Markdown>
+        {thoughts && (
+          <details open={isThinking}>
+            <summary>Reasoning</summary>
+            <Markdown>{thoughts}</Markdown>
+          </details>
+        )}
       </div>
     </div>
   );
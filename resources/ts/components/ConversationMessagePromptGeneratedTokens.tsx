@@ -97,7 +97,12 @@ export const ConversationMessagePromptGeneratedTokens = memo(
           .pipe(
             scan(function (
               message: Message,
-              { done, error, token }: InferenceServiceGenerateTokensResponse,
+              {
+                done,
+                error,
+                reasoning,
+                token,
+              }: InferenceServiceGenerateTokensResponse,
             ) {
               if (error) {
                 return Object.freeze({
@@ -117,27 +122,7 @@ export const ConversationMessagePromptGeneratedTokens = memo(
                 });
               }
 
-              if ("<think>" === token) {
-                return Object.freeze({
-                  errors: message.errors,
-                  isEmpty: false,
-                  isThinking: true,
-                  response: message.response,
-                  thoughts: message.thoughts,
-                });
-              }
-
-              if ("</think>" === token) {
-                return Object.freeze({
-                  errors: message.errors,
-                  isEmpty: false,
-                  isThinking: false,
-                  response: message.response,
-                  thoughts: message.thoughts,
-                });
-              }
-
-              if (message.isThinking) {
+              if (reasoning) {
                 return Object.freeze({
                   errors: message.errors,
                   isEmpty: false,
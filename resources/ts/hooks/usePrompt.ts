@@ -71,6 +71,10 @@ export function usePrompt({
                   return;
                 }
 
+                if (validatedMessage.reasoning) {
+                  continue;
+                }
+
                 setMessage(function (prevMessage) {
                   return `${prevMessage}${validatedMessage.token}`;
                 });
@@ -4,6 +4,7 @@ import { AgentIssueSchema } from "./AgentIssue";
 
 export const AgentSchema = z
   .object({
+    accepts_embeddings: z.boolean(),
     desired_slots_total: z.number(),
     download_current: z.number(),
     download_filename: z.string().nullable(),
@@ -11,7 +12,10 @@ export const AgentSchema = z
     id: z.string(),
     issues: z.array(AgentIssueSchema),
     model_path: z.string().nullable(),
+    model_pool: z.string().nullable(),
     name: z.string().nullable(),
+    prefix_cache_hits: z.number(),
+    prefix_cache_misses: z.number(),
     slots_processing: z.number(),
     slots_total: z.number(),
     state_application_status: z.enum([
@@ -2,14 +2,21 @@ import { z } from "zod";
 
 import { AgentDesiredModelSchema } from "./AgentDesiredModel";
 import { ChatTemplateSchema } from "./ChatTemplate";
+import { DispatchStrategySchema } from "./DispatchStrategy";
 import { InferenceParametersSchema } from "./InferenceParameters";
+import { ModelPoolDesiredStateSchema } from "./ModelPoolDesiredState";
+import { RolloutStrategySchema } from "./RolloutStrategy";
 
 export const BalancerDesiredStateSchema = z
   .object({
     chat_template_override: ChatTemplateSchema.nullable(),
+    dispatch_strategy: DispatchStrategySchema.nullable(),
     inference_parameters: InferenceParametersSchema,
     model: AgentDesiredModelSchema,
+    model_pools: z.record(z.string(), ModelPoolDesiredStateSchema),
     multimodal_projection: AgentDesiredModelSchema,
+    prefetch_model: AgentDesiredModelSchema,
+    rollout_strategy: RolloutStrategySchema,
     use_chat_template_override: z.boolean(),
   })
   .strict();
@@ -0,0 +1,11 @@
+import { z } from "zod";
+
+export const DispatchStrategySchema = z.enum([
+  "LeastBusy",
+  "LeastOutstandingTokens",
+  "PowerOfTwoChoices",
+  "RoundRobin",
+  "Weighted",
+]);
+
+export type DispatchStrategy = z.infer<typeof DispatchStrategySchema>;
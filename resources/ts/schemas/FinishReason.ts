@@ -0,0 +1,13 @@
+import { z } from "zod";
+
+export const FinishReasonSchema = z.union([
+  z.literal("Cancelled"),
+  z.literal("Eos"),
+  z.literal("Evicted"),
+  z.literal("MaxTokens"),
+  z.object({
+    StopSequence: z.string(),
+  }),
+]);
+
+export type FinishReason = z.infer<typeof FinishReasonSchema>;
@@ -21,24 +21,80 @@ export const poolingTypes = [
   "Unspecified",
 ] as const;
 
+export const samplerTypes = [
+  "Dry",
+  "MinP",
+  "Penalties",
+  "Temperature",
+  "TopK",
+  "TopNSigma",
+  "TopP",
+  "TypicalP",
+  "Xtc",
+] as const;
+
 export const InferenceParametersSchema = z
   .object({
     batch_n_tokens: z.number(),
     context_size: z.number(),
+    dry: z
+      .object({
+        allowed_length: z.number().int().min(0),
+        base: z.number(),
+        multiplier: z.number(),
+        penalty_last_n: z.number().int(),
+        sequence_breakers: z.array(z.string()),
+      })
+      .strict()
+      .nullable(),
     enable_embeddings: z.boolean(),
     image_resize_to_fit: z.number().int().min(1),
     k_cache_dtype: z.enum(cacheDtypes),
     v_cache_dtype: z.enum(cacheDtypes),
+    logit_bias: z.array(
+      z
+        .object({
+          bias: z.number(),
+          token: z.union([z.number().int().min(0), z.string()]),
+        })
+        .strict(),
+    ),
     min_p: z.number(),
+    mirostat: z
+      .object({
+        eta: z.number(),
+        tau: z.number(),
+        version: z.enum(["V1", "V2"]),
+      })
+      .strict()
+      .nullable(),
     n_gpu_layers: z.number().int().min(0),
     penalty_frequency: z.number(),
     penalty_last_n: z.number(),
     penalty_presence: z.number(),
     penalty_repeat: z.number(),
     pooling_type: z.enum(poolingTypes),
+    reasoning_markers: z
+      .object({
+        end: z.string(),
+        start: z.string(),
+      })
+      .strict()
+      .nullable(),
+    sampler_order: z.array(z.enum(samplerTypes)),
+    seed: z.number().int().min(0).nullable(),
     temperature: z.number(),
     top_k: z.number(),
+    top_n_sigma: z.number().nullable(),
     top_p: z.number(),
+    typical_p: z.number().nullable(),
+    xtc: z
+      .object({
+        probability: z.number(),
+        threshold: z.number(),
+      })
+      .strict()
+      .nullable(),
   })
   .strict();
 
@@ -1,5 +1,7 @@
 import { z } from "zod";
 
+import { FinishReasonSchema } from "./FinishReason";
+
 export const InferenceServiceGenerateTokensResponseSchema = z
   .union([
     z.object({
@@ -19,16 +21,44 @@ export const InferenceServiceGenerateTokensResponseSchema = z
             z.object({
               ChatTemplateError: z.string(),
             }),
-            z.literal("Done"),
+            z.object({
+              ContextOverflow: z.object({
+                context_size: z.number(),
+                max_tokens: z.number(),
+                prompt_tokens: z.number(),
+              }),
+            }),
+            z.object({
+              Done: FinishReasonSchema,
+            }),
             z.object({
               ImageDecodingFailed: z.string(),
             }),
             z.object({
               MultimodalNotSupported: z.string(),
             }),
+            z.object({
+              ReasoningToken: z.string(),
+            }),
+            z.object({
+              Seed: z.number(),
+            }),
             z.object({
               Token: z.string(),
             }),
+            z.object({
+              ToolCall: z.object({
+                arguments: z.unknown(),
+                id: z.string(),
+                name: z.string(),
+              }),
+            }),
+            z.object({
+              Usage: z.object({
+                completion_tokens: z.number(),
+                prompt_tokens: z.number(),
+              }),
+            }),
           ]),
         }),
       }),
@@ -39,6 +69,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
         done: true;
         error: null;
         ok: true;
+        reasoning: false;
         request_id: string;
         token: null;
       }
@@ -46,6 +77,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
         done: false;
         error: null;
         ok: true;
+        reasoning: boolean;
         request_id: string;
         token: string;
       }
@@ -56,6 +88,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
           description: string;
         };
         ok: false;
+        reasoning: false;
         request_id: string;
         token: null;
       } {
@@ -64,16 +97,18 @@ export const InferenceServiceGenerateTokensResponseSchema = z
         done: true,
         error: data.Error.error,
         ok: false,
+        reasoning: false,
         request_id: data.Error.request_id,
         token: null,
       });
     }
 
-    if (data.Response.response.GeneratedToken === "Done") {
+    if ("Done" in data.Response.response.GeneratedToken) {
       return Object.freeze({
         done: true,
         error: null,
         ok: true,
+        reasoning: false,
         request_id: data.Response.request_id,
         token: null,
       });
@@ -87,6 +122,24 @@ export const InferenceServiceGenerateTokensResponseSchema = z
           description: data.Response.response.GeneratedToken.ChatTemplateError,
         }),
         ok: false,
+        reasoning: false,
+        request_id: data.Response.request_id,
+        token: null,
+      });
+    }
+
+    if ("ContextOverflow" in data.Response.response.GeneratedToken) {
+      const { context_size, max_tokens, prompt_tokens } =
+        data.Response.response.GeneratedToken.ContextOverflow;
+
+      return Object.freeze({
+        done: true,
+        error: Object.freeze({
+          code: 400,
+          description: `prompt of ${prompt_tokens} tokens and up to ${max_tokens} generated tokens do not fit in the context of ${context_size} tokens`,
+        }),
+        ok: false,
+        reasoning: false,
         request_id: data.Response.request_id,
         token: null,
       });
@@ -101,6 +154,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
             data.Response.response.GeneratedToken.ImageDecodingFailed,
         }),
         ok: false,
+        reasoning: false,
         request_id: data.Response.request_id,
         token: null,
       });
@@ -115,16 +169,44 @@ export const InferenceServiceGenerateTokensResponseSchema = z
             data.Response.response.GeneratedToken.MultimodalNotSupported,
         }),
         ok: false,
+        reasoning: false,
         request_id: data.Response.request_id,
         token: null,
       });
     }
 
+    if (
+      "Seed" in data.Response.response.GeneratedToken ||
+      "ToolCall" in data.Response.response.GeneratedToken ||
+      "Usage" in data.Response.response.GeneratedToken
+    ) {
+      return Object.freeze({
+        done: false,
+        error: null,
+        ok: true,
+        reasoning: false,
+        request_id: data.Response.request_id,
+        token: "",
+      });
+    }
+
+    if ("ReasoningToken" in data.Response.response.GeneratedToken) {
+      return Object.freeze({
+        done: false,
+        error: null,
+        ok: true,
+        reasoning: true,
+        request_id: data.Response.request_id,
+        token: data.Response.response.GeneratedToken.ReasoningToken,
+      });
+    }
+
     if ("Token" in data.Response.response.GeneratedToken) {
       return Object.freeze({
         done: false,
         error: null,
         ok: true,
+        reasoning: false,
         request_id: data.Response.request_id,
         token: data.Response.response.GeneratedToken.Token,
       });
@@ -134,6 +216,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       done: true,
       error: null,
       ok: true,
+      reasoning: false,
       request_id: data.Response.request_id,
       token: null,
     });
@@ -0,0 +1,18 @@
+import { z } from "zod";
+
+import { AgentDesiredModelSchema } from "./AgentDesiredModel";
+import { ChatTemplateSchema } from "./ChatTemplate";
+import { InferenceParametersSchema } from "./InferenceParameters";
+
+export const ModelPoolDesiredStateSchema = z
+  .object({
+    chat_template_override: ChatTemplateSchema.nullable(),
+    inference_parameters: InferenceParametersSchema,
+    model: AgentDesiredModelSchema,
+    multimodal_projection: AgentDesiredModelSchema,
+    prefetch_model: AgentDesiredModelSchema,
+    use_chat_template_override: z.boolean(),
+  })
+  .strict();
+
+export type ModelPoolDesiredState = z.infer<typeof ModelPoolDesiredStateSchema>;
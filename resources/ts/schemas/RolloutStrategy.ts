@@ -0,0 +1,5 @@
+import { z } from "zod";
+
+export const RolloutStrategySchema = z.enum(["AllAtOnce", "AgentByAgent"]);
+
+export type RolloutStrategy = z.infer<typeof RolloutStrategySchema>;